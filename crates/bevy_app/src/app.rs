@@ -24,6 +24,9 @@ use log::debug;
 #[cfg(feature = "trace")]
 use tracing::info_span;
 
+#[cfg(feature = "std")]
+use bevy_ecs::schedule::GraphExportFormat;
+
 #[cfg(feature = "std")]
 use std::{
     panic::{catch_unwind, resume_unwind},
@@ -635,6 +638,46 @@ impl App {
         self
     }
 
+    /// Starts recording the last `capacity` values of the component `C` of each entity in the
+    /// [`ComponentHistory`](bevy_ecs::reflect::ComponentHistory) resource.
+    ///
+    /// The values are recorded at the end of each frame by
+    /// [`record_component_history`](bevy_ecs::reflect::record_component_history), which is added
+    /// to the [`Last`](crate::Last) schedule the first time this is called. `C` must be registered
+    /// with [`ReflectComponent`](bevy_ecs::reflect::ReflectComponent) for its values to be recorded.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::Reflect;
+    /// #
+    /// #[derive(Component, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Health(u32);
+    ///
+    /// App::new()
+    ///     .register_type::<Health>()
+    ///     .track_component_history::<Health>(16);
+    /// ```
+    #[cfg(feature = "bevy_reflect")]
+    pub fn track_component_history<C: Component>(&mut self, capacity: usize) -> &mut Self {
+        use bevy_ecs::reflect::record_component_history;
+
+        /// Marks that [`record_component_history`] was added to the [`Last`](crate::Last) schedule,
+        /// which the [`ComponentHistory`](bevy_ecs::reflect::ComponentHistory) resource doesn't tell,
+        /// since it can also be added with [`World::track_component_history`].
+        #[derive(Resource)]
+        struct ComponentHistoryRecorded;
+
+        if !self.world().contains_resource::<ComponentHistoryRecorded>() {
+            self.insert_resource(ComponentHistoryRecorded);
+            self.add_systems(crate::Last, record_component_history);
+        }
+        self.world_mut().track_component_history::<C>(capacity);
+        self
+    }
+
     /// Registers the given function into the [`AppFunctionRegistry`] resource.
     ///
     /// The given function will internally be stored as a [`DynamicFunction`]
@@ -1284,6 +1327,55 @@ impl App {
         self
     }
 
+    /// Writes the graph of every schedule in the main world's [`Schedules`] to its own file in `directory`,
+    /// using [`Schedule::export_graph`].
+    ///
+    /// Each file is named after the label of its schedule, with the [extension](GraphExportFormat::extension)
+    /// of `format`. `directory` is created if it does not exist.
+    ///
+    /// The schedules are [initialized](Schedule::initialize) first, so that the exported graphs include
+    /// automatically inserted sync points and ambiguities. Schedules that fail to build are still exported,
+    /// with a warning.
+    #[cfg(feature = "std")]
+    pub fn export_schedule_graphs(
+        &mut self,
+        directory: impl AsRef<std::path::Path>,
+        format: GraphExportFormat,
+    ) -> std::io::Result<()> {
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory)?;
+        let world = self.world_mut();
+        let labels: Vec<_> = world
+            .get_resource::<Schedules>()
+            .map(|schedules| {
+                schedules
+                    .iter()
+                    .map(|(_, schedule)| schedule.label())
+                    .collect()
+            })
+            .unwrap_or_default();
+        for label in labels {
+            let graph = world.schedule_scope(label, |world, schedule| {
+                if let Err(error) = schedule.initialize(world) {
+                    log::warn!("Schedule {label:?} failed to build: {error}");
+                }
+                schedule.export_graph(format)
+            });
+            let name: String = alloc::format!("{label:?}")
+                .chars()
+                .map(|c| match c {
+                    'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                    _ => '_',
+                })
+                .collect();
+            std::fs::write(
+                directory.join(name).with_extension(format.extension()),
+                graph,
+            )?;
+        }
+        Ok(())
+    }
+
     /// Attempts to determine if an [`AppExit`] was raised since the last update.
     ///
     /// Will attempt to return the first [`Error`](AppExit::Error) it encounters.
@@ -1489,7 +1581,7 @@ mod tests {
         lifecycle::RemovedComponents,
         query::With,
         resource::Resource,
-        schedule::{IntoScheduleConfigs, ScheduleLabel},
+        schedule::{GraphExportFormat, IntoScheduleConfigs, ScheduleLabel},
         system::{Commands, Query},
         world::{FromWorld, World},
     };
@@ -1888,4 +1980,48 @@ mod tests {
         assert_eq!(test_events.len(), 2); // Events are double-buffered, so we see 2 + 0 = 2
         assert_eq!(test_events.iter_current_update_events().count(), 0);
     }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn track_component_history_after_world() {
+        use bevy_ecs::reflect::{ComponentHistory, ReflectComponent};
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        let mut app = App::new();
+        app.register_type::<Health>();
+        // The resource already exists, but the recording system still has to be added.
+        app.world_mut().track_component_history::<Health>(4);
+        app.track_component_history::<Health>(4);
+        let entity = app.world_mut().spawn(Health(10)).id();
+        app.update();
+
+        let component_id = app.world().component_id::<Health>().unwrap();
+        let history = app.world().resource::<ComponentHistory>();
+        assert_eq!(history.get(entity, component_id).count(), 1);
+    }
+
+    #[test]
+    fn export_schedule_graphs() {
+        fn spawn(mut commands: Commands) {
+            commands.spawn_empty();
+        }
+
+        let directory = std::env::temp_dir().join(alloc::format!(
+            "bevy_app_export_schedule_graphs_{}",
+            std::process::id()
+        ));
+        let mut app = App::new();
+        app.add_systems(Update, spawn);
+        app.export_schedule_graphs(&directory, GraphExportFormat::Mermaid)
+            .unwrap();
+
+        let update = std::fs::read_to_string(directory.join("Update.mmd")).unwrap();
+        assert!(update.starts_with("flowchart LR\n"));
+        assert!(directory.join("Main.mmd").exists());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
 }
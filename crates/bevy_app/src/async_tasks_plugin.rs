@@ -0,0 +1,18 @@
+use crate::{App, Plugin, PreUpdate};
+
+use bevy_ecs::async_world::{run_async_tasks, AsyncTasks};
+
+/// Drives the async tasks spawned with [`World::spawn_async`](bevy_ecs::world::World::spawn_async),
+/// by running [`run_async_tasks`] in [`PreUpdate`].
+///
+/// Without this plugin, async tasks can still be spawned, but they only make progress when
+/// [`run_async_tasks`] is run by hand.
+#[derive(Default)]
+pub struct AsyncTasksPlugin;
+
+impl Plugin for AsyncTasksPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AsyncTasks>()
+            .add_systems(PreUpdate, run_async_tasks);
+    }
+}
@@ -0,0 +1,493 @@
+//! Memory accounting for assets, and eviction of cached assets exceeding a memory budget.
+
+use crate::{Asset, AssetEvent, AssetId, AssetPath, AssetServer, Assets, Handle, UntypedAssetId};
+use alloc::{sync::Arc, sync::Weak, vec::Vec};
+use bevy_ecs::{
+    event::{BufferedEvent, EventReader, EventWriter},
+    resource::Resource,
+    system::{Res, ResMut},
+    world::World,
+};
+use bevy_platform::collections::HashMap;
+use core::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use parking_lot::Mutex;
+
+/// An [`Asset`] whose memory usage can be measured, to account for it in [`AssetMemory`].
+///
+/// See [`AssetApp::register_asset_memory_budget`](crate::AssetApp::register_asset_memory_budget).
+pub trait AssetSize: Asset {
+    /// Returns the number of bytes used by this asset, including its heap allocations.
+    fn byte_size(&self) -> usize;
+}
+
+/// Tracks the memory used by the assets of type `A` stored in [`Assets<A>`], and the budget they
+/// should fit in.
+///
+/// When the assets use more than the budget, the least recently used assets held only by
+/// [`CacheHandle`]s are evicted from [`Assets<A>`] until they fit again.
+///
+/// Assets which are only stored in the render world, such as images and meshes which
+/// only have the [`RenderAssetUsages::RENDER_WORLD`](crate::RenderAssetUsages::RENDER_WORLD) usage,
+/// aren't accounted for.
+#[derive(Resource)]
+pub struct AssetMemory<A: AssetSize> {
+    /// The number of bytes the assets should fit in, or [`None`] to never evict assets.
+    pub budget: Option<usize>,
+    sizes: HashMap<AssetId<A>, usize>,
+    total: usize,
+}
+
+impl<A: AssetSize> AssetMemory<A> {
+    /// Creates an [`AssetMemory`] with the given `budget`, in bytes.
+    pub fn new(budget: Option<usize>) -> Self {
+        Self {
+            budget,
+            sizes: HashMap::default(),
+            total: 0,
+        }
+    }
+
+    /// Returns the number of bytes used by the assets of type `A`.
+    pub fn total_bytes(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of bytes used by the asset `id`, if it is stored in [`Assets<A>`].
+    pub fn byte_size(&self, id: impl Into<AssetId<A>>) -> Option<usize> {
+        self.sizes.get(&id.into()).copied()
+    }
+
+    /// Returns `true` if the assets use more bytes than the budget.
+    pub fn is_over_budget(&self) -> bool {
+        self.budget.is_some_and(|budget| self.total > budget)
+    }
+
+    fn set(&mut self, id: AssetId<A>, size: usize) {
+        let previous = self.sizes.insert(id, size).unwrap_or(0);
+        self.total = self.total - previous + size;
+    }
+
+    fn remove(&mut self, id: AssetId<A>) {
+        if let Some(size) = self.sizes.remove(&id) {
+            self.total -= size;
+        }
+    }
+}
+
+impl<A: AssetSize> Default for AssetMemory<A> {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+/// A [`BufferedEvent`] emitted when an [`Asset`] is evicted from [`Assets<A>`] to fit in its
+/// [`AssetMemory`] budget.
+///
+/// Evicting an asset doesn't send an [`AssetEvent::Removed`], as the asset is still referenced by
+/// [`CacheHandle`]s, and is reloaded when they are used again. Render assets derived from the
+/// asset can be dropped until then.
+///
+/// The [`AssetServer`] isn't told about evictions: [`AssetServer::load_state`] and the other load
+/// states of an evicted asset stay [`LoadState::Loaded`](crate::LoadState::Loaded). Use
+/// [`Assets::contains`] or [`CacheHandle::get`] to know whether an asset is currently resident.
+#[derive(BufferedEvent, Clone, Debug)]
+pub struct AssetEvicted<A: Asset> {
+    /// The id of the evicted asset.
+    pub id: AssetId<A>,
+}
+
+const RESIDENT: u8 = 0;
+const EVICTED: u8 = 1;
+const RELOAD_REQUESTED: u8 = 2;
+
+struct CacheEntry<A: Asset> {
+    handle: Handle<A>,
+    last_used: AtomicU64,
+    clock: Arc<AtomicU64>,
+    state: AtomicU8,
+}
+
+impl<A: Asset> CacheEntry<A> {
+    fn touch(&self) {
+        let now = self.clock.fetch_add(1, Ordering::Relaxed);
+        self.last_used.store(now, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the asset is only kept alive by this entry.
+    fn is_only_cached(&self) -> bool {
+        match &self.handle {
+            Handle::Strong(handle) => Arc::strong_count(handle) == 1,
+            Handle::Uuid(..) => false,
+        }
+    }
+}
+
+/// A handle to an [`Asset`] which may be evicted from [`Assets<A>`] when the memory used by the
+/// assets of type `A` exceeds their [`AssetMemory`] budget.
+///
+/// An asset is only evicted when no [`Handle`] to it is alive besides the ones held by cache
+/// handles, and when it was loaded from a path so that it can be reloaded. Cache handles are
+/// created by [`AssetCache`]. Evicted assets are reloaded when they are accessed with
+/// [`CacheHandle::get`].
+pub struct CacheHandle<A: Asset> {
+    entry: Arc<CacheEntry<A>>,
+}
+
+impl<A: Asset> Clone for CacheHandle<A> {
+    fn clone(&self) -> Self {
+        Self {
+            entry: self.entry.clone(),
+        }
+    }
+}
+
+impl<A: Asset> core::fmt::Debug for CacheHandle<A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CacheHandle")
+            .field("id", &self.id())
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+impl<A: Asset> CacheHandle<A> {
+    /// Returns the [`AssetId`] of the cached asset.
+    pub fn id(&self) -> AssetId<A> {
+        self.entry.handle.id()
+    }
+
+    /// Returns the path of the cached asset, if it has one.
+    pub fn path(&self) -> Option<&AssetPath<'static>> {
+        self.entry.handle.path()
+    }
+
+    /// Returns the cached asset, marking it as recently used.
+    ///
+    /// If the asset was evicted, this returns [`None`] and requests the asset to be reloaded.
+    pub fn get<'a>(&self, assets: &'a Assets<A>) -> Option<&'a A> {
+        self.entry.touch();
+        match assets.get(self.id()) {
+            Some(asset) => {
+                self.entry.state.store(RESIDENT, Ordering::Relaxed);
+                Some(asset)
+            }
+            None => {
+                let _ = self.entry.state.compare_exchange(
+                    EVICTED,
+                    RELOAD_REQUESTED,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                );
+                None
+            }
+        }
+    }
+
+    /// Marks the cached asset as recently used, making it one of the last candidates for eviction.
+    pub fn touch(&self) {
+        self.entry.touch();
+    }
+
+    /// Returns a [`Handle`] to the cached asset, which prevents its eviction while it is alive.
+    pub fn handle(&self) -> Handle<A> {
+        self.entry.touch();
+        self.entry.handle.clone()
+    }
+}
+
+/// Creates the [`CacheHandle`]s of the assets of type `A`, and keeps track of them to evict the
+/// least recently used assets when they exceed their [`AssetMemory`] budget.
+#[derive(Resource)]
+pub struct AssetCache<A: Asset> {
+    entries: Mutex<HashMap<AssetId<A>, Weak<CacheEntry<A>>>>,
+    clock: Arc<AtomicU64>,
+}
+
+impl<A: Asset> Default for AssetCache<A> {
+    fn default() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::default()),
+            clock: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+impl<A: Asset> AssetCache<A> {
+    /// Begins loading the asset at `path`, like [`AssetServer::load`], returning a
+    /// [`CacheHandle`] to it.
+    pub fn load<'a>(
+        &self,
+        asset_server: &AssetServer,
+        path: impl Into<AssetPath<'a>>,
+    ) -> CacheHandle<A> {
+        self.insert(asset_server.load(path))
+    }
+
+    /// Returns a [`CacheHandle`] holding the given strong `handle`.
+    ///
+    /// If a cache handle to the same asset exists, a clone of it is returned.
+    pub fn insert(&self, handle: Handle<A>) -> CacheHandle<A> {
+        let mut entries = self.entries.lock();
+        if let Some(entry) = entries.get(&handle.id()).and_then(Weak::upgrade) {
+            entry.touch();
+            return CacheHandle { entry };
+        }
+        let entry = Arc::new(CacheEntry {
+            handle,
+            last_used: AtomicU64::new(0),
+            clock: self.clock.clone(),
+            state: AtomicU8::new(RESIDENT),
+        });
+        entry.touch();
+        entries.insert(entry.handle.id(), Arc::downgrade(&entry));
+        CacheHandle { entry }
+    }
+
+    /// Returns the number of assets with a living [`CacheHandle`].
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .values()
+            .filter(|entry| entry.strong_count() > 0)
+            .count()
+    }
+
+    /// Returns `true` if there is no living [`CacheHandle`].
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Returns the number of bytes used by the asset `id`, as tracked by [`AssetMemory<A>`].
+pub(crate) fn asset_byte_size<A: AssetSize>(world: &World, id: UntypedAssetId) -> Option<usize> {
+    world
+        .get_resource::<AssetMemory<A>>()?
+        .byte_size(id.try_typed::<A>().ok()?)
+}
+
+/// Updates [`AssetMemory<A>`] from the [`AssetEvent`]s of the assets of type `A`.
+pub fn track_asset_memory<A: AssetSize>(
+    mut events: EventReader<AssetEvent<A>>,
+    assets: Res<Assets<A>>,
+    mut memory: ResMut<AssetMemory<A>>,
+) {
+    for event in events.read() {
+        match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => match assets.get(id) {
+                Some(asset) => memory.set(id, asset.byte_size()),
+                None => memory.remove(id),
+            },
+            AssetEvent::Removed { id } | AssetEvent::Unused { id } => memory.remove(id),
+            AssetEvent::LoadedWithDependencies { .. } => {}
+        }
+    }
+}
+
+/// Reloads the evicted assets requested through [`CacheHandle::get`], then evicts the least
+/// recently used assets held only by [`CacheHandle`]s until the assets of type `A` fit in their
+/// [`AssetMemory`] budget.
+pub fn evict_cached_assets<A: AssetSize>(
+    cache: Res<AssetCache<A>>,
+    asset_server: Res<AssetServer>,
+    mut assets: ResMut<Assets<A>>,
+    mut memory: ResMut<AssetMemory<A>>,
+    mut evicted_events: EventWriter<AssetEvicted<A>>,
+) {
+    let mut entries = cache.entries.lock();
+    let mut candidates = Vec::new();
+    entries.retain(|_, entry| {
+        let Some(entry) = entry.upgrade() else {
+            return false;
+        };
+        // Marking the asset as resident while it reloads ensures it is only reloaded once.
+        if entry
+            .state
+            .compare_exchange(
+                RELOAD_REQUESTED,
+                RESIDENT,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+        {
+            if let Some(path) = entry.handle.path() {
+                asset_server.reload(path.clone());
+            }
+        } else if entry.is_only_cached()
+            && entry.handle.path().is_some()
+            && memory.byte_size(entry.handle.id()).is_some()
+        {
+            candidates.push(entry);
+        }
+        true
+    });
+    drop(entries);
+
+    if !memory.is_over_budget() {
+        return;
+    }
+    candidates.sort_by_key(|entry| entry.last_used.load(Ordering::Relaxed));
+    for entry in candidates {
+        if !memory.is_over_budget() {
+            break;
+        }
+        let id = entry.handle.id();
+        if assets.remove_untracked(id).is_some() {
+            memory.remove(id);
+            entry.state.store(EVICTED, Ordering::Relaxed);
+            evicted_events.write(AssetEvicted { id });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AssetCache, AssetEvicted, AssetMemory, AssetSize};
+    use crate::AssetLoader;
+    use crate::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceId, Reader,
+        },
+        tests::run_app_until,
+        Asset, AssetApp, AssetEvent, AssetId, AssetPlugin, AssetServer, Assets, LoadContext,
+        LoadState,
+    };
+    use alloc::{boxed::Box, vec, vec::Vec};
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_ecs::event::{EventCursor, Events};
+    use bevy_reflect::TypePath;
+    use std::path::Path;
+
+    #[derive(Asset, TypePath, Debug)]
+    struct Blob(Vec<u8>);
+
+    impl AssetSize for Blob {
+        fn byte_size(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    struct BlobLoader;
+
+    impl AssetLoader for BlobLoader {
+        type Asset = Blob;
+        type Settings = ();
+        type Error = std::io::Error;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            _settings: &Self::Settings,
+            _load_context: &mut LoadContext<'_>,
+        ) -> Result<Self::Asset, Self::Error> {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(Blob(bytes))
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["blob"]
+        }
+    }
+
+    fn test_app(budget: usize) -> App {
+        let dir = Dir::default();
+        for path in ["a.blob", "b.blob", "c.blob"] {
+            dir.insert_asset(Path::new(path), vec![0; 100]);
+        }
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<Blob>()
+        .register_asset_loader(BlobLoader)
+        .register_asset_memory_budget::<Blob>(Some(budget));
+        app
+    }
+
+    fn total_bytes(app: &App) -> usize {
+        app.world().resource::<AssetMemory<Blob>>().total_bytes()
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut app = test_app(250);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let cache = app.world().resource::<AssetCache<Blob>>();
+        let a = cache.load(&asset_server, "a.blob");
+        let b = cache.load(&asset_server, "b.blob");
+        let c = asset_server.load::<Blob>("c.blob");
+        a.touch();
+
+        // "b" is the least recently used asset, and "c" can't be evicted as it has a handle.
+        let mut cursor = EventCursor::<AssetEvicted<Blob>>::default();
+        let mut asset_cursor = EventCursor::<AssetEvent<Blob>>::default();
+        let mut evicted: Vec<AssetId<Blob>> = Vec::new();
+        run_app_until(&mut app, |world| {
+            let events = world.resource::<Events<AssetEvicted<Blob>>>();
+            evicted.extend(cursor.read(events).map(|event| event.id));
+            let asset_events = world.resource::<Events<AssetEvent<Blob>>>();
+            // Evictions aren't removals.
+            assert!(asset_cursor
+                .read(asset_events)
+                .all(|event| !matches!(event, AssetEvent::Removed { .. })));
+            (!evicted.is_empty()).then_some(())
+        });
+        assert_eq!(evicted, [b.id()]);
+        assert!(matches!(asset_server.load_state(b.id()), LoadState::Loaded));
+        let assets = app.world().resource::<Assets<Blob>>();
+        assert!(b.get(assets).is_none());
+        assert!(a.get(assets).is_some());
+        assert!(assets.contains(&c));
+        assert_eq!(total_bytes(&app), 200);
+
+        // Accessing "b" reloads it, which evicts "a" instead.
+        run_app_until(&mut app, |world| {
+            let assets = world.resource::<Assets<Blob>>();
+            (b.get(assets).is_some() && !assets.contains(a.id())).then_some(())
+        });
+        assert_eq!(total_bytes(&app), 200);
+        assert_eq!(
+            app.world()
+                .resource::<AssetMemory<Blob>>()
+                .byte_size(b.id()),
+            Some(100)
+        );
+    }
+
+    #[test]
+    fn handles_prevent_eviction() {
+        let mut app = test_app(0);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let cache = app.world().resource::<AssetCache<Blob>>();
+        let a = cache.load(&asset_server, "a.blob");
+        assert_eq!(cache.insert(a.handle()).id(), a.id());
+        assert_eq!(cache.len(), 1);
+        let handle = a.handle();
+
+        run_app_until(&mut app, |world| {
+            world
+                .resource::<Assets<Blob>>()
+                .contains(&handle)
+                .then_some(())
+        });
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(total_bytes(&app), 100);
+
+        drop(handle);
+        app.update();
+        assert_eq!(total_bytes(&app), 0);
+        assert!(!app.world().resource::<Assets<Blob>>().contains(a.id()));
+
+        // Dropping all cache handles releases the asset.
+        drop(a);
+        app.update();
+        assert!(app.world().resource::<AssetCache<Blob>>().is_empty());
+    }
+}
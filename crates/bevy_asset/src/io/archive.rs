@@ -0,0 +1,756 @@
+//! Packed archives of assets, read with an [`ArchiveAssetReader`].
+//!
+//! An archive stores the bytes of many assets and of their meta files in a single file, along
+//! with an index of their paths. Archives are written with an [`ArchiveWriter`], usually from the
+//! processed assets of an [`AssetProcessor`](crate::processor::AssetProcessor), and several of
+//! them can be layered in an [`ArchiveAssetReader`] to ship patches or mods on top of the base
+//! game assets.
+//!
+//! # Format
+//!
+//! All integers are little endian.
+//!
+//! ```text
+//! header: b"BPAK", version: u32
+//! data:   the bytes of every asset and meta file, one after the other
+//! index:  count: u32, then for each entry:
+//!         kind: u8 (0 for an asset, 1 for a meta file), path length: u32, path: UTF-8 with `/`
+//!         separators, offset: u64, length: u64
+//! footer: index offset: u64, b"BPAK"
+//! ```
+
+use crate::io::{
+    memory::Value, AssetReader, AssetReaderError, ErasedAssetReader, PathStream, Reader,
+    SliceReader,
+};
+use alloc::{boxed::Box, collections::BTreeSet, string::String, sync::Arc, vec, vec::Vec};
+use bevy_platform::collections::HashMap;
+use futures_lite::StreamExt;
+use std::{
+    io::Write,
+    path::{Component, Path, PathBuf},
+};
+use thiserror::Error;
+
+/// The bytes identifying an archive, at its start and at its end.
+const MAGIC: [u8; 4] = *b"BPAK";
+/// The version of the archive format written by [`ArchiveWriter`].
+const VERSION: u32 = 1;
+const HEADER_SIZE: usize = MAGIC.len() + size_of::<u32>();
+const FOOTER_SIZE: usize = size_of::<u64>() + MAGIC.len();
+
+const ASSET_ENTRY: u8 = 0;
+const META_ENTRY: u8 = 1;
+
+/// An error that occurs when opening an [`Archive`].
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    /// Encountered an I/O error while reading the archive.
+    #[error("encountered an io error while reading the archive: {0}")]
+    Io(#[from] std::io::Error),
+    /// The data does not start or end like an archive.
+    #[error("the data is not an asset archive")]
+    NotAnArchive,
+    /// The archive was written in a version of the format which is not supported.
+    #[error("unsupported archive version {0}, expected version {VERSION}")]
+    UnsupportedVersion(u32),
+    /// The index of the archive is invalid.
+    #[error("the index of the archive is corrupted")]
+    CorruptedIndex,
+}
+
+/// The location of the bytes of an asset or meta file in an [`Archive`].
+#[derive(Clone, Copy, Debug)]
+struct ArchiveEntry {
+    offset: u64,
+    len: u64,
+}
+
+#[derive(Default, Debug)]
+struct ArchiveIndex {
+    assets: HashMap<PathBuf, ArchiveEntry>,
+    meta: HashMap<PathBuf, ArchiveEntry>,
+    /// The assets and subdirectories of each directory containing assets.
+    directories: HashMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+impl ArchiveIndex {
+    fn parse(mut bytes: &[u8], data_end: u64) -> Result<Self, ArchiveError> {
+        let mut index = Self::default();
+        let count = read_u32(&mut bytes)?;
+        for _ in 0..count {
+            let kind = read_bytes(&mut bytes, 1)?[0];
+            let path_len = read_u32(&mut bytes)? as usize;
+            let path = core::str::from_utf8(read_bytes(&mut bytes, path_len)?)
+                .map_err(|_| ArchiveError::CorruptedIndex)?;
+            let entry = ArchiveEntry {
+                offset: read_u64(&mut bytes)?,
+                len: read_u64(&mut bytes)?,
+            };
+            let in_bounds = entry
+                .offset
+                .checked_add(entry.len)
+                .is_some_and(|end| entry.offset >= HEADER_SIZE as u64 && end <= data_end);
+            if !in_bounds {
+                return Err(ArchiveError::CorruptedIndex);
+            }
+            let path = PathBuf::from(path);
+            match kind {
+                ASSET_ENTRY => {
+                    let mut child = path.as_path();
+                    while let Some(parent) = child.parent() {
+                        index
+                            .directories
+                            .entry(parent.to_path_buf())
+                            .or_default()
+                            .insert(child.to_path_buf());
+                        child = parent;
+                    }
+                    index.assets.insert(path, entry);
+                }
+                META_ENTRY => {
+                    index.meta.insert(path, entry);
+                }
+                _ => return Err(ArchiveError::CorruptedIndex),
+            }
+        }
+        if !bytes.is_empty() {
+            return Err(ArchiveError::CorruptedIndex);
+        }
+        Ok(index)
+    }
+}
+
+fn read_bytes<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], ArchiveError> {
+    if bytes.len() < len {
+        return Err(ArchiveError::CorruptedIndex);
+    }
+    let (read, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(read)
+}
+
+fn read_u32(bytes: &mut &[u8]) -> Result<u32, ArchiveError> {
+    let read = read_bytes(bytes, size_of::<u32>())?;
+    Ok(u32::from_le_bytes(read.try_into().unwrap()))
+}
+
+fn read_u64(bytes: &mut &[u8]) -> Result<u64, ArchiveError> {
+    let read = read_bytes(bytes, size_of::<u64>())?;
+    Ok(u64::from_le_bytes(read.try_into().unwrap()))
+}
+
+/// Checks the header of an archive.
+fn check_header(header: &[u8]) -> Result<(), ArchiveError> {
+    if header.len() < HEADER_SIZE || header[..MAGIC.len()] != MAGIC {
+        return Err(ArchiveError::NotAnArchive);
+    }
+    let version = u32::from_le_bytes(header[MAGIC.len()..HEADER_SIZE].try_into().unwrap());
+    if version != VERSION {
+        return Err(ArchiveError::UnsupportedVersion(version));
+    }
+    Ok(())
+}
+
+/// Checks the footer of an archive of `len` bytes, returning the offset of its index.
+fn parse_footer(footer: &[u8], len: u64) -> Result<u64, ArchiveError> {
+    if footer.len() != FOOTER_SIZE || footer[size_of::<u64>()..] != MAGIC {
+        return Err(ArchiveError::NotAnArchive);
+    }
+    let index_offset = u64::from_le_bytes(footer[..size_of::<u64>()].try_into().unwrap());
+    if index_offset < HEADER_SIZE as u64 || index_offset > len - FOOTER_SIZE as u64 {
+        return Err(ArchiveError::CorruptedIndex);
+    }
+    Ok(index_offset)
+}
+
+#[derive(Clone, Debug)]
+enum ArchiveData {
+    Bytes(Value),
+    #[cfg(not(target_arch = "wasm32"))]
+    File(PathBuf),
+}
+
+/// A packed archive of assets, written by an [`ArchiveWriter`].
+///
+/// An archive is either held in memory, or read from a file on demand. It is cheap to clone.
+#[derive(Clone, Debug)]
+pub struct Archive {
+    data: ArchiveData,
+    index: Arc<ArchiveIndex>,
+}
+
+impl Archive {
+    /// Opens the archive stored in `bytes`.
+    ///
+    /// This is suited to small archives, or archives embedded in the executable with
+    /// [`include_bytes`].
+    pub fn from_bytes(bytes: impl Into<Value>) -> Result<Self, ArchiveError> {
+        let bytes = bytes.into();
+        let slice = bytes.as_slice();
+        check_header(slice)?;
+        if slice.len() < HEADER_SIZE + FOOTER_SIZE {
+            return Err(ArchiveError::NotAnArchive);
+        }
+        let footer_offset = slice.len() - FOOTER_SIZE;
+        let index_offset = parse_footer(&slice[footer_offset..], slice.len() as u64)?;
+        let index =
+            ArchiveIndex::parse(&slice[index_offset as usize..footer_offset], index_offset)?;
+        Ok(Self {
+            data: ArchiveData::Bytes(bytes),
+            index: Arc::new(index),
+        })
+    }
+
+    /// Opens the archive stored in the file at `path`.
+    ///
+    /// Only the index of the archive is read when opening it: the bytes of its assets are read
+    /// from the file when they are loaded.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, ArchiveError> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let path = path.into();
+        let mut file = std::fs::File::open(&path)?;
+        let len = file.metadata()?.len();
+        if len < (HEADER_SIZE + FOOTER_SIZE) as u64 {
+            return Err(ArchiveError::NotAnArchive);
+        }
+        let mut header = [0; HEADER_SIZE];
+        file.read_exact(&mut header)?;
+        check_header(&header)?;
+
+        let mut footer = [0; FOOTER_SIZE];
+        file.seek(SeekFrom::End(-(FOOTER_SIZE as i64)))?;
+        file.read_exact(&mut footer)?;
+        let index_offset = parse_footer(&footer, len)?;
+
+        let mut index = vec![0; (len - FOOTER_SIZE as u64 - index_offset) as usize];
+        file.seek(SeekFrom::Start(index_offset))?;
+        file.read_exact(&mut index)?;
+        let index = ArchiveIndex::parse(&index, index_offset)?;
+        Ok(Self {
+            data: ArchiveData::File(path),
+            index: Arc::new(index),
+        })
+    }
+
+    /// Returns `true` if the archive contains an asset at `path`.
+    pub fn contains_asset(&self, path: &Path) -> bool {
+        self.index.assets.contains_key(path)
+    }
+
+    /// Returns `true` if the archive contains the meta file of the asset at `path`.
+    pub fn contains_meta(&self, path: &Path) -> bool {
+        self.index.meta.contains_key(path)
+    }
+
+    /// Returns the paths of the assets in the archive, in no particular order.
+    pub fn asset_paths(&self) -> impl Iterator<Item = &Path> {
+        self.index.assets.keys().map(PathBuf::as_path)
+    }
+
+    async fn read_entry<'a>(
+        &'a self,
+        path: &Path,
+        entry: ArchiveEntry,
+    ) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        match &self.data {
+            ArchiveData::Bytes(bytes) => {
+                let start = entry.offset as usize;
+                let bytes = &bytes.as_slice()[start..start + entry.len as usize];
+                Ok(Box::new(SliceReader::new(bytes)))
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            ArchiveData::File(archive_path) => {
+                use futures_lite::{AsyncReadExt, AsyncSeekExt};
+
+                let mut file = async_fs::File::open(archive_path).await.map_err(|e| {
+                    if e.kind() == std::io::ErrorKind::NotFound {
+                        AssetReaderError::NotFound(path.to_path_buf())
+                    } else {
+                        e.into()
+                    }
+                })?;
+                file.seek(std::io::SeekFrom::Start(entry.offset)).await?;
+                let mut bytes = vec![0; entry.len as usize];
+                file.read_exact(&mut bytes).await?;
+                Ok(Box::new(crate::io::VecReader::new(bytes)))
+            }
+        }
+    }
+}
+
+/// An [`AssetReader`] reading assets from layered [`Archive`]s.
+///
+/// Later layers take precedence over earlier ones: an asset is read from the last archive that
+/// contains it, along with its meta file. This allows patches and mods to ship archives adding or
+/// replacing assets of the base archive, without repacking it.
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{
+/// #     io::{archive::{Archive, ArchiveAssetReader}, AssetSourceBuilder, AssetSourceId},
+/// #     AssetApp,
+/// # };
+/// # let mut app = App::new();
+/// let reader = ArchiveAssetReader::new(Archive::open("assets.pak").unwrap())
+///     .with_layer(Archive::open("patch.pak").unwrap());
+/// app.register_asset_source(
+///     AssetSourceId::Default,
+///     AssetSourceBuilder::default().with_reader(move || Box::new(reader.clone())),
+/// );
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct ArchiveAssetReader {
+    layers: Vec<Archive>,
+}
+
+impl ArchiveAssetReader {
+    /// Creates a reader reading assets from `archive`.
+    pub fn new(archive: Archive) -> Self {
+        Self {
+            layers: vec![archive],
+        }
+    }
+
+    /// Adds `archive` on top of the current layers, so that its assets replace theirs.
+    pub fn with_layer(mut self, archive: Archive) -> Self {
+        self.layers.push(archive);
+        self
+    }
+
+    /// Returns the layered archives, from the bottom one to the top one.
+    pub fn layers(&self) -> &[Archive] {
+        &self.layers
+    }
+
+    /// Returns the top archive containing the asset at `path`, or its meta file.
+    fn layer_of(&self, path: &Path) -> Option<&Archive> {
+        self.layers
+            .iter()
+            .rev()
+            .find(|archive| archive.contains_asset(path) || archive.contains_meta(path))
+    }
+}
+
+impl AssetReader for ArchiveAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let archive = self.layer_of(path);
+        match archive.and_then(|archive| Some((archive, *archive.index.assets.get(path)?))) {
+            Some((archive, entry)) => archive.read_entry(path, entry).await,
+            None => Err(AssetReaderError::NotFound(path.to_path_buf())),
+        }
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let archive = self.layer_of(path);
+        match archive.and_then(|archive| Some((archive, *archive.index.meta.get(path)?))) {
+            Some((archive, entry)) => archive.read_entry(path, entry).await,
+            None => Err(AssetReaderError::NotFound(path.to_path_buf())),
+        }
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let mut found = false;
+        let mut children = BTreeSet::new();
+        for archive in &self.layers {
+            if let Some(directory) = archive.index.directories.get(path) {
+                found = true;
+                children.extend(directory.iter().cloned());
+            }
+        }
+        if !found {
+            return Err(AssetReaderError::NotFound(path.to_path_buf()));
+        }
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(children));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(self
+            .layers
+            .iter()
+            .any(|archive| archive.index.directories.contains_key(path)))
+    }
+}
+
+/// An error that occurs when writing an archive with an [`ArchiveWriter`].
+#[derive(Error, Debug)]
+pub enum ArchiveWriteError {
+    /// Failed to read an asset or meta file to add to the archive.
+    #[error("failed to read an asset to add to the archive: {0}")]
+    Read(#[from] AssetReaderError),
+    /// Failed to write the archive.
+    #[error("failed to write the archive: {0}")]
+    Write(#[from] std::io::Error),
+}
+
+/// Writes an [`Archive`] of the bytes of assets and of their meta files.
+///
+/// Entries are streamed into the underlying [`Write`] as they are added, and only their paths
+/// are kept in memory until the index is written by [`ArchiveWriter::finish`]. If an asset or a
+/// meta file is added several times at the same path, the last one is read from the archive.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_asset::io::archive::{Archive, ArchiveWriter};
+/// # use std::path::Path;
+/// let mut writer = ArchiveWriter::new(Vec::new()).unwrap();
+/// writer
+///     .add_asset("textures/grass.png", b"...")
+///     .unwrap()
+///     .add_meta("textures/grass.png", b"(...)")
+///     .unwrap();
+/// let archive = Archive::from_bytes(writer.finish().unwrap()).unwrap();
+/// assert!(archive.contains_asset(Path::new("textures/grass.png")));
+/// ```
+#[derive(Debug)]
+pub struct ArchiveWriter<W: Write> {
+    writer: W,
+    position: u64,
+    entries: Vec<(u8, String, ArchiveEntry)>,
+    assets: usize,
+}
+
+/// Returns `path` with `/` separators, as stored in archives.
+///
+/// Fails if `path` is empty, isn't valid UTF-8, or isn't relative to the root of the archive.
+fn archive_path(path: &Path) -> std::io::Result<String> {
+    let invalid_path = || {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            alloc::format!("invalid archive path {path:?}"),
+        )
+    };
+    let mut archive_path = String::new();
+    for component in path.components() {
+        let Component::Normal(component) = component else {
+            return Err(invalid_path());
+        };
+        if !archive_path.is_empty() {
+            archive_path.push('/');
+        }
+        archive_path.push_str(component.to_str().ok_or_else(invalid_path)?);
+    }
+    if archive_path.is_empty() {
+        return Err(invalid_path());
+    }
+    Ok(archive_path)
+}
+
+/// Converts `len` to the `u32` stored in archives.
+fn archive_u32(len: usize, what: &str) -> std::io::Result<u32> {
+    u32::try_from(len).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            alloc::format!("too many {what} for an archive: {len}"),
+        )
+    })
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    /// Starts writing an archive into `writer`.
+    pub fn new(mut writer: W) -> std::io::Result<Self> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            position: HEADER_SIZE as u64,
+            entries: Vec::new(),
+            assets: 0,
+        })
+    }
+
+    fn add_entry(&mut self, kind: u8, path: &Path, bytes: &[u8]) -> std::io::Result<&mut Self> {
+        let path = archive_path(path)?;
+        archive_u32(path.len(), "bytes in a path")?;
+        self.writer.write_all(bytes)?;
+        let entry = ArchiveEntry {
+            offset: self.position,
+            len: bytes.len() as u64,
+        };
+        self.position += entry.len;
+        self.entries.push((kind, path, entry));
+        Ok(self)
+    }
+
+    /// Adds the asset at `path`.
+    ///
+    /// Fails with [`InvalidInput`](std::io::ErrorKind::InvalidInput) if `path` is absolute or
+    /// contains `.` or `..` components.
+    pub fn add_asset(
+        &mut self,
+        path: impl AsRef<Path>,
+        bytes: &[u8],
+    ) -> std::io::Result<&mut Self> {
+        self.add_entry(ASSET_ENTRY, path.as_ref(), bytes)?;
+        self.assets += 1;
+        Ok(self)
+    }
+
+    /// Adds the meta file of the asset at `path`.
+    ///
+    /// Fails like [`ArchiveWriter::add_asset`] on invalid paths.
+    pub fn add_meta(&mut self, path: impl AsRef<Path>, bytes: &[u8]) -> std::io::Result<&mut Self> {
+        self.add_entry(META_ENTRY, path.as_ref(), bytes)
+    }
+
+    /// Returns the number of assets added to the archive.
+    pub fn len(&self) -> usize {
+        self.assets
+    }
+
+    /// Returns `true` if no asset was added to the archive.
+    pub fn is_empty(&self) -> bool {
+        self.assets == 0
+    }
+
+    /// Adds the assets in the directory at `path` of `reader`, and in its subdirectories, along
+    /// with their meta files.
+    ///
+    /// The assets keep their path in `reader`. They are read one at a time, and added sorted by
+    /// path, so that packing the same assets always produces the same archive.
+    pub async fn add_directory(
+        &mut self,
+        reader: &dyn ErasedAssetReader,
+        path: &Path,
+    ) -> Result<&mut Self, ArchiveWriteError> {
+        let mut directories = vec![path.to_path_buf()];
+        while let Some(directory) = directories.pop() {
+            let mut paths: Vec<PathBuf> = reader.read_directory(&directory).await?.collect().await;
+            paths.sort();
+            let mut subdirectories = Vec::new();
+            for path in paths {
+                if reader.is_directory(&path).await? {
+                    subdirectories.push(path);
+                    continue;
+                }
+                let mut bytes = Vec::new();
+                reader.read(&path).await?.read_to_end(&mut bytes).await?;
+                self.add_asset(&path, &bytes)?;
+                match reader.read_meta_bytes(&path).await {
+                    Ok(meta) => {
+                        self.add_meta(&path, &meta)?;
+                    }
+                    Err(AssetReaderError::NotFound(_)) => {}
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            directories.extend(subdirectories.into_iter().rev());
+        }
+        Ok(self)
+    }
+
+    /// Writes the index of the archive, and returns the underlying writer.
+    ///
+    /// Fails with [`InvalidInput`](std::io::ErrorKind::InvalidInput) if more than [`u32::MAX`]
+    /// assets and meta files were added.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        let mut index = Vec::new();
+        index.extend_from_slice(&archive_u32(self.entries.len(), "entries")?.to_le_bytes());
+        for (kind, path, entry) in &self.entries {
+            index.push(*kind);
+            index.extend_from_slice(&archive_u32(path.len(), "bytes in a path")?.to_le_bytes());
+            index.extend_from_slice(path.as_bytes());
+            index.extend_from_slice(&entry.offset.to_le_bytes());
+            index.extend_from_slice(&entry.len.to_le_bytes());
+        }
+        index.extend_from_slice(&self.position.to_le_bytes());
+        index.extend_from_slice(&MAGIC);
+        self.writer.write_all(&index)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Archive, ArchiveAssetReader, ArchiveError, ArchiveWriter};
+    use crate::io::{
+        memory::{Dir, MemoryAssetReader},
+        AssetReader, AssetReaderError, Reader,
+    };
+    use alloc::{string::String, vec::Vec};
+    use futures_lite::StreamExt;
+    use std::path::{Path, PathBuf};
+
+    fn read(reader: &ArchiveAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        bevy_tasks::block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    fn read_meta(reader: &ArchiveAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        bevy_tasks::block_on(async {
+            let bytes = reader.read_meta_bytes(Path::new(path)).await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    fn base_archive() -> Archive {
+        let mut writer = ArchiveWriter::new(Vec::new()).unwrap();
+        writer
+            .add_asset("a.txt", b"a")
+            .unwrap()
+            .add_meta("a.txt", b"a meta")
+            .unwrap()
+            .add_asset("x/y/b.txt", b"b")
+            .unwrap()
+            .add_asset("x/c.txt", b"c")
+            .unwrap();
+        Archive::from_bytes(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn read_assets_and_directories() {
+        let reader = ArchiveAssetReader::new(base_archive());
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        assert_eq!(read_meta(&reader, "a.txt").unwrap(), "a meta");
+        assert_eq!(read(&reader, "x/y/b.txt").unwrap(), "b");
+        assert_eq!(
+            read(&reader, "missing.txt"),
+            Err(AssetReaderError::NotFound(PathBuf::from("missing.txt")))
+        );
+        assert!(read_meta(&reader, "x/c.txt").is_err());
+
+        let is_directory = |path| bevy_tasks::block_on(reader.is_directory(Path::new(path)));
+        assert!(is_directory("x/y").unwrap());
+        assert!(!is_directory("a.txt").unwrap());
+
+        let entries: Vec<_> = bevy_tasks::block_on(async {
+            reader
+                .read_directory(Path::new("x"))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        });
+        assert_eq!(entries, [PathBuf::from("x/c.txt"), PathBuf::from("x/y")]);
+    }
+
+    #[test]
+    fn later_layers_replace_assets() {
+        let mut patch = ArchiveWriter::new(Vec::new()).unwrap();
+        patch
+            .add_asset("a.txt", b"patched a")
+            .unwrap()
+            .add_asset("x/d.txt", b"d")
+            .unwrap();
+        let patch = Archive::from_bytes(patch.finish().unwrap()).unwrap();
+        let reader = ArchiveAssetReader::new(base_archive()).with_layer(patch);
+
+        assert_eq!(read(&reader, "a.txt").unwrap(), "patched a");
+        // The meta file of the base asset does not apply to the patched one.
+        assert!(read_meta(&reader, "a.txt").is_err());
+        assert_eq!(read(&reader, "x/c.txt").unwrap(), "c");
+        assert_eq!(read(&reader, "x/d.txt").unwrap(), "d");
+
+        let entries: Vec<_> = bevy_tasks::block_on(async {
+            reader
+                .read_directory(Path::new("x"))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        });
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[test]
+    fn pack_directory_of_reader() {
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("a.txt"), "a");
+        dir.insert_meta_text(Path::new("a.txt"), "a meta");
+        dir.insert_asset_text(Path::new("x/y/b.txt"), "b");
+        let source = MemoryAssetReader { root: dir };
+
+        let mut writer = ArchiveWriter::new(Vec::new()).unwrap();
+        bevy_tasks::block_on(writer.add_directory(&source, Path::new(""))).unwrap();
+        assert_eq!(writer.len(), 2);
+
+        let reader =
+            ArchiveAssetReader::new(Archive::from_bytes(writer.finish().unwrap()).unwrap());
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        assert_eq!(read_meta(&reader, "a.txt").unwrap(), "a meta");
+        assert_eq!(read(&reader, "x/y/b.txt").unwrap(), "b");
+    }
+
+    #[test]
+    fn read_archive_file() {
+        let path = std::env::temp_dir().join(alloc::format!(
+            "bevy_asset_archive_{}.pak",
+            std::process::id()
+        ));
+        let file = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+        let mut writer = ArchiveWriter::new(file).unwrap();
+        writer
+            .add_asset("a.txt", b"a")
+            .unwrap()
+            .add_meta("a.txt", b"a meta")
+            .unwrap();
+        writer.finish().unwrap();
+
+        let reader = ArchiveAssetReader::new(Archive::open(&path).unwrap());
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        assert_eq!(read_meta(&reader, "a.txt").unwrap(), "a meta");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn invalid_archives() {
+        assert!(matches!(
+            Archive::from_bytes(b"not an archive".to_vec()),
+            Err(ArchiveError::NotAnArchive)
+        ));
+
+        let mut writer = ArchiveWriter::new(Vec::new()).unwrap();
+        writer.add_asset("a.txt", b"a").unwrap();
+        let mut bytes = writer.finish().unwrap();
+        bytes[4] = 2;
+        assert!(matches!(
+            Archive::from_bytes(bytes.clone()),
+            Err(ArchiveError::UnsupportedVersion(2))
+        ));
+
+        bytes[4] = 1;
+        // Point the index before the header.
+        let footer = bytes.len() - 12;
+        bytes[footer..footer + 8].copy_from_slice(&2u64.to_le_bytes());
+        assert!(matches!(
+            Archive::from_bytes(bytes),
+            Err(ArchiveError::CorruptedIndex)
+        ));
+    }
+
+    #[test]
+    fn invalid_paths() {
+        let mut writer = ArchiveWriter::new(Vec::new()).unwrap();
+        for path in ["", "/a.txt", "../a.txt", "x/../a.txt", "./a.txt"] {
+            let err = writer.add_asset(path, b"a").err().unwrap();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{path}");
+            let err = writer.add_meta(path, b"a meta").err().unwrap();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{path}");
+        }
+        assert!(writer.is_empty());
+
+        writer.add_asset("x/a.txt", b"a").unwrap();
+        let archive = Archive::from_bytes(writer.finish().unwrap()).unwrap();
+        assert_eq!(
+            read(&ArchiveAssetReader::new(archive), "x/a.txt").unwrap(),
+            "a"
+        );
+    }
+}
@@ -198,7 +198,14 @@ impl Data {
         &self.path
     }
     fn value(&self) -> &[u8] {
-        match &self.value {
+        self.value.as_slice()
+    }
+}
+
+impl Value {
+    /// Returns the stored bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
             Value::Vec(vec) => vec,
             Value::Static(value) => value,
         }
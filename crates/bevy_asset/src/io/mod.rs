@@ -7,6 +7,7 @@ compile_error!(
 
 #[cfg(target_os = "android")]
 pub mod android;
+pub mod archive;
 pub mod embedded;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod file;
@@ -174,6 +174,7 @@ pub mod prelude {
 
 mod asset_changed;
 mod assets;
+mod budget;
 mod direct_access_ext;
 mod event;
 mod folder;
@@ -188,6 +189,7 @@ mod server;
 
 pub use assets::*;
 pub use bevy_asset_macros::Asset;
+pub use budget::*;
 pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
@@ -256,6 +258,15 @@ pub struct AssetPlugin {
     /// Approved folders are [`AssetPlugin::file_path`] and the folder of each
     /// [`AssetSource`](io::AssetSource). Subfolders within these folders are also valid.
     pub unapproved_path_mode: UnapprovedPathMode,
+    /// The maximum number of asset loads running at the same time, or [`None`] for no limit.
+    ///
+    /// See [`AssetServer::set_max_concurrent_loads`].
+    pub max_concurrent_loads: Option<usize>,
+    /// The maximum number of assets processed at the same time, or [`None`] for no limit. Only
+    /// used with [`AssetMode::Processed`] and the `asset_processor` feature.
+    ///
+    /// See [`AssetProcessor::set_max_concurrent_processing`].
+    pub max_concurrent_processing: Option<usize>,
 }
 
 /// Determines how to react to attempts to load assets not inside the approved folders.
@@ -332,6 +343,8 @@ impl Default for AssetPlugin {
             watch_for_changes_override: None,
             meta_check: AssetMetaCheck::default(),
             unapproved_path_mode: UnapprovedPathMode::default(),
+            max_concurrent_loads: None,
+            max_concurrent_processing: None,
         }
     }
 }
@@ -380,6 +393,8 @@ impl Plugin for AssetPlugin {
                     {
                         let mut builders = app.world_mut().resource_mut::<AssetSourceBuilders>();
                         let processor = AssetProcessor::new(&mut builders);
+                        processor.set_max_concurrent_processing(self.max_concurrent_processing);
+                        processor.data.set_send_progress_events(true);
                         let mut sources = builders.build_sources(false, watch);
                         sources.gate_on_processor(processor.data.clone());
                         // the main asset server shares loaders with the processor asset server
@@ -392,7 +407,10 @@ impl Plugin for AssetPlugin {
                             self.unapproved_path_mode.clone(),
                         ))
                         .insert_resource(processor)
-                        .add_systems(bevy_app::Startup, AssetProcessor::start);
+                        .init_resource::<processor::ProcessorProgress>()
+                        .add_event::<processor::AssetProcessingEvent>()
+                        .add_systems(bevy_app::Startup, AssetProcessor::start)
+                        .add_systems(PreUpdate, AssetProcessor::update_progress);
                     }
                     #[cfg(not(feature = "asset_processor"))]
                     {
@@ -409,6 +427,9 @@ impl Plugin for AssetPlugin {
                 }
             }
         }
+        app.world()
+            .resource::<AssetServer>()
+            .set_max_concurrent_loads(self.max_concurrent_loads);
         app.insert_resource(embedded)
             .init_asset::<LoadedFolder>()
             .init_asset::<LoadedUntypedAsset>()
@@ -539,6 +560,11 @@ pub trait AssetApp {
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self;
+    /// Tracks the memory used by the assets of type `A` in [`AssetMemory<A>`], and evicts the least recently
+    /// used assets held only by [`CacheHandle`]s from [`Assets<A>`] when they use more than `budget` bytes.
+    ///
+    /// The [`Asset`] must be initialized with [`AssetApp::init_asset`] first.
+    fn register_asset_memory_budget<A: AssetSize>(&mut self, budget: Option<usize>) -> &mut Self;
 }
 
 impl AssetApp for App {
@@ -609,6 +635,7 @@ impl AssetApp for App {
             .allow_ambiguous_resource::<Assets<A>>()
             .add_event::<AssetEvent<A>>()
             .add_event::<AssetLoadFailedEvent<A>>()
+            .add_event::<AssetEvicted<A>>()
             .register_type::<Handle<A>>()
             .add_systems(
                 PostUpdate,
@@ -645,6 +672,24 @@ impl AssetApp for App {
             .preregister_loader::<L>(extensions);
         self
     }
+
+    fn register_asset_memory_budget<A: AssetSize>(&mut self, budget: Option<usize>) -> &mut Self {
+        self.world()
+            .resource::<AssetServer>()
+            .data
+            .infos
+            .write()
+            .byte_size_getters
+            .insert(TypeId::of::<A>(), asset_byte_size::<A>);
+        self.insert_resource(AssetMemory::<A>::new(budget))
+            .init_resource::<AssetCache<A>>()
+            .add_systems(
+                PostUpdate,
+                (track_asset_memory::<A>, evict_cached_assets::<A>)
+                    .chain()
+                    .after(AssetEventSystems),
+            )
+    }
 }
 
 /// A system set that holds all "track asset" operations.
@@ -677,7 +722,7 @@ mod tests {
         },
         loader::{AssetLoader, LoadContext},
         Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, LoadState, UnapprovedPathMode,
+        AssetPlugin, AssetServer, Assets, LoadPriority, LoadState, UnapprovedPathMode,
     };
     use alloc::{
         boxed::Box,
@@ -1667,6 +1712,209 @@ mod tests {
         });
     }
 
+    fn queued_loads_test_app() -> (App, GateOpener) {
+        let dir = Dir::default();
+        for name in ["a", "b", "c", "d"] {
+            dir.insert_asset_text(
+                Path::new(&format!("{name}.cool.ron")),
+                &format!(
+                    "(text: \"{name}\", dependencies: [], embedded_dependencies: [], sub_texts: [])"
+                ),
+            );
+        }
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        app.world()
+            .resource::<AssetServer>()
+            .set_max_concurrent_loads(Some(1));
+        (app, gate_opener)
+    }
+
+    #[test]
+    fn load_priorities() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let (mut app, gate_opener) = queued_loads_test_app();
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let b: Handle<CoolText> = asset_server.load_with_priority("b.cool.ron", LoadPriority::LOW);
+        let c: Handle<CoolText> = asset_server.load_with_priority("c.cool.ron", LoadPriority::HIGH);
+        let d: Handle<CoolText> = asset_server.load("d.cool.ron");
+        app.update();
+
+        assert!(asset_server.load_state(&a).is_loading());
+        assert!(!asset_server.load_state(&a).is_queued());
+        for handle in [&b, &c, &d] {
+            assert!(asset_server.load_state(handle).is_queued());
+        }
+        assert_eq!(asset_server.pending_load_counts(), (1, 3));
+        assert_eq!(asset_server.get_load_priority(&c), Some(LoadPriority::HIGH));
+
+        // Re-prioritize "d" above "c". Loads which aren't queued can't be re-prioritized.
+        assert!(asset_server.set_load_priority(&d, LoadPriority(200)));
+        assert!(!asset_server.set_load_priority(&a, LoadPriority::HIGH));
+
+        // Queued loads start one at a time, by order of priority.
+        let queued_after_load: [(&str, &Handle<CoolText>, &[&Handle<CoolText>]); 3] = [
+            ("a.cool.ron", &a, &[&b, &c]),
+            ("d.cool.ron", &d, &[&b]),
+            ("c.cool.ron", &c, &[]),
+        ];
+        for (path, handle, queued) in queued_after_load {
+            gate_opener.open(path);
+            run_app_until(&mut app, |world| {
+                get::<CoolText>(world, handle.id())?;
+                Some(())
+            });
+            assert!(asset_server.load_state(handle).is_loaded());
+            for queued in queued {
+                assert!(asset_server.load_state(*queued).is_queued());
+            }
+        }
+
+        gate_opener.open("b.cool.ron");
+        run_app_until(&mut app, |world| {
+            let b = get::<CoolText>(world, b.id())?;
+            assert_eq!(b.text, "b");
+            Some(())
+        });
+        assert_eq!(asset_server.pending_load_counts(), (0, 0));
+    }
+
+    #[test]
+    fn cancel_load_when_handles_are_dropped() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let (mut app, gate_opener) = queued_loads_test_app();
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let b: Handle<CoolText> = asset_server.load("b.cool.ron");
+        let (a_id, b_id) = (a.id(), b.id());
+        app.update();
+        assert!(asset_server.load_state(a_id).is_loading());
+        assert!(asset_server.load_state(b_id).is_queued());
+
+        // Dropping the handle of a queued load removes it from the queue before it reads anything.
+        drop(b);
+        app.update();
+        assert!(matches!(
+            asset_server.load_state(b_id),
+            LoadState::NotLoaded
+        ));
+        assert_eq!(asset_server.pending_load_counts(), (1, 0));
+
+        // Dropping the handle of a running load cancels it and frees its slot.
+        drop(a);
+        app.update();
+        assert!(matches!(
+            asset_server.load_state(a_id),
+            LoadState::NotLoaded
+        ));
+        assert_eq!(asset_server.pending_load_counts(), (0, 0));
+
+        let c: Handle<CoolText> = asset_server.load("c.cool.ron");
+        app.update();
+        assert!(!asset_server.load_state(&c).is_queued());
+        gate_opener.open("a.cool.ron");
+        gate_opener.open("c.cool.ron");
+        run_app_until(&mut app, |world| {
+            get::<CoolText>(world, c.id())?;
+            Some(())
+        });
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(get::<CoolText>(app.world(), a_id).is_none());
+        assert!(get::<CoolText>(app.world(), b_id).is_none());
+        assert_eq!(app.world().resource::<Assets<CoolText>>().len(), 1);
+
+        // A cancelled asset can be loaded again.
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        gate_opener.open("a.cool.ron");
+        run_app_until(&mut app, |world| {
+            let a = get::<CoolText>(world, a.id())?;
+            assert_eq!(a.text, "a");
+            Some(())
+        });
+    }
+
+    #[test]
+    fn dependency_graph() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            r#"(text: "a", dependencies: ["b.cool.ron", "c.cool.ron"], embedded_dependencies: [], sub_texts: [])"#,
+        );
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            r#"(text: "b", dependencies: [], embedded_dependencies: [], sub_texts: [])"#,
+        );
+        dir.insert_asset_text(
+            Path::new("c.cool.ron"),
+            r#"(text: "c", dependencies: ["d.cool.ron"], embedded_dependencies: [], sub_texts: [])"#,
+        );
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        for path in ["a.cool.ron", "b.cool.ron", "c.cool.ron", "d.cool.ron"] {
+            gate_opener.open(path);
+        }
+        run_app_until(&mut app, |_| {
+            asset_server
+                .recursive_dependency_load_state(&a)
+                .is_failed()
+                .then_some(())
+        });
+
+        let graph = asset_server.dependency_graph();
+        let paths: Vec<_> = graph
+            .nodes
+            .iter()
+            .map(|node| node.path.as_ref().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            ["a.cool.ron", "b.cool.ron", "c.cool.ron", "d.cool.ron"]
+        );
+        let node = |path: &str| graph.get_by_path(&AssetPath::from(path)).next().unwrap();
+        let (a, b, c, d) = (
+            node("a.cool.ron"),
+            node("b.cool.ron"),
+            node("c.cool.ron"),
+            node("d.cool.ron"),
+        );
+
+        assert_eq!(a.type_path, Some(CoolText::type_path()));
+        assert!(a.load_state.is_loaded());
+        assert!(c.load_state.is_loaded());
+        assert!(d.load_state.is_failed());
+        let mut a_dependencies = vec![b.id, c.id];
+        a_dependencies.sort_unstable();
+        assert_eq!(a.dependencies, a_dependencies);
+        assert_eq!(b.dependants, [a.id]);
+        assert_eq!(c.dependencies, [d.id]);
+        assert_eq!(d.dependants, [c.id]);
+        assert_eq!(
+            graph.roots().map(|node| node.id).collect::<Vec<_>>(),
+            [a.id]
+        );
+        assert_eq!(a.byte_size, None);
+    }
+
     /// Tests that `AssetLoadFailedEvent<A>` events are emitted and can be used to retry failed assets.
     #[test]
     fn load_error_events() {
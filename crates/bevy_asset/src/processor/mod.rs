@@ -39,27 +39,38 @@
 
 mod log;
 mod process;
+mod progress;
+mod slots;
 
 pub use log::*;
 pub use process::*;
+pub use progress::*;
+
+use slots::ProcessingSlots;
 
 use crate::{
     io::{
+        archive::{ArchiveWriteError, ArchiveWriter},
         AssetReaderError, AssetSource, AssetSourceBuilders, AssetSourceEvent, AssetSourceId,
         AssetSources, AssetWriterError, ErasedAssetReader, ErasedAssetWriter,
-        MissingAssetSourceError,
+        MissingAssetSourceError, MissingProcessedAssetReaderError,
     },
     meta::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
         AssetMetaDyn, AssetMetaMinimal, ProcessedInfo, ProcessedInfoMinimal,
     },
     AssetLoadError, AssetMetaCheck, AssetPath, AssetServer, AssetServerMode, DeserializeMetaError,
-    MissingAssetLoaderForExtensionError, UnapprovedPathMode, WriteDefaultMetaError,
+    ErasedLoadedAsset, MissingAssetLoaderForExtensionError, UnapprovedPathMode,
+    WriteDefaultMetaError,
+};
+use alloc::{
+    borrow::ToOwned, boxed::Box, collections::VecDeque, string::ToString, sync::Arc, vec, vec::Vec,
 };
-use alloc::{borrow::ToOwned, boxed::Box, collections::VecDeque, sync::Arc, vec, vec::Vec};
+use bevy_app::{App, AppExit, PluginsState};
 use bevy_ecs::prelude::*;
 use bevy_platform::collections::{HashMap, HashSet};
 use bevy_tasks::IoTaskPool;
+use core::sync::atomic::{AtomicBool, Ordering};
 use futures_io::ErrorKind;
 use futures_lite::{AsyncReadExt, AsyncWriteExt, StreamExt};
 use parking_lot::RwLock;
@@ -69,7 +80,6 @@ use tracing::{debug, error, trace, warn};
 
 #[cfg(feature = "trace")]
 use {
-    alloc::string::ToString,
     bevy_tasks::ConditionalSendFuture,
     tracing::{info_span, instrument::Instrument},
 };
@@ -108,6 +118,12 @@ pub struct AssetProcessorData {
     initialized_receiver: async_broadcast::Receiver<()>,
     finished_sender: async_broadcast::Sender<()>,
     finished_receiver: async_broadcast::Receiver<()>,
+    processing_slots: ProcessingSlots,
+    progress: RwLock<ProcessorProgress>,
+    /// Whether [`AssetProcessingEvent`]s are sent, which is only the case when a system drains them.
+    send_progress_events: AtomicBool,
+    progress_sender: crossbeam_channel::Sender<AssetProcessingEvent>,
+    progress_receiver: crossbeam_channel::Receiver<AssetProcessingEvent>,
 }
 
 impl AssetProcessor {
@@ -168,6 +184,134 @@ impl AssetProcessor {
         &self.data.sources
     }
 
+    /// Waits until processing has finished, then writes an archive of the processed assets of
+    /// `source` into `writer`, along with their meta files, and returns `writer`.
+    ///
+    /// The assets are streamed into `writer` one at a time. The archive can be read with an
+    /// [`ArchiveAssetReader`](crate::io::archive::ArchiveAssetReader) registered as the reader of
+    /// the source, to ship the processed assets in a single file.
+    pub async fn pack_processed_assets<W: std::io::Write>(
+        &self,
+        source: AssetSourceId<'_>,
+        writer: W,
+    ) -> Result<W, PackProcessedAssetsError> {
+        self.data.wait_until_finished().await;
+        let reader = self.get_source(source)?.processed_reader()?;
+        let mut writer = ArchiveWriter::new(writer).map_err(ArchiveWriteError::from)?;
+        writer.add_directory(reader, Path::new("")).await?;
+        Ok(writer.finish().map_err(ArchiveWriteError::from)?)
+    }
+
+    /// Waits until processing has finished, then lists the source assets which are not
+    /// transitively referenced by any of the `roots`.
+    ///
+    /// An asset references the assets it depended on while being processed, and the assets its
+    /// processed version loads or depends on when it is loaded. Assets listed as unused can
+    /// usually be removed from builds which only load the `roots` directly.
+    ///
+    /// The references of an asset are only known once it is loaded, so this loads and decodes the
+    /// processed version of every referenced asset, one at a time and without keeping it around.
+    /// This can take about as long as loading all of them, and is meant to be run by build tools
+    /// rather than by a running app.
+    pub async fn unused_assets_report<'a>(
+        &self,
+        roots: impl IntoIterator<Item = impl Into<AssetPath<'a>>>,
+    ) -> UnusedAssetsReport {
+        self.data.wait_until_finished().await;
+        let mut referenced: HashSet<AssetPath<'static>> = HashSet::default();
+        let mut queue: VecDeque<_> = roots
+            .into_iter()
+            .map(|root| root.into().without_label().into_owned())
+            .collect();
+        while let Some(path) = queue.pop_front() {
+            if !referenced.insert(path.clone()) {
+                continue;
+            }
+            let mut references = self.load_references(&path).await;
+            if let Some(processed_info) = self
+                .data
+                .asset_infos
+                .read()
+                .await
+                .get(&path)
+                .and_then(|info| info.processed_info.as_ref())
+            {
+                references.extend(
+                    processed_info
+                        .process_dependencies
+                        .iter()
+                        .map(|dependency| dependency.path.clone()),
+                );
+            }
+            queue.extend(
+                references
+                    .into_iter()
+                    .filter(|reference| !referenced.contains(reference)),
+            );
+        }
+
+        let mut unused: Vec<_> = self
+            .data
+            .asset_infos
+            .read()
+            .await
+            .infos
+            .iter()
+            .filter(|(path, info)| {
+                info.status != Some(ProcessStatus::NonExistent) && !referenced.contains(*path)
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+        let mut referenced: Vec<_> = referenced.into_iter().collect();
+        referenced.sort_by_cached_key(ToString::to_string);
+        unused.sort_by_cached_key(ToString::to_string);
+        UnusedAssetsReport { referenced, unused }
+    }
+
+    /// Loads the processed version of the asset at `path` without its dependencies, and returns
+    /// the paths of the assets it references.
+    async fn load_references(&self, path: &AssetPath<'static>) -> Vec<AssetPath<'static>> {
+        fn collect(
+            server: &AssetServer,
+            asset: &ErasedLoadedAsset,
+            references: &mut Vec<AssetPath<'static>>,
+        ) {
+            references.extend(asset.loader_dependencies.keys().cloned());
+            references.extend(
+                asset
+                    .dependencies
+                    .iter()
+                    .filter_map(|id| server.get_path(*id))
+                    .map(|path| path.without_label().into_owned()),
+            );
+            for labeled in asset.labeled_assets.values() {
+                collect(server, &labeled.asset, references);
+            }
+        }
+
+        let mut references = Vec::new();
+        let loaded = match self.server.get_meta_loader_and_reader(path, None).await {
+            Ok((meta, loader, mut reader)) => {
+                self.server
+                    .load_with_meta_loader_and_reader(
+                        path,
+                        meta.as_ref(),
+                        &*loader,
+                        &mut *reader,
+                        false,
+                        false,
+                    )
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        match loaded {
+            Ok(asset) => collect(&self.server, &asset, &mut references),
+            Err(err) => debug!("Failed to load {path} to find the assets it references: {err}"),
+        }
+        references
+    }
+
     /// Logs an unrecoverable error. On the next run of the processor, all assets will be regenerated. This should only be used as a last resort.
     /// Every call to this should be considered with scrutiny and ideally replaced with something more granular.
     async fn log_unrecoverable(&self) {
@@ -205,6 +349,112 @@ impl AssetProcessor {
         }
     }
 
+    /// An [`App`] runner which processes all assets once, then exits without running the app.
+    ///
+    /// This is meant to be the entry point of a command line tool which builds the processed
+    /// assets, for example in CI: the returned [`AppExit`] is an error if an asset failed to
+    /// process, so the tool can exit with a failure code.
+    ///
+    /// The app must use [`AssetMode::Processed`](crate::AssetMode::Processed), and initialize the
+    /// task pools (for example with `TaskPoolPlugin`).
+    ///
+    /// ```no_run
+    /// # use bevy_app::{App, TaskPoolPlugin};
+    /// # use bevy_asset::{processor::AssetProcessor, AssetMode, AssetPlugin};
+    /// fn main() {
+    ///     let exit = App::new()
+    ///         .add_plugins((
+    ///             TaskPoolPlugin::default(),
+    ///             AssetPlugin {
+    ///                 mode: AssetMode::Processed,
+    ///                 ..Default::default()
+    ///             },
+    ///         ))
+    ///         // Register the asset loaders and processors here
+    ///         .set_runner(AssetProcessor::run_headless)
+    ///         .run();
+    ///     if exit.is_error() {
+    ///         std::process::exit(1);
+    ///     }
+    /// }
+    /// ```
+    pub fn run_headless(mut app: App) -> AppExit {
+        if app.plugins_state() != PluginsState::Cleaned {
+            while app.plugins_state() == PluginsState::Adding {
+                #[cfg(not(target_arch = "wasm32"))]
+                bevy_tasks::tick_global_task_pools_on_main_thread();
+            }
+            app.finish();
+            app.cleanup();
+        }
+        let Some(processor) = app.world().get_resource::<AssetProcessor>().cloned() else {
+            error!("Cannot process assets: the app has no AssetProcessor. Make sure AssetPlugin uses AssetMode::Processed.");
+            return AppExit::error();
+        };
+
+        #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
+        {
+            let _ = processor;
+            error!("Cannot run AssetProcessor in single threaded mode (or Wasm) yet.");
+            AppExit::error()
+        }
+        #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+        {
+            // The app's systems never run, so the progress events would never be read
+            processor.data.set_send_progress_events(false);
+            processor.process_assets();
+            let progress = processor.progress();
+            let mut failed: Vec<_> = progress.failed().map(ToString::to_string).collect();
+            if failed.is_empty() {
+                tracing::info!("Processed {} assets", progress.len());
+                AppExit::Success
+            } else {
+                failed.sort();
+                error!(
+                    "Failed to process {} of {} assets: {}",
+                    failed.len(),
+                    progress.len(),
+                    failed.join(", ")
+                );
+                AppExit::error()
+            }
+        }
+    }
+
+    /// Sets the maximum number of assets processed at the same time, or [`None`] for no limit,
+    /// which is the default. A limit of `0` is treated as `1`.
+    ///
+    /// Limiting processing keeps memory usage in check when processing many large assets. Assets
+    /// which other assets (or the app) wait for are processed even when the limit is reached.
+    pub fn set_max_concurrent_processing(&self, max: Option<usize>) {
+        self.data.processing_slots.set_max(max);
+    }
+
+    /// Returns the maximum number of assets processed at the same time.
+    ///
+    /// See [`AssetProcessor::set_max_concurrent_processing`].
+    pub fn max_concurrent_processing(&self) -> Option<usize> {
+        self.data.processing_slots.max()
+    }
+
+    /// Returns a snapshot of the [`ProcessingStage`] of each asset seen by the processor.
+    pub fn progress(&self) -> ProcessorProgress {
+        self.data.progress.read().clone()
+    }
+
+    /// Updates the [`ProcessorProgress`] resource, and sends the [`AssetProcessingEvent`]s
+    /// reported by the processor since the last update.
+    pub fn update_progress(
+        processor: Res<Self>,
+        mut progress: ResMut<ProcessorProgress>,
+        mut events: EventWriter<AssetProcessingEvent>,
+    ) {
+        for event in processor.data.progress_receiver.try_iter() {
+            progress.apply(&event);
+            events.write(event);
+        }
+    }
+
     /// Processes all assets. This will:
     /// * For each "processed [`AssetSource`]:
     /// * Scan the [`ProcessorTransactionLog`] and recover from any failures detected
@@ -785,9 +1035,21 @@ impl AssetProcessor {
     /// [`ProcessorGatedReader`]: crate::io::processor_gated::ProcessorGatedReader
     async fn process_asset(&self, source: &AssetSource, path: PathBuf) {
         let asset_path = AssetPath::from(path).with_source(source.id());
+        self.data
+            .report_progress(&asset_path, ProcessingStage::Queued);
+        let slot = self.data.processing_slots.acquire(&asset_path).await;
+        self.data
+            .report_progress(&asset_path, ProcessingStage::Processing);
         let result = self.process_asset_internal(source, &asset_path).await;
+        drop(slot);
         let mut infos = self.data.asset_infos.write().await;
-        infos.finish_processing(asset_path, result).await;
+        infos.finish_processing(asset_path.clone(), result).await;
+        let stage = match infos.get(&asset_path).and_then(|info| info.status) {
+            Some(ProcessStatus::Failed) => ProcessingStage::Failed,
+            _ => ProcessingStage::Finished,
+        };
+        drop(infos);
+        self.data.report_progress(&asset_path, stage);
     }
 
     async fn process_asset_internal(
@@ -1068,6 +1330,8 @@ impl AssetProcessorData {
         finished_sender.set_overflow(true);
         initialized_sender.set_overflow(true);
 
+        let (progress_sender, progress_receiver) = crossbeam_channel::unbounded();
+
         AssetProcessorData {
             sources: source,
             finished_sender,
@@ -1079,6 +1343,39 @@ impl AssetProcessorData {
             processors: Default::default(),
             asset_infos: Default::default(),
             default_processors: Default::default(),
+            processing_slots: Default::default(),
+            progress: Default::default(),
+            send_progress_events: AtomicBool::new(false),
+            progress_sender,
+            progress_receiver,
+        }
+    }
+
+    /// Records that the asset at `path` moved to `stage`, and sends the matching
+    /// [`AssetProcessingEvent`].
+    fn report_progress(&self, path: &AssetPath<'static>, stage: ProcessingStage) {
+        let event = AssetProcessingEvent {
+            path: path.clone(),
+            stage,
+        };
+        self.progress.write().apply(&event);
+        if self.send_progress_events.load(Ordering::Relaxed) {
+            // The receiver lives as long as `self`, so this can't fail
+            let _ = self.progress_sender.send(event);
+        }
+    }
+
+    /// Sets whether [`AssetProcessingEvent`]s are sent to [`AssetProcessor::update_progress`].
+    /// This must only be enabled while that system runs, as nothing else drains the events.
+    #[cfg(any(
+        feature = "asset_processor",
+        all(not(target_arch = "wasm32"), feature = "multi_threaded")
+    ))]
+    pub(crate) fn set_send_progress_events(&self, send: bool) {
+        self.send_progress_events.store(send, Ordering::Relaxed);
+        if !send {
+            // Drop the events nothing will read anymore
+            self.progress_receiver.try_iter().for_each(drop);
         }
     }
 
@@ -1091,8 +1388,13 @@ impl AssetProcessorData {
             match info {
                 Some(info) => match info.status {
                     Some(result) => return result,
-                    // This receiver must be created prior to losing the read lock to ensure this is transactional
-                    None => info.status_receiver.clone(),
+                    None => {
+                        // Something needs this asset now: don't make it wait behind other assets
+                        // for a processing slot, which could also deadlock when the waiter holds one.
+                        self.processing_slots.prioritize(path.clone());
+                        // This receiver must be created prior to losing the read lock to ensure this is transactional
+                        info.status_receiver.clone()
+                    }
                 },
                 None => return ProcessStatus::NonExistent,
             }
@@ -1451,6 +1753,17 @@ impl ProcessorAssetInfos {
     }
 }
 
+/// The source assets referenced, and not referenced, by a set of root assets.
+///
+/// Returned by [`AssetProcessor::unused_assets_report`].
+#[derive(Clone, Debug, Default)]
+pub struct UnusedAssetsReport {
+    /// The paths of the roots and of the assets they transitively reference.
+    pub referenced: Vec<AssetPath<'static>>,
+    /// The paths of the source assets which no root transitively references.
+    pub unused: Vec<AssetPath<'static>>,
+}
+
 /// The current state of the [`AssetProcessor`].
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum ProcessorState {
@@ -1464,6 +1777,18 @@ pub enum ProcessorState {
     Finished,
 }
 
+/// An error that occurs when packing processed assets with
+/// [`AssetProcessor::pack_processed_assets`].
+#[derive(Error, Debug)]
+pub enum PackProcessedAssetsError {
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingProcessedAssetReader(#[from] MissingProcessedAssetReaderError),
+    #[error("Failed to pack processed assets: {0}")]
+    ArchiveWriteError(#[from] ArchiveWriteError),
+}
+
 /// An error that occurs when initializing the [`AssetProcessor`].
 #[derive(Error, Debug)]
 pub enum InitializeError {
@@ -1474,3 +1799,304 @@ pub enum InitializeError {
     #[error("Failed to validate asset log: {0}")]
     ValidateLogError(#[from] ValidateLogError),
 }
+
+#[cfg(all(test, feature = "multi_threaded", not(target_arch = "wasm32")))]
+mod tests {
+    use super::{
+        AssetProcessingEvent, AssetProcessor, Process, ProcessContext, ProcessError,
+        ProcessingStage,
+    };
+    use crate::{
+        io::{
+            archive::{Archive, ArchiveAssetReader},
+            file::FileAssetReader,
+            AssetReader, AssetSourceId, Reader, Writer,
+        },
+        meta::AssetMeta,
+        tests::{CoolText, CoolTextLoader, SubText},
+        AssetApp, AssetMode, AssetPath, AssetPlugin,
+    };
+    use alloc::{format, string::ToString, sync::Arc, vec::Vec};
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_ecs::{event::Events, system::RunSystemOnce};
+    use bevy_platform::collections::HashMap;
+    use core::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+    use futures_lite::AsyncWriteExt;
+    use std::{
+        path::{Path, PathBuf},
+        sync::{Mutex, MutexGuard, PoisonError},
+    };
+
+    /// Processors share their transaction log, which is always written at the same path.
+    static PROCESSOR_LOG: Mutex<()> = Mutex::new(());
+
+    /// A directory of source and processed assets, removed with the processor log when dropped.
+    pub(crate) struct ProcessorTestDir {
+        pub(crate) path: PathBuf,
+        _log: MutexGuard<'static, ()>,
+    }
+
+    impl ProcessorTestDir {
+        pub(crate) fn new(name: &str, assets: &[(&str, &str)]) -> Self {
+            let log = PROCESSOR_LOG.lock().unwrap_or_else(PoisonError::into_inner);
+            let path = std::env::temp_dir().join(format!(
+                "bevy_asset_processor_{name}_{}",
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&path);
+            for (asset_path, text) in assets {
+                let asset_path = path.join("source").join(asset_path);
+                std::fs::create_dir_all(asset_path.parent().unwrap()).unwrap();
+                std::fs::write(asset_path, text).unwrap();
+            }
+            Self { path, _log: log }
+        }
+
+        /// Returns an [`AssetPlugin`] processing the assets of this directory.
+        pub(crate) fn asset_plugin(&self) -> AssetPlugin {
+            AssetPlugin {
+                mode: AssetMode::Processed,
+                file_path: self.path.join("source").to_string_lossy().into_owned(),
+                processed_file_path: self.path.join("processed").to_string_lossy().into_owned(),
+                watch_for_changes_override: Some(false),
+                ..Default::default()
+            }
+        }
+
+        /// Creates an app processing the assets of this directory.
+        pub(crate) fn app(&self) -> App {
+            self.app_with(self.asset_plugin())
+        }
+
+        /// Creates an app processing the assets of this directory with the given `asset_plugin`.
+        pub(crate) fn app_with(&self, asset_plugin: AssetPlugin) -> App {
+            let mut app = App::new();
+            app.add_plugins((TaskPoolPlugin::default(), asset_plugin))
+                .init_asset::<CoolText>()
+                .init_asset::<SubText>()
+                .register_asset_loader(CoolTextLoader);
+            app
+        }
+    }
+
+    impl Drop for ProcessorTestDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.path);
+            let _ =
+                std::fs::remove_dir_all(FileAssetReader::get_base_path().join("imported_assets"));
+        }
+    }
+
+    /// Copies the source asset, or fails if its file name starts with `fail`. Keeps track of how
+    /// many assets it processes at the same time.
+    #[derive(Default, Clone)]
+    struct CopyProcessor {
+        running: Arc<AtomicUsize>,
+        max_running: Arc<AtomicUsize>,
+    }
+
+    impl Process for CopyProcessor {
+        type Settings = ();
+        type OutputLoader = CoolTextLoader;
+
+        async fn process(
+            &self,
+            context: &mut ProcessContext<'_>,
+            _meta: AssetMeta<(), Self>,
+            writer: &mut Writer,
+        ) -> Result<(), ProcessError> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(20));
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            let path = context.path().clone();
+            if path
+                .path()
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("fail")
+            {
+                return Err(ProcessError::AssetSaveError("failed on purpose".into()));
+            }
+            writer
+                .write_all(context.asset_bytes())
+                .await
+                .map_err(|err| ProcessError::AssetWriterError {
+                    path,
+                    err: err.into(),
+                })
+        }
+    }
+
+    fn register_copy_processor(app: &mut App) -> CopyProcessor {
+        let processor = CopyProcessor::default();
+        app.register_asset_processor(processor.clone())
+            .set_default_asset_processor::<CopyProcessor>("cool.ron");
+        processor
+    }
+
+    pub(crate) fn cool_text(dependencies: &[&str]) -> alloc::string::String {
+        let dependencies: Vec<_> = dependencies
+            .iter()
+            .map(|path| format!("{path:?}"))
+            .collect();
+        format!(
+            "(text: \"text\", dependencies: [{}], embedded_dependencies: [], sub_texts: [])",
+            dependencies.join(", ")
+        )
+    }
+
+    #[test]
+    fn unused_assets_report() {
+        let dir = ProcessorTestDir::new(
+            "unused_assets_report",
+            &[
+                ("root.cool.ron", &cool_text(&["a.cool.ron"])),
+                ("a.cool.ron", &cool_text(&["nested/b.cool.ron"])),
+                ("nested/b.cool.ron", &cool_text(&[])),
+                ("orphan.cool.ron", &cool_text(&["a.cool.ron"])),
+            ],
+        );
+        let app = dir.app();
+        let processor = app.world().resource::<AssetProcessor>().clone();
+        processor.process_assets();
+
+        let report = bevy_tasks::block_on(processor.unused_assets_report(["root.cool.ron"]));
+        let paths = |paths: &[AssetPath<'static>]| -> Vec<_> {
+            paths.iter().map(ToString::to_string).collect()
+        };
+        assert_eq!(
+            paths(&report.referenced),
+            ["a.cool.ron", "nested/b.cool.ron", "root.cool.ron"]
+        );
+        assert_eq!(paths(&report.unused), ["orphan.cool.ron"]);
+    }
+
+    #[test]
+    fn pack_processed_assets() {
+        let dir = ProcessorTestDir::new(
+            "pack_processed_assets",
+            &[
+                ("a.cool.ron", &cool_text(&[])),
+                ("nested/b.cool.ron", &cool_text(&["a.cool.ron"])),
+            ],
+        );
+        let mut app = dir.app();
+        register_copy_processor(&mut app);
+        let processor = app.world().resource::<AssetProcessor>().clone();
+        processor.process_assets();
+
+        let bytes = bevy_tasks::block_on(
+            processor.pack_processed_assets(AssetSourceId::Default, Vec::new()),
+        )
+        .unwrap();
+        let reader = ArchiveAssetReader::new(Archive::from_bytes(bytes).unwrap());
+        for path in ["a.cool.ron", "nested/b.cool.ron"] {
+            let processed = dir.path.join("processed").join(path);
+            bevy_tasks::block_on(async {
+                let mut asset = Vec::new();
+                reader
+                    .read(Path::new(path))
+                    .await
+                    .unwrap()
+                    .read_to_end(&mut asset)
+                    .await
+                    .unwrap();
+                assert_eq!(asset, std::fs::read(&processed).unwrap(), "{path}");
+
+                let meta = reader.read_meta_bytes(Path::new(path)).await.unwrap();
+                let mut meta_path = processed.into_os_string();
+                meta_path.push(".meta");
+                assert_eq!(meta, std::fs::read(meta_path).unwrap(), "{path}");
+            });
+        }
+    }
+
+    #[test]
+    fn processing_events() {
+        let dir = ProcessorTestDir::new(
+            "processing_events",
+            &[
+                ("a.cool.ron", &cool_text(&[])),
+                ("fail.cool.ron", &cool_text(&[])),
+            ],
+        );
+        let mut app = dir.app();
+        register_copy_processor(&mut app);
+        let processor = app.world().resource::<AssetProcessor>().clone();
+        processor.process_assets();
+        app.world_mut()
+            .run_system_once(AssetProcessor::update_progress)
+            .unwrap();
+
+        let events = app.world().resource::<Events<AssetProcessingEvent>>();
+        let mut stages = HashMap::<_, Vec<_>>::default();
+        for event in events.get_cursor().read(events) {
+            stages
+                .entry(event.path.to_string())
+                .or_default()
+                .push(event.stage);
+        }
+        use ProcessingStage::*;
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages["a.cool.ron"], [Queued, Processing, Finished]);
+        assert_eq!(stages["fail.cool.ron"], [Queued, Processing, Failed]);
+
+        let progress = app.world().resource::<super::ProcessorProgress>();
+        assert_eq!(progress.count(Finished), 1);
+        assert_eq!(
+            progress
+                .failed()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["fail.cool.ron"]
+        );
+    }
+
+    #[test]
+    fn max_concurrent_processing() {
+        let dir = ProcessorTestDir::new(
+            "max_concurrent_processing",
+            &[
+                ("a.cool.ron", &cool_text(&[])),
+                ("b.cool.ron", &cool_text(&[])),
+                ("c.cool.ron", &cool_text(&[])),
+                ("d.cool.ron", &cool_text(&[])),
+            ],
+        );
+        let mut app = dir.app_with(AssetPlugin {
+            max_concurrent_processing: Some(1),
+            ..dir.asset_plugin()
+        });
+        let copy_processor = register_copy_processor(&mut app);
+        let processor = app.world().resource::<AssetProcessor>().clone();
+        processor.process_assets();
+
+        assert_eq!(processor.progress().count(ProcessingStage::Finished), 4);
+        assert_eq!(copy_processor.max_running.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn run_headless_fails_if_an_asset_fails() {
+        let dir = ProcessorTestDir::new(
+            "run_headless",
+            &[
+                ("a.cool.ron", &cool_text(&[])),
+                ("fail.cool.ron", &cool_text(&[])),
+            ],
+        );
+        let mut app = dir.app();
+        register_copy_processor(&mut app);
+        assert!(AssetProcessor::run_headless(app).is_error());
+
+        std::fs::remove_file(dir.path.join("source/fail.cool.ron")).unwrap();
+        let mut app = dir.app();
+        register_copy_processor(&mut app);
+        assert!(AssetProcessor::run_headless(app).is_success());
+    }
+}
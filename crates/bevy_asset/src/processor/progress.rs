@@ -0,0 +1,85 @@
+use crate::AssetPath;
+use bevy_ecs::{event::BufferedEvent, resource::Resource};
+use bevy_platform::collections::HashMap;
+
+/// Where an asset is in the [`AssetProcessor`](super::AssetProcessor)'s pipeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProcessingStage {
+    /// The asset waits for a free processing slot.
+    ///
+    /// See [`AssetProcessor::set_max_concurrent_processing`](super::AssetProcessor::set_max_concurrent_processing).
+    Queued,
+    /// The asset is being processed.
+    Processing,
+    /// The asset was processed, or didn't need to be.
+    Finished,
+    /// Processing the asset failed. The error is logged by the processor.
+    Failed,
+}
+
+/// Sent when an asset moves to another [`ProcessingStage`].
+///
+/// These events are sent in the app using the [`AssetProcessor`](super::AssetProcessor), which
+/// also keeps the latest stage of each asset in the [`ProcessorProgress`] resource.
+#[derive(BufferedEvent, Clone, Debug, PartialEq, Eq)]
+pub struct AssetProcessingEvent {
+    /// The path of the asset.
+    pub path: AssetPath<'static>,
+    /// The new stage of the asset.
+    pub stage: ProcessingStage,
+}
+
+/// The latest [`ProcessingStage`] of each asset seen by the [`AssetProcessor`](super::AssetProcessor).
+///
+/// This resource is updated in [`PreUpdate`](bevy_app::PreUpdate) from the processor's progress. A
+/// snapshot can also be taken from outside of an app with
+/// [`AssetProcessor::progress`](super::AssetProcessor::progress).
+#[derive(Resource, Clone, Debug, Default)]
+pub struct ProcessorProgress {
+    stages: HashMap<AssetPath<'static>, ProcessingStage>,
+}
+
+impl ProcessorProgress {
+    /// Returns the latest stage of the asset at `path`, if the processor has seen it.
+    pub fn stage(&self, path: &AssetPath<'_>) -> Option<ProcessingStage> {
+        self.stages.get(path).copied()
+    }
+
+    /// Iterates over the assets seen by the processor, and their latest stage.
+    pub fn iter(&self) -> impl Iterator<Item = (&AssetPath<'static>, ProcessingStage)> {
+        self.stages.iter().map(|(path, stage)| (path, *stage))
+    }
+
+    /// Returns the number of assets in the given `stage`.
+    pub fn count(&self, stage: ProcessingStage) -> usize {
+        self.stages.values().filter(|s| **s == stage).count()
+    }
+
+    /// Returns the number of assets seen by the processor.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns `true` if the processor hasn't seen any asset yet.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Returns `true` if no asset is queued or being processed.
+    pub fn is_idle(&self) -> bool {
+        self.stages
+            .values()
+            .all(|stage| matches!(stage, ProcessingStage::Finished | ProcessingStage::Failed))
+    }
+
+    /// Iterates over the paths of the assets which failed to process.
+    pub fn failed(&self) -> impl Iterator<Item = &AssetPath<'static>> {
+        self.iter()
+            .filter(|(_, stage)| *stage == ProcessingStage::Failed)
+            .map(|(path, _)| path)
+    }
+
+    pub(crate) fn apply(&mut self, event: &AssetProcessingEvent) {
+        self.stages.insert(event.path.clone(), event.stage);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::AssetPath;
+use bevy_platform::collections::HashSet;
+use parking_lot::Mutex;
+
+/// Limits the number of assets the [`AssetProcessor`](super::AssetProcessor) processes at the same
+/// time.
+///
+/// Processing an asset can wait for other assets to be processed (its process dependencies). To
+/// not deadlock when every slot is taken by such waiting assets, the assets being waited on are
+/// [prioritized](ProcessingSlots::prioritize), and get a slot even if the limit is reached.
+pub(crate) struct ProcessingSlots {
+    state: Mutex<SlotsState>,
+    released_sender: async_broadcast::Sender<()>,
+    released_receiver: async_broadcast::InactiveReceiver<()>,
+}
+
+#[derive(Default)]
+struct SlotsState {
+    max: Option<usize>,
+    running: usize,
+    prioritized: HashSet<AssetPath<'static>>,
+}
+
+/// A slot taken by an asset being processed, released when dropped.
+pub(crate) struct ProcessingSlot<'a> {
+    slots: &'a ProcessingSlots,
+}
+
+impl Default for ProcessingSlots {
+    fn default() -> Self {
+        let (mut released_sender, released_receiver) = async_broadcast::broadcast(1);
+        // Waiters only need to know that something changed since they last checked the state.
+        released_sender.set_overflow(true);
+        released_sender.set_await_active(false);
+        Self {
+            state: Default::default(),
+            released_sender,
+            released_receiver: released_receiver.deactivate(),
+        }
+    }
+}
+
+impl ProcessingSlots {
+    /// Returns the maximum number of assets processed at the same time.
+    pub(crate) fn max(&self) -> Option<usize> {
+        self.state.lock().max
+    }
+
+    /// Sets the maximum number of assets processed at the same time, or [`None`] for no limit.
+    pub(crate) fn set_max(&self, max: Option<usize>) {
+        self.state.lock().max = max.map(|max| max.max(1));
+        self.notify();
+    }
+
+    /// Lets the asset at `path` take a slot even if the limit is reached, because other assets
+    /// wait for it to be processed.
+    pub(crate) fn prioritize(&self, path: AssetPath<'static>) {
+        if self.state.lock().prioritized.insert(path) {
+            self.notify();
+        }
+    }
+
+    /// Waits for a free slot to process the asset at `path`.
+    pub(crate) async fn acquire(&self, path: &AssetPath<'static>) -> ProcessingSlot<'_> {
+        loop {
+            let mut receiver = {
+                let mut state = self.state.lock();
+                let is_prioritized = state.prioritized.remove(path);
+                if is_prioritized || state.max.is_none_or(|max| state.running < max) {
+                    state.running += 1;
+                    return ProcessingSlot { slots: self };
+                }
+                // This receiver must be created prior to releasing the lock to not miss a release
+                self.released_receiver.activate_cloned()
+            };
+            // The sender lives as long as `self`, so this can't fail
+            let _ = receiver.recv().await;
+        }
+    }
+
+    fn notify(&self) {
+        let _ = self.released_sender.try_broadcast(());
+    }
+}
+
+impl Drop for ProcessingSlot<'_> {
+    fn drop(&mut self) {
+        self.slots.state.lock().running -= 1;
+        self.slots.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProcessingSlots;
+    use crate::AssetPath;
+    use futures_lite::future;
+
+    #[test]
+    fn prioritized_assets_bypass_the_limit() {
+        let slots = ProcessingSlots::default();
+        slots.set_max(Some(1));
+        let a = AssetPath::from("a.txt");
+        let b = AssetPath::from("b.txt");
+
+        let slot_a = future::block_on(slots.acquire(&a));
+        assert!(future::block_on(future::poll_once(slots.acquire(&b))).is_none());
+
+        slots.prioritize(b.clone());
+        let slot_b = future::block_on(slots.acquire(&b));
+        drop(slot_a);
+        // The prioritized slot still counts towards the limit until it is released.
+        assert!(future::block_on(future::poll_once(slots.acquire(&a))).is_none());
+        drop(slot_b);
+        assert!(future::block_on(future::poll_once(slots.acquire(&a))).is_some());
+    }
+}
@@ -0,0 +1,123 @@
+use super::{AssetServer, LoadState};
+use crate::{AssetPath, UntypedAssetId};
+use alloc::{string::ToString, vec::Vec};
+use bevy_ecs::world::World;
+use bevy_platform::collections::HashMap;
+
+/// A snapshot of the assets managed by an [`AssetServer`], and of the dependencies between them.
+///
+/// Returned by [`AssetServer::dependency_graph`].
+#[derive(Clone, Debug, Default)]
+pub struct AssetDependencyGraph {
+    /// The assets, sorted by path. Assets without a path come last.
+    pub nodes: Vec<AssetDependencyNode>,
+}
+
+/// An asset in an [`AssetDependencyGraph`].
+#[derive(Clone, Debug)]
+pub struct AssetDependencyNode {
+    /// The id of the asset.
+    pub id: UntypedAssetId,
+    /// The path of the asset, if it was loaded from a path.
+    pub path: Option<AssetPath<'static>>,
+    /// The type path of the asset type, if the type was initialized with
+    /// [`AssetApp::init_asset`](crate::AssetApp::init_asset).
+    pub type_path: Option<&'static str>,
+    /// The load state of the asset.
+    pub load_state: LoadState,
+    /// The assets this asset depended on when it was last loaded.
+    pub dependencies: Vec<UntypedAssetId>,
+    /// The assets depending on this asset.
+    pub dependants: Vec<UntypedAssetId>,
+    /// The number of bytes used by the asset, if its type implements [`AssetSize`](crate::AssetSize)
+    /// and is registered with [`AssetApp::register_asset_memory_budget`](crate::AssetApp::register_asset_memory_budget).
+    pub byte_size: Option<usize>,
+}
+
+impl AssetDependencyGraph {
+    /// Returns the node of the asset `id`.
+    pub fn get(&self, id: impl Into<UntypedAssetId>) -> Option<&AssetDependencyNode> {
+        let id = id.into();
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Returns the nodes of the assets loaded from `path`.
+    pub fn get_by_path<'a, 'p>(
+        &'a self,
+        path: &'p AssetPath<'_>,
+    ) -> impl Iterator<Item = &'a AssetDependencyNode> + 'p
+    where
+        'a: 'p,
+    {
+        self.nodes
+            .iter()
+            .filter(move |node| node.path.as_ref() == Some(path))
+    }
+
+    /// Returns the nodes of the assets which no other asset depends on.
+    pub fn roots(&self) -> impl Iterator<Item = &AssetDependencyNode> {
+        self.nodes.iter().filter(|node| node.dependants.is_empty())
+    }
+}
+
+impl AssetServer {
+    /// Returns a snapshot of the assets managed by this server and of their dependencies.
+    ///
+    /// The byte sizes of the assets are not filled in, as they are stored in the [`World`]: use
+    /// [`AssetServer::dependency_graph_with_byte_sizes`] to get them.
+    pub fn dependency_graph(&self) -> AssetDependencyGraph {
+        let infos = self.data.infos.read();
+        let mut nodes: Vec<_> = infos
+            .iter()
+            .map(|(id, info)| AssetDependencyNode {
+                id,
+                path: info.path.clone(),
+                type_path: infos.asset_type_paths.get(&id.type_id()).copied(),
+                load_state: info.load_state.clone(),
+                dependencies: info.dependencies.iter().copied().collect(),
+                dependants: Vec::new(),
+                byte_size: None,
+            })
+            .collect();
+        drop(infos);
+
+        nodes.sort_by_cached_key(|node| {
+            (
+                node.path.is_none(),
+                node.path.as_ref().map(ToString::to_string),
+                node.id,
+            )
+        });
+        let indices: HashMap<_, _> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id, index))
+            .collect();
+        for index in 0..nodes.len() {
+            for dependency in nodes[index].dependencies.clone() {
+                if let Some(&dependency) = indices.get(&dependency) {
+                    let dependant = nodes[index].id;
+                    nodes[dependency].dependants.push(dependant);
+                }
+            }
+        }
+        for node in &mut nodes {
+            node.dependencies.sort_unstable();
+            node.dependants.sort_unstable();
+        }
+        AssetDependencyGraph { nodes }
+    }
+
+    /// Returns a snapshot of the assets managed by this server and of their dependencies, like
+    /// [`AssetServer::dependency_graph`], with the byte sizes of the assets stored in `world`.
+    pub fn dependency_graph_with_byte_sizes(&self, world: &World) -> AssetDependencyGraph {
+        let mut graph = self.dependency_graph();
+        let byte_size_getters = self.data.infos.read().byte_size_getters.clone();
+        for node in &mut graph.nodes {
+            if let Some(byte_size) = byte_size_getters.get(&node.id.type_id()) {
+                node.byte_size = byte_size(world, node.id);
+            }
+        }
+        graph
+    }
+}
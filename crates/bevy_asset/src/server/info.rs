@@ -1,3 +1,4 @@
+use super::load_queue::LoadQueue;
 use crate::{
     meta::{AssetHash, MetaTransform},
     Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
@@ -45,6 +46,8 @@ pub(crate) struct AssetInfo {
     handle_drops_to_skip: usize,
     /// List of tasks waiting for this asset to complete loading
     pub(crate) waiting_tasks: Vec<Waker>,
+    /// The assets this asset depended on when it was last loaded.
+    pub(crate) dependencies: HashSet<UntypedAssetId>,
 }
 
 impl AssetInfo {
@@ -64,6 +67,7 @@ impl AssetInfo {
             dependents_waiting_on_recursive_dep_load: HashSet::default(),
             handle_drops_to_skip: 0,
             waiting_tasks: Vec::new(),
+            dependencies: HashSet::default(),
         }
     }
 }
@@ -86,6 +90,11 @@ pub(crate) struct AssetInfos {
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, UntypedAssetId, AssetPath<'static>, AssetLoadError)>,
     pub(crate) pending_tasks: HashMap<UntypedAssetId, Task<()>>,
+    pub(crate) load_queue: LoadQueue,
+    /// The type paths of the registered asset types.
+    pub(crate) asset_type_paths: TypeIdMap<&'static str>,
+    /// Returns the number of bytes used by an asset, for asset types implementing [`AssetSize`](crate::AssetSize).
+    pub(crate) byte_size_getters: TypeIdMap<fn(&World, UntypedAssetId) -> Option<usize>>,
 }
 
 impl core::fmt::Debug for AssetInfos {
@@ -287,6 +296,10 @@ impl AssetInfos {
         self.infos.contains_key(&id)
     }
 
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (UntypedAssetId, &AssetInfo)> {
+        self.infos.iter().map(|(id, info)| (*id, info))
+    }
+
     pub(crate) fn get_mut(&mut self, id: UntypedAssetId) -> Option<&mut AssetInfo> {
         self.infos.get_mut(&id)
     }
@@ -373,6 +386,7 @@ impl AssetInfos {
             &mut self.loader_dependents,
             &mut self.living_labeled_assets,
             &mut self.pending_tasks,
+            &mut self.load_queue,
             self.watching_for_changes,
             id,
         )
@@ -392,6 +406,9 @@ impl AssetInfos {
         }
 
         loaded_asset.value.insert(loaded_asset_id, world);
+        if let Some(info) = self.infos.get_mut(&loaded_asset_id) {
+            info.dependencies.clone_from(&loaded_asset.dependencies);
+        }
         let mut loading_deps = loaded_asset.dependencies;
         let mut failed_deps = <HashSet<_>>::default();
         let mut dep_error = None;
@@ -421,7 +438,7 @@ impl AssetInfos {
                     }
                 }
                 match dep_info.load_state {
-                    LoadState::NotLoaded | LoadState::Loading => {
+                    LoadState::NotLoaded | LoadState::Queued | LoadState::Loading => {
                         // If dependency is loading, wait for it.
                         dep_info.dependents_waiting_on_load.insert(loaded_asset_id);
                         true
@@ -677,6 +694,7 @@ impl AssetInfos {
         loader_dependents: &mut HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
         living_labeled_assets: &mut HashMap<AssetPath<'static>, HashSet<Box<str>>>,
         pending_tasks: &mut HashMap<UntypedAssetId, Task<()>>,
+        load_queue: &mut LoadQueue,
         watching_for_changes: bool,
         id: UntypedAssetId,
     ) -> bool {
@@ -691,11 +709,17 @@ impl AssetInfos {
             return false;
         }
 
+        // Dropping the task of a load which hasn't finished yet cancels it.
         pending_tasks.remove(&id);
+        load_queue.remove(id);
 
         let type_id = entry.key().type_id();
 
-        let info = entry.remove();
+        let mut info = entry.remove();
+        // Tasks waiting for a cancelled load will now see that the asset isn't loaded.
+        for waker in info.waiting_tasks.drain(..) {
+            waker.wake();
+        }
         let Some(path) = &info.path else {
             return true;
         };
@@ -736,6 +760,7 @@ impl AssetInfos {
                         &mut self.loader_dependents,
                         &mut self.living_labeled_assets,
                         &mut self.pending_tasks,
+                        &mut self.load_queue,
                         self.watching_for_changes,
                         id.untyped(provider.type_id),
                     );
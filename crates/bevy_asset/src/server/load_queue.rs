@@ -0,0 +1,135 @@
+use crate::{AssetPath, UntypedAssetId};
+use alloc::{boxed::Box, collections::BinaryHeap};
+use bevy_platform::collections::{HashMap, HashSet};
+use core::{any::Any, cmp::Reverse};
+
+/// The priority of an asset load, used by the [`AssetServer`](crate::AssetServer) to pick which
+/// queued load starts next when the number of concurrent loads is limited.
+///
+/// Loads with a higher priority start first. Loads with the same priority start in the order they
+/// were requested. See [`AssetServer::load_with_priority`](crate::AssetServer::load_with_priority)
+/// and [`AssetServer::set_max_concurrent_loads`](crate::AssetServer::set_max_concurrent_loads).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LoadPriority(pub i32);
+
+impl LoadPriority {
+    /// The priority of loads which can wait, such as assets far away from the player.
+    pub const LOW: Self = Self(-100);
+    /// The priority of loads started by [`AssetServer::load`](crate::AssetServer::load).
+    pub const NORMAL: Self = Self(0);
+    /// The priority of loads which are needed as soon as possible.
+    pub const HIGH: Self = Self(100);
+}
+
+/// A load waiting for a free slot in the [`LoadQueue`].
+pub(crate) struct QueuedLoad {
+    pub(crate) path: AssetPath<'static>,
+    pub(crate) guard: Box<dyn Any + Send + Sync>,
+    priority: LoadPriority,
+    sequence: u64,
+}
+
+/// Tracks the loads started by the asset server, and the loads waiting for a free slot when the
+/// number of concurrent loads is limited.
+#[derive(Default)]
+pub(crate) struct LoadQueue {
+    queued: HashMap<UntypedAssetId, QueuedLoad>,
+    /// Queued loads ordered by priority, then by request order. Entries whose load was
+    /// re-prioritized or removed from `queued` are stale, and are skipped when popped.
+    order: BinaryHeap<(LoadPriority, Reverse<u64>, UntypedAssetId)>,
+    next_sequence: u64,
+    running: HashSet<UntypedAssetId>,
+    pub(crate) max_concurrent_loads: Option<usize>,
+}
+
+impl LoadQueue {
+    /// Returns `true` if a new load can start without being queued.
+    pub(crate) fn has_free_slot(&self) -> bool {
+        self.max_concurrent_loads
+            .is_none_or(|max| self.running.len() < max)
+    }
+
+    /// Queues the load of the asset `id`, which starts once a slot is free and no load with a
+    /// higher priority is queued.
+    pub(crate) fn push(
+        &mut self,
+        id: UntypedAssetId,
+        path: AssetPath<'static>,
+        priority: LoadPriority,
+        guard: Box<dyn Any + Send + Sync>,
+    ) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.order.push((priority, Reverse(sequence), id));
+        self.queued.insert(
+            id,
+            QueuedLoad {
+                path,
+                guard,
+                priority,
+                sequence,
+            },
+        );
+    }
+
+    /// Changes the priority of the queued load of the asset `id`, returning `false` if it isn't
+    /// queued.
+    pub(crate) fn set_priority(&mut self, id: UntypedAssetId, priority: LoadPriority) -> bool {
+        let Some(queued) = self.queued.get_mut(&id) else {
+            return false;
+        };
+        if queued.priority != priority {
+            queued.priority = priority;
+            self.order.push((priority, Reverse(queued.sequence), id));
+        }
+        true
+    }
+
+    /// Returns the priority of the queued load of the asset `id`.
+    pub(crate) fn priority(&self, id: UntypedAssetId) -> Option<LoadPriority> {
+        self.queued.get(&id).map(|queued| queued.priority)
+    }
+
+    /// Removes the next queued load and marks it as running, if a slot is free.
+    pub(crate) fn pop(&mut self) -> Option<(UntypedAssetId, QueuedLoad)> {
+        if !self.has_free_slot() {
+            return None;
+        }
+        while let Some((priority, Reverse(sequence), id)) = self.order.pop() {
+            let is_current = self
+                .queued
+                .get(&id)
+                .is_some_and(|queued| queued.priority == priority && queued.sequence == sequence);
+            if is_current {
+                let queued = self.queued.remove(&id).unwrap();
+                self.running.insert(id);
+                return Some((id, queued));
+            }
+        }
+        None
+    }
+
+    /// Marks the load of the asset `id` as running.
+    pub(crate) fn start(&mut self, id: UntypedAssetId) {
+        self.running.insert(id);
+    }
+
+    /// Forgets the load of the asset `id`, whether it is queued or running, freeing its slot.
+    pub(crate) fn remove(&mut self, id: UntypedAssetId) {
+        self.queued.remove(&id);
+        self.running.remove(&id);
+        if self.queued.is_empty() {
+            self.order.clear();
+        }
+    }
+
+    /// Returns the number of loads waiting for a free slot.
+    pub(crate) fn queued_len(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// Returns the number of loads currently running.
+    pub(crate) fn running_len(&self) -> usize {
+        self.running.len()
+    }
+}
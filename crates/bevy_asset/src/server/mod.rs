@@ -1,6 +1,11 @@
+mod graph;
 mod info;
+mod load_queue;
 mod loaders;
 
+pub use graph::{AssetDependencyGraph, AssetDependencyNode};
+pub use load_queue::LoadPriority;
+
 use crate::{
     folder::LoadedFolder,
     io::{
@@ -193,6 +198,10 @@ impl AssetServer {
         infos
             .dependency_failed_event_sender
             .insert(TypeId::of::<A>(), failed_sender::<A>);
+
+        infos
+            .asset_type_paths
+            .insert(TypeId::of::<A>(), A::type_path());
     }
 
     pub(crate) fn register_handle_provider(&self, handle_provider: AssetHandleProvider) {
@@ -454,12 +463,47 @@ impl AssetServer {
         )
     }
 
+    /// Begins loading an [`Asset`] of type `A` stored at `path`, like [`load`](AssetServer::load), with the given `priority`.
+    ///
+    /// The priority only matters when the number of concurrent loads is limited by
+    /// [`AssetServer::set_max_concurrent_loads`]: loads which can't start yet are queued (and are in the
+    /// [`LoadState::Queued`] state), and the queued load with the highest priority starts whenever a running
+    /// load finishes. The priority of a queued load can be changed with [`AssetServer::set_load_priority`].
+    ///
+    /// If the asset at this path is already loading or loaded, the existing handle is returned and the
+    /// priority is ignored.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_priority<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        priority: LoadPriority,
+    ) -> Handle<A> {
+        self.load_with_meta_transform_and_priority(path, None, priority, (), false)
+    }
+
     pub(crate) fn load_with_meta_transform<'a, A: Asset, G: Send + Sync + 'static>(
         &self,
         path: impl Into<AssetPath<'a>>,
         meta_transform: Option<MetaTransform>,
         guard: G,
         override_unapproved: bool,
+    ) -> Handle<A> {
+        self.load_with_meta_transform_and_priority(
+            path,
+            meta_transform,
+            LoadPriority::NORMAL,
+            guard,
+            override_unapproved,
+        )
+    }
+
+    fn load_with_meta_transform_and_priority<'a, A: Asset, G: Send + Sync + 'static>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        meta_transform: Option<MetaTransform>,
+        priority: LoadPriority,
+        guard: G,
+        override_unapproved: bool,
     ) -> Handle<A> {
         let path = path.into().into_owned();
 
@@ -481,7 +525,7 @@ impl AssetServer {
         );
 
         if should_load {
-            self.spawn_load_task(handle.clone().untyped(), path, infos, guard);
+            self.spawn_load_task_with_priority(handle.id().untyped(), path, priority, infos, guard);
         }
 
         handle
@@ -517,33 +561,123 @@ impl AssetServer {
         path: AssetPath<'static>,
         infos: RwLockWriteGuard<AssetInfos>,
         guard: G,
+    ) {
+        self.spawn_load_task_with_priority(handle.id(), path, LoadPriority::NORMAL, infos, guard);
+    }
+
+    /// Starts loading the asset `id`, or queues its load if the number of concurrent loads is limited.
+    fn spawn_load_task_with_priority<G: Send + Sync + 'static>(
+        &self,
+        id: UntypedAssetId,
+        path: AssetPath<'static>,
+        priority: LoadPriority,
+        mut infos: RwLockWriteGuard<AssetInfos>,
+        guard: G,
+    ) {
+        if infos.load_queue.max_concurrent_loads.is_none() {
+            infos.load_queue.start(id);
+            self.start_load_task(id, path, infos, guard);
+            return;
+        }
+
+        infos.load_queue.push(id, path, priority, Box::new(guard));
+        if let Some(info) = infos.get_mut(id) {
+            info.load_state = LoadState::Queued;
+        }
+        self.start_queued_loads(infos);
+    }
+
+    /// Starts the queued loads with the highest priority, until no slot is free.
+    fn start_queued_loads<'a>(&'a self, mut infos: RwLockWriteGuard<'a, AssetInfos>) {
+        while let Some((id, queued)) = infos.load_queue.pop() {
+            if let Some(info) = infos.get_mut(id) {
+                info.load_state = LoadState::Loading;
+            }
+            self.start_load_task(id, queued.path, infos, queued.guard);
+            infos = self.data.infos.write();
+        }
+    }
+
+    fn start_load_task<G: Send + Sync + 'static>(
+        &self,
+        id: UntypedAssetId,
+        path: AssetPath<'static>,
+        infos: RwLockWriteGuard<AssetInfos>,
+        guard: G,
     ) {
         // drop the lock on `AssetInfos` before spawning a task that may block on it in single-threaded
         #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
         drop(infos);
 
-        let owned_handle = handle.clone();
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
-            if let Err(err) = server
-                .load_internal(Some(owned_handle), path, false, None)
-                .await
-            {
+            // The task doesn't hold a handle to the asset, so that its load is cancelled when all
+            // of its handles are dropped.
+            if let Err(err) = server.load_internal(Some(id), path, false, None).await {
                 error!("{}", err);
             }
             drop(guard);
+
+            let mut infos = server.data.infos.write();
+            infos.load_queue.remove(id);
+            server.start_queued_loads(infos);
         });
 
         #[cfg(not(any(target_arch = "wasm32", not(feature = "multi_threaded"))))]
         {
             let mut infos = infos;
-            infos.pending_tasks.insert(handle.id(), task);
+            infos.pending_tasks.insert(id, task);
         }
 
         #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
         task.detach();
     }
 
+    /// Changes the [`LoadPriority`] of the queued load of the asset `id`, returning `false` if the asset
+    /// isn't waiting to start loading.
+    ///
+    /// See [`AssetServer::load_with_priority`].
+    pub fn set_load_priority(&self, id: impl Into<UntypedAssetId>, priority: LoadPriority) -> bool {
+        self.data
+            .infos
+            .write()
+            .load_queue
+            .set_priority(id.into(), priority)
+    }
+
+    /// Returns the [`LoadPriority`] of the queued load of the asset `id`, or [`None`] if the asset isn't
+    /// waiting to start loading.
+    pub fn get_load_priority(&self, id: impl Into<UntypedAssetId>) -> Option<LoadPriority> {
+        self.data.infos.read().load_queue.priority(id.into())
+    }
+
+    /// Limits the number of asset loads running at the same time. Loads started while the limit is
+    /// reached are queued, and start by order of [`LoadPriority`] as running loads finish. [`None`] removes
+    /// the limit, which is the default.
+    ///
+    /// A limit of `0` is treated as `1`. Lowering the limit doesn't stop running loads.
+    pub fn set_max_concurrent_loads(&self, max_concurrent_loads: Option<usize>) {
+        let mut infos = self.data.infos.write();
+        infos.load_queue.max_concurrent_loads = max_concurrent_loads.map(|max| max.max(1));
+        self.start_queued_loads(infos);
+    }
+
+    /// Returns the maximum number of asset loads running at the same time, set with
+    /// [`AssetServer::set_max_concurrent_loads`].
+    pub fn max_concurrent_loads(&self) -> Option<usize> {
+        self.data.infos.read().load_queue.max_concurrent_loads
+    }
+
+    /// Returns the number of asset loads which are running, and the number of asset loads which are
+    /// queued, waiting for a running load to finish.
+    pub fn pending_load_counts(&self) -> (usize, usize) {
+        let infos = self.data.infos.read();
+        (
+            infos.load_queue.running_len(),
+            infos.load_queue.queued_len(),
+        )
+    }
+
     /// Asynchronously load an asset that you do not know the type of statically. If you _do_ know the type of the asset,
     /// you should use [`AssetServer::load`]. If you don't know the type of the asset, but you can't use an async method,
     /// consider using [`AssetServer::load_untyped`].
@@ -653,12 +787,18 @@ impl AssetServer {
     /// [`None`].
     async fn load_internal<'a>(
         &self,
-        input_handle: Option<UntypedHandle>,
+        input_id: Option<UntypedAssetId>,
         path: AssetPath<'a>,
         force: bool,
         meta_transform: Option<MetaTransform>,
     ) -> Result<Option<UntypedHandle>, AssetLoadError> {
-        let input_handle_type_id = input_handle.as_ref().map(UntypedHandle::type_id);
+        // The load of an input asset is cancelled if all of its handles were dropped. Only a weak
+        // reference to the asset is kept until then, so that its handles can be dropped while its
+        // meta is read.
+        if input_id.is_some_and(|id| self.get_id_handle_untyped(id).is_none()) {
+            return Ok(None);
+        }
+        let input_handle_type_id = input_id.map(|id| id.type_id());
 
         let path = path.into_owned();
         let path_clone = path.clone();
@@ -668,25 +808,30 @@ impl AssetServer {
             .inspect_err(|e| {
                 // if there was an input handle, a "load" operation has already started, so we must produce a "failure" event, if
                 // we cannot find the meta and loader
-                if let Some(handle) = &input_handle {
+                if let Some(id) = input_id {
                     self.send_asset_event(InternalAssetEvent::Failed {
-                        id: handle.id(),
+                        id,
                         path: path.clone_owned(),
                         error: e.clone(),
                     });
                 }
             })?;
 
-        if let Some(meta_transform) = input_handle.as_ref().and_then(|h| h.meta_transform()) {
-            (*meta_transform)(&mut *meta);
+        if let Some(id) = input_id {
+            let Some(input_handle) = self.get_id_handle_untyped(id) else {
+                return Ok(None);
+            };
+            if let Some(meta_transform) = input_handle.meta_transform() {
+                (*meta_transform)(&mut *meta);
+            }
         }
 
         let asset_id; // The asset ID of the asset we are trying to load.
         let fetched_handle; // The handle if one was looked up/created.
         let should_load; // Whether we need to load the asset.
-        if let Some(input_handle) = input_handle {
-            asset_id = Some(input_handle.id());
-            // In this case, we intentionally drop the input handle so we can cancel loading the
+        if let Some(input_id) = input_id {
+            asset_id = Some(input_id);
+            // In this case, we intentionally don't hold a handle so we can cancel loading the
             // asset if the handle gets dropped (externally) before it finishes loading.
             fetched_handle = None;
             // The handle was passed in, so the "should_load" check was already done.
@@ -836,7 +981,7 @@ impl AssetServer {
                     .infos
                     .read()
                     .get_path_handles(&path)
-                    .map(|handle| server.load_internal(Some(handle), path.clone(), true, None))
+                    .map(|handle| server.load_internal(Some(handle.id()), path.clone(), true, None))
                     .collect::<Vec<_>>();
 
                 for result in requests {
@@ -1541,7 +1686,7 @@ impl AssetServer {
             // Return an error immediately if the asset is not in the process of loading
             (LoadState::NotLoaded, _) => Poll::Ready(Err(WaitForAssetError::NotLoaded)),
             // If the asset is loading, leave our waker behind
-            (LoadState::Loading, _)
+            (LoadState::Queued | LoadState::Loading, _)
             | (_, RecursiveDependencyLoadState::Loading)
             | (LoadState::Loaded, RecursiveDependencyLoadState::NotLoaded) => {
                 // Check if our waker is already there
@@ -1568,7 +1713,7 @@ impl AssetServer {
                 // reawaken the task
                 let is_loading = matches!(
                     (&info.load_state, &info.rec_dep_load_state),
-                    (LoadState::Loading, _)
+                    (LoadState::Queued | LoadState::Loading, _)
                         | (_, RecursiveDependencyLoadState::Loading)
                         | (LoadState::Loaded, RecursiveDependencyLoadState::NotLoaded)
                 );
@@ -1766,6 +1911,9 @@ pub fn handle_internal_asset_events(world: &mut World) {
         infos
             .pending_tasks
             .retain(|_, load_task| !load_task.is_finished());
+
+        // Loads cancelled since the last update freed their slot.
+        server.start_queued_loads(infos);
     });
 }
 
@@ -1788,9 +1936,14 @@ pub(crate) enum InternalAssetEvent {
 /// The load state of an asset.
 #[derive(Component, Clone, Debug)]
 pub enum LoadState {
-    /// The asset has not started loading yet
+    /// The asset has not started loading yet, or its load was cancelled because all of its handles
+    /// were dropped before it finished.
     NotLoaded,
 
+    /// The asset was requested, but waits for other loads to finish before it starts loading.
+    /// See [`AssetServer::set_max_concurrent_loads`].
+    Queued,
+
     /// The asset is in the process of loading.
     Loading,
 
@@ -1804,9 +1957,14 @@ pub enum LoadState {
 }
 
 impl LoadState {
-    /// Returns `true` if this instance is [`LoadState::Loading`]
+    /// Returns `true` if this instance is [`LoadState::Loading`] or [`LoadState::Queued`]
     pub fn is_loading(&self) -> bool {
-        matches!(self, Self::Loading)
+        matches!(self, Self::Queued | Self::Loading)
+    }
+
+    /// Returns `true` if this instance is [`LoadState::Queued`]
+    pub fn is_queued(&self) -> bool {
+        matches!(self, Self::Queued)
     }
 
     /// Returns `true` if this instance is [`LoadState::Loaded`]
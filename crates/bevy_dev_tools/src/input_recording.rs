@@ -0,0 +1,503 @@
+//! Recording of input events and frame times, and deterministic replay of those recordings.
+//!
+//! [`InputRecorderPlugin`] captures every `bevy_input` event together with the real time delta
+//! of each frame into an [`InputRecording`], which can be saved as a [`ron`] file. The
+//! [`InputReplayPlugin`] then feeds a recording back through the same events, driving [`Time`]
+//! with [`TimeUpdateStrategy::ManualDuration`] so that the frame timings match the original run.
+//!
+//! This makes it possible to attach a recording to a bug report, or to replay one headlessly in
+//! a regression test.
+//!
+//! [`Time`]: bevy_time::Time
+
+use bevy_app::{prelude::*, AppExit};
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    event::{EventReader, EventWriter},
+    prelude::*,
+    system::SystemParam,
+};
+use bevy_input::{
+    gamepad::{
+        GamepadConnectionEvent, RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent,
+        RawGamepadEvent,
+    },
+    gestures::{DoubleTapGesture, PanGesture, PinchGesture, RotationGesture},
+    keyboard::{KeyboardFocusLost, KeyboardInput},
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+    InputSystems,
+};
+use bevy_time::{Real, Time, TimeSystems, TimeUpdateStrategy};
+use bevy_window::PrimaryWindow;
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// A recording of input events and frame times, produced by [`InputRecorderPlugin`] and
+/// played back by [`InputReplayPlugin`].
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct InputRecording {
+    /// Every recorded frame, in order.
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    /// Loads a recording from a [`ron`] file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, InputRecordingError> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(ron::from_str(&content)?)
+    }
+
+    /// Saves this recording to a [`ron`] file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), InputRecordingError> {
+        let content = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// An error that occurred while loading or saving an [`InputRecording`].
+#[derive(Error, Debug)]
+pub enum InputRecordingError {
+    /// The recording file could not be read or written.
+    #[error("could not access input recording file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The recording file could not be parsed.
+    #[error("could not deserialize input recording: {0}")]
+    Deserialize(#[from] ron::error::SpannedError),
+    /// The recording could not be serialized.
+    #[error("could not serialize input recording: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+/// The input events and time delta of a single frame of an [`InputRecording`].
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct RecordedFrame {
+    /// The [`Time<Real>`] delta of this frame.
+    pub delta: Duration,
+    /// The input events sent during this frame.
+    pub events: Vec<RecordedInput>,
+}
+
+/// A single recorded input event.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum RecordedInput {
+    /// A [`KeyboardInput`] event.
+    Keyboard(KeyboardInput),
+    /// A [`KeyboardFocusLost`] event.
+    KeyboardFocusLost,
+    /// A [`MouseButtonInput`] event.
+    MouseButton(MouseButtonInput),
+    /// A [`MouseMotion`] event.
+    MouseMotion(MouseMotion),
+    /// A [`MouseWheel`] event.
+    MouseWheel(MouseWheel),
+    /// A [`TouchInput`] event.
+    Touch(TouchInput),
+    /// A [`GamepadConnectionEvent`].
+    GamepadConnection(GamepadConnectionEvent),
+    /// A [`RawGamepadButtonChangedEvent`].
+    GamepadButton(RawGamepadButtonChangedEvent),
+    /// A [`RawGamepadAxisChangedEvent`].
+    GamepadAxis(RawGamepadAxisChangedEvent),
+    /// A [`PinchGesture`] event.
+    PinchGesture(PinchGesture),
+    /// A [`RotationGesture`] event.
+    RotationGesture(RotationGesture),
+    /// A [`DoubleTapGesture`] event.
+    DoubleTapGesture,
+    /// A [`PanGesture`] event.
+    PanGesture(PanGesture),
+}
+
+/// A plugin that records every input event and frame time into the [`InputRecording`] resource.
+///
+/// If a path is set, the recording is saved to it when the app sends [`AppExit`].
+#[derive(Default)]
+pub struct InputRecorderPlugin {
+    /// The file the recording is saved to on exit.
+    pub path: Option<PathBuf>,
+}
+
+impl InputRecorderPlugin {
+    /// Creates a recorder that saves to `path` on exit.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+        }
+    }
+}
+
+impl Plugin for InputRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputRecording>()
+            .insert_resource(InputRecordingPath(self.path.clone()))
+            .add_systems(Last, (record_frame, save_recording_on_exit).chain());
+    }
+}
+
+/// A plugin that replays an [`InputRecording`], one recorded frame per app update.
+///
+/// The recorded events are sent in [`PreUpdate`] before [`InputSystems`], the same place the
+/// windowing and gamepad backends send them. Gamepads from the recording are mapped to new
+/// entities, and window events are sent to the [`PrimaryWindow`] if there is one.
+///
+/// Real input is not suppressed, so replays are best run headlessly.
+pub struct InputReplayPlugin {
+    /// The recording to replay.
+    pub recording: InputRecording,
+    /// Whether to send [`AppExit::Success`] once every frame has been replayed.
+    pub exit_on_finish: bool,
+}
+
+impl InputReplayPlugin {
+    /// Creates a plugin replaying `recording`, exiting once it is finished.
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            recording,
+            exit_on_finish: true,
+        }
+    }
+
+    /// Creates a plugin replaying the recording saved at `path`, exiting once it is finished.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, InputRecordingError> {
+        InputRecording::load(path).map(Self::new)
+    }
+}
+
+impl Plugin for InputReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InputReplay {
+            recording: self.recording.clone(),
+            frame: 0,
+            exit_on_finish: self.exit_on_finish,
+            gamepads: EntityHashMap::default(),
+        })
+        .add_systems(First, replay_frame_time.before(TimeSystems))
+        .add_systems(PreUpdate, replay_input_events.before(InputSystems));
+    }
+}
+
+/// The state of an [`InputRecording`] being replayed by [`InputReplayPlugin`].
+#[derive(Resource)]
+pub struct InputReplay {
+    recording: InputRecording,
+    frame: usize,
+    exit_on_finish: bool,
+    /// Maps recorded gamepad entities to the entities spawned for them during the replay.
+    gamepads: EntityHashMap<Entity>,
+}
+
+impl InputReplay {
+    /// Returns the index of the next frame to be replayed.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns `true` once every recorded frame has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.recording.frames.len()
+    }
+
+    fn gamepad(&mut self, commands: &mut Commands, recorded: Entity) -> Entity {
+        *self
+            .gamepads
+            .entry(recorded)
+            .or_insert_with(|| commands.spawn_empty().id())
+    }
+}
+
+#[derive(Resource)]
+struct InputRecordingPath(Option<PathBuf>);
+
+#[derive(SystemParam)]
+struct InputEventReaders<'w, 's> {
+    keyboard: EventReader<'w, 's, KeyboardInput>,
+    keyboard_focus_lost: EventReader<'w, 's, KeyboardFocusLost>,
+    mouse_button: EventReader<'w, 's, MouseButtonInput>,
+    mouse_motion: EventReader<'w, 's, MouseMotion>,
+    mouse_wheel: EventReader<'w, 's, MouseWheel>,
+    touch: EventReader<'w, 's, TouchInput>,
+    gamepad_connection: EventReader<'w, 's, GamepadConnectionEvent>,
+    gamepad_button: EventReader<'w, 's, RawGamepadButtonChangedEvent>,
+    gamepad_axis: EventReader<'w, 's, RawGamepadAxisChangedEvent>,
+    pinch: EventReader<'w, 's, PinchGesture>,
+    rotation: EventReader<'w, 's, RotationGesture>,
+    double_tap: EventReader<'w, 's, DoubleTapGesture>,
+    pan: EventReader<'w, 's, PanGesture>,
+}
+
+#[derive(SystemParam)]
+struct InputEventWriters<'w> {
+    keyboard: EventWriter<'w, KeyboardInput>,
+    keyboard_focus_lost: EventWriter<'w, KeyboardFocusLost>,
+    mouse_button: EventWriter<'w, MouseButtonInput>,
+    mouse_motion: EventWriter<'w, MouseMotion>,
+    mouse_wheel: EventWriter<'w, MouseWheel>,
+    touch: EventWriter<'w, TouchInput>,
+    gamepad: EventWriter<'w, RawGamepadEvent>,
+    gamepad_connection: EventWriter<'w, GamepadConnectionEvent>,
+    gamepad_button: EventWriter<'w, RawGamepadButtonChangedEvent>,
+    gamepad_axis: EventWriter<'w, RawGamepadAxisChangedEvent>,
+    pinch: EventWriter<'w, PinchGesture>,
+    rotation: EventWriter<'w, RotationGesture>,
+    double_tap: EventWriter<'w, DoubleTapGesture>,
+    pan: EventWriter<'w, PanGesture>,
+}
+
+fn record_frame(
+    mut recording: ResMut<InputRecording>,
+    mut readers: InputEventReaders,
+    time: Res<Time<Real>>,
+) {
+    let mut events = Vec::new();
+    events.extend(
+        readers
+            .keyboard
+            .read()
+            .cloned()
+            .map(RecordedInput::Keyboard),
+    );
+    events.extend(
+        readers
+            .keyboard_focus_lost
+            .read()
+            .map(|_| RecordedInput::KeyboardFocusLost),
+    );
+    events.extend(
+        readers
+            .mouse_button
+            .read()
+            .cloned()
+            .map(RecordedInput::MouseButton),
+    );
+    events.extend(
+        readers
+            .mouse_motion
+            .read()
+            .cloned()
+            .map(RecordedInput::MouseMotion),
+    );
+    events.extend(
+        readers
+            .mouse_wheel
+            .read()
+            .cloned()
+            .map(RecordedInput::MouseWheel),
+    );
+    events.extend(readers.touch.read().cloned().map(RecordedInput::Touch));
+    events.extend(
+        readers
+            .gamepad_connection
+            .read()
+            .cloned()
+            .map(RecordedInput::GamepadConnection),
+    );
+    events.extend(
+        readers
+            .gamepad_button
+            .read()
+            .cloned()
+            .map(RecordedInput::GamepadButton),
+    );
+    events.extend(
+        readers
+            .gamepad_axis
+            .read()
+            .cloned()
+            .map(RecordedInput::GamepadAxis),
+    );
+    events.extend(
+        readers
+            .pinch
+            .read()
+            .cloned()
+            .map(RecordedInput::PinchGesture),
+    );
+    events.extend(
+        readers
+            .rotation
+            .read()
+            .cloned()
+            .map(RecordedInput::RotationGesture),
+    );
+    events.extend(
+        readers
+            .double_tap
+            .read()
+            .map(|_| RecordedInput::DoubleTapGesture),
+    );
+    events.extend(readers.pan.read().cloned().map(RecordedInput::PanGesture));
+
+    recording.frames.push(RecordedFrame {
+        delta: time.delta(),
+        events,
+    });
+}
+
+fn save_recording_on_exit(
+    mut exit: EventReader<AppExit>,
+    recording: Res<InputRecording>,
+    path: Res<InputRecordingPath>,
+) {
+    if exit.is_empty() {
+        return;
+    }
+    exit.clear();
+
+    let Some(path) = &path.0 else {
+        return;
+    };
+    match recording.save(path) {
+        Ok(()) => tracing::info!("Saved input recording to {}", path.display()),
+        Err(error) => tracing::error!("Failed to save input recording: {error}"),
+    }
+}
+
+fn replay_frame_time(replay: Res<InputReplay>, mut strategy: ResMut<TimeUpdateStrategy>) {
+    if let Some(frame) = replay.recording.frames.get(replay.frame) {
+        *strategy = TimeUpdateStrategy::ManualDuration(frame.delta);
+    }
+}
+
+fn replay_input_events(
+    mut commands: Commands,
+    mut replay: ResMut<InputReplay>,
+    mut writers: InputEventWriters,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let replay = &mut *replay;
+    let Some(frame) = replay.recording.frames.get(replay.frame) else {
+        if replay.exit_on_finish {
+            exit.write(AppExit::Success);
+        }
+        return;
+    };
+    replay.frame += 1;
+
+    let window = |recorded: Entity| primary_window.single().unwrap_or(recorded);
+    for event in frame.events.clone() {
+        match event {
+            RecordedInput::Keyboard(mut event) => {
+                event.window = window(event.window);
+                writers.keyboard.write(event);
+            }
+            RecordedInput::KeyboardFocusLost => {
+                writers.keyboard_focus_lost.write(KeyboardFocusLost);
+            }
+            RecordedInput::MouseButton(mut event) => {
+                event.window = window(event.window);
+                writers.mouse_button.write(event);
+            }
+            RecordedInput::MouseMotion(event) => {
+                writers.mouse_motion.write(event);
+            }
+            RecordedInput::MouseWheel(mut event) => {
+                event.window = window(event.window);
+                writers.mouse_wheel.write(event);
+            }
+            RecordedInput::Touch(mut event) => {
+                event.window = window(event.window);
+                writers.touch.write(event);
+            }
+            RecordedInput::GamepadConnection(mut event) => {
+                event.gamepad = replay.gamepad(&mut commands, event.gamepad);
+                writers.gamepad.write(event.clone().into());
+                writers.gamepad_connection.write(event);
+            }
+            RecordedInput::GamepadButton(mut event) => {
+                event.gamepad = replay.gamepad(&mut commands, event.gamepad);
+                writers.gamepad.write(event.into());
+                writers.gamepad_button.write(event);
+            }
+            RecordedInput::GamepadAxis(mut event) => {
+                event.gamepad = replay.gamepad(&mut commands, event.gamepad);
+                writers.gamepad.write(event.into());
+                writers.gamepad_axis.write(event);
+            }
+            RecordedInput::PinchGesture(event) => {
+                writers.pinch.write(event);
+            }
+            RecordedInput::RotationGesture(event) => {
+                writers.rotation.write(event);
+            }
+            RecordedInput::DoubleTapGesture => {
+                writers.double_tap.write(DoubleTapGesture);
+            }
+            RecordedInput::PanGesture(event) => {
+                writers.pan.write(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_input::keyboard::KeyCode;
+    use bevy_input::{keyboard::Key, ButtonInput, ButtonState, InputPlugin};
+    use bevy_time::TimePlugin;
+
+    fn key_event(state: ButtonState) -> KeyboardInput {
+        KeyboardInput {
+            key_code: KeyCode::Space,
+            logical_key: Key::Space,
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        }
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, InputPlugin, InputRecorderPlugin::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                10,
+            )));
+        app.update();
+        app.world_mut().write_event(key_event(ButtonState::Pressed));
+        app.update();
+        app.world_mut()
+            .write_event(key_event(ButtonState::Released));
+        app.update();
+
+        let recording = app.world().resource::<InputRecording>().clone();
+        assert_eq!(recording.frames.len(), 3);
+        assert!(recording.frames[0].events.is_empty());
+        assert_eq!(
+            recording.frames[1].events,
+            [RecordedInput::Keyboard(key_event(ButtonState::Pressed))]
+        );
+        assert_eq!(recording.frames[2].delta, Duration::from_millis(10));
+
+        let recording: InputRecording =
+            ron::from_str(&ron::to_string(&recording).unwrap()).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, InputPlugin, InputReplayPlugin::new(recording)));
+        app.update();
+        app.update();
+        assert!(app
+            .world()
+            .resource::<ButtonInput<KeyCode>>()
+            .just_pressed(KeyCode::Space));
+        app.update();
+        assert!(app
+            .world()
+            .resource::<ButtonInput<KeyCode>>()
+            .just_released(KeyCode::Space));
+        assert_eq!(
+            app.world().resource::<Time<Real>>().delta(),
+            Duration::from_millis(10)
+        );
+        assert!(app.world().resource::<InputReplay>().is_finished());
+
+        app.update();
+        assert!(app.should_exit().is_some());
+    }
+}
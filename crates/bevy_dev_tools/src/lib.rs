@@ -13,6 +13,9 @@ use bevy_app::prelude::*;
 #[cfg(feature = "bevy_ci_testing")]
 pub mod ci_testing;
 
+#[cfg(feature = "bevy_input_recording")]
+pub mod input_recording;
+
 pub mod fps_overlay;
 pub mod frame_time_graph;
 
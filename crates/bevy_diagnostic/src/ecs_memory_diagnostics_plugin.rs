@@ -0,0 +1,92 @@
+use bevy_app::prelude::*;
+use bevy_ecs::world::World;
+
+use crate::{
+    Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic, DEFAULT_MAX_HISTORY_LENGTH,
+};
+
+/// Adds "ECS memory" diagnostics to an App, measuring the memory held by the storages of the
+/// main world in KiB.
+///
+/// The measurements are the totals of [`World::memory_report`], which can be used to find out
+/// which archetypes, tables, components or resources the memory is held by.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct EcsMemoryDiagnosticsPlugin {
+    /// The total number of values to keep.
+    pub max_history_length: usize,
+}
+
+impl Default for EcsMemoryDiagnosticsPlugin {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_HISTORY_LENGTH)
+    }
+}
+
+impl EcsMemoryDiagnosticsPlugin {
+    /// Creates a new `EcsMemoryDiagnosticsPlugin` with the specified `max_history_length`.
+    pub fn new(max_history_length: usize) -> Self {
+        Self { max_history_length }
+    }
+}
+
+impl Plugin for EcsMemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(
+            Diagnostic::new(Self::USED)
+                .with_suffix("KiB")
+                .with_max_history_length(self.max_history_length),
+        )
+        .register_diagnostic(
+            Diagnostic::new(Self::ALLOCATED)
+                .with_suffix("KiB")
+                .with_max_history_length(self.max_history_length),
+        )
+        .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl EcsMemoryDiagnosticsPlugin {
+    /// Memory holding the values stored in the world.
+    pub const USED: DiagnosticPath = DiagnosticPath::const_new("ecs_memory/used");
+
+    /// Memory allocated by the storages of the world, including unused capacity.
+    pub const ALLOCATED: DiagnosticPath = DiagnosticPath::const_new("ecs_memory/allocated");
+
+    /// Updates the ECS memory measurements.
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, world: &World) {
+        let mut total = None;
+        let mut total = || *total.get_or_insert_with(|| world.memory_report().total());
+        diagnostics.add_measurement(&Self::USED, || total().used as f64 / 1024.0);
+        diagnostics.add_measurement(&Self::ALLOCATED, || total().allocated as f64 / 1024.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiagnosticsPlugin, DiagnosticsStore};
+
+    #[test]
+    fn measures_ecs_memory() {
+        let mut app = App::new();
+        app.add_plugins((DiagnosticsPlugin, EcsMemoryDiagnosticsPlugin::default()));
+        app.update();
+
+        let diagnostics = app.world().resource::<DiagnosticsStore>();
+        let used = diagnostics
+            .get(&EcsMemoryDiagnosticsPlugin::USED)
+            .unwrap()
+            .value()
+            .unwrap();
+        let allocated = diagnostics
+            .get(&EcsMemoryDiagnosticsPlugin::ALLOCATED)
+            .unwrap()
+            .value()
+            .unwrap();
+        assert!(used > 0.0);
+        assert!(allocated >= used);
+    }
+}
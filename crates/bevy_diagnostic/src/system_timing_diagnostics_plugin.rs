@@ -0,0 +1,213 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, SystemTimings},
+};
+use bevy_platform::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+
+use crate::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore, DEFAULT_MAX_HISTORY_LENGTH,
+};
+
+/// Adds "system time" and "schedule time" diagnostics to an App.
+///
+/// Every frame, the wall time spent running each system and each schedule of the main world is
+/// added, in milliseconds, to a [`Diagnostic`] at [`SystemTimingDiagnosticsPlugin::system_path`]
+/// or [`SystemTimingDiagnosticsPlugin::schedule_path`]. These diagnostics are created the first time
+/// their system or schedule runs. Systems are told apart by their name, which requires the `debug`
+/// feature of `bevy_ecs`.
+///
+/// Measurements are taken by the schedule executors through the [`SystemTimings`] resource, which
+/// this plugin inserts.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct SystemTimingDiagnosticsPlugin {
+    /// The total number of values to keep.
+    pub max_history_length: usize,
+    /// If `Some`, only the system time diagnostics of this many systems, the slowest ones, are kept
+    /// enabled, so that only those are logged by the [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin).
+    ///
+    /// Disabled system time diagnostics are still measured, so that a system becoming slower
+    /// can take the place of another.
+    pub log_slowest: Option<usize>,
+}
+
+impl Default for SystemTimingDiagnosticsPlugin {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_HISTORY_LENGTH)
+    }
+}
+
+impl SystemTimingDiagnosticsPlugin {
+    /// Creates a new `SystemTimingDiagnosticsPlugin` with the specified `max_history_length`.
+    pub fn new(max_history_length: usize) -> Self {
+        Self {
+            max_history_length,
+            log_slowest: None,
+        }
+    }
+
+    /// Only keeps the system time diagnostics of the `count` slowest systems enabled.
+    pub fn with_log_slowest(mut self, count: usize) -> Self {
+        self.log_slowest = Some(count);
+        self
+    }
+}
+
+impl Plugin for SystemTimingDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let max_history_length = self.max_history_length;
+        let log_slowest = self.log_slowest;
+
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<SystemTimings>()
+            .add_systems(
+                Last,
+                move |timings: Res<SystemTimings>,
+                      diagnostics: ResMut<DiagnosticsStore>,
+                      system_paths: Local<HashSet<DiagnosticPath>>| {
+                    Self::diagnostic_system(
+                        timings,
+                        diagnostics,
+                        system_paths,
+                        max_history_length,
+                        log_slowest,
+                    );
+                },
+            );
+    }
+}
+
+impl SystemTimingDiagnosticsPlugin {
+    /// Prefix of the paths of the system time diagnostics.
+    pub const SYSTEM_TIME: DiagnosticPath = DiagnosticPath::const_new("system_time");
+
+    /// Prefix of the paths of the schedule time diagnostics.
+    pub const SCHEDULE_TIME: DiagnosticPath = DiagnosticPath::const_new("schedule_time");
+
+    /// The path of the diagnostic measuring the time spent running the system named `system`
+    /// in the schedule `schedule`.
+    pub fn system_path(schedule: InternedScheduleLabel, system: &str) -> DiagnosticPath {
+        DiagnosticPath::from_components([
+            Self::SYSTEM_TIME.as_str(),
+            &path_component(&format!("{schedule:?}")),
+            &path_component(system),
+        ])
+    }
+
+    /// The path of the diagnostic measuring the time spent running the schedule `schedule`.
+    pub fn schedule_path(schedule: InternedScheduleLabel) -> DiagnosticPath {
+        DiagnosticPath::from_components([
+            Self::SCHEDULE_TIME.as_str(),
+            &path_component(&format!("{schedule:?}")),
+        ])
+    }
+
+    /// Adds the time spent running each system and schedule since the last run as measurements.
+    fn diagnostic_system(
+        timings: Res<SystemTimings>,
+        mut diagnostics: ResMut<DiagnosticsStore>,
+        mut system_paths: Local<HashSet<DiagnosticPath>>,
+        max_history_length: usize,
+        log_slowest: Option<usize>,
+    ) {
+        // A system or schedule may run several times per frame, so their runs are summed.
+        let mut frame_times = HashMap::<DiagnosticPath, f64>::default();
+        for timing in timings.drain() {
+            let path = match &timing.system {
+                Some(system) => {
+                    let path = Self::system_path(timing.schedule, &system.to_string());
+                    system_paths.insert(path.clone());
+                    path
+                }
+                None => Self::schedule_path(timing.schedule),
+            };
+            *frame_times.entry(path).or_default() += timing.duration.as_secs_f64() * 1000.0;
+        }
+
+        let time = Instant::now();
+        for (path, value) in frame_times {
+            if diagnostics.get(&path).is_none() {
+                diagnostics.add(
+                    Diagnostic::new(path.clone())
+                        .with_suffix("ms")
+                        .with_max_history_length(max_history_length),
+                );
+            }
+            if let Some(diagnostic) = diagnostics.get_mut(&path) {
+                diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+            }
+        }
+
+        let Some(log_slowest) = log_slowest else {
+            return;
+        };
+        let mut slowest: Vec<(&DiagnosticPath, f64)> = system_paths
+            .iter()
+            .filter_map(|path| Some((path, diagnostics.get(path)?.smoothed()?)))
+            .collect();
+        slowest.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        let enabled: HashSet<&DiagnosticPath> = slowest
+            .into_iter()
+            .take(log_slowest)
+            .map(|(path, _)| path)
+            .collect();
+        for path in system_paths.iter() {
+            if let Some(diagnostic) = diagnostics.get_mut(path) {
+                diagnostic.is_enabled = enabled.contains(path);
+            }
+        }
+    }
+}
+
+/// Makes `name` usable as a single component of a [`DiagnosticPath`].
+fn path_component(name: &str) -> String {
+    let component = name.replace('/', "_");
+    if component.is_empty() {
+        String::from("_")
+    } else {
+        component
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_system_and_schedule_times() {
+        let mut app = App::new();
+        app.add_plugins(SystemTimingDiagnosticsPlugin::default().with_log_slowest(0))
+            .add_systems(Update, || {});
+        app.update();
+        app.update();
+
+        let diagnostics = app.world().resource::<DiagnosticsStore>();
+        let update = DiagnosticPath::const_new("schedule_time/Update");
+        assert_eq!(diagnostics.get(&update).unwrap().history_len(), 2);
+        assert!(diagnostics.get(&update).unwrap().is_enabled);
+
+        let systems: Vec<_> = diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                diagnostic
+                    .path()
+                    .as_str()
+                    .starts_with("system_time/Update/")
+            })
+            .collect();
+        assert!(!systems.is_empty());
+        assert!(systems.iter().all(|diagnostic| !diagnostic.is_enabled));
+    }
+}
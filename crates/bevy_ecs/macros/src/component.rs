@@ -134,13 +134,22 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         Ok(value) => value,
         Err(err) => err.into_compile_error().into(),
     };
+    let many_relationship = match derive_many_relationship(&ast, &attrs, &bevy_ecs_path) {
+        Ok(value) => value,
+        Err(err) => err.into_compile_error().into(),
+    };
+    let many_relationship_target =
+        match derive_many_relationship_target(&ast, &attrs, &bevy_ecs_path) {
+            Ok(value) => value,
+            Err(err) => err.into_compile_error().into(),
+        };
 
     let map_entities = map_entities(
         &ast.data,
         &bevy_ecs_path,
         Ident::new("this", Span::call_site()),
-        relationship.is_some(),
-        relationship_target.is_some(),
+        relationship.is_some() || many_relationship.is_some(),
+        relationship_target.is_some() || many_relationship_target.is_some(),
         attrs.map_entities
     ).map(|map_entities_impl| quote! {
         fn map_entities<M: #bevy_ecs_path::entity::EntityMapper>(this: &mut Self, mapper: &mut M) {
@@ -169,6 +178,17 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         }
 
         Some(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_insert))
+    } else if many_relationship.is_some() {
+        if attrs.on_insert.is_some() {
+            return syn::Error::new(
+                ast.span(),
+                "Custom on_insert hooks are not supported as ManyRelationships already define an on_insert hook",
+            )
+            .into_compile_error()
+            .into();
+        }
+
+        Some(quote!(<Self as #bevy_ecs_path::relationship::ManyRelationship>::on_insert))
     } else {
         attrs
             .on_insert
@@ -197,6 +217,28 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         }
 
         Some(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_replace))
+    } else if many_relationship.is_some() {
+        if attrs.on_replace.is_some() {
+            return syn::Error::new(
+                ast.span(),
+                "Custom on_replace hooks are not supported as ManyRelationships already define an on_replace hook",
+            )
+            .into_compile_error()
+            .into();
+        }
+
+        Some(quote!(<Self as #bevy_ecs_path::relationship::ManyRelationship>::on_replace))
+    } else if many_relationship_target.is_some() {
+        if attrs.on_replace.is_some() {
+            return syn::Error::new(
+                ast.span(),
+                "Custom on_replace hooks are not supported as ManyRelationshipTarget already defines an on_replace hook",
+            )
+            .into_compile_error()
+            .into();
+        }
+
+        Some(quote!(<Self as #bevy_ecs_path::relationship::ManyRelationshipTarget>::on_replace))
     } else {
         attrs
             .on_replace
@@ -205,6 +247,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
 
     let on_despawn_path = if attrs
         .relationship_target
+        .as_ref()
         .is_some_and(|target| target.linked_spawn)
     {
         if attrs.on_despawn.is_some() {
@@ -217,6 +260,21 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         }
 
         Some(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_despawn))
+    } else if attrs
+        .many_relationship_target
+        .as_ref()
+        .is_some_and(|target| target.linked_spawn)
+    {
+        if attrs.on_despawn.is_some() {
+            return syn::Error::new(
+                ast.span(),
+                "Custom on_despawn hooks are not supported as this ManyRelationshipTarget already defines an on_despawn hook, via the 'linked_spawn' attribute",
+            )
+            .into_compile_error()
+            .into();
+        }
+
+        Some(quote!(<Self as #bevy_ecs_path::relationship::ManyRelationshipTarget>::on_despawn))
     } else {
         attrs
             .on_despawn
@@ -265,9 +323,12 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         }
     });
 
-    let mutable_type = (attrs.immutable || relationship.is_some())
-        .then_some(quote! { #bevy_ecs_path::component::Immutable })
-        .unwrap_or(quote! { #bevy_ecs_path::component::Mutable });
+    let mutable_type = (attrs.immutable
+        || matches!(attrs.storage, StorageTy::Shared)
+        || relationship.is_some()
+        || many_relationship.is_some())
+    .then_some(quote! { #bevy_ecs_path::component::Immutable })
+    .unwrap_or(quote! { #bevy_ecs_path::component::Mutable });
 
     let clone_behavior = if relationship_target.is_some() || relationship.is_some() {
         quote!(
@@ -277,6 +338,9 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
                 };
             (&&&&&&&#bevy_ecs_path::relationship::RelationshipCloneBehaviorSpecialization::<Self>::default()).default_clone_behavior()
         )
+    } else if many_relationship_target.is_some() {
+        // The targets are rebuilt by the `ManyRelationship` hooks of the cloned sources.
+        quote!(#bevy_ecs_path::component::ComponentCloneBehavior::Ignore)
     } else if let Some(behavior) = attrs.clone_behavior {
         quote!(#bevy_ecs_path::component::ComponentCloneBehavior::#behavior)
     } else {
@@ -316,6 +380,10 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         #relationship
 
         #relationship_target
+
+        #many_relationship
+
+        #many_relationship_target
     })
 }
 
@@ -417,6 +485,8 @@ pub const STORAGE: &str = "storage";
 pub const REQUIRE: &str = "require";
 pub const RELATIONSHIP: &str = "relationship";
 pub const RELATIONSHIP_TARGET: &str = "relationship_target";
+pub const MANY_RELATIONSHIP: &str = "many_relationship";
+pub const MANY_RELATIONSHIP_TARGET: &str = "many_relationship_target";
 
 pub const ON_ADD: &str = "on_add";
 pub const ON_INSERT: &str = "on_insert";
@@ -542,6 +612,8 @@ struct Attrs {
     on_despawn: Option<HookAttributeKind>,
     relationship: Option<Relationship>,
     relationship_target: Option<RelationshipTarget>,
+    many_relationship: Option<Relationship>,
+    many_relationship_target: Option<RelationshipTarget>,
     immutable: bool,
     clone_behavior: Option<Expr>,
     map_entities: Option<MapEntitiesAttributeKind>,
@@ -551,6 +623,7 @@ struct Attrs {
 enum StorageTy {
     Table,
     SparseSet,
+    Shared,
 }
 
 struct Require {
@@ -570,6 +643,7 @@ struct RelationshipTarget {
 // values for `storage` attribute
 const TABLE: &str = "Table";
 const SPARSE_SET: &str = "SparseSet";
+const SHARED: &str = "Shared";
 
 fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     let mut attrs = Attrs {
@@ -582,6 +656,8 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
         requires: None,
         relationship: None,
         relationship_target: None,
+        many_relationship: None,
+        many_relationship_target: None,
         immutable: false,
         clone_behavior: None,
         map_entities: None,
@@ -595,9 +671,10 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                     attrs.storage = match nested.value()?.parse::<LitStr>()?.value() {
                         s if s == TABLE => StorageTy::Table,
                         s if s == SPARSE_SET => StorageTy::SparseSet,
+                        s if s == SHARED => StorageTy::Shared,
                         s => {
                             return Err(nested.error(format!(
-                                "Invalid storage type `{s}`, expected '{TABLE}' or '{SPARSE_SET}'.",
+                                "Invalid storage type `{s}`, expected '{TABLE}', '{SPARSE_SET}' or '{SHARED}'.",
                             )));
                         }
                     };
@@ -652,10 +729,31 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
         } else if attr.path().is_ident(RELATIONSHIP_TARGET) {
             let relationship_target = attr.parse_args::<RelationshipTarget>()?;
             attrs.relationship_target = Some(relationship_target);
+        } else if attr.path().is_ident(MANY_RELATIONSHIP) {
+            let relationship = attr.parse_args::<Relationship>()?;
+            attrs.many_relationship = Some(relationship);
+        } else if attr.path().is_ident(MANY_RELATIONSHIP_TARGET) {
+            let relationship_target = attr.parse_args::<RelationshipTarget>()?;
+            attrs.many_relationship_target = Some(relationship_target);
         }
     }
 
-    if attrs.relationship_target.is_some() && attrs.clone_behavior.is_some() {
+    let relationship_kinds = [
+        attrs.relationship.is_some(),
+        attrs.relationship_target.is_some(),
+        attrs.many_relationship.is_some(),
+        attrs.many_relationship_target.is_some(),
+    ];
+    if relationship_kinds.into_iter().filter(|kind| *kind).count() > 1 {
+        return Err(syn::Error::new(
+            ast.span(),
+            "A component can only be one of Relationship, RelationshipTarget, ManyRelationship or ManyRelationshipTarget",
+        ));
+    }
+
+    if (attrs.relationship_target.is_some() || attrs.many_relationship_target.is_some())
+        && attrs.clone_behavior.is_some()
+    {
         return Err(syn::Error::new(
                 attrs.clone_behavior.span(),
                 "A Relationship Target already has its own clone behavior, please remove `clone_behavior = ...`",
@@ -733,6 +831,7 @@ fn storage_path(bevy_ecs_path: &Path, ty: StorageTy) -> TokenStream2 {
     let storage_type = match ty {
         StorageTy::Table => Ident::new("Table", Span::call_site()),
         StorageTy::SparseSet => Ident::new("SparseSet", Span::call_site()),
+        StorageTy::Shared => Ident::new("Shared", Span::call_site()),
     };
 
     quote! { #bevy_ecs_path::component::StorageType::#storage_type }
@@ -917,6 +1016,112 @@ fn derive_relationship_target(
     }))
 }
 
+fn derive_many_relationship(
+    ast: &DeriveInput,
+    attrs: &Attrs,
+    bevy_ecs_path: &Path,
+) -> Result<Option<TokenStream2>> {
+    let Some(relationship) = &attrs.many_relationship else {
+        return Ok(None);
+    };
+    let Data::Struct(DataStruct {
+        fields,
+        struct_token,
+        ..
+    }) = &ast.data
+    else {
+        return Err(syn::Error::new(
+            ast.span(),
+            "ManyRelationship can only be derived for structs.",
+        ));
+    };
+    let field = relationship_field(fields, "ManyRelationship", struct_token.span())?;
+    let accessors = collection_accessors(fields, field);
+
+    let relationship_target = &relationship.relationship_target;
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+    Ok(Some(quote! {
+        impl #impl_generics #bevy_ecs_path::relationship::ManyRelationship for #struct_name #type_generics #where_clause {
+            type RelationshipTarget = #relationship_target;
+
+            #accessors
+        }
+    }))
+}
+
+fn derive_many_relationship_target(
+    ast: &DeriveInput,
+    attrs: &Attrs,
+    bevy_ecs_path: &Path,
+) -> Result<Option<TokenStream2>> {
+    let Some(relationship_target) = &attrs.many_relationship_target else {
+        return Ok(None);
+    };
+    let Data::Struct(DataStruct {
+        fields,
+        struct_token,
+        ..
+    }) = &ast.data
+    else {
+        return Err(syn::Error::new(
+            ast.span(),
+            "ManyRelationshipTarget can only be derived for structs.",
+        ));
+    };
+    let field = relationship_field(fields, "ManyRelationshipTarget", struct_token.span())?;
+
+    if field.vis != Visibility::Inherited {
+        return Err(syn::Error::new(field.span(), "The collection in ManyRelationshipTarget must be private to prevent users from directly mutating it, which could invalidate the correctness of relationships."));
+    }
+    let accessors = collection_accessors(fields, field);
+
+    let relationship = &relationship_target.relationship;
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+    let linked_spawn = relationship_target.linked_spawn;
+    Ok(Some(quote! {
+        impl #impl_generics #bevy_ecs_path::relationship::ManyRelationshipTarget for #struct_name #type_generics #where_clause {
+            const LINKED_SPAWN: bool = #linked_spawn;
+            type Relationship = #relationship;
+
+            #accessors
+        }
+    }))
+}
+
+/// Returns the `Collection` type and its accessors for a collection stored in `field`,
+/// shared by the many-to-many relationship derives.
+fn collection_accessors(fields: &Fields, field: &Field) -> TokenStream2 {
+    let collection = &field.ty;
+    let relationship_member = field.ident.clone().map_or(Member::from(0), Member::Named);
+    let members = fields
+        .members()
+        .filter(|member| member != &relationship_member);
+
+    quote! {
+        type Collection = #collection;
+
+        #[inline]
+        fn collection(&self) -> &Self::Collection {
+            &self.#relationship_member
+        }
+
+        #[inline]
+        fn collection_mut_risky(&mut self) -> &mut Self::Collection {
+            &mut self.#relationship_member
+        }
+
+        #[inline]
+        fn from_collection_risky(collection: Self::Collection) -> Self {
+            Self {
+                #(#members: core::default::Default::default(),)*
+                #relationship_member: collection
+            }
+        }
+    }
+}
+
 /// Returns the field with the `#[relationship]` attribute, the only field if unnamed,
 /// or the only field in a [`Fields::Named`] with one field, otherwise `Err`.
 fn relationship_field<'a>(
@@ -647,6 +647,17 @@ pub fn derive_resource(input: TokenStream) -> TokenStream {
 /// pub struct Children(Vec<Entity>);
 /// ```
 ///
+/// Many-to-many relationships, where each source can relate to any number of targets:
+/// ```ignore
+/// #[derive(Component)]
+/// #[many_relationship(relationship_target = TargetedBy)]
+/// pub struct Targeting(pub Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[many_relationship_target(relationship = Targeting, linked_spawn)]
+/// pub struct TargetedBy(Vec<Entity>);
+/// ```
+///
 /// ## Hooks
 /// ```ignore
 /// #[derive(Component)]
@@ -666,7 +677,15 @@ pub fn derive_resource(input: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro_derive(
     Component,
-    attributes(component, require, relationship, relationship_target, entities)
+    attributes(
+        component,
+        require,
+        relationship,
+        relationship_target,
+        many_relationship,
+        many_relationship_target,
+        entities
+    )
 )]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     component::derive_component(input)
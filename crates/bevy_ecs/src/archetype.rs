@@ -4,12 +4,13 @@
 //! An archetype uniquely describes a group of entities that share the same components:
 //! a world only has one archetype for each unique combination of components, and all
 //! entities that have those components and only those components belong to that
-//! archetype.
+//! archetype. Entities with different values of a [`Shared`](crate::component::StorageType::Shared)
+//! component are in different archetypes.
 //!
 //! Archetypes are not to be confused with [`Table`]s. Each archetype stores its table
 //! components in one table, and each archetype uniquely points to one table, but multiple
 //! archetypes may store their table components in the same table. These archetypes
-//! differ only by the [`SparseSet`] components.
+//! differ only by the [`SparseSet`] and shared components.
 //!
 //! Like tables, archetypes can be created but are never cleaned up. Empty archetypes are
 //! not removed, and persist until the world is dropped.
@@ -25,7 +26,9 @@ use crate::{
     entity::{Entity, EntityLocation},
     event::Event,
     observer::Observers,
-    storage::{ImmutableSparseSet, SparseArray, SparseSet, TableId, TableRow},
+    storage::{
+        ImmutableSparseSet, MemoryUsage, SharedValueId, SparseArray, SparseSet, TableId, TableRow,
+    },
 };
 use alloc::{boxed::Box, vec::Vec};
 use bevy_platform::collections::{hash_map::Entry, HashMap};
@@ -354,6 +357,8 @@ pub(crate) struct ArchetypeSwapRemoveResult {
 /// [`Component`]: crate::component::Component
 struct ArchetypeComponentInfo {
     storage_type: StorageType,
+    /// The value of the component, if it is a [`Shared`](StorageType::Shared) component.
+    shared_value: Option<SharedValueId>,
 }
 
 bitflags::bitflags! {
@@ -391,7 +396,7 @@ pub struct Archetype {
 }
 
 impl Archetype {
-    /// `table_components` and `sparse_set_components` must be sorted
+    /// `table_components`, `sparse_set_components` and `shared_components` must be sorted
     pub(crate) fn new(
         components: &Components,
         component_index: &mut ComponentIndex,
@@ -400,11 +405,14 @@ impl Archetype {
         table_id: TableId,
         table_components: impl Iterator<Item = ComponentId>,
         sparse_set_components: impl Iterator<Item = ComponentId>,
+        shared_components: impl Iterator<Item = (ComponentId, SharedValueId)>,
     ) -> Self {
         let (min_table, _) = table_components.size_hint();
         let (min_sparse, _) = sparse_set_components.size_hint();
+        let (min_shared, _) = shared_components.size_hint();
         let mut flags = ArchetypeFlags::empty();
-        let mut archetype_components = SparseSet::with_capacity(min_table + min_sparse);
+        let mut archetype_components =
+            SparseSet::with_capacity(min_table + min_sparse + min_shared);
         for (idx, component_id) in table_components.enumerate() {
             // SAFETY: We are creating an archetype that includes this component so it must exist
             let info = unsafe { components.get_info_unchecked(component_id) };
@@ -414,6 +422,7 @@ impl Archetype {
                 component_id,
                 ArchetypeComponentInfo {
                     storage_type: StorageType::Table,
+                    shared_value: None,
                 },
             );
             // NOTE: the `table_components` are sorted AND they were inserted in the `Table` in the same
@@ -434,6 +443,25 @@ impl Archetype {
                 component_id,
                 ArchetypeComponentInfo {
                     storage_type: StorageType::SparseSet,
+                    shared_value: None,
+                },
+            );
+            component_index
+                .entry(component_id)
+                .or_default()
+                .insert(id, ArchetypeRecord { column: None });
+        }
+
+        for (component_id, value) in shared_components {
+            // SAFETY: We are creating an archetype that includes this component so it must exist
+            let info = unsafe { components.get_info_unchecked(component_id) };
+            info.update_archetype_flags(&mut flags);
+            observers.update_archetype_flags(component_id, &mut flags);
+            archetype_components.insert(
+                component_id,
+                ArchetypeComponentInfo {
+                    storage_type: StorageType::Shared,
+                    shared_value: Some(value),
                 },
             );
             component_index
@@ -477,6 +505,21 @@ impl Archetype {
         &self.entities
     }
 
+    /// Returns the memory held by the archetype's list of entities and of components.
+    ///
+    /// The components of its entities are stored in [`Table`]s and [`SparseSet`]s, and are not
+    /// included.
+    ///
+    /// [`Table`]: crate::storage::Table
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::of_vec(&self.entities) + self.components.memory_usage()
+    }
+
+    /// Shrinks the capacity of the archetype's list of entities to its number of entities.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.entities.shrink_to_fit();
+    }
+
     /// Fetches the entities contained in this archetype.
     #[inline]
     pub fn entities_with_location(&self) -> impl Iterator<Item = (Entity, EntityLocation)> {
@@ -524,6 +567,29 @@ impl Archetype {
             .map(|(id, _)| *id)
     }
 
+    /// Gets an iterator of all of the [`Shared`](StorageType::Shared) components of the archetype,
+    /// along with their value.
+    ///
+    /// All of the IDs are unique.
+    #[inline]
+    pub fn shared_components(&self) -> impl Iterator<Item = (ComponentId, SharedValueId)> + '_ {
+        self.components
+            .iter()
+            .filter_map(|(id, component)| Some((*id, component.shared_value?)))
+    }
+
+    /// Gets the value of a [`Shared`](StorageType::Shared) component for all the entities of the
+    /// archetype.
+    ///
+    /// Returns `None` if the component is not part of the archetype or is not a shared component.
+    /// This runs in `O(1)` time.
+    #[inline]
+    pub fn get_shared_value(&self, component_id: ComponentId) -> Option<SharedValueId> {
+        self.components
+            .get(component_id)
+            .and_then(|info| info.shared_value)
+    }
+
     /// Gets an iterator of all of the components in the archetype.
     ///
     /// All of the IDs are unique.
@@ -751,6 +817,7 @@ impl ArchetypeGeneration {
 struct ArchetypeComponents {
     table_components: Box<[ComponentId]>,
     sparse_set_components: Box<[ComponentId]>,
+    shared_components: Box<[(ComponentId, SharedValueId)]>,
 }
 
 /// Maps a [`ComponentId`] to the list of [`Archetypes`]([`Archetype`]) that contain the [`Component`](crate::component::Component),
@@ -774,7 +841,7 @@ pub struct Archetypes {
 /// Metadata about how a component is stored in an [`Archetype`].
 pub struct ArchetypeRecord {
     /// Index of the component in the archetype's [`Table`](crate::storage::Table),
-    /// or None if the component is a sparse set component.
+    /// or None if the component is a sparse set or shared component.
     #[expect(
         dead_code,
         reason = "Currently unused, but planned to be used to implement a component index to improve performance of fragmenting relations."
@@ -797,6 +864,7 @@ impl Archetypes {
                 TableId::empty(),
                 Vec::new(),
                 Vec::new(),
+                Vec::new(),
             );
         }
         archetypes
@@ -878,11 +946,12 @@ impl Archetypes {
     /// Specifically, it returns a tuple where the first element
     /// is the [`ArchetypeId`] that the given inputs belong to, and the second element is a boolean indicating whether a new archetype was created.
     ///
-    /// `table_components` and `sparse_set_components` must be sorted
+    /// `table_components`, `sparse_set_components` and `shared_components` must be sorted
     ///
     /// # Safety
     /// [`TableId`] must exist in tables
-    /// `table_components` and `sparse_set_components` must exist in `components`
+    /// `table_components`, `sparse_set_components` and `shared_components` must exist in `components`
+    /// The values of `shared_components` must exist in their [`SharedComponentValues`](crate::storage::SharedComponentValues)
     pub(crate) unsafe fn get_id_or_insert(
         &mut self,
         components: &Components,
@@ -890,10 +959,12 @@ impl Archetypes {
         table_id: TableId,
         table_components: Vec<ComponentId>,
         sparse_set_components: Vec<ComponentId>,
+        shared_components: Vec<(ComponentId, SharedValueId)>,
     ) -> (ArchetypeId, bool) {
         let archetype_identity = ArchetypeComponents {
             sparse_set_components: sparse_set_components.into_boxed_slice(),
             table_components: table_components.into_boxed_slice(),
+            shared_components: shared_components.into_boxed_slice(),
         };
 
         let archetypes = &mut self.archetypes;
@@ -904,6 +975,7 @@ impl Archetypes {
                 let ArchetypeComponents {
                     table_components,
                     sparse_set_components,
+                    shared_components,
                 } = vacant.key();
                 let id = ArchetypeId::new(archetypes.len());
                 archetypes.push(Archetype::new(
@@ -914,6 +986,7 @@ impl Archetypes {
                     table_id,
                     table_components.iter().copied(),
                     sparse_set_components.iter().copied(),
+                    shared_components.iter().copied(),
                 ));
                 vacant.insert(id);
                 (id, true)
@@ -0,0 +1,458 @@
+//! Async tasks with scoped [`World`] access.
+//!
+//! Multi-frame logic, such as gameplay scripts or loading flows, is awkward to write as systems:
+//! its state has to be stored between frames and resumed by hand. Async tasks let it be written
+//! as a linear `async` block instead, which `await`s access to the [`World`] through an
+//! [`AsyncWorld`] handle.
+//!
+//! Async tasks are spawned with [`World::spawn_async`] or [`Commands::spawn_async`], and are
+//! driven by the [`run_async_tasks`] exclusive system, which is the sync point where their world
+//! accesses are applied. Every time it runs, each task is polled and the world accesses it awaits
+//! are run with exclusive access to the [`World`], until every task either completed or waits for
+//! something else, such as the [next frame](AsyncWorld::next_frame). A task can make at most
+//! [`MAX_ASYNC_TASK_ROUNDS`] world accesses in a row per run, so one that keeps accessing the
+//! world resumes the next time [`run_async_tasks`] runs instead of stalling the frame.
+//!
+//! ```
+//! use bevy_ecs::{async_world::run_async_tasks, prelude::*};
+//!
+//! #[derive(Resource, Default)]
+//! struct Loaded(bool);
+//!
+//! let mut world = World::new();
+//! world.init_resource::<Loaded>();
+//! world.spawn_async(|world| async move {
+//!     // Wait for the rest of the game to be ready.
+//!     world.wait_frames(2).await;
+//!     world.run(|world| world.resource_mut::<Loaded>().0 = true).await;
+//! });
+//!
+//! for _ in 0..2 {
+//!     run_async_tasks(&mut world);
+//!     assert!(!world.resource::<Loaded>().0);
+//! }
+//! run_async_tasks(&mut world);
+//! assert!(world.resource::<Loaded>().0);
+//! ```
+//!
+//! Async tasks can `await` any future, for example a [`Task`](bevy_tasks::Task) spawned on one of the
+//! task pools. Such futures are only polled when [`run_async_tasks`] runs.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    future::{poll_fn, Future},
+    mem,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use bevy_platform::{
+    cell::SyncCell,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, PoisonError, Weak,
+    },
+};
+use concurrent_queue::ConcurrentQueue;
+
+use crate::{
+    event::{BufferedEvent, Events},
+    query::{QueryData, QueryFilter},
+    resource::Resource,
+    system::{Commands, IntoSystem, Query, RunSystemError, RunSystemOnce},
+    world::World,
+};
+
+type WorldJob = Box<dyn FnOnce(&mut World) + Send>;
+
+type BoxedTask = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// State shared by the [`AsyncTasks`] resource and every [`AsyncWorld`] handle.
+struct Shared {
+    jobs: ConcurrentQueue<WorldJob>,
+    frame: AtomicU32,
+}
+
+/// A handle giving async tasks access to the [`World`].
+///
+/// Each access is a future which completes the next time [`run_async_tasks`] runs, with exclusive
+/// access to the [`World`]. An [`AsyncWorld`] is passed to every task spawned with
+/// [`World::spawn_async`], and can be cloned freely.
+#[derive(Clone)]
+pub struct AsyncWorld {
+    shared: Arc<Shared>,
+}
+
+impl AsyncWorld {
+    /// Runs `f` with exclusive access to the [`World`], and returns its result.
+    ///
+    /// The world accesses of every task are run one after the other, so `f` should not block.
+    /// If the returned future is dropped before `f` runs, `f` is never run.
+    pub fn run<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut World) -> R + Send + 'static,
+    ) -> impl Future<Output = R> + Send + 'static {
+        let shared = self.shared.clone();
+        async move {
+            let result = Arc::new(Mutex::new(None));
+            // Only the future holds a strong reference, so the job is skipped once it is dropped.
+            let job_result = Arc::downgrade(&result);
+            let job: WorldJob = Box::new(move |world| {
+                let Some(result) = Weak::upgrade(&job_result) else {
+                    return;
+                };
+                let value = f(world);
+                *result.lock().unwrap_or_else(PoisonError::into_inner) = Some(value);
+            });
+            // The queue is never closed, so this cannot fail.
+            let _ = shared.jobs.push(job);
+            poll_fn(
+                move |_| match result.lock().unwrap_or_else(PoisonError::into_inner).take() {
+                    Some(value) => Poll::Ready(value),
+                    None => Poll::Pending,
+                },
+            )
+            .await
+        }
+    }
+
+    /// Runs `f` on a [`Query`] over the [`World`], and returns its result.
+    pub fn query<D, F, R>(
+        &self,
+        f: impl FnOnce(Query<D, F>) -> R + Send + 'static,
+    ) -> impl Future<Output = R> + Send + 'static
+    where
+        D: QueryData + 'static,
+        F: QueryFilter + 'static,
+        R: Send + 'static,
+    {
+        self.run(move |world| {
+            let mut state = world.query_filtered::<D, F>();
+            f(state.query_mut(world))
+        })
+    }
+
+    /// Runs `f` with [`Commands`], which are applied to the [`World`] right after.
+    pub fn commands(
+        &self,
+        f: impl FnOnce(Commands) + Send + 'static,
+    ) -> impl Future<Output = ()> + Send + 'static {
+        self.run(move |world| {
+            f(world.commands());
+            world.flush();
+        })
+    }
+
+    /// Runs `system` once on the [`World`], and returns its output.
+    ///
+    /// See [`RunSystemOnce::run_system_once`].
+    pub fn run_system<S, Out, Marker>(
+        &self,
+        system: S,
+    ) -> impl Future<Output = Result<Out, RunSystemError>> + Send + 'static
+    where
+        S: IntoSystem<(), Out, Marker> + Send + 'static,
+        Out: Send + 'static,
+    {
+        self.run(move |world| world.run_system_once(system))
+    }
+
+    /// Waits until the next time [`run_async_tasks`] runs.
+    pub fn next_frame(&self) -> impl Future<Output = ()> + Send + 'static {
+        let shared = self.shared.clone();
+        let frame = shared.frame.load(Ordering::Acquire);
+        poll_fn(move |_| {
+            if shared.frame.load(Ordering::Acquire) == frame {
+                Poll::Pending
+            } else {
+                Poll::Ready(())
+            }
+        })
+    }
+
+    /// Waits until [`run_async_tasks`] ran `frames` more times.
+    pub fn wait_frames(&self, frames: u32) -> impl Future<Output = ()> + Send + 'static {
+        let world = self.clone();
+        async move {
+            for _ in 0..frames {
+                world.next_frame().await;
+            }
+        }
+    }
+
+    /// Waits for the next event of type `E` sent after this is first polled, and returns a copy of it.
+    ///
+    /// The [`Events<E>`] resource is checked each time [`run_async_tasks`] runs, so events are missed
+    /// if it is updated twice in between. If several events are sent at once, only the first one is returned.
+    pub fn event<E: BufferedEvent + Clone>(&self) -> impl Future<Output = E> + Send + 'static {
+        let world = self.clone();
+        async move {
+            let mut cursor = world
+                .run(|world| {
+                    world
+                        .get_resource::<Events<E>>()
+                        .map(Events::get_cursor_current)
+                        .unwrap_or_default()
+                })
+                .await;
+            loop {
+                let (event, next_cursor) = world
+                    .run(move |world| {
+                        let event = world
+                            .get_resource::<Events<E>>()
+                            .and_then(|events| cursor.read(events).next().cloned());
+                        (event, cursor)
+                    })
+                    .await;
+                if let Some(event) = event {
+                    return event;
+                }
+                cursor = next_cursor;
+                world.next_frame().await;
+            }
+        }
+    }
+}
+
+/// The [`Resource`] storing the async tasks spawned with [`World::spawn_async`].
+#[derive(Resource)]
+pub struct AsyncTasks {
+    shared: Arc<Shared>,
+    tasks: Vec<SyncCell<BoxedTask>>,
+    spawned: Vec<SyncCell<BoxedTask>>,
+}
+
+impl Default for AsyncTasks {
+    fn default() -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                jobs: ConcurrentQueue::unbounded(),
+                frame: AtomicU32::new(0),
+            }),
+            tasks: Vec::new(),
+            spawned: Vec::new(),
+        }
+    }
+}
+
+impl AsyncTasks {
+    /// Returns a new [`AsyncWorld`] handle.
+    pub fn world(&self) -> AsyncWorld {
+        AsyncWorld {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Spawns an async task, which will first be polled the next time [`run_async_tasks`] runs.
+    pub fn spawn<Fut>(&mut self, task: impl FnOnce(AsyncWorld) -> Fut)
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let task = task(self.world());
+        self.spawned.push(SyncCell::new(Box::pin(task)));
+    }
+
+    /// Returns the number of async tasks that did not complete yet.
+    pub fn len(&self) -> usize {
+        self.tasks.len() + self.spawned.len()
+    }
+
+    /// Returns `true` if every async task completed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The maximum number of times [`run_async_tasks`] polls the async tasks and runs the world
+/// accesses they await, each time it runs.
+pub const MAX_ASYNC_TASK_ROUNDS: usize = 16;
+
+/// Drives the async tasks spawned with [`World::spawn_async`].
+///
+/// Each task is polled, then the world accesses they await are run, and this repeats until no
+/// task awaits any world access anymore, or [`MAX_ASYNC_TASK_ROUNDS`] times. Tasks awaiting
+/// [`AsyncWorld::next_frame`], or still awaiting world accesses after the last round, resume the
+/// next time this runs.
+pub fn run_async_tasks(world: &mut World) {
+    let Some(mut async_tasks) = world.get_resource_mut::<AsyncTasks>() else {
+        return;
+    };
+    let shared = async_tasks.shared.clone();
+    let mut tasks = mem::take(&mut async_tasks.tasks);
+    shared.frame.fetch_add(1, Ordering::AcqRel);
+
+    let mut context = Context::from_waker(Waker::noop());
+    for _ in 0..MAX_ASYNC_TASK_ROUNDS {
+        if let Some(mut async_tasks) = world.get_resource_mut::<AsyncTasks>() {
+            tasks.append(&mut async_tasks.spawned);
+        }
+        tasks.retain_mut(|task| task.get().as_mut().poll(&mut context).is_pending());
+
+        if shared.jobs.is_empty() {
+            break;
+        }
+        while let Ok(job) = shared.jobs.pop() {
+            job(world);
+        }
+    }
+
+    let mut async_tasks = world.get_resource_or_init::<AsyncTasks>();
+    tasks.append(&mut async_tasks.tasks);
+    async_tasks.tasks = tasks;
+}
+
+impl World {
+    /// Spawns an async task, which is given an [`AsyncWorld`] to access this world.
+    ///
+    /// The task is driven by [`run_async_tasks`]. See the [module docs](crate::async_world) for more information.
+    pub fn spawn_async<Fut>(&mut self, task: impl FnOnce(AsyncWorld) -> Fut)
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.get_resource_or_init::<AsyncTasks>().spawn(task);
+    }
+}
+
+impl Commands<'_, '_> {
+    /// Spawns an async task, which is given an [`AsyncWorld`] to access the world.
+    ///
+    /// See [`World::spawn_async`].
+    pub fn spawn_async<Fut>(&mut self, task: impl FnOnce(AsyncWorld) -> Fut + Send + 'static)
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.queue(move |world: &mut World| world.spawn_async(task));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_async_tasks, AsyncTasks, MAX_ASYNC_TASK_ROUNDS};
+    use crate::{event::Events, prelude::*};
+    use alloc::boxed::Box;
+    use core::{
+        future::{poll_fn, Future},
+        task::Poll,
+    };
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Health(u32);
+
+    #[derive(Resource, Default)]
+    struct Log(alloc::vec::Vec<&'static str>);
+
+    #[derive(BufferedEvent, Clone, PartialEq, Debug)]
+    struct Trigger(u32);
+
+    #[test]
+    fn world_access_runs_within_one_frame() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.spawn_async(|world| async move {
+            let entity = world.run(|world| world.spawn(Health(10)).id()).await;
+            world
+                .query::<&mut Health, (), _>(|mut query| {
+                    for mut health in &mut query {
+                        health.0 += 5;
+                    }
+                })
+                .await;
+            world
+                .commands(move |mut commands| {
+                    commands.entity(entity).insert(Name::new("spawned"));
+                })
+                .await;
+            world
+                .run(|world| world.resource_mut::<Log>().0.push("first"))
+                .await;
+            world.next_frame().await;
+            world
+                .run(|world| world.resource_mut::<Log>().0.push("second"))
+                .await;
+        });
+
+        run_async_tasks(&mut world);
+        assert_eq!(world.resource::<Log>().0, ["first"]);
+        let mut query = world.query_filtered::<&Health, With<Name>>();
+        assert_eq!(query.single(&world).unwrap(), &Health(15));
+
+        run_async_tasks(&mut world);
+        assert_eq!(world.resource::<Log>().0, ["first", "second"]);
+        assert!(world.resource::<AsyncTasks>().is_empty());
+    }
+
+    #[test]
+    fn wait_for_event_and_spawn_from_commands() {
+        let mut world = World::new();
+        world.init_resource::<Events<Trigger>>();
+        world.init_resource::<Log>();
+        world.commands().spawn_async(|world| async move {
+            let Trigger(value) = world.event::<Trigger>().await;
+            assert_eq!(value, 2);
+            world.wait_frames(2).await;
+            world
+                .run(|world| world.resource_mut::<Log>().0.push("done"))
+                .await;
+        });
+        world.flush();
+
+        world.write_event(Trigger(1));
+        world.resource_mut::<Events<Trigger>>().update();
+        run_async_tasks(&mut world);
+        run_async_tasks(&mut world);
+
+        world.write_event(Trigger(2));
+        run_async_tasks(&mut world);
+        run_async_tasks(&mut world);
+        assert!(world.resource::<Log>().0.is_empty());
+        run_async_tasks(&mut world);
+        assert_eq!(world.resource::<Log>().0, ["done"]);
+        assert!(world.resource::<AsyncTasks>().is_empty());
+    }
+
+    #[derive(Resource, Default)]
+    struct Polls(usize);
+
+    #[test]
+    fn endless_world_access_does_not_stall_the_frame() {
+        let mut world = World::new();
+        world.init_resource::<Polls>();
+        world.spawn_async(|world| async move {
+            loop {
+                world
+                    .run(|world| world.resource_mut::<Polls>().0 += 1)
+                    .await;
+            }
+        });
+
+        run_async_tasks(&mut world);
+        assert_eq!(world.resource::<Polls>().0, MAX_ASYNC_TASK_ROUNDS);
+        run_async_tasks(&mut world);
+        assert_eq!(world.resource::<Polls>().0, 2 * MAX_ASYNC_TASK_ROUNDS);
+        assert_eq!(world.resource::<AsyncTasks>().len(), 1);
+    }
+
+    #[test]
+    fn dropped_world_access_is_not_run() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.spawn_async(|world| async move {
+            let mut access =
+                Box::pin(world.run(|world| world.resource_mut::<Log>().0.push("dropped")));
+            // Queue the world access, then drop it before it runs.
+            poll_fn(|context| {
+                assert!(access.as_mut().poll(context).is_pending());
+                Poll::Ready(())
+            })
+            .await;
+            drop(access);
+            world
+                .run(|world| world.resource_mut::<Log>().0.push("kept"))
+                .await;
+        });
+
+        run_async_tasks(&mut world);
+        assert_eq!(world.resource::<Log>().0, ["kept"]);
+        assert!(world.resource::<AsyncTasks>().is_empty());
+    }
+}
@@ -49,6 +49,30 @@ impl SparseSetIndex for BundleId {
     }
 }
 
+/// Where a component of a [`Bundle`] is stored once the bundle is inserted.
+///
+/// [`Shared`](StorageType::Shared) components have no equivalent, as their value decides the
+/// archetype of the entity and they can't be inserted as part of a bundle.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum BundleStorageType {
+    /// See [`StorageType::Table`].
+    Table,
+    /// See [`StorageType::SparseSet`].
+    SparseSet,
+}
+
+impl BundleStorageType {
+    /// Returns where a component with the given `storage_type` is stored once inserted, or `None`
+    /// for [`Shared`](StorageType::Shared) components.
+    pub(crate) const fn new(storage_type: StorageType) -> Option<Self> {
+        match storage_type {
+            StorageType::Table => Some(Self::Table),
+            StorageType::SparseSet => Some(Self::SparseSet),
+            StorageType::Shared => None,
+        }
+    }
+}
+
 /// What to do on insertion if a component already exists.
 #[derive(Clone, Copy, Eq, PartialEq)]
 pub enum InsertMode {
@@ -76,6 +100,10 @@ pub struct BundleInfo {
 
     /// The list of constructors for all required components indirectly contributed by this bundle.
     pub(super) required_component_constructors: Vec<RequiredComponentConstructor>,
+
+    /// Where each of the `contributed_component_ids` is stored once the bundle is inserted, or the
+    /// first [`Shared`](StorageType::Shared) component of the bundle, which can't be inserted.
+    pub(super) insert_storage_types: Result<Box<[BundleStorageType]>, ComponentId>,
 }
 
 impl BundleInfo {
@@ -144,6 +172,15 @@ impl BundleInfo {
             .map(|(_, required_component)| required_component.constructor)
             .collect::<Vec<_>>();
 
+        let insert_storage_types = component_ids
+            .iter()
+            .map(|&id| {
+                // SAFETY: caller has verified that all ids are valid
+                let info = unsafe { components.get_info_unchecked(id) };
+                BundleStorageType::new(info.storage_type()).ok_or(id)
+            })
+            .collect();
+
         // SAFETY: The caller ensures that component_ids:
         // - is valid for the associated world
         // - has had its storage initialized
@@ -152,6 +189,7 @@ impl BundleInfo {
             id,
             contributed_component_ids: component_ids,
             required_component_constructors: required_components,
+            insert_storage_types,
         }
     }
 
@@ -211,6 +249,25 @@ impl BundleInfo {
         self.required_components().iter().copied()
     }
 
+    /// Returns where each of the [`contributed_components`](Self::contributed_components) is
+    /// stored once this bundle is inserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bundle contains a [`Shared`](StorageType::Shared) component, which can only
+    /// be inserted with [`EntityWorldMut::insert_shared`](crate::world::EntityWorldMut::insert_shared).
+    #[inline]
+    pub(super) fn insert_storage_types(&self, components: &Components) -> &[BundleStorageType] {
+        match &self.insert_storage_types {
+            Ok(storage_types) => storage_types,
+            Err(shared_component) => panic!(
+                "Shared component {} can't be inserted as part of a bundle or as a required component, use `insert_shared` instead",
+                // SAFETY: The ids of the bundle are valid in the world of `components`.
+                unsafe { components.get_info_unchecked(*shared_component) }.name()
+            ),
+        }
+    }
+
     /// This writes components from a given [`Bundle`] to the given entity.
     ///
     /// # Safety
@@ -230,6 +287,9 @@ impl BundleInfo {
     ///
     /// `table` must be the "new" table for `entity`. `table_row` must have space allocated for the
     /// `entity`, `bundle` must match this [`BundleInfo`]'s type
+    ///
+    /// The bundle must not contain [`Shared`](StorageType::Shared) components, which
+    /// [`BundleInfo::insert_bundle_into_archetype`] ensures.
     #[inline]
     pub(super) unsafe fn write_components<'a, T: DynamicBundle, S: BundleComponentStatus>(
         &self,
@@ -246,15 +306,18 @@ impl BundleInfo {
     ) -> T::Effect {
         // NOTE: get_components calls this closure on each component in "bundle order".
         // bundle_info.component_ids are also in "bundle order"
+        // SAFETY: The caller ensures the bundle has no shared components.
+        let storage_types = unsafe { self.insert_storage_types.as_deref().debug_checked_unwrap() };
         let mut bundle_component = 0;
-        let after_effect = bundle.get_components(&mut |storage_type, component_ptr| {
+        let after_effect = bundle.get_components(&mut |_, component_ptr| {
             let component_id = *self
                 .contributed_component_ids
                 .get_unchecked(bundle_component);
             // SAFETY: bundle_component is a valid index for this bundle
             let status = unsafe { bundle_component_status.get_status(bundle_component) };
-            match storage_type {
-                StorageType::Table => {
+            // SAFETY: `storage_types` has an entry for each of the contributed components.
+            match unsafe { *storage_types.get_unchecked(bundle_component) } {
+                BundleStorageType::Table => {
                     let column =
                         // SAFETY: If component_id is in self.component_ids, BundleInfo::new ensures that
                         // the target table contains the component.
@@ -273,7 +336,7 @@ impl BundleInfo {
                         }
                     }
                 }
-                StorageType::SparseSet => {
+                BundleStorageType::SparseSet => {
                     let sparse_set =
                         // SAFETY: If component_id is in self.component_ids, BundleInfo::new ensures that
                         // a sparse set exists for the component.
@@ -326,20 +389,20 @@ impl BundleInfo {
         table_row: TableRow,
         entity: Entity,
         component_id: ComponentId,
-        storage_type: StorageType,
+        storage_type: BundleStorageType,
         component_ptr: OwningPtr,
         caller: MaybeLocation,
     ) {
         {
             match storage_type {
-                StorageType::Table => {
+                BundleStorageType::Table => {
                     let column =
                         // SAFETY: If component_id is in required_components, BundleInfo::new requires that
                         // the target table contains the component.
                         unsafe { table.get_column_mut(component_id).debug_checked_unwrap() };
                     column.initialize(table_row, component_ptr, change_tick, caller);
                 }
-                StorageType::SparseSet => {
+                BundleStorageType::SparseSet => {
                     let sparse_set =
                         // SAFETY: If component_id is in required_components, BundleInfo::new requires that
                         // a sparse set exists for the component.
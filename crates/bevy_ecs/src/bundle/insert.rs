@@ -7,9 +7,12 @@ use crate::{
         Archetype, ArchetypeAfterBundleInsert, ArchetypeCreated, ArchetypeId, Archetypes,
         ComponentStatus,
     },
-    bundle::{ArchetypeMoveType, Bundle, BundleId, BundleInfo, DynamicBundle, InsertMode},
+    bundle::{
+        ArchetypeMoveType, Bundle, BundleId, BundleInfo, BundleStorageType, DynamicBundle,
+        InsertMode,
+    },
     change_detection::MaybeLocation,
-    component::{Components, ComponentsRegistrator, StorageType, Tick},
+    component::{Components, ComponentsRegistrator, Tick},
     entity::{Entities, Entity, EntityLocation},
     lifecycle::{ADD, INSERT, REPLACE},
     observer::Observers,
@@ -415,6 +418,9 @@ impl BundleInfo {
     ///
     /// Results are cached in the [`Archetype`] graph to avoid redundant work.
     ///
+    /// # Panics
+    /// Panics if the bundle contains a [`Shared`](crate::component::StorageType::Shared) component.
+    ///
     /// # Safety
     /// `components` must be the same components as passed in [`Self::new`]
     pub(crate) unsafe fn insert_bundle_into_archetype(
@@ -425,6 +431,7 @@ impl BundleInfo {
         observers: &Observers,
         archetype_id: ArchetypeId,
     ) -> (ArchetypeId, bool) {
+        let storage_types = self.insert_storage_types(components);
         if let Some(archetype_after_insert_id) = archetypes[archetype_id]
             .edges()
             .get_archetype_after_bundle_insert(self.id)
@@ -439,33 +446,37 @@ impl BundleInfo {
         let mut existing = Vec::new();
 
         let current_archetype = &mut archetypes[archetype_id];
-        for component_id in self.iter_explicit_components() {
+        let (explicit_storage_types, required_storage_types) =
+            storage_types.split_at(self.explicit_components_len());
+        for (component_id, storage_type) in
+            self.iter_explicit_components().zip(explicit_storage_types)
+        {
             if current_archetype.contains(component_id) {
                 bundle_status.push(ComponentStatus::Existing);
                 existing.push(component_id);
             } else {
                 bundle_status.push(ComponentStatus::Added);
                 added.push(component_id);
-                // SAFETY: component_id exists
-                let component_info = unsafe { components.get_info_unchecked(component_id) };
-                match component_info.storage_type() {
-                    StorageType::Table => new_table_components.push(component_id),
-                    StorageType::SparseSet => new_sparse_set_components.push(component_id),
+                match storage_type {
+                    BundleStorageType::Table => new_table_components.push(component_id),
+                    BundleStorageType::SparseSet => new_sparse_set_components.push(component_id),
                 }
             }
         }
 
-        for (index, component_id) in self.iter_required_components().enumerate() {
+        for (index, (component_id, storage_type)) in self
+            .iter_required_components()
+            .zip(required_storage_types)
+            .enumerate()
+        {
             if !current_archetype.contains(component_id) {
                 added_required_components.push(self.required_component_constructors[index].clone());
                 added.push(component_id);
-                // SAFETY: component_id exists
-                let component_info = unsafe { components.get_info_unchecked(component_id) };
-                match component_info.storage_type() {
-                    StorageType::Table => {
+                match storage_type {
+                    BundleStorageType::Table => {
                         new_table_components.push(component_id);
                     }
-                    StorageType::SparseSet => {
+                    BundleStorageType::SparseSet => {
                         new_sparse_set_components.push(component_id);
                     }
                 }
@@ -525,6 +536,7 @@ impl BundleInfo {
                 table_id,
                 table_components,
                 sparse_set_components,
+                archetypes[archetype_id].shared_components().collect(),
             );
 
             // Add an edge from the old archetype to the new archetype.
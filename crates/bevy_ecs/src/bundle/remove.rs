@@ -309,6 +309,10 @@ impl BundleInfo {
     /// If `intersection` is true, components in the bundle but not in the current archetype
     /// will be ignored.
     ///
+    /// # Panics
+    /// Panics if `intersection` is false and the bundle contains a
+    /// [`Shared`](StorageType::Shared) component that is in the current archetype.
+    ///
     /// # Safety
     /// `archetype_id` must exist and components in `bundle_info` must exist
     pub(crate) unsafe fn remove_bundle_from_archetype(
@@ -336,11 +340,13 @@ impl BundleInfo {
         } else {
             let mut next_table_components;
             let mut next_sparse_set_components;
+            let mut next_shared_components: Vec<_>;
             let next_table_id;
             {
                 let current_archetype = &mut archetypes[archetype_id];
                 let mut removed_table_components = Vec::new();
                 let mut removed_sparse_set_components = Vec::new();
+                let mut removed_shared_components = Vec::new();
                 for component_id in self.iter_explicit_components() {
                     if current_archetype.contains(component_id) {
                         // SAFETY: bundle components were already initialized by bundles.get_info
@@ -350,6 +356,16 @@ impl BundleInfo {
                             StorageType::SparseSet => {
                                 removed_sparse_set_components.push(component_id);
                             }
+                            StorageType::Shared => {
+                                // Taking a component moves its value out, but shared values are
+                                // not owned by any one entity.
+                                assert!(
+                                    intersection,
+                                    "Shared component {} can't be taken from an entity, use `remove` instead",
+                                    component_info.name()
+                                );
+                                removed_shared_components.push(component_id);
+                            }
                         }
                     } else if !intersection {
                         // A component in the bundle was not present in the entity's archetype, so this
@@ -372,6 +388,9 @@ impl BundleInfo {
                     &mut next_sparse_set_components,
                     &removed_sparse_set_components,
                 );
+                next_shared_components = current_archetype.shared_components().collect();
+                next_shared_components
+                    .retain(|(component_id, _)| !removed_shared_components.contains(component_id));
 
                 next_table_id = if removed_table_components.is_empty() {
                     current_archetype.table_id()
@@ -391,6 +410,7 @@ impl BundleInfo {
                 next_table_id,
                 next_table_components,
                 next_sparse_set_components,
+                next_shared_components,
             );
             (Some(new_archetype_id), is_new_created)
         };
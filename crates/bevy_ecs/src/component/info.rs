@@ -221,6 +221,14 @@ pub struct ComponentDescriptor {
     clone_behavior: ComponentCloneBehavior,
 }
 
+/// Shared values are used by many entities at once, so they can't be mutated through any one of them.
+fn assert_shared_immutable(storage_type: StorageType, mutable: bool) {
+    assert!(
+        storage_type != StorageType::Shared || !mutable,
+        "components with `StorageType::Shared` must be immutable"
+    );
+}
+
 // We need to ignore the `drop` field in our `Debug` impl
 impl Debug for ComponentDescriptor {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -248,7 +256,12 @@ impl ComponentDescriptor {
     }
 
     /// Create a new `ComponentDescriptor` for the type `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is a mutable [`StorageType::Shared`] component.
     pub fn new<T: Component>() -> Self {
+        assert_shared_immutable(T::STORAGE_TYPE, T::Mutability::MUTABLE);
         Self {
             name: DebugName::type_name::<T>(),
             storage_type: T::STORAGE_TYPE,
@@ -266,6 +279,10 @@ impl ComponentDescriptor {
     /// # Safety
     /// - the `drop` fn must be usable on a pointer with a value of the layout `layout`
     /// - the component type must be safe to access from any thread (Send + Sync in rust terms)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `storage_type` is [`StorageType::Shared`] and `mutable` is `true`.
     pub unsafe fn new_with_layout(
         name: impl Into<Cow<'static, str>>,
         storage_type: StorageType,
@@ -274,6 +291,7 @@ impl ComponentDescriptor {
         mutable: bool,
         clone_behavior: ComponentCloneBehavior,
     ) -> Self {
+        assert_shared_immutable(storage_type, mutable);
         Self {
             name: name.into().into(),
             storage_type,
@@ -94,8 +94,26 @@ use core::{fmt::Debug, marker::PhantomData, ops::Deref};
 /// struct ComponentA;
 /// ```
 ///
+/// Finally, immutable components that many entities have the same value of can be added to the
+/// [`Shared`](StorageType::Shared) storage with `#[component(storage = "Shared")]`, where each
+/// distinct value is stored only once. Shared components are inserted with
+/// [`EntityWorldMut::insert_shared`] rather than as part of a [`Bundle`]:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #
+/// #[derive(Component, PartialEq, Eq, Hash)]
+/// #[component(storage = "Shared")]
+/// struct Team(u32);
+///
+/// # let mut world = World::new();
+/// world.spawn_empty().insert_shared(Team(1));
+/// ```
+///
 /// [`Table`]: crate::storage::Table
 /// [`SparseSet`]: crate::storage::SparseSet
+/// [`EntityWorldMut::insert_shared`]: crate::world::EntityWorldMut::insert_shared
+/// [`Bundle`]: crate::bundle::Bundle
 ///
 /// # Required Components
 ///
@@ -696,6 +714,7 @@ impl ComponentMutability for Mutable {
 /// struct A;
 /// ```
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum StorageType {
     /// Provides fast and cache-friendly iteration, but slower addition and removal of components.
     /// This is the default storage type.
@@ -703,6 +722,40 @@ pub enum StorageType {
     Table,
     /// Provides fast addition and removal of components, but slower iteration.
     SparseSet,
+    /// Stores each distinct value of the component once, shared by all the entities with that value.
+    ///
+    /// Entities are grouped into archetypes by the value of their shared components, so queries
+    /// iterate entities with the same value together and a value can be changed for all of its
+    /// entities at once with [`World::replace_shared`](crate::world::World::replace_shared).
+    /// Shared components must be immutable.
+    ///
+    /// Change detection ticks are stored per value rather than per entity:
+    /// [`Ref::is_added`](crate::change_detection::DetectChanges::is_added) and
+    /// [`Ref::is_changed`](crate::change_detection::DetectChanges::is_changed) report when the
+    /// value was first stored or last replaced, not when an entity got it. For this reason the
+    /// [`Added`](crate::query::Added) and [`Changed`](crate::query::Changed) filters can't be used
+    /// with shared components.
+    ///
+    /// ```compile_fail
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, PartialEq, Eq, Hash)]
+    /// #[component(storage = "Shared")]
+    /// struct Team(u32);
+    ///
+    /// let mut world = World::new();
+    /// let query = world.query_filtered::<Entity, Added<Team>>();
+    /// ```
+    ///
+    /// Shared components can't be inserted as part of a [`Bundle`](crate::bundle::Bundle) or be
+    /// required components: use [`EntityWorldMut::insert_shared`](crate::world::EntityWorldMut::insert_shared).
+    ///
+    /// # Warning
+    ///
+    /// Values and the archetypes created for them are never freed, even once no entity has them
+    /// anymore. Every distinct value ever inserted keeps using memory for the lifetime of the
+    /// [`World`], and keeps its archetypes around to be matched by new queries. Only use this
+    /// storage for components with a small, bounded set of values.
+    Shared,
 }
 
 /// A [`SystemParam`] that provides access to the [`ComponentId`] for a specific component type.
@@ -1,14 +1,15 @@
 use alloc::{format, vec::Vec};
 use bevy_platform::{hash::FixedHasher, sync::Arc};
 use bevy_ptr::OwningPtr;
+use bevy_utils::prelude::DebugName;
 use core::fmt::Debug;
 use indexmap::{IndexMap, IndexSet};
 use thiserror::Error;
 
 use crate::{
-    bundle::BundleInfo,
+    bundle::{BundleInfo, BundleStorageType},
     change_detection::MaybeLocation,
-    component::{Component, ComponentId, Components, ComponentsRegistrator, Tick},
+    component::{Component, ComponentId, Components, ComponentsRegistrator, StorageType, Tick},
     entity::Entity,
     query::DebugCheckedUnwrap as _,
     storage::{SparseSets, Table, TableRow},
@@ -31,10 +32,20 @@ pub struct RequiredComponentConstructor(
 impl RequiredComponentConstructor {
     /// Creates a new instance of `RequiredComponentConstructor` for the given type
     ///
+    /// # Panics
+    ///
+    /// Panics if `C` is a [`Shared`](StorageType::Shared) component.
+    ///
     /// # Safety
     ///
     /// - `component_id` must be a valid component for type `C`.
     pub unsafe fn new<C: Component>(component_id: ComponentId, constructor: fn() -> C) -> Self {
+        let storage_type = BundleStorageType::new(C::STORAGE_TYPE).unwrap_or_else(|| {
+            panic!(
+                "Shared component {} can't be a required component, use `insert_shared` instead",
+                DebugName::type_name::<C>()
+            )
+        });
         RequiredComponentConstructor({
             // `portable-atomic-util` `Arc` is not able to coerce an unsized
             // type like `std::sync::Arc` can. Creating a `Box` first does the
@@ -65,7 +76,7 @@ impl RequiredComponentConstructor {
                     OwningPtr::make(constructor(), |ptr| {
                         // SAFETY: This will only be called in the context of `BundleInfo::write_components`, which will
                         // pass in a valid table_row and entity requiring a C constructor
-                        // C::STORAGE_TYPE is the storage type associated with `component_id` / `C`
+                        // `storage_type` comes from C::STORAGE_TYPE, the storage type associated with `component_id` / `C`
                         // `ptr` points to valid `C` data, which matches the type associated with `component_id`
                         unsafe {
                             BundleInfo::initialize_required_component(
@@ -75,7 +86,7 @@ impl RequiredComponentConstructor {
                                 table_row,
                                 entity,
                                 component_id,
-                                C::STORAGE_TYPE,
+                                storage_type,
                                 ptr,
                                 caller,
                             );
@@ -176,6 +187,12 @@ impl RequiredComponents {
         components: &Components,
         constructor: fn() -> C,
     ) {
+        assert!(
+            C::STORAGE_TYPE != StorageType::Shared,
+            "Shared component {} can't be a required component, use `insert_shared` instead",
+            DebugName::type_name::<C>()
+        );
+
         // SAFETY: the caller guarantees that `component_id` is valid for the type `C`.
         let constructor =
             || unsafe { RequiredComponentConstructor::new(component_id, constructor) };
@@ -345,7 +362,8 @@ impl Components {
     ///
     /// # Errors
     ///
-    /// Returns a [`RequiredComponentsError`] if either of these are true:
+    /// Returns a [`RequiredComponentsError`] if any of these are true:
+    /// - the `required` component is a [`Shared`](StorageType::Shared) component;
     /// - the `required` component is already a *directly* required component for the `requiree`; indirect
     ///   requirements through other components are allowed. In those cases, the more specific
     ///   registration will be used.
@@ -358,6 +376,11 @@ impl Components {
     ) -> Result<(), RequiredComponentsError> {
         // First step: validate inputs and return errors.
 
+        // Shared components are only inserted with `insert_shared`.
+        if R::STORAGE_TYPE == StorageType::Shared {
+            return Err(RequiredComponentsError::SharedRequirement(required));
+        }
+
         // SAFETY: The caller ensures that the `required` is valid.
         let required_required_components = unsafe {
             self.get_required_components(required)
@@ -522,6 +545,9 @@ pub enum RequiredComponentsError {
     /// An archetype with the component that requires other components already exists
     #[error("An archetype with the component {0:?} that requires other components already exists")]
     ArchetypeExists(ComponentId),
+    /// The required component is a [`Shared`](StorageType::Shared) component.
+    #[error("The shared component {0:?} can't be a required component")]
+    SharedRequirement(ComponentId),
 }
 
 pub(super) fn enforce_no_required_components_recursion(
@@ -14,6 +14,7 @@ use crate::{
     entity::{hash_map::EntityHashMap, Entities, Entity, EntityMapper},
     query::DebugCheckedUnwrap,
     relationship::RelationshipHookMode,
+    storage::SharedValueId,
     world::World,
 };
 
@@ -570,6 +571,7 @@ impl EntityCloner {
         let mut bundle_scratch: BundleScratch;
         let mut moved_components: Vec<ComponentId> = Vec::new();
         let mut deferred_cloned_component_ids: Vec<ComponentId> = Vec::new();
+        let mut shared_components: Vec<(ComponentId, SharedValueId)> = Vec::new();
         {
             let world = world.as_unsafe_world_cell();
             let source_entity = world.get_entity(source).expect("Source entity must exist");
@@ -617,6 +619,15 @@ impl EntityCloner {
                     None => state.default_clone_fn,
                 };
 
+                // Shared values are not cloned: the target gets the same value as the source.
+                if let Some(value) = source_archetype.get_shared_value(component) {
+                    shared_components.push((component, value));
+                    if state.move_components {
+                        deferred_cloned_component_ids.push(component);
+                    }
+                    return;
+                }
+
                 // SAFETY: This component exists because it is present on the archetype.
                 let info = unsafe { world.components().get_info_unchecked(component) };
 
@@ -744,6 +755,26 @@ impl EntityCloner {
         // - All `component_ids` are from the same world as `target` entity
         // - All `component_data_ptrs` are valid types represented by `component_ids`
         unsafe { bundle_scratch.write(world, target, relationship_hook_insert_mode) };
+
+        for (component, value) in shared_components {
+            let location = world
+                .entities
+                .get(target)
+                .expect("Target entity must exist");
+            // SAFETY: `component` is a shared component of this world, and `value` one of its
+            // values, since they were on the source entity's archetype.
+            unsafe {
+                world.set_shared_value(
+                    target,
+                    location,
+                    component,
+                    value,
+                    MaybeLocation::caller(),
+                    relationship_hook_insert_mode,
+                );
+            }
+            world.flush();
+        }
         target
     }
 }
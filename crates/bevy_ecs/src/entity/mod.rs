@@ -77,7 +77,7 @@ use crate::{
     archetype::{ArchetypeId, ArchetypeRow},
     change_detection::MaybeLocation,
     component::{CheckChangeTicks, Tick},
-    storage::{SparseSetIndex, TableId, TableRow},
+    storage::{MemoryUsage, SparseSetIndex, TableId, TableRow},
 };
 use alloc::vec::Vec;
 use bevy_platform::sync::atomic::Ordering;
@@ -1131,6 +1131,27 @@ impl Entities {
         self.len() == 0
     }
 
+    /// Returns the memory held by the entity metadata and the list of freed entities.
+    pub(crate) fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::of_vec(&self.meta) + MemoryUsage::of_vec(&self.pending)
+    }
+
+    /// Shrinks the capacity of the entity metadata and of the list of freed entities to fit.
+    ///
+    /// The metadata of freed entities is kept, so that their generation is still bumped when
+    /// their index is reused and stale [`Entity`] handles keep being detected as such.
+    ///
+    /// # Panics
+    /// Panics in debug builds if there are reserved entities which have not been flushed.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        debug_assert!(
+            !self.needs_flush(),
+            "reserved entities must be flushed before shrinking `Entities`"
+        );
+        self.meta.shrink_to_fit();
+        self.pending.shrink_to_fit();
+    }
+
     /// Try to get the source code location from which this entity has last been
     /// spawned, despawned or flushed.
     ///
@@ -1197,6 +1218,42 @@ impl Entities {
         }
     }
 
+    /// Captures the allocator state, so that the same ids are handed out after [`Entities::restore`].
+    pub(crate) fn snapshot(&mut self) -> EntitiesSnapshot {
+        self.verify_flushed();
+        EntitiesSnapshot {
+            meta: self
+                .meta
+                .iter()
+                .map(|meta| (meta.generation, meta.spawned_or_despawned))
+                .collect(),
+            pending: self.pending.clone(),
+        }
+    }
+
+    /// Restores the allocator state captured by [`Entities::snapshot`].
+    ///
+    /// Locations are not restored: every row that is currently alive must also be alive in `snapshot`,
+    /// with the same generation, and entities that are only alive in `snapshot` are left without a location.
+    pub(crate) fn restore(&mut self, snapshot: &EntitiesSnapshot, check: CheckChangeTicks) {
+        self.verify_flushed();
+        debug_assert!(self.meta.iter().enumerate().all(|(index, meta)| {
+            meta.location.is_none()
+                || snapshot
+                    .meta
+                    .get(index)
+                    .is_some_and(|(generation, _)| *generation == meta.generation)
+        }));
+        self.meta.resize(snapshot.meta.len(), EntityMeta::EMPTY);
+        for (meta, (generation, spawned_or_despawned)) in self.meta.iter_mut().zip(&snapshot.meta) {
+            meta.generation = *generation;
+            meta.spawned_or_despawned = *spawned_or_despawned;
+            meta.spawned_or_despawned.at.check_tick(check);
+        }
+        self.pending.clone_from(&snapshot.pending);
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+    }
+
     /// Constructs a message explaining why an entity does not exist, if known.
     pub(crate) fn entity_does_not_exist_error_details(
         &self,
@@ -1250,6 +1307,13 @@ impl fmt::Display for EntityDoesNotExistDetails {
     }
 }
 
+/// The allocator state of [`Entities`], captured by [`Entities::snapshot`].
+#[derive(Clone, Debug)]
+pub(crate) struct EntitiesSnapshot {
+    meta: Vec<(EntityGeneration, SpawnedOrDespawned)>,
+    pending: Vec<EntityRow>,
+}
+
 #[derive(Copy, Clone, Debug)]
 struct EntityMeta {
     /// The current [`EntityGeneration`] of the [`EntityRow`].
@@ -0,0 +1,260 @@
+//! Lookups of entities by component value.
+//!
+//! Finding the entities with a given component value normally means iterating a whole [`Query`].
+//! A [`ComponentValueIndex`] keeps track of which entities hold each value of an [`IndexableComponent`],
+//! so they can be found in constant time instead, either directly or through the [`QueryByIndex`]
+//! system parameter.
+//!
+//! The index is kept up to date with [component hooks](crate::lifecycle::ComponentHooks), which only
+//! run when a component is inserted, replaced, removed or despawned. Indexed components must
+//! therefore be [immutable](crate::component::Immutable), so that their value cannot change
+//! without going through those hooks.
+//!
+//! ```
+//! use bevy_ecs::prelude::*;
+//! use bevy_ecs::index::QueryByIndex;
+//!
+//! #[derive(Component, Clone, PartialEq, Eq, Hash)]
+//! #[component(immutable)]
+//! struct ChunkCoord(i32, i32);
+//!
+//! #[derive(Component)]
+//! struct Tile(u8);
+//!
+//! let mut world = World::new();
+//! world.register_component_index::<ChunkCoord>();
+//! world.spawn((ChunkCoord(3, 4), Tile(1)));
+//! world.spawn((ChunkCoord(3, 4), Tile(2)));
+//! world.spawn((ChunkCoord(0, 0), Tile(3)));
+//!
+//! fn count_tiles(tiles: QueryByIndex<ChunkCoord, &Tile>) {
+//!     assert_eq!(2, tiles.iter(&ChunkCoord(3, 4)).count());
+//! }
+//! # world.run_system_cached(count_tiles).unwrap();
+//! ```
+
+use core::hash::Hash;
+
+use bevy_platform::collections::HashMap;
+
+use crate::{
+    component::{Component, Immutable},
+    entity::{hash_set, EntityHashSet},
+    lifecycle::HookContext,
+    query::{QueryData, QueryFilter, QueryManyUniqueIter},
+    resource::Resource,
+    system::{Query, Res, SystemParam},
+    world::{DeferredWorld, World},
+};
+
+/// A [`Component`] that can be looked up by value with a [`ComponentValueIndex`].
+///
+/// This is implemented for every immutable component that can be used as a hash map key.
+pub trait IndexableComponent: Component<Mutability = Immutable> + Eq + Hash + Clone {}
+
+impl<C: Component<Mutability = Immutable> + Eq + Hash + Clone> IndexableComponent for C {}
+
+/// A [`Resource`] mapping each value of the component `C` to the entities holding it.
+///
+/// Register it with [`World::register_component_index`]. It is only updated by the hooks
+/// registered there, and can be read through [`Res`] or [`QueryByIndex::index`].
+#[derive(Resource)]
+pub struct ComponentValueIndex<C: IndexableComponent> {
+    entities: HashMap<C, EntityHashSet>,
+    empty: EntityHashSet,
+}
+
+impl<C: IndexableComponent> ComponentValueIndex<C> {
+    fn new() -> Self {
+        Self {
+            entities: HashMap::default(),
+            empty: EntityHashSet::default(),
+        }
+    }
+
+    /// Returns the entities holding `value`.
+    pub fn get(&self, value: &C) -> &EntityHashSet {
+        self.entities.get(value).unwrap_or(&self.empty)
+    }
+
+    /// Returns `true` if at least one entity holds `value`.
+    pub fn contains(&self, value: &C) -> bool {
+        self.entities.contains_key(value)
+    }
+
+    /// Returns the number of distinct values held by entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity holds the component.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Iterates over every value held by entities, along with those entities.
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &EntityHashSet)> {
+        self.entities.iter()
+    }
+
+    /// The `on_insert` [hook](crate::lifecycle::ComponentHook) maintaining the index.
+    pub(crate) fn on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let Some(value) = world.get::<C>(entity).cloned() else {
+            return;
+        };
+        if let Some(mut index) = world.get_resource_mut::<Self>() {
+            index.entities.entry(value).or_default().insert(entity);
+        }
+    }
+
+    /// The `on_replace` [hook](crate::lifecycle::ComponentHook) maintaining the index.
+    pub(crate) fn on_replace(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let Some(value) = world.get::<C>(entity).cloned() else {
+            return;
+        };
+        if let Some(mut index) = world.get_resource_mut::<Self>()
+            && let Some(entities) = index.entities.get_mut(&value)
+        {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                index.entities.remove(&value);
+            }
+        }
+    }
+}
+
+impl World {
+    /// Starts maintaining a [`ComponentValueIndex`] for the component `C`.
+    ///
+    /// This registers `on_insert` and `on_replace` hooks for `C`, and is subject to the same
+    /// restrictions as [`World::register_component_hooks`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` already exists in any archetype, or already has an `on_insert` or `on_replace`
+    /// hook.
+    pub fn register_component_index<C: IndexableComponent>(&mut self) -> &mut Self {
+        self.register_component_hooks::<C>()
+            .on_insert(ComponentValueIndex::<C>::on_insert)
+            .on_replace(ComponentValueIndex::<C>::on_replace);
+        self.insert_resource(ComponentValueIndex::<C>::new());
+        self
+    }
+}
+
+/// A [`SystemParam`] combining a [`Query`] with the [`ComponentValueIndex`] of `C`, to iterate over
+/// the query items of the entities holding a given value of `C`.
+///
+/// The [`ComponentValueIndex`] must have been registered with [`World::register_component_index`].
+#[derive(SystemParam)]
+pub struct QueryByIndex<
+    'w,
+    's,
+    C: IndexableComponent,
+    D: QueryData + 'static,
+    F: QueryFilter + 'static = (),
+> {
+    index: Res<'w, ComponentValueIndex<C>>,
+    query: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, C: IndexableComponent, D: QueryData, F: QueryFilter> QueryByIndex<'w, 's, C, D, F> {
+    /// Returns an iterator over the read-only query items of the entities holding `value`.
+    pub fn iter(
+        &self,
+        value: &C,
+    ) -> QueryManyUniqueIter<'_, 's, D::ReadOnly, F, hash_set::Iter<'_>> {
+        self.query.iter_many_unique(self.index.get(value))
+    }
+
+    /// Returns an iterator over the query items of the entities holding `value`.
+    pub fn iter_mut(&mut self, value: &C) -> QueryManyUniqueIter<'_, 's, D, F, hash_set::Iter<'_>> {
+        self.query.iter_many_unique_mut(self.index.get(value))
+    }
+
+    /// Returns the entities holding `value`, whether or not they match the query.
+    pub fn entities(&self, value: &C) -> &EntityHashSet {
+        self.index.get(value)
+    }
+
+    /// Returns the underlying [`ComponentValueIndex`].
+    pub fn index(&self) -> &ComponentValueIndex<C> {
+        &self.index
+    }
+
+    /// Returns the underlying [`Query`].
+    pub fn query(&self) -> &Query<'w, 's, D, F> {
+        &self.query
+    }
+
+    /// Returns the underlying [`Query`] mutably.
+    pub fn query_mut(&mut self) -> &mut Query<'w, 's, D, F> {
+        &mut self.query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentValueIndex, QueryByIndex};
+    use crate::{entity::EntityHashSet, prelude::*};
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    #[component(immutable)]
+    struct PlayerId(u32);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Score(u32);
+
+    fn entities<const N: usize>(entities: [Entity; N]) -> EntityHashSet {
+        entities.into_iter().collect()
+    }
+
+    #[test]
+    fn index_tracks_insert_replace_remove_and_despawn() {
+        let mut world = World::new();
+        world.register_component_index::<PlayerId>();
+
+        let a = world.spawn(PlayerId(1)).id();
+        let b = world.spawn(PlayerId(1)).id();
+        let c = world.spawn(PlayerId(2)).id();
+        let index = world.resource::<ComponentValueIndex<PlayerId>>();
+        assert_eq!(&entities([a, b]), index.get(&PlayerId(1)));
+        assert_eq!(&entities([c]), index.get(&PlayerId(2)));
+
+        world.entity_mut(a).insert(PlayerId(2));
+        let index = world.resource::<ComponentValueIndex<PlayerId>>();
+        assert_eq!(&entities([b]), index.get(&PlayerId(1)));
+        assert_eq!(&entities([a, c]), index.get(&PlayerId(2)));
+
+        world.entity_mut(b).remove::<PlayerId>();
+        world.despawn(c);
+        let index = world.resource::<ComponentValueIndex<PlayerId>>();
+        assert!(!index.contains(&PlayerId(1)));
+        assert_eq!(&entities([a]), index.get(&PlayerId(2)));
+        assert_eq!(1, index.len());
+    }
+
+    #[test]
+    fn query_by_index() {
+        let mut world = World::new();
+        world.register_component_index::<PlayerId>();
+
+        let a = world.spawn((PlayerId(7), Score(0))).id();
+        world.spawn((PlayerId(8), Score(0)));
+        world.spawn(PlayerId(7));
+
+        world
+            .run_system_cached(|mut query: QueryByIndex<PlayerId, &mut Score>| {
+                assert_eq!(2, query.entities(&PlayerId(7)).len());
+                for mut score in query.iter_mut(&PlayerId(7)) {
+                    score.0 += 10;
+                }
+                assert_eq!(0, query.iter(&PlayerId(9)).count());
+            })
+            .unwrap();
+
+        assert_eq!(Some(&Score(10)), world.get::<Score>(a));
+        let mut scores = world.query::<&Score>();
+        assert_eq!(10, scores.iter(&world).map(|score| score.0).sum::<u32>());
+    }
+}
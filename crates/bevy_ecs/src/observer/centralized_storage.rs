@@ -8,15 +8,21 @@
 //! - [`CachedObservers`] contains maps of [`ObserverRunner`]s, which are the actual functions that will be run when the observer is triggered.
 //!     - These are split by target type, in order to allow for different lookup strategies.
 //!     - [`CachedComponentObservers`] is one of these maps, which contains observers that are specifically targeted at a component.
+//! - [`Observers`] also tracks the priority and ordering constraints of every observer,
+//!   which decide the order in which the observers matching a trigger are run.
 
+use core::cmp::Reverse;
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
 use bevy_platform::collections::HashMap;
+use smallvec::SmallVec;
 
 use crate::{
     archetype::ArchetypeFlags,
     change_detection::MaybeLocation,
     component::ComponentId,
     entity::EntityHashMap,
-    observer::{ObserverRunner, ObserverTrigger},
+    observer::{ObserverDescriptor, ObserverRunner, ObserverTrigger},
     prelude::*,
     world::DeferredWorld,
 };
@@ -38,6 +44,18 @@ pub struct Observers {
     despawn: CachedObservers,
     // Map from trigger type to set of observers listening to that trigger
     cache: HashMap<EventKey, CachedObservers>,
+    // Priority and ordering constraints of the registered observers which have any
+    ordering: EntityHashMap<ObserverOrdering>,
+}
+
+/// The position of a registered observer relative to the other observers.
+///
+/// Only observers with a non-default priority or an ordering constraint have one.
+#[derive(Debug)]
+struct ObserverOrdering {
+    priority: i32,
+    before: Vec<Entity>,
+    after: Vec<Entity>,
 }
 
 impl Observers {
@@ -71,6 +89,129 @@ impl Observers {
         }
     }
 
+    /// Records the priority and ordering constraints of a newly registered observer.
+    ///
+    /// Returns `true` if the observer has a non-default priority or any ordering constraint.
+    pub(crate) fn register_ordering(
+        &mut self,
+        observer: Entity,
+        descriptor: &ObserverDescriptor,
+    ) -> bool {
+        let ordered = descriptor.priority != 0
+            || !descriptor.before.is_empty()
+            || !descriptor.after.is_empty();
+        if ordered {
+            self.ordering.insert(
+                observer,
+                ObserverOrdering {
+                    priority: descriptor.priority,
+                    before: descriptor.before.clone(),
+                    after: descriptor.after.clone(),
+                },
+            );
+        }
+        ordered
+    }
+
+    /// Forgets the priority and ordering constraints of an unregistered observer.
+    ///
+    /// Returns `true` if the observer had a non-default priority or any ordering constraint.
+    pub(crate) fn unregister_ordering(&mut self, observer: Entity) -> bool {
+        self.ordering.remove(&observer).is_some()
+    }
+
+    /// Sorts the observers matching a trigger into the order they should run in.
+    ///
+    /// Observers are ordered by descending priority, keeping the order they were collected in
+    /// when their priority is the same, and are then moved as little as possible to satisfy their
+    /// `before` and `after` constraints. When constraints form a cycle, the first observer of the
+    /// cycle in that order runs first.
+    fn sort_triggered(&self, triggered: &mut SmallVec<[(Entity, ObserverRunner); 8]>) {
+        // Look up the ordering of each observer once, rather than once per comparison.
+        let mut keyed = triggered
+            .drain(..)
+            .map(|(observer, runner)| (self.ordering.get(&observer), observer, runner))
+            .collect::<SmallVec<[_; 8]>>();
+        keyed.sort_by_key(|(ordering, ..)| {
+            Reverse(ordering.map_or(0, |ordering| ordering.priority))
+        });
+
+        let constrained = keyed.iter().any(|(ordering, ..)| {
+            ordering
+                .is_some_and(|ordering| !ordering.before.is_empty() || !ordering.after.is_empty())
+        });
+        if !constrained {
+            triggered.extend(
+                keyed
+                    .into_iter()
+                    .map(|(_, observer, runner)| (observer, runner)),
+            );
+            return;
+        }
+
+        // Build the "runs before" edges between the triggered observers.
+        let positions = keyed
+            .iter()
+            .enumerate()
+            .map(|(position, (_, observer, _))| (*observer, position))
+            .collect::<EntityHashMap<usize>>();
+        let mut successors = vec![SmallVec::<[usize; 2]>::new(); keyed.len()];
+        let mut predecessors = vec![0usize; keyed.len()];
+        for (position, (ordering, ..)) in keyed.iter().enumerate() {
+            let Some(ordering) = ordering else {
+                continue;
+            };
+            let before = ordering
+                .before
+                .iter()
+                .filter_map(|observer| positions.get(observer))
+                .map(|&other| (position, other));
+            let after = ordering
+                .after
+                .iter()
+                .filter_map(|observer| positions.get(observer))
+                .map(|&other| (other, position));
+            for (first, second) in before.chain(after) {
+                if first != second && !successors[first].contains(&second) {
+                    successors[first].push(second);
+                    predecessors[second] += 1;
+                }
+            }
+        }
+
+        // Repeatedly pick the first remaining observer that no other remaining observer must precede.
+        let mut ready = (0..keyed.len())
+            .filter(|&position| predecessors[position] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        let mut done = vec![false; keyed.len()];
+        let mut order = Vec::with_capacity(keyed.len());
+        while order.len() < keyed.len() {
+            let next = match ready.pop() {
+                Some(Reverse(position)) => position,
+                // Every remaining observer is part of a cycle or waits for one: break it.
+                None => (0..keyed.len()).find(|&position| !done[position]).unwrap(),
+            };
+            if done[next] {
+                continue;
+            }
+            done[next] = true;
+            order.push(next);
+            for &successor in &successors[next] {
+                predecessors[successor] -= 1;
+                if predecessors[successor] == 0 && !done[successor] {
+                    ready.push(Reverse(successor));
+                }
+            }
+        }
+
+        triggered.extend(
+            order
+                .into_iter()
+                .map(|position| (keyed[position].1, keyed[position].2)),
+        );
+    }
+
     /// This will run the observers of the given `event_key`, targeting the given `entity` and `components`.
     pub(crate) fn invoke<T>(
         mut world: DeferredWorld,
@@ -83,66 +224,68 @@ impl Observers {
         caller: MaybeLocation,
     ) {
         // SAFETY: You cannot get a mutable reference to `observers` from `DeferredWorld`
-        let (mut world, observers) = unsafe {
+        let (mut world, all_observers, observers) = unsafe {
             let world = world.as_unsafe_world_cell();
             // SAFETY: There are no outstanding world references
             world.increment_trigger_id();
-            let observers = world.observers();
-            let Some(observers) = observers.try_get_observers(event_key) else {
+            let all_observers = world.observers();
+            let Some(observers) = all_observers.try_get_observers(event_key) else {
                 return;
             };
             // SAFETY: The only outstanding reference to world is `observers`
-            (world.into_deferred(), observers)
+            (world.into_deferred(), all_observers, observers)
         };
 
-        let trigger_for_components = components.clone();
-
-        let mut trigger_observer = |(&observer, runner): (&Entity, &ObserverRunner)| {
-            (runner)(
-                world.reborrow(),
-                ObserverTrigger {
-                    observer,
-                    event_key,
-                    components: components.clone().collect(),
-                    current_target,
-                    original_target,
-                    caller,
-                },
-                data.into(),
-                propagate,
-            );
+        let mut triggered = SmallVec::<[(Entity, ObserverRunner); 8]>::new();
+        let mut collect = |map: &ObserverMap| {
+            triggered.extend(map.iter().map(|(&observer, &runner)| (observer, runner)));
         };
-        // Trigger observers listening for any kind of this trigger
-        observers
-            .global_observers
-            .iter()
-            .for_each(&mut trigger_observer);
 
-        // Trigger entity observers listening for this kind of trigger
+        // Collect observers listening for any kind of this trigger
+        collect(&observers.global_observers);
+
+        // Collect entity observers listening for this kind of trigger
         if let Some(target_entity) = current_target {
             if let Some(map) = observers.entity_observers.get(&target_entity) {
-                map.iter().for_each(&mut trigger_observer);
+                collect(map);
             }
         }
 
-        // Trigger observers listening to this trigger targeting a specific component
-        trigger_for_components.for_each(|id| {
+        // Collect observers listening to this trigger targeting a specific component
+        components.clone().for_each(|id| {
             if let Some(component_observers) = observers.component_observers.get(&id) {
-                component_observers
-                    .global_observers
-                    .iter()
-                    .for_each(&mut trigger_observer);
+                collect(&component_observers.global_observers);
 
                 if let Some(target_entity) = current_target {
                     if let Some(map) = component_observers
                         .entity_component_observers
                         .get(&target_entity)
                     {
-                        map.iter().for_each(&mut trigger_observer);
+                        collect(map);
                     }
                 }
             }
         });
+
+        if observers.ordered_observers > 0 {
+            all_observers.sort_triggered(&mut triggered);
+        }
+
+        for (observer, runner) in triggered {
+            (runner)(
+                world.reborrow(),
+                ObserverTrigger {
+                    observer,
+                    event_key,
+                    components: components.clone().collect(),
+                    current_target,
+                    original_target,
+                    caller,
+                },
+                data.into(),
+                propagate,
+            );
+        }
     }
 
     pub(crate) fn is_archetype_cached(event_key: EventKey) -> Option<ArchetypeFlags> {
@@ -197,6 +340,9 @@ pub struct CachedObservers {
     pub(super) component_observers: HashMap<ComponentId, CachedComponentObservers>,
     // Observers listening for this trigger fired at a specific entity
     pub(super) entity_observers: EntityHashMap<ObserverMap>,
+    // Number of these observers with a non-default priority or an ordering constraint.
+    // Observers are only sorted when this isn't zero.
+    pub(super) ordered_observers: usize,
 }
 
 impl CachedObservers {
@@ -314,6 +314,32 @@ impl Observer {
         self
     }
 
+    /// Sets the priority of this observer, `0` by default.
+    ///
+    /// Among the observers matching a trigger, those with a higher priority run first.
+    /// The relative order of observers with the same priority is unspecified.
+    /// Note that if this is called _after_ an [`Observer`] is spawned, it will produce no effects.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.descriptor.priority = priority;
+        self
+    }
+
+    /// Runs this observer before the observer `observer`, whenever both are triggered by the same event,
+    /// regardless of their priorities.
+    /// Note that if this is called _after_ an [`Observer`] is spawned, it will produce no effects.
+    pub fn before(mut self, observer: Entity) -> Self {
+        self.descriptor.before.push(observer);
+        self
+    }
+
+    /// Runs this observer after the observer `observer`, whenever both are triggered by the same event,
+    /// regardless of their priorities.
+    /// Note that if this is called _after_ an [`Observer`] is spawned, it will produce no effects.
+    pub fn after(mut self, observer: Entity) -> Self {
+        self.descriptor.after.push(observer);
+        self
+    }
+
     /// Sets the error handler to use for this observer.
     ///
     /// See the [`error` module-level documentation](crate::error) for more information.
@@ -382,6 +408,15 @@ pub struct ObserverDescriptor {
 
     /// The entities the observer is watching.
     pub(super) entities: Vec<Entity>,
+
+    /// The priority of the observer, higher priorities running first.
+    pub(super) priority: i32,
+
+    /// The observers this observer runs before.
+    pub(super) before: Vec<Entity>,
+
+    /// The observers this observer runs after.
+    pub(super) after: Vec<Entity>,
 }
 
 impl ObserverDescriptor {
@@ -420,6 +455,21 @@ impl ObserverDescriptor {
     pub fn entities(&self) -> &[Entity] {
         &self.entities
     }
+
+    /// Returns the `priority` of the observer.
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Returns the observers that the observer runs before.
+    pub fn before(&self) -> &[Entity] {
+        &self.before
+    }
+
+    /// Returns the observers that the observer runs after.
+    pub fn after(&self) -> &[Entity] {
+        &self.after
+    }
 }
 
 /// A [`ComponentHook`] used by [`Observer`] to handle its [`on-add`](`crate::lifecycle::ComponentHooks::on_add`).
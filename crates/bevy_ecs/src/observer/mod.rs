@@ -81,8 +81,9 @@
 //! To control the relative ordering of observers sent from different systems,
 //! order the systems in the schedule relative to each other.
 //!
-//! Currently, Bevy does not provide [a way to specify the ordering of observers](https://github.com/bevyengine/bevy/issues/14890)
-//! listening to the same event relative to each other.
+//! By default, the order in which observers listening to the same event run is unspecified.
+//! This can be changed with [`Observer::with_priority`], as observers with a higher priority run first,
+//! or with [`Observer::before`] and [`Observer::after`] to order an observer relative to another observer entity.
 //!
 //! Commands sent by observers are [currently not immediately applied](https://github.com/bevyengine/bevy/issues/19569).
 //! Instead, all queued observers will run, and then all of the commands from those observers will be applied.
@@ -378,9 +379,11 @@ impl World {
             (&*observer_state, &mut self.archetypes, &mut self.observers)
         };
         let descriptor = &observer_state.descriptor;
+        let ordered = observers.register_ordering(observer_entity, descriptor);
 
         for &event_key in &descriptor.event_keys {
             let cache = observers.get_observers_mut(event_key);
+            cache.ordered_observers += usize::from(ordered);
 
             if descriptor.components.is_empty() && descriptor.entities.is_empty() {
                 cache
@@ -429,9 +432,11 @@ impl World {
     pub(crate) fn unregister_observer(&mut self, entity: Entity, descriptor: ObserverDescriptor) {
         let archetypes = &mut self.archetypes;
         let observers = &mut self.observers;
+        let ordered = observers.unregister_ordering(entity);
 
         for &event_key in &descriptor.event_keys {
             let cache = observers.get_observers_mut(event_key);
+            cache.ordered_observers -= usize::from(ordered);
             if descriptor.components.is_empty() && descriptor.entities.is_empty() {
                 cache.global_observers.remove(&entity);
             } else if descriptor.components.is_empty() {
@@ -728,6 +733,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn observer_priority() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.spawn(Observer::new(|_: On<Add, A>, mut res: ResMut<Order>| {
+            res.observed("default");
+        }));
+        world.spawn(
+            Observer::new(|_: On<Add, A>, mut res: ResMut<Order>| res.observed("low"))
+                .with_priority(-1),
+        );
+        world.spawn(
+            Observer::new(|_: On<Add, A>, mut res: ResMut<Order>| res.observed("high"))
+                .with_priority(10),
+        );
+        world.spawn(Observer::new(|_: On<Add>, mut res: ResMut<Order>| {
+            res.observed("any_add");
+        }));
+
+        world.spawn(A).flush();
+        assert_eq!(
+            vec!["high", "any_add", "default", "low"],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_before_after() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let entity = world.spawn_empty().id();
+        let first = world
+            .add_observer(|_: On<EventA>, mut res: ResMut<Order>| res.observed("first"))
+            .id();
+        let last = world
+            .spawn(
+                Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("last"))
+                    .with_priority(100),
+            )
+            .id();
+        world.spawn(
+            Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("middle"))
+                .with_entity(entity)
+                .with_priority(100)
+                .after(first)
+                .before(last),
+        );
+        world.spawn(
+            Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("end"))
+                .with_priority(-1)
+                .after(last),
+        );
+        world.flush();
+
+        world.trigger_targets(EventA, entity);
+        assert_eq!(
+            vec!["first", "middle", "last", "end"],
+            world.resource::<Order>().0
+        );
+
+        // Constraints only apply between observers that are triggered together.
+        world.resource_mut::<Order>().0.clear();
+        world.trigger(EventA);
+        assert_eq!(vec!["last", "first", "end"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_ordering_cycle() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let a = world
+            .spawn(
+                Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("a"))
+                    .with_priority(2),
+            )
+            .id();
+        let b = world
+            .spawn(
+                Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("b"))
+                    .with_priority(1)
+                    .before(a),
+            )
+            .id();
+        world.spawn(
+            Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("c"))
+                .before(b)
+                .after(a),
+        );
+        world.flush();
+
+        // The cycle is broken by running the observer with the highest priority first.
+        for _ in 0..3 {
+            world.resource_mut::<Order>().0.clear();
+            world.trigger(EventA);
+            assert_eq!(vec!["a", "c", "b"], world.resource::<Order>().0);
+        }
+    }
+
     #[test]
     fn observer_multiple_events() {
         let mut world = World::new();
@@ -2,16 +2,16 @@ use crate::{
     archetype::{Archetype, Archetypes},
     bundle::Bundle,
     change_detection::{MaybeLocation, Ticks, TicksMut},
-    component::{Component, ComponentId, Components, Mutable, StorageType, Tick},
+    component::{Component, ComponentId, Components, Mutable, StorageType, Tick, TickCells},
     entity::{Entities, Entity, EntityLocation},
     query::{Access, DebugCheckedUnwrap, FilteredAccess, WorldQuery},
-    storage::{ComponentSparseSet, Table, TableRow},
+    storage::{ComponentSparseSet, SharedComponentValues, SharedValueId, Table, TableRow},
     world::{
         unsafe_world_cell::UnsafeWorldCell, EntityMut, EntityMutExcept, EntityRef, EntityRefExcept,
         FilteredEntityMut, FilteredEntityRef, Mut, Ref, World,
     },
 };
-use bevy_ptr::{ThinSlicePtr, UnsafeCellDeref};
+use bevy_ptr::{Ptr, ThinSlicePtr, UnsafeCellDeref};
 use bevy_utils::prelude::DebugName;
 use core::{cell::UnsafeCell, marker::PhantomData, panic::Location};
 use variadics_please::all_tuples;
@@ -1486,6 +1486,8 @@ pub struct ReadFetch<'w, T: Component> {
         Option<ThinSlicePtr<'w, UnsafeCell<T>>>,
         // T::STORAGE_TYPE = StorageType::SparseSet
         Option<&'w ComponentSparseSet>,
+        // T::STORAGE_TYPE = StorageType::Shared
+        SharedFetch<'w>,
     >,
 }
 
@@ -1527,6 +1529,9 @@ unsafe impl<T: Component> WorldQuery for &T {
                     // reference to the sparse set, which is used to access the components in `Self::fetch`.
                     unsafe { world.storages().sparse_sets.get(component_id) }
                 },
+                // SAFETY: The underlying type associated with `component_id` is `T`,
+                // which we are allowed to access since we registered it in `update_component_access`.
+                || unsafe { SharedFetch::new(world, component_id) },
             ),
         }
     }
@@ -1534,22 +1539,27 @@ unsafe impl<T: Component> WorldQuery for &T {
     const IS_DENSE: bool = {
         match T::STORAGE_TYPE {
             StorageType::Table => true,
-            StorageType::SparseSet => false,
+            StorageType::SparseSet | StorageType::Shared => false,
         }
     };
 
     #[inline]
     unsafe fn set_archetype<'w>(
         fetch: &mut ReadFetch<'w, T>,
-        component_id: &ComponentId,
-        _archetype: &'w Archetype,
+        &component_id: &ComponentId,
+        archetype: &'w Archetype,
         table: &'w Table,
     ) {
-        if Self::IS_DENSE {
+        match T::STORAGE_TYPE {
             // SAFETY: `set_archetype`'s safety rules are a super set of the `set_table`'s ones.
-            unsafe {
-                Self::set_table(fetch, component_id, table);
-            }
+            StorageType::Table => unsafe { Self::set_table(fetch, &component_id, table) },
+            StorageType::SparseSet => {}
+            // SAFETY: T::STORAGE_TYPE = StorageType::Shared
+            StorageType::Shared => unsafe {
+                fetch
+                    .components
+                    .set_shared_archetype(component_id, archetype);
+            },
         }
     }
 
@@ -1634,6 +1644,12 @@ unsafe impl<T: Component> QueryData for &T {
                 };
                 item.deref()
             },
+            |shared| {
+                // SAFETY: set_archetype was previously called with the archetype of `entity`.
+                let (item, _, _) = unsafe { shared.get_with_ticks() };
+                // SAFETY: The value is of type `T`.
+                unsafe { item.deref() }
+            },
         )
     }
 }
@@ -1661,6 +1677,8 @@ pub struct RefFetch<'w, T: Component> {
         // T::STORAGE_TYPE = StorageType::SparseSet
         // Can be `None` when the component has never been inserted
         Option<&'w ComponentSparseSet>,
+        // T::STORAGE_TYPE = StorageType::Shared
+        SharedFetch<'w>,
     >,
     last_run: Tick,
     this_run: Tick,
@@ -1704,6 +1722,9 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
                     // reference to the sparse set, which is used to access the components in `Self::fetch`.
                     unsafe { world.storages().sparse_sets.get(component_id) }
                 },
+                // SAFETY: The underlying type associated with `component_id` is `T`,
+                // which we are allowed to access since we registered it in `update_component_access`.
+                || unsafe { SharedFetch::new(world, component_id) },
             ),
             last_run,
             this_run,
@@ -1713,22 +1734,27 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
     const IS_DENSE: bool = {
         match T::STORAGE_TYPE {
             StorageType::Table => true,
-            StorageType::SparseSet => false,
+            StorageType::SparseSet | StorageType::Shared => false,
         }
     };
 
     #[inline]
     unsafe fn set_archetype<'w>(
         fetch: &mut RefFetch<'w, T>,
-        component_id: &ComponentId,
-        _archetype: &'w Archetype,
+        &component_id: &ComponentId,
+        archetype: &'w Archetype,
         table: &'w Table,
     ) {
-        if Self::IS_DENSE {
+        match T::STORAGE_TYPE {
             // SAFETY: `set_archetype`'s safety rules are a super set of the `set_table`'s ones.
-            unsafe {
-                Self::set_table(fetch, component_id, table);
-            }
+            StorageType::Table => unsafe { Self::set_table(fetch, &component_id, table) },
+            StorageType::SparseSet => {}
+            // SAFETY: T::STORAGE_TYPE = StorageType::Shared
+            StorageType::Shared => unsafe {
+                fetch
+                    .components
+                    .set_shared_archetype(component_id, archetype);
+            },
         }
     }
 
@@ -1837,6 +1863,16 @@ unsafe impl<'__w, T: Component> QueryData for Ref<'__w, T> {
                         .debug_checked_unwrap()
                 };
 
+                Ref {
+                    value: component.deref(),
+                    ticks: Ticks::from_tick_cells(ticks, fetch.last_run, fetch.this_run),
+                    changed_by: caller.map(|caller| caller.deref()),
+                }
+            },
+            |shared| {
+                // SAFETY: set_archetype was previously called with the archetype of `entity`.
+                let (component, ticks, caller) = unsafe { shared.get_with_ticks() };
+
                 Ref {
                     value: component.deref(),
                     ticks: Ticks::from_tick_cells(ticks, fetch.last_run, fetch.this_run),
@@ -1913,6 +1949,8 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                     // reference to the sparse set, which is used to access the components in `Self::fetch`.
                     unsafe { world.storages().sparse_sets.get(component_id) }
                 },
+                // Shared components are immutable, see `ComponentDescriptor::new`.
+                || (),
             ),
             last_run,
             this_run,
@@ -1922,7 +1960,7 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
     const IS_DENSE: bool = {
         match T::STORAGE_TYPE {
             StorageType::Table => true,
-            StorageType::SparseSet => false,
+            StorageType::SparseSet | StorageType::Shared => false,
         }
     };
 
@@ -2052,6 +2090,8 @@ unsafe impl<'__w, T: Component<Mutability = Mutable>> QueryData for &'__w mut T
                     changed_by: caller.map(|caller| caller.deref_mut()),
                 }
             },
+            // Shared components are immutable, see `ComponentDescriptor::new`.
+            |()| unreachable!(),
         )
     }
 }
@@ -2415,7 +2455,7 @@ unsafe impl<T: Component> WorldQuery for Has<T> {
     const IS_DENSE: bool = {
         match T::STORAGE_TYPE {
             StorageType::Table => true,
-            StorageType::SparseSet => false,
+            StorageType::SparseSet | StorageType::Shared => false,
         }
     };
 
@@ -2940,25 +2980,32 @@ impl<T: ?Sized> ReleaseStateQueryData for PhantomData<T> {
     fn release_state<'w>(_item: Self::Item<'w, '_>) -> Self::Item<'w, 'static> {}
 }
 
-/// A compile-time checked union of two different types that differs based on the
+/// A compile-time checked union of three different types that differs based on the
 /// [`StorageType`] of a given component.
-pub(super) union StorageSwitch<C: Component, T: Copy, S: Copy> {
+pub(super) union StorageSwitch<C: Component, T: Copy, S: Copy, H: Copy = ()> {
     /// The table variant. Requires the component to be a table component.
     table: T,
     /// The sparse set variant. Requires the component to be a sparse set component.
     sparse_set: S,
+    /// The shared variant. Requires the component to be a shared component.
+    shared: H,
     _marker: PhantomData<C>,
 }
 
-impl<C: Component, T: Copy, S: Copy> StorageSwitch<C, T, S> {
+impl<C: Component, T: Copy, S: Copy, H: Copy> StorageSwitch<C, T, S, H> {
     /// Creates a new [`StorageSwitch`] using the given closures to initialize
     /// the variant corresponding to the component's [`StorageType`].
-    pub fn new(table: impl FnOnce() -> T, sparse_set: impl FnOnce() -> S) -> Self {
+    pub fn new(
+        table: impl FnOnce() -> T,
+        sparse_set: impl FnOnce() -> S,
+        shared: impl FnOnce() -> H,
+    ) -> Self {
         match C::STORAGE_TYPE {
             StorageType::Table => Self { table: table() },
             StorageType::SparseSet => Self {
                 sparse_set: sparse_set(),
             },
+            StorageType::Shared => Self { shared: shared() },
         }
     }
 
@@ -2986,7 +3033,12 @@ impl<C: Component, T: Copy, S: Copy> StorageSwitch<C, T, S> {
 
     /// Fetches the internal value from the variant that corresponds to the
     /// component's [`StorageType`].
-    pub fn extract<R>(&self, table: impl FnOnce(T) -> R, sparse_set: impl FnOnce(S) -> R) -> R {
+    pub fn extract<R>(
+        &self,
+        table: impl FnOnce(T) -> R,
+        sparse_set: impl FnOnce(S) -> R,
+        shared: impl FnOnce(H) -> R,
+    ) -> R {
         match C::STORAGE_TYPE {
             StorageType::Table => table(
                 // SAFETY: C::STORAGE_TYPE == StorageType::Table
@@ -2996,17 +3048,102 @@ impl<C: Component, T: Copy, S: Copy> StorageSwitch<C, T, S> {
                 // SAFETY: C::STORAGE_TYPE == StorageType::SparseSet
                 unsafe { self.sparse_set },
             ),
+            StorageType::Shared => shared(
+                // SAFETY: C::STORAGE_TYPE == StorageType::Shared
+                unsafe { self.shared },
+            ),
         }
     }
 }
 
-impl<C: Component, T: Copy, S: Copy> Clone for StorageSwitch<C, T, S> {
+impl<'w, C: Component, T: Copy, S: Copy> StorageSwitch<C, T, S, SharedFetch<'w>> {
+    /// Sets the shared variant to the value of `C` in `archetype`.
+    ///
+    /// # Panics
+    ///
+    /// This will panic on debug builds if `C` is not a shared component.
+    ///
+    /// # Safety
+    ///
+    /// `C` must be a shared component.
+    #[inline]
+    pub unsafe fn set_shared_archetype(
+        &mut self,
+        component_id: ComponentId,
+        archetype: &Archetype,
+    ) {
+        match C::STORAGE_TYPE {
+            StorageType::Shared => {
+                // SAFETY: C::STORAGE_TYPE == StorageType::Shared
+                let shared = unsafe { &mut self.shared };
+                shared.value = archetype.get_shared_value(component_id);
+            }
+            _ => {
+                #[cfg(debug_assertions)]
+                unreachable!();
+                #[cfg(not(debug_assertions))]
+                core::hint::unreachable_unchecked()
+            }
+        }
+    }
+}
+
+impl<C: Component, T: Copy, S: Copy, H: Copy> Clone for StorageSwitch<C, T, S, H> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<C: Component, T: Copy, S: Copy> Copy for StorageSwitch<C, T, S> {}
+impl<C: Component, T: Copy, S: Copy, H: Copy> Copy for StorageSwitch<C, T, S, H> {}
+
+/// The shared variant of a [`StorageSwitch`]: the values of a [`Shared`](StorageType::Shared)
+/// component, and the value of the current archetype.
+#[derive(Clone, Copy)]
+pub(super) struct SharedFetch<'w> {
+    values: Option<&'w SharedComponentValues>,
+    value: Option<SharedValueId>,
+}
+
+impl<'w> SharedFetch<'w> {
+    /// Creates a new [`SharedFetch`], with no current archetype.
+    ///
+    /// # Safety
+    ///
+    /// The values of `component_id` must only be used in ways `world` has permission for.
+    pub unsafe fn new(world: UnsafeWorldCell<'w>, component_id: ComponentId) -> Self {
+        Self {
+            // SAFETY: Ensured by the caller. Note that we do not actually access any values here,
+            // we just get a shared reference to the storage, which is used when fetching.
+            values: unsafe { world.storages() }
+                .shared_components
+                .get(component_id),
+            value: None,
+        }
+    }
+
+    /// Returns the value of the current archetype, and its ticks.
+    ///
+    /// # Safety
+    ///
+    /// [`StorageSwitch::set_shared_archetype`] must have been called with an archetype that has
+    /// the component.
+    #[inline]
+    pub unsafe fn get_with_ticks(
+        self,
+    ) -> (
+        Ptr<'w>,
+        TickCells<'w>,
+        MaybeLocation<&'w UnsafeCell<&'static Location<'static>>>,
+    ) {
+        // SAFETY: The archetype has the component, so its values exist and contain its value.
+        unsafe {
+            self.values
+                .debug_checked_unwrap()
+                .get_with_ticks(self.value.debug_checked_unwrap())
+                .debug_checked_unwrap()
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
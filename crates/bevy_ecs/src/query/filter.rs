@@ -163,7 +163,7 @@ unsafe impl<T: Component> WorldQuery for With<T> {
     const IS_DENSE: bool = {
         match T::STORAGE_TYPE {
             StorageType::Table => true,
-            StorageType::SparseSet => false,
+            StorageType::SparseSet | StorageType::Shared => false,
         }
     };
 
@@ -264,7 +264,7 @@ unsafe impl<T: Component> WorldQuery for Without<T> {
     const IS_DENSE: bool = {
         match T::STORAGE_TYPE {
             StorageType::Table => true,
-            StorageType::SparseSet => false,
+            StorageType::SparseSet | StorageType::Shared => false,
         }
     };
 
@@ -637,6 +637,9 @@ unsafe impl<T: Component> QueryFilter for Allows<T> {
 ///
 /// **Note** that this includes changes that happened before the first time this `Query` was run.
 ///
+/// `Added` can't be used with [`Shared`](StorageType::Shared) components: using it with one is a
+/// compile error.
+///
 /// # Deferred
 ///
 /// Note, that entity modifications issued with [`Commands`](crate::system::Commands)
@@ -748,6 +751,7 @@ unsafe impl<T: Component> WorldQuery for Added<T> {
                     // reference to the sparse set, which is used to access the components' ticks in `Self::fetch`.
                     unsafe { world.storages().sparse_sets.get(id) }
                 },
+                || (),
             ),
             last_run,
             this_run,
@@ -757,7 +761,7 @@ unsafe impl<T: Component> WorldQuery for Added<T> {
     const IS_DENSE: bool = {
         match T::STORAGE_TYPE {
             StorageType::Table => true,
-            StorageType::SparseSet => false,
+            StorageType::SparseSet | StorageType::Shared => false,
         }
     };
 
@@ -794,6 +798,12 @@ unsafe impl<T: Component> WorldQuery for Added<T> {
 
     #[inline]
     fn update_component_access(&id: &ComponentId, access: &mut FilteredAccess<ComponentId>) {
+        const {
+            assert!(
+                !matches!(T::STORAGE_TYPE, StorageType::Shared),
+                "`Added` can't be used with shared components, whose change detection ticks are kept per value rather than per entity"
+            );
+        }
         if access.access().has_component_write(id) {
             panic!("$state_name<{}> conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.", DebugName::type_name::<T>());
         }
@@ -847,6 +857,8 @@ unsafe impl<T: Component> QueryFilter for Added<T> {
 
                 tick.deref().is_newer_than(fetch.last_run, fetch.this_run)
             },
+            // `Added` can't be used with shared components, see `update_component_access`.
+            |()| unreachable!(),
         )
     }
 }
@@ -863,6 +875,9 @@ unsafe impl<T: Component> QueryFilter for Added<T> {
 ///
 /// **Note** that this includes changes that happened before the first time this `Query` was run.
 ///
+/// `Changed` can't be used with [`Shared`](StorageType::Shared) components: using it with one is a
+/// compile error.
+///
 /// # Deferred
 ///
 /// Note, that entity modifications issued with [`Commands`](crate::system::Commands)
@@ -975,6 +990,7 @@ unsafe impl<T: Component> WorldQuery for Changed<T> {
                     // reference to the sparse set, which is used to access the components' ticks in `Self::fetch`.
                     unsafe { world.storages().sparse_sets.get(id) }
                 },
+                || (),
             ),
             last_run,
             this_run,
@@ -984,7 +1000,7 @@ unsafe impl<T: Component> WorldQuery for Changed<T> {
     const IS_DENSE: bool = {
         match T::STORAGE_TYPE {
             StorageType::Table => true,
-            StorageType::SparseSet => false,
+            StorageType::SparseSet | StorageType::Shared => false,
         }
     };
 
@@ -1021,6 +1037,12 @@ unsafe impl<T: Component> WorldQuery for Changed<T> {
 
     #[inline]
     fn update_component_access(&id: &ComponentId, access: &mut FilteredAccess<ComponentId>) {
+        const {
+            assert!(
+                !matches!(T::STORAGE_TYPE, StorageType::Shared),
+                "`Changed` can't be used with shared components, whose change detection ticks are kept per value rather than per entity"
+            );
+        }
         if access.access().has_component_write(id) {
             panic!("$state_name<{}> conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.", DebugName::type_name::<T>());
         }
@@ -1075,6 +1097,8 @@ unsafe impl<T: Component> QueryFilter for Changed<T> {
 
                 tick.deref().is_newer_than(fetch.last_run, fetch.this_run)
             },
+            // `Changed` can't be used with shared components, see `update_component_access`.
+            |()| unreachable!(),
         )
     }
 }
@@ -0,0 +1,339 @@
+//! Opt-in recording of the recent values of components, in a [`ComponentHistory`].
+
+use alloc::{boxed::Box, collections::VecDeque};
+
+use bevy_platform::collections::HashMap;
+use bevy_reflect::PartialReflect;
+use bevy_utils::prelude::DebugName;
+
+use crate::{
+    component::{Component, ComponentId, ComponentInfo, Tick},
+    entity::{Entity, EntityHashMap},
+    reflect::{AppTypeRegistry, ReflectComponent},
+    resource::Resource,
+    schedule::Schedules,
+    world::{Mut, World},
+};
+
+/// A [`Resource`] keeping the last values of selected components for each entity, along with the
+/// tick at which they changed and the system that changed them.
+///
+/// Components are selected with [`World::track_component_history`] or
+/// [`ComponentHistory::track`], and their values are recorded by the
+/// [`record_component_history`] system. Values are captured through reflection, so a component
+/// is only recorded if it is registered in the [`AppTypeRegistry`] with [`ReflectComponent`].
+///
+/// Each run of [`record_component_history`] records the value of the components that changed
+/// since its previous run: if a component changes several times in between, only its last value
+/// is recorded.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, reflect::{record_component_history, ComponentHistory}};
+/// # use bevy_reflect::Reflect;
+/// #
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Health>();
+/// world.track_component_history::<Health>(8);
+///
+/// let entity = world.spawn(Health(10)).id();
+/// record_component_history(&mut world);
+/// world.get_mut::<Health>(entity).unwrap().0 = 5;
+/// record_component_history(&mut world);
+///
+/// let health = world.component_id::<Health>().unwrap();
+/// let values: Vec<_> = world
+///     .resource::<ComponentHistory>()
+///     .get(entity, health)
+///     .map(|entry| entry.value.try_downcast_ref::<Health>().unwrap().0)
+///     .collect();
+/// assert_eq!(values, [10, 5]);
+/// ```
+#[derive(Resource, Default)]
+pub struct ComponentHistory {
+    tracked: HashMap<ComponentId, TrackedComponent>,
+    last_recorded: Option<Tick>,
+}
+
+struct TrackedComponent {
+    capacity: usize,
+    entities: EntityHashMap<VecDeque<ComponentHistoryEntry>>,
+}
+
+/// A value of a component recorded in the [`ComponentHistory`].
+#[derive(Debug)]
+pub struct ComponentHistoryEntry {
+    /// The value of the component when it was recorded.
+    pub value: Box<dyn PartialReflect>,
+    /// The tick at which the component was last changed before being recorded.
+    pub tick: Tick,
+    /// The name of the system that changed the component.
+    ///
+    /// This is `None` if the component was changed outside of a system, or by a system which is
+    /// not part of a schedule in [`Schedules`] when the value is recorded, such as the systems
+    /// of the schedule [`record_component_history`] runs in. Changes made by commands are
+    /// attributed to the system that applied them.
+    pub system: Option<DebugName>,
+}
+
+impl ComponentHistory {
+    /// Starts recording the last `capacity` values of the component `component_id` of each entity.
+    ///
+    /// If the component is already tracked, its capacity is changed, dropping its oldest values
+    /// if there are more than `capacity` of them.
+    pub fn track(&mut self, component_id: ComponentId, capacity: usize) {
+        let tracked = self
+            .tracked
+            .entry(component_id)
+            .or_insert_with(|| TrackedComponent {
+                capacity,
+                entities: EntityHashMap::default(),
+            });
+        tracked.capacity = capacity;
+        for entries in tracked.entities.values_mut() {
+            let excess = entries.len().saturating_sub(capacity);
+            entries.drain(..excess);
+        }
+    }
+
+    /// Stops recording the values of the component `component_id`, dropping its recorded values.
+    ///
+    /// Returns `false` if the component was not tracked.
+    pub fn untrack(&mut self, component_id: ComponentId) -> bool {
+        self.tracked.remove(&component_id).is_some()
+    }
+
+    /// Returns `true` if the values of the component `component_id` are recorded.
+    pub fn is_tracked(&self, component_id: ComponentId) -> bool {
+        self.tracked.contains_key(&component_id)
+    }
+
+    /// Returns the recorded values of the component `component_id` of `entity`, from the oldest
+    /// to the most recent.
+    pub fn get(
+        &self,
+        entity: Entity,
+        component_id: ComponentId,
+    ) -> impl DoubleEndedIterator<Item = &ComponentHistoryEntry> {
+        self.tracked
+            .get(&component_id)
+            .and_then(|tracked| tracked.entities.get(&entity))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Drops all the recorded values, while keeping the same components tracked.
+    pub fn clear(&mut self) {
+        for tracked in self.tracked.values_mut() {
+            tracked.entities.clear();
+        }
+    }
+}
+
+impl World {
+    /// Starts recording the last `capacity` values of the component `C` of each entity in the
+    /// [`ComponentHistory`], which is initialized if needed.
+    ///
+    /// See [`ComponentHistory::track`].
+    pub fn track_component_history<C: Component>(&mut self, capacity: usize) -> &mut Self {
+        let component_id = self.register_component::<C>();
+        self.get_resource_or_init::<ComponentHistory>()
+            .track(component_id, capacity);
+        self
+    }
+}
+
+/// Records the values of the components tracked by the [`ComponentHistory`] which changed since
+/// the last time this system ran.
+///
+/// The first run records the current value of every tracked component. The history of entities
+/// which were despawned is dropped.
+///
+/// Does nothing if there is no [`ComponentHistory`] or [`AppTypeRegistry`] resource.
+pub fn record_component_history(world: &mut World) {
+    if !world.contains_resource::<AppTypeRegistry>() {
+        return;
+    }
+    world.try_resource_scope(|world, mut history: Mut<ComponentHistory>| {
+        // Changes made from now on happen after `this_run`.
+        let this_run = world.increment_change_tick();
+        let last_run = history.last_recorded.replace(this_run);
+
+        // Every system run has a distinct tick, which is the one of the changes it makes.
+        let mut systems = HashMap::<Tick, DebugName>::default();
+        if let Some(schedules) = world.get_resource::<Schedules>() {
+            for (_, schedule) in schedules.iter() {
+                let Ok(schedule_systems) = schedule.systems() else {
+                    continue;
+                };
+                for (_, system) in schedule_systems {
+                    let tick = system.get_last_run();
+                    if last_run.is_none_or(|last_run| tick.is_newer_than(last_run, this_run)) {
+                        systems.insert(tick, system.name());
+                    }
+                }
+            }
+        }
+
+        let registry = world.resource::<AppTypeRegistry>().read();
+        for (&component_id, tracked) in &mut history.tracked {
+            tracked
+                .entities
+                .retain(|&entity, _| world.entities().contains(entity));
+            let Some(reflect_component) = world
+                .components()
+                .get_info(component_id)
+                .and_then(ComponentInfo::type_id)
+                .and_then(|type_id| registry.get_type_data::<ReflectComponent>(type_id))
+            else {
+                continue;
+            };
+
+            for archetype in world.archetypes().iter() {
+                if !archetype.contains(component_id) {
+                    continue;
+                }
+                for archetype_entity in archetype.entities() {
+                    let entity = world.entity(archetype_entity.id());
+                    let Some(ticks) = entity.get_change_ticks_by_id(component_id) else {
+                        continue;
+                    };
+                    if last_run.is_some_and(|last_run| !ticks.is_changed(last_run, this_run)) {
+                        continue;
+                    }
+                    let Some(value) = reflect_component.reflect(entity) else {
+                        continue;
+                    };
+                    let value = value
+                        .reflect_clone()
+                        .map(PartialReflect::into_partial_reflect)
+                        .unwrap_or_else(|_| value.to_dynamic());
+
+                    let entries = tracked.entities.entry(entity.id()).or_default();
+                    entries.push_back(ComponentHistoryEntry {
+                        value,
+                        tick: ticks.changed,
+                        system: systems.get(&ticks.changed).cloned(),
+                    });
+                    if entries.len() > tracked.capacity {
+                        entries.pop_front();
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record_component_history, ComponentHistory};
+    use crate::{
+        prelude::*,
+        schedule::{Schedule, ScheduleLabel},
+    };
+    use alloc::vec::Vec;
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct Other;
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Damage;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Health>();
+        world.track_component_history::<Health>(2);
+        world
+    }
+
+    fn values(world: &World, entity: Entity) -> Vec<u32> {
+        let health = world.component_id::<Health>().unwrap();
+        world
+            .resource::<ComponentHistory>()
+            .get(entity, health)
+            .map(|entry| entry.value.try_downcast_ref::<Health>().unwrap().0)
+            .collect()
+    }
+
+    #[test]
+    fn records_changed_values_up_to_capacity() {
+        let mut world = world();
+        let a = world.spawn((Health(3), Other)).id();
+        let b = world.spawn(Health(7)).id();
+        record_component_history(&mut world);
+        assert_eq!(values(&world, a), [3]);
+        assert_eq!(values(&world, b), [7]);
+
+        world.get_mut::<Health>(a).unwrap().0 = 2;
+        record_component_history(&mut world);
+        assert_eq!(values(&world, a), [3, 2]);
+        assert_eq!(values(&world, b), [7]);
+
+        world.get_mut::<Health>(a).unwrap().0 = 1;
+        world.get_mut::<Health>(a).unwrap().0 = 0;
+        record_component_history(&mut world);
+        assert_eq!(values(&world, a), [2, 0]);
+
+        world.despawn(a);
+        record_component_history(&mut world);
+        assert_eq!(values(&world, a), []);
+        assert_eq!(values(&world, b), [7]);
+    }
+
+    #[test]
+    fn records_writing_system() {
+        let mut world = world();
+        let entity = world.spawn(Health(3)).id();
+        record_component_history(&mut world);
+
+        let mut schedule = Schedule::new(Damage);
+        schedule.add_systems(|mut query: Query<&mut Health>| {
+            for mut health in &mut query {
+                health.0 -= 1;
+            }
+        });
+        world.add_schedule(schedule);
+        world.run_schedule(Damage);
+        world.get_mut::<Health>(entity).unwrap().0 = 1;
+        world.run_schedule(Damage);
+        record_component_history(&mut world);
+
+        let health = world.component_id::<Health>().unwrap();
+        let history = world.resource::<ComponentHistory>();
+        let entry = history.get(entity, health).last().unwrap();
+        assert_eq!(entry.value.try_downcast_ref::<Health>(), Some(&Health(0)));
+        assert!(entry.system.is_some());
+        assert!(history.get(entity, health).next().unwrap().system.is_none());
+    }
+
+    #[test]
+    fn shrinking_capacity_drops_oldest_values() {
+        let mut world = world();
+        let entity = world.spawn(Health(3)).id();
+        record_component_history(&mut world);
+        world.get_mut::<Health>(entity).unwrap().0 = 2;
+        record_component_history(&mut world);
+
+        world.track_component_history::<Health>(1);
+        assert_eq!(values(&world, entity), [2]);
+
+        let health = world.component_id::<Health>().unwrap();
+        assert!(world.resource_mut::<ComponentHistory>().untrack(health));
+        assert_eq!(values(&world, entity), []);
+    }
+}
@@ -0,0 +1,913 @@
+//! Undoable edits to a [`World`], recorded in an [`EditHistory`].
+
+use alloc::{boxed::Box, string::String, string::ToString, vec, vec::Vec};
+use core::any::TypeId;
+
+use bevy_reflect::{GetPath, PartialReflect, Reflect, TypePath, TypeRegistry};
+use thiserror::Error;
+
+use crate::{
+    change_detection::Mut,
+    component::{Component, ComponentCloneBehavior},
+    entity::{Entity, EntityHashMap, EntityMapper},
+    hierarchy::Children,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    relationship::RelationshipHookMode,
+    resource::Resource,
+    system::Commands,
+    world::World,
+};
+
+/// A single reversible change to a [`World`].
+///
+/// Edits only touch components that are registered in the [`AppTypeRegistry`] with
+/// [`ReflectComponent`]. Applying an edit through [`World::apply_edit`] records its inverse
+/// in the [`EditHistory`], so that it can be reverted with [`World::undo_edit`].
+///
+/// Entities are identified by the id they had when the edit was first applied. If an entity is
+/// despawned and later restored by undoing that despawn, it is spawned again with a new id, and
+/// the [`EditHistory`] keeps track of which entity currently stands in for the original one.
+#[derive(Debug)]
+pub enum Edit {
+    /// Spawns entities with the given components.
+    ///
+    /// The first snapshot is the root of the spawned hierarchy, the remaining ones are its
+    /// descendants. Relationship hooks only run for the root: the relationships between the
+    /// descendants are expected to be captured on both sides, as they are by [`Edit::Despawn`].
+    ///
+    /// If an entity of a snapshot still exists, its components are inserted into it instead.
+    /// Undoing this edit despawns the root.
+    Spawn(Vec<EntitySnapshot>),
+    /// Despawns an entity along with its [`Children`].
+    ///
+    /// Undoing this edit spawns the entity and its descendants again with every reflected
+    /// component they had. Components without reflection data are lost, as are relationships
+    /// pointing to the despawned entities from outside of the hierarchy.
+    Despawn(Entity),
+    /// Inserts a component, replacing the previous value if there was one.
+    Insert {
+        /// The entity to insert the component into.
+        entity: Entity,
+        /// The component value.
+        component: Box<dyn PartialReflect>,
+    },
+    /// Removes a component.
+    Remove {
+        /// The entity to remove the component from.
+        entity: Entity,
+        /// The [`TypeId`] of the component to remove.
+        type_id: TypeId,
+    },
+    /// Applies a value to a field of a component, using a [reflection path](bevy_reflect::GetPath).
+    SetField {
+        /// The entity owning the component.
+        entity: Entity,
+        /// The [`TypeId`] of the component.
+        type_id: TypeId,
+        /// The path to the field within the component, such as `"translation.x"`.
+        path: String,
+        /// The value to apply to the field.
+        value: Box<dyn PartialReflect>,
+    },
+    /// Applies several edits in order as a single step.
+    ///
+    /// If one of them fails, the ones applied before it are reverted.
+    Group(Vec<Edit>),
+}
+
+/// The reflected components of an entity, as spawned by [`Edit::Spawn`].
+#[derive(Debug)]
+pub struct EntitySnapshot {
+    /// The entity the components belong to.
+    pub entity: Entity,
+    /// The reflected components.
+    pub components: Vec<Box<dyn PartialReflect>>,
+}
+
+impl Edit {
+    /// Creates an [`Edit::Spawn`] inserting `components` into `entity`, which is usually a fresh
+    /// entity from [`World::spawn_empty`] or [`Commands::spawn_empty`].
+    pub fn spawn(
+        entity: Entity,
+        components: impl IntoIterator<Item = Box<dyn PartialReflect>>,
+    ) -> Self {
+        Edit::Spawn(vec![EntitySnapshot {
+            entity,
+            components: components.into_iter().collect(),
+        }])
+    }
+
+    /// Creates an [`Edit::Insert`] for the given component.
+    pub fn insert<C: Component + Reflect>(entity: Entity, component: C) -> Self {
+        Edit::Insert {
+            entity,
+            component: Box::new(component),
+        }
+    }
+
+    /// Creates an [`Edit::Remove`] for the component `C`.
+    pub fn remove<C: Component>(entity: Entity) -> Self {
+        Edit::Remove {
+            entity,
+            type_id: TypeId::of::<C>(),
+        }
+    }
+
+    /// Creates an [`Edit::SetField`] setting the field at `path` of the component `C` to `value`.
+    pub fn set_field<C: Component + TypePath>(
+        entity: Entity,
+        path: impl Into<String>,
+        value: impl PartialReflect,
+    ) -> Self {
+        Edit::SetField {
+            entity,
+            type_id: TypeId::of::<C>(),
+            path: path.into(),
+            value: Box::new(value),
+        }
+    }
+
+    /// Applies this edit, returning the edit that reverts it.
+    ///
+    /// `self` must refer to entities by their original ids, see [`EntityIds`].
+    fn apply(
+        self,
+        world: &mut World,
+        ids: &mut EntityIds,
+        registry: &TypeRegistry,
+    ) -> Result<Edit, EditError> {
+        match self {
+            Edit::Spawn(snapshots) => {
+                // Validate everything up front so that a failure leaves the world untouched.
+                let reflect_components = snapshots
+                    .iter()
+                    .map(|snapshot| {
+                        snapshot
+                            .components
+                            .iter()
+                            .map(|component| reflect_component_of(component.as_ref(), registry))
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let Some(root) = snapshots.first().map(|snapshot| snapshot.entity) else {
+                    return Ok(Edit::Group(Vec::new()));
+                };
+
+                for snapshot in &snapshots {
+                    if world.get_entity(ids.live(snapshot.entity)).is_err() {
+                        let entity = world.spawn_empty().id();
+                        ids.bind(snapshot.entity, entity);
+                    }
+                }
+                for (index, (snapshot, reflect_components)) in
+                    snapshots.iter().zip(reflect_components).enumerate()
+                {
+                    let hook_mode = if index == 0 {
+                        RelationshipHookMode::Run
+                    } else {
+                        RelationshipHookMode::Skip
+                    };
+                    let entity = ids.live(snapshot.entity);
+                    for (component, reflect_component) in
+                        snapshot.components.iter().zip(reflect_components)
+                    {
+                        reflect_component.apply_or_insert_mapped(
+                            &mut world.entity_mut(entity),
+                            component.as_ref(),
+                            registry,
+                            &mut ids.to_live,
+                            hook_mode,
+                        );
+                    }
+                }
+                Ok(Edit::Despawn(root))
+            }
+            Edit::Despawn(entity) => {
+                let live = ids.live(entity);
+                world
+                    .get_entity(live)
+                    .map_err(|_| EditError::NoSuchEntity(entity))?;
+
+                let mut hierarchy = vec![live];
+                let mut index = 0;
+                while let Some(&parent) = hierarchy.get(index) {
+                    if let Some(children) = world.get::<Children>(parent) {
+                        hierarchy.extend(children.iter());
+                    }
+                    index += 1;
+                }
+
+                let snapshots = hierarchy
+                    .iter()
+                    .map(|&live| EntitySnapshot {
+                        entity: ids.original(live),
+                        components: capture_components(world, live, ids, registry),
+                    })
+                    .collect();
+                world.despawn(live);
+                Ok(Edit::Spawn(snapshots))
+            }
+            Edit::Insert { entity, component } => {
+                let reflect_component = reflect_component_of(component.as_ref(), registry)?;
+                let live = ids.live(entity);
+                let previous = capture_component(world, live, reflect_component, ids)
+                    .map_err(|_| EditError::NoSuchEntity(entity))?;
+                reflect_component.apply_or_insert_mapped(
+                    &mut world.entity_mut(live),
+                    component.as_ref(),
+                    registry,
+                    &mut ids.to_live,
+                    RelationshipHookMode::Run,
+                );
+                Ok(match previous {
+                    Some(component) => Edit::Insert { entity, component },
+                    None => Edit::Remove {
+                        entity,
+                        type_id: represented_type_id(component.as_ref())?,
+                    },
+                })
+            }
+            Edit::Remove { entity, type_id } => {
+                let reflect_component = registry
+                    .get_type_data::<ReflectComponent>(type_id)
+                    .ok_or(EditError::UnregisteredComponent(type_id))?;
+                let live = ids.live(entity);
+                let previous = capture_component(world, live, reflect_component, ids)
+                    .map_err(|_| EditError::NoSuchEntity(entity))?;
+                reflect_component.remove(&mut world.entity_mut(live));
+                Ok(match previous {
+                    Some(component) => Edit::Insert { entity, component },
+                    None => Edit::Group(Vec::new()),
+                })
+            }
+            Edit::SetField {
+                entity,
+                type_id,
+                path,
+                value,
+            } => {
+                let reflect_component = registry
+                    .get_type_data::<ReflectComponent>(type_id)
+                    .ok_or(EditError::UnregisteredComponent(type_id))?;
+                let mut entity_mut = world
+                    .get_entity_mut(ids.live(entity))
+                    .map_err(|_| EditError::NoSuchEntity(entity))?;
+                let mut component = reflect_component
+                    .reflect_mut(&mut entity_mut)
+                    .ok_or(EditError::MissingComponent { entity, type_id })?;
+                let field = component.reflect_path_mut(path.as_str()).map_err(|error| {
+                    EditError::InvalidPath {
+                        path: path.clone(),
+                        message: error.to_string(),
+                    }
+                })?;
+                let previous = clone_value(field);
+                field
+                    .try_apply(value.as_ref())
+                    .map_err(|error| EditError::InvalidPath {
+                        path: path.clone(),
+                        message: error.to_string(),
+                    })?;
+                Ok(Edit::SetField {
+                    entity,
+                    type_id,
+                    path,
+                    value: previous,
+                })
+            }
+            Edit::Group(edits) => {
+                let mut inverses = Vec::with_capacity(edits.len());
+                for edit in edits {
+                    match edit.apply(world, ids, registry) {
+                        Ok(inverse) => inverses.push(inverse),
+                        Err(error) => {
+                            for inverse in inverses.into_iter().rev() {
+                                // Best effort: these edits were generated from the current state.
+                                let _ = inverse.apply(world, ids, registry);
+                            }
+                            return Err(error);
+                        }
+                    }
+                }
+                inverses.reverse();
+                Ok(Edit::Group(inverses))
+            }
+        }
+    }
+
+    /// Rewrites the entities referenced by an edit coming from user code to their original ids.
+    fn into_original(self, ids: &EntityIds, registry: &TypeRegistry) -> Edit {
+        if ids.to_original.is_empty() {
+            return self;
+        }
+        match self {
+            Edit::Spawn(snapshots) => Edit::Spawn(
+                snapshots
+                    .into_iter()
+                    .map(|snapshot| EntitySnapshot {
+                        entity: ids.original(snapshot.entity),
+                        components: snapshot
+                            .components
+                            .into_iter()
+                            .map(|component| map_to_original(component, ids, registry))
+                            .collect(),
+                    })
+                    .collect(),
+            ),
+            Edit::Despawn(entity) => Edit::Despawn(ids.original(entity)),
+            Edit::Insert { entity, component } => Edit::Insert {
+                entity: ids.original(entity),
+                component: map_to_original(component, ids, registry),
+            },
+            Edit::Remove { entity, type_id } => Edit::Remove {
+                entity: ids.original(entity),
+                type_id,
+            },
+            Edit::SetField {
+                entity,
+                type_id,
+                path,
+                value,
+            } => Edit::SetField {
+                entity: ids.original(entity),
+                type_id,
+                path,
+                value,
+            },
+            Edit::Group(edits) => Edit::Group(
+                edits
+                    .into_iter()
+                    .map(|edit| edit.into_original(ids, registry))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// An error that occurred while applying an [`Edit`].
+#[derive(Error, Debug)]
+pub enum EditError {
+    /// The [`World`] was missing the [`AppTypeRegistry`] resource.
+    #[error("The `World` was missing the `AppTypeRegistry` resource")]
+    MissingAppTypeRegistry,
+    /// The entity does not exist.
+    #[error("The entity {0} does not exist")]
+    NoSuchEntity(Entity),
+    /// The type is not registered with [`ReflectComponent`] type data.
+    #[error(
+        "{0:?} is not registered as a reflected component (did you call App::register_type()?)"
+    )]
+    UnregisteredComponent(TypeId),
+    /// The type of a reflected value could not be determined.
+    #[error("Could not determine the represented type of `{0}`")]
+    NoRepresentedType(String),
+    /// The entity does not have the component.
+    #[error("The entity {entity} does not have a component of type {type_id:?}")]
+    MissingComponent {
+        /// The entity.
+        entity: Entity,
+        /// The [`TypeId`] of the component.
+        type_id: TypeId,
+    },
+    /// The field path of an [`Edit::SetField`] did not resolve, or the value did not fit the field.
+    #[error("Could not set the field at `{path}`: {message}")]
+    InvalidPath {
+        /// The path to the field.
+        path: String,
+        /// What went wrong.
+        message: String,
+    },
+}
+
+/// Maps the entities referenced by recorded [`Edit`]s to the entities currently standing in for them.
+///
+/// Edits keep referring to entities by the id they had when the edit was first applied. When such an
+/// entity is spawned again by an [`Edit::Spawn`], the new entity is bound to the original id here.
+#[derive(Default, Debug)]
+struct EntityIds {
+    to_live: EntityHashMap<Entity>,
+    to_original: EntityHashMap<Entity>,
+}
+
+impl EntityIds {
+    fn live(&self, original: Entity) -> Entity {
+        self.to_live.get(&original).copied().unwrap_or(original)
+    }
+
+    fn original(&self, live: Entity) -> Entity {
+        self.to_original.get(&live).copied().unwrap_or(live)
+    }
+
+    fn bind(&mut self, original: Entity, live: Entity) {
+        if let Some(previous) = self.to_live.insert(original, live) {
+            self.to_original.remove(&previous);
+        }
+        if original != live {
+            self.to_original.insert(live, original);
+        } else {
+            self.to_live.remove(&original);
+        }
+    }
+}
+
+/// An [`EntityMapper`] rewriting live entities to their original ids.
+struct ToOriginal<'a>(&'a EntityIds);
+
+impl EntityMapper for ToOriginal<'_> {
+    fn get_mapped(&mut self, source: Entity) -> Entity {
+        self.0.original(source)
+    }
+
+    fn set_mapped(&mut self, _source: Entity, _target: Entity) {}
+}
+
+/// A [`Resource`] recording the [`Edit`]s applied to a [`World`] so they can be undone and redone.
+///
+/// It is created on demand by [`World::apply_edit`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::reflect::{Edit, EditHistory};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, Debug, PartialEq)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Health>();
+///
+/// let entity = world.spawn(Health(10)).id();
+/// world.apply_edit(Edit::set_field::<Health>(entity, ".0", 5u32)).unwrap();
+/// assert_eq!(world.get::<Health>(entity), Some(&Health(5)));
+///
+/// world.undo_edit().unwrap();
+/// assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+///
+/// world.redo_edit().unwrap();
+/// assert_eq!(world.get::<Health>(entity), Some(&Health(5)));
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct EditHistory {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    group: Vec<Edit>,
+    group_depth: usize,
+    ids: EntityIds,
+    limit: Option<usize>,
+}
+
+impl EditHistory {
+    /// Creates a history that keeps at most `limit` undo steps, dropping the oldest ones first.
+    pub fn with_limit(limit: usize) -> Self {
+        EditHistory {
+            limit: Some(limit),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the number of steps that can be undone.
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Returns the number of steps that can be redone.
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// Returns `true` if there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns `true` if there is a step to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Returns the entity currently standing in for `entity`.
+    ///
+    /// This differs from `entity` once it has been despawned and spawned again by undoing or redoing.
+    pub fn live_entity(&self, entity: Entity) -> Entity {
+        self.ids.live(entity)
+    }
+
+    /// Forgets every recorded step.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.group.clear();
+        self.group_depth = 0;
+    }
+
+    fn push_undo(&mut self, edit: Edit) {
+        self.undo.push(edit);
+        if let Some(limit) = self.limit
+            && self.undo.len() > limit
+        {
+            self.undo.remove(0);
+        }
+    }
+
+    fn close_group(&mut self) {
+        self.group_depth = 0;
+        let mut group = core::mem::take(&mut self.group);
+        if !group.is_empty() {
+            group.reverse();
+            self.push_undo(Edit::Group(group));
+        }
+    }
+}
+
+impl World {
+    /// Applies `edit` and records it in the [`EditHistory`], clearing the steps that could be redone.
+    ///
+    /// While an edit group is open (see [`World::begin_edit_group`]), the edit becomes part of it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EditError`] if the edit could not be applied, in which case nothing is recorded.
+    pub fn apply_edit(&mut self, edit: Edit) -> Result<(), EditError> {
+        self.with_edit_history(|world, history, registry| {
+            let edit = edit.into_original(&history.ids, registry);
+            let inverse = edit.apply(world, &mut history.ids, registry)?;
+            history.redo.clear();
+            if history.group_depth > 0 {
+                history.group.push(inverse);
+            } else {
+                history.push_undo(inverse);
+            }
+            Ok(())
+        })
+    }
+
+    /// Reverts the last recorded step, closing any open edit group first.
+    ///
+    /// Returns `false` if there was nothing to undo.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EditError`] if the world changed in a way that prevents reverting the step,
+    /// in which case the step is dropped from the history.
+    pub fn undo_edit(&mut self) -> Result<bool, EditError> {
+        self.with_edit_history(|world, history, registry| {
+            history.close_group();
+            let Some(edit) = history.undo.pop() else {
+                return Ok(false);
+            };
+            let inverse = edit.apply(world, &mut history.ids, registry)?;
+            history.redo.push(inverse);
+            Ok(true)
+        })
+    }
+
+    /// Re-applies the last undone step.
+    ///
+    /// Returns `false` if there was nothing to redo.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EditError`] if the world changed in a way that prevents re-applying the step,
+    /// in which case the step is dropped from the history.
+    pub fn redo_edit(&mut self) -> Result<bool, EditError> {
+        self.with_edit_history(|world, history, registry| {
+            let Some(edit) = history.redo.pop() else {
+                return Ok(false);
+            };
+            let inverse = edit.apply(world, &mut history.ids, registry)?;
+            history.push_undo(inverse);
+            Ok(true)
+        })
+    }
+
+    /// Starts grouping the edits applied with [`World::apply_edit`] into a single undo step.
+    ///
+    /// Groups can be nested; the step is recorded once every group has been ended with
+    /// [`World::end_edit_group`].
+    pub fn begin_edit_group(&mut self) {
+        self.get_resource_or_init::<EditHistory>().group_depth += 1;
+    }
+
+    /// Ends the edit group started by the matching [`World::begin_edit_group`].
+    pub fn end_edit_group(&mut self) {
+        let mut history = self.get_resource_or_init::<EditHistory>();
+        match history.group_depth {
+            0 => {}
+            1 => history.close_group(),
+            _ => history.group_depth -= 1,
+        }
+    }
+
+    fn with_edit_history<R>(
+        &mut self,
+        f: impl FnOnce(&mut World, &mut EditHistory, &TypeRegistry) -> Result<R, EditError>,
+    ) -> Result<R, EditError> {
+        let registry = self
+            .get_resource::<AppTypeRegistry>()
+            .ok_or(EditError::MissingAppTypeRegistry)?
+            .clone();
+        let registry = registry.read();
+        self.init_resource::<EditHistory>();
+        self.resource_scope(|world, mut history: Mut<EditHistory>| {
+            f(world, &mut history, &registry)
+        })
+    }
+}
+
+/// An extension trait for [`Commands`] to apply, undo and redo [`Edit`]s.
+///
+/// Errors are reported through the command error handler.
+pub trait EditCommandsExt {
+    /// Queues [`World::apply_edit`].
+    fn apply_edit(&mut self, edit: Edit);
+    /// Queues [`World::undo_edit`].
+    fn undo_edit(&mut self);
+    /// Queues [`World::redo_edit`].
+    fn redo_edit(&mut self);
+}
+
+impl EditCommandsExt for Commands<'_, '_> {
+    fn apply_edit(&mut self, edit: Edit) {
+        self.queue(move |world: &mut World| -> crate::error::Result {
+            world.apply_edit(edit)?;
+            Ok(())
+        });
+    }
+
+    fn undo_edit(&mut self) {
+        self.queue(|world: &mut World| -> crate::error::Result {
+            world.undo_edit()?;
+            Ok(())
+        });
+    }
+
+    fn redo_edit(&mut self) {
+        self.queue(|world: &mut World| -> crate::error::Result {
+            world.redo_edit()?;
+            Ok(())
+        });
+    }
+}
+
+fn represented_type_id(value: &dyn PartialReflect) -> Result<TypeId, EditError> {
+    value
+        .get_represented_type_info()
+        .map(bevy_reflect::TypeInfo::type_id)
+        .ok_or_else(|| EditError::NoRepresentedType(value.reflect_type_path().to_string()))
+}
+
+fn reflect_component_of<'a>(
+    value: &dyn PartialReflect,
+    registry: &'a TypeRegistry,
+) -> Result<&'a ReflectComponent, EditError> {
+    let type_id = represented_type_id(value)?;
+    registry
+        .get_type_data::<ReflectComponent>(type_id)
+        .ok_or(EditError::UnregisteredComponent(type_id))
+}
+
+fn clone_value(value: &dyn PartialReflect) -> Box<dyn PartialReflect> {
+    value
+        .reflect_clone()
+        .map(PartialReflect::into_partial_reflect)
+        .unwrap_or_else(|_| value.to_dynamic())
+}
+
+/// Clones a component of `entity`, with its entity references rewritten to their original ids.
+fn capture_component(
+    world: &World,
+    entity: Entity,
+    reflect_component: &ReflectComponent,
+    ids: &EntityIds,
+) -> Result<Option<Box<dyn PartialReflect>>, ()> {
+    let entity_ref = world.get_entity(entity).map_err(|_| ())?;
+    Ok(reflect_component
+        .reflect(entity_ref)
+        .map(|component| match component.reflect_clone() {
+            Ok(mut component) => {
+                reflect_component.map_entities(&mut *component, &mut ToOriginal(ids));
+                component.into_partial_reflect()
+            }
+            Err(_) => component.to_dynamic(),
+        }))
+}
+
+/// Clones every reflected component of `entity` that allows cloning.
+fn capture_components(
+    world: &World,
+    entity: Entity,
+    ids: &EntityIds,
+    registry: &TypeRegistry,
+) -> Vec<Box<dyn PartialReflect>> {
+    let entity_ref = world.entity(entity);
+    entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| world.components().get_info(component_id))
+        .filter(|info| *info.clone_behavior() != ComponentCloneBehavior::Ignore)
+        .filter_map(|info| registry.get_type_data::<ReflectComponent>(info.type_id()?))
+        .filter_map(|reflect_component| {
+            capture_component(world, entity, reflect_component, ids)
+                .ok()
+                .flatten()
+        })
+        .collect()
+}
+
+fn map_to_original(
+    component: Box<dyn PartialReflect>,
+    ids: &EntityIds,
+    registry: &TypeRegistry,
+) -> Box<dyn PartialReflect> {
+    let Ok(reflect_component) = reflect_component_of(component.as_ref(), registry) else {
+        return component;
+    };
+    match component.try_into_reflect() {
+        Ok(mut component) => {
+            reflect_component.map_entities(&mut *component, &mut ToOriginal(ids));
+            component.into_partial_reflect()
+        }
+        Err(component) => component,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Edit, EditHistory};
+    use crate::{
+        hierarchy::{ChildOf, Children},
+        prelude::*,
+    };
+    use alloc::{boxed::Box, vec};
+    use bevy_reflect::{PartialReflect, Reflect};
+
+    #[derive(Component, Reflect, Debug, PartialEq, Clone)]
+    #[reflect(Component)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Name(&'static str);
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let registry = world.resource::<AppTypeRegistry>();
+            let mut registry = registry.write();
+            registry.register::<Position>();
+            registry.register::<Name>();
+            registry.register::<ChildOf>();
+            registry.register::<Children>();
+        }
+        world
+    }
+
+    #[test]
+    fn undo_redo_component_edits() {
+        let mut world = world();
+        let entity = world.spawn(Position { x: 1.0, y: 2.0 }).id();
+
+        world
+            .apply_edit(Edit::insert(entity, Position { x: 3.0, y: 4.0 }))
+            .unwrap();
+        world.apply_edit(Edit::insert(entity, Name("a"))).unwrap();
+        world
+            .apply_edit(Edit::set_field::<Position>(entity, "x", 5.0f32))
+            .unwrap();
+        world.apply_edit(Edit::remove::<Name>(entity)).unwrap();
+        assert_eq!(4, world.resource::<EditHistory>().undo_len());
+        assert!(world.get::<Name>(entity).is_none());
+
+        assert!(world.undo_edit().unwrap());
+        assert_eq!(Some(&Name("a")), world.get::<Name>(entity));
+        assert!(world.undo_edit().unwrap());
+        assert_eq!(Some(&Position { x: 3.0, y: 4.0 }), world.get(entity));
+        assert!(world.undo_edit().unwrap());
+        assert!(world.get::<Name>(entity).is_none());
+        assert!(world.undo_edit().unwrap());
+        assert_eq!(Some(&Position { x: 1.0, y: 2.0 }), world.get(entity));
+        assert!(!world.undo_edit().unwrap());
+
+        assert!(world.redo_edit().unwrap());
+        assert!(world.redo_edit().unwrap());
+        assert_eq!(Some(&Name("a")), world.get::<Name>(entity));
+
+        // A new edit discards the steps that could be redone.
+        world
+            .apply_edit(Edit::set_field::<Position>(entity, "y", 0.0f32))
+            .unwrap();
+        assert!(!world.redo_edit().unwrap());
+        assert_eq!(Some(&Position { x: 3.0, y: 0.0 }), world.get(entity));
+    }
+
+    #[test]
+    fn undo_despawn_restores_hierarchy_and_identity() {
+        let mut world = world();
+        let root = world.spawn(Name("root")).id();
+        let parent = world.spawn((Name("parent"), ChildOf(root))).id();
+        let child = world.spawn((Name("child"), ChildOf(parent))).id();
+
+        let spawned = world.spawn_empty().id();
+        world
+            .apply_edit(Edit::spawn(
+                spawned,
+                [Box::new(Position { x: 0.0, y: 0.0 }) as Box<dyn PartialReflect>],
+            ))
+            .unwrap();
+        world.apply_edit(Edit::Despawn(parent)).unwrap();
+        assert!(world.get_entity(child).is_err());
+        assert!(world.entity(root).get::<Children>().is_none());
+
+        world.undo_edit().unwrap();
+        let history = world.resource::<EditHistory>();
+        let (new_parent, new_child) = (history.live_entity(parent), history.live_entity(child));
+        assert_ne!(parent, new_parent);
+        assert_eq!(Some(&Name("parent")), world.get::<Name>(new_parent));
+        assert_eq!(Some(&ChildOf(root)), world.get::<ChildOf>(new_parent));
+        assert_eq!(Some(&ChildOf(new_parent)), world.get::<ChildOf>(new_child));
+        assert_eq!(
+            &[new_parent][..],
+            &**world.entity(root).get::<Children>().unwrap()
+        );
+        assert_eq!(
+            &[new_child][..],
+            &**world.entity(new_parent).get::<Children>().unwrap()
+        );
+
+        // Edits made through the new id are recorded against the original one.
+        world
+            .apply_edit(Edit::insert(new_child, Position { x: 1.0, y: 1.0 }))
+            .unwrap();
+        world.undo_edit().unwrap();
+        world.undo_edit().unwrap();
+        world.undo_edit().unwrap();
+        assert!(world.get_entity(spawned).is_err());
+        world.redo_edit().unwrap();
+        world.redo_edit().unwrap();
+        world.redo_edit().unwrap();
+        let history = world.resource::<EditHistory>();
+        let new_child = history.live_entity(child);
+        assert!(world.get_entity(history.live_entity(spawned)).is_ok());
+        assert_eq!(Some(&Position { x: 1.0, y: 1.0 }), world.get(new_child));
+        assert_eq!(Some(&Name("child")), world.get(new_child));
+    }
+
+    #[test]
+    fn groups_are_undone_as_one_step() {
+        let mut world = world();
+        let entity = world.spawn(Position { x: 0.0, y: 0.0 }).id();
+
+        world.begin_edit_group();
+        world
+            .apply_edit(Edit::set_field::<Position>(entity, "x", 1.0f32))
+            .unwrap();
+        world.begin_edit_group();
+        world
+            .apply_edit(Edit::set_field::<Position>(entity, "y", 2.0f32))
+            .unwrap();
+        world.end_edit_group();
+        world.apply_edit(Edit::insert(entity, Name("a"))).unwrap();
+        world.end_edit_group();
+        assert_eq!(1, world.resource::<EditHistory>().undo_len());
+
+        world.undo_edit().unwrap();
+        assert_eq!(Some(&Position { x: 0.0, y: 0.0 }), world.get(entity));
+        assert!(world.get::<Name>(entity).is_none());
+
+        // A failing group is rolled back.
+        let result = world.apply_edit(Edit::Group(vec![
+            Edit::insert(entity, Name("b")),
+            Edit::set_field::<Position>(entity, "z", 1.0f32),
+        ]));
+        assert!(result.is_err());
+        assert!(world.get::<Name>(entity).is_none());
+        assert_eq!(0, world.resource::<EditHistory>().undo_len());
+        assert_eq!(1, world.resource::<EditHistory>().redo_len());
+    }
+
+    #[test]
+    fn edit_commands() {
+        use super::EditCommandsExt;
+
+        let mut world = world();
+        let entity = world.spawn(Position { x: 0.0, y: 0.0 }).id();
+
+        let mut commands = world.commands();
+        commands.apply_edit(Edit::insert(entity, Name("a")));
+        commands.apply_edit(Edit::insert(entity, Name("b")));
+        commands.undo_edit();
+        world.flush();
+        assert_eq!(Some(&Name("a")), world.get::<Name>(entity));
+
+        world.commands().redo_edit();
+        world.flush();
+        assert_eq!(Some(&Name("b")), world.get::<Name>(entity));
+    }
+}
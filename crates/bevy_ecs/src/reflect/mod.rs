@@ -13,6 +13,8 @@ use bevy_reflect::{
 
 mod bundle;
 mod component;
+mod component_history;
+mod edit_history;
 mod entity_commands;
 mod from_world;
 mod map_entities;
@@ -21,6 +23,8 @@ mod resource;
 use bevy_utils::prelude::DebugName;
 pub use bundle::{ReflectBundle, ReflectBundleFns};
 pub use component::{ReflectComponent, ReflectComponentFns};
+pub use component_history::{record_component_history, ComponentHistory, ComponentHistoryEntry};
+pub use edit_history::{Edit, EditCommandsExt, EditError, EditHistory, EntitySnapshot};
 pub use entity_commands::ReflectCommandExt;
 pub use from_world::{ReflectFromWorld, ReflectFromWorldFns};
 pub use map_entities::ReflectMapEntities;
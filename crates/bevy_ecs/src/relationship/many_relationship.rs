@@ -0,0 +1,518 @@
+use alloc::{format, vec::Vec};
+
+use bevy_utils::prelude::DebugName;
+use log::warn;
+
+use crate::{
+    component::{Component, Mutable},
+    entity::Entity,
+    lifecycle::HookContext,
+    relationship::{RelationshipHookMode, RelationshipSourceCollection},
+    world::{DeferredWorld, EntityWorldMut, World},
+};
+
+/// A [`Component`] on a "source" [`Entity`] that references any number of "target" entities, creating a
+/// many-to-many relationship between them.
+///
+/// This is the many-to-many counterpart of [`Relationship`](super::Relationship): every
+/// [`ManyRelationship`] has a corresponding [`ManyRelationshipTarget`], which is inserted on each
+/// target and contains the list of all sources that relate to it. The [`ManyRelationship`] is the
+/// source of truth, and component hooks keep both sides in sync when it is inserted, replaced or
+/// removed, or when either entity is despawned.
+///
+/// [`ManyRelationship`] components are immutable. Use [`EntityWorldMut::add_targets`] and
+/// [`EntityWorldMut::remove_targets`] (or their [`EntityCommands`](crate::system::EntityCommands)
+/// equivalents) to change the targets of an existing relationship.
+///
+/// ## Derive
+///
+/// Both traits are derived via the [`Component`] derive, using the same field rules as
+/// [`Relationship`](super::Relationship) and [`RelationshipTarget`](super::RelationshipTarget).
+/// Only the collection of the [`ManyRelationshipTarget`] needs to be private.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// #[many_relationship(relationship_target = TargetedBy)]
+/// pub struct Targeting(pub Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[many_relationship_target(relationship = Targeting)]
+/// pub struct TargetedBy(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// let a = world.spawn_empty().id();
+/// let b = world.spawn_empty().id();
+/// let turret = world.spawn(Targeting(vec![a, b])).id();
+/// let missile = world.spawn(Targeting(vec![a])).id();
+/// assert_eq!(world.get::<TargetedBy>(a).unwrap().0, [turret, missile]);
+/// assert_eq!(world.get::<TargetedBy>(b).unwrap().0, [turret]);
+/// ```
+///
+/// With `#[many_relationship_target(relationship = X, linked_spawn)]`, despawning a target also
+/// despawns the sources that are left without any other live target.
+pub trait ManyRelationship: Component + Sized {
+    /// The [`Component`] added to the "target" entities of this [`ManyRelationship`], which contains the list of
+    /// all "source" entities that relate to the "target".
+    type RelationshipTarget: ManyRelationshipTarget<Relationship = Self>;
+
+    /// The collection type that stores the "target" entities of this [`ManyRelationship`].
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`ManyRelationship::Collection`].
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns a mutable reference to the stored [`ManyRelationship::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyRelationship`] from the given [`ManyRelationship::Collection`].
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// Creates this [`ManyRelationship`] relating to the given targets.
+    fn from_targets(targets: impl IntoIterator<Item = Entity>) -> Self {
+        let mut collection = <Self::Collection as RelationshipSourceCollection>::new();
+        for target in targets {
+            if !collection.iter().any(|entity| entity == target) {
+                collection.add(target);
+            }
+        }
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the target entities of this relationship.
+    #[inline]
+    fn iter(&self) -> <Self::Collection as RelationshipSourceCollection>::SourceIter<'_> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of target entities of this relationship.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this relationship has no target.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+
+    /// The `on_insert` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    fn on_insert(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            caller,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            RelationshipHookMode::Skip => return,
+            RelationshipHookMode::RunIfNotLinked => {
+                if <Self::RelationshipTarget as ManyRelationshipTarget>::LINKED_SPAWN {
+                    return;
+                }
+            }
+        }
+        let targets = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        let mut invalid = Vec::new();
+        for target_entity in targets {
+            if target_entity == entity {
+                warn!(
+                    "{}The {} relationship on entity {entity:?} points to itself. The invalid target has been removed.",
+                    caller.map(|location| format!("{location}: ")).unwrap_or_default(),
+                    DebugName::type_name::<Self>(),
+                );
+                invalid.push(target_entity);
+                continue;
+            }
+            if let Ok(mut entity_commands) = world.commands().get_entity(target_entity) {
+                // Deferring is necessary for batch mode
+                entity_commands
+                    .entry::<Self::RelationshipTarget>()
+                    .and_modify(move |mut relationship_target| {
+                        let sources = relationship_target.collection_mut_risky();
+                        if !sources.iter().any(|source| source == entity) {
+                            sources.add(entity);
+                        }
+                    })
+                    .or_insert_with(move || {
+                        let mut sources = <<Self::RelationshipTarget as ManyRelationshipTarget>::Collection as RelationshipSourceCollection>::with_capacity(1);
+                        sources.add(entity);
+                        Self::RelationshipTarget::from_collection_risky(sources)
+                    });
+            } else {
+                warn!(
+                    "{}The {} relationship on entity {entity:?} relates to {target_entity:?}, which does not exist. The invalid target has been removed.",
+                    caller.map(|location| format!("{location}: ")).unwrap_or_default(),
+                    DebugName::type_name::<Self>(),
+                );
+                invalid.push(target_entity);
+            }
+        }
+        if !invalid.is_empty() {
+            world.commands().queue(move |world: &mut World| {
+                prune_targets::<Self>(world, entity, &invalid);
+            });
+        }
+    }
+
+    /// The `on_replace` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_replace(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            RelationshipHookMode::Skip => return,
+            RelationshipHookMode::RunIfNotLinked => {
+                if <Self::RelationshipTarget as ManyRelationshipTarget>::LINKED_SPAWN {
+                    return;
+                }
+            }
+        }
+        let targets = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        for target_entity in targets {
+            if let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity)
+                && let Some(mut relationship_target) =
+                    target_entity_mut.get_mut::<Self::RelationshipTarget>()
+            {
+                relationship_target.collection_mut_risky().remove(entity);
+                if relationship_target.is_empty() {
+                    let command = |mut entity: EntityWorldMut| {
+                        // this "remove" operation must check emptiness because in the event that an identical
+                        // relationship is inserted on top, this despawn would result in the removal of that identical
+                        // relationship ... not what we want!
+                        if entity
+                            .get::<Self::RelationshipTarget>()
+                            .is_some_and(ManyRelationshipTarget::is_empty)
+                        {
+                            entity.remove::<Self::RelationshipTarget>();
+                        }
+                    };
+
+                    world
+                        .commands()
+                        .entity(target_entity)
+                        .queue_silenced(command);
+                }
+            }
+        }
+    }
+}
+
+/// A [`Component`] containing the collection of entities that relate to this [`Entity`] via the associated
+/// [`ManyRelationship`] type. See the [`ManyRelationship`] documentation for more information.
+pub trait ManyRelationshipTarget: Component<Mutability = Mutable> + Sized {
+    /// If this is true, when a target is despawned, the source entities that are left without any other live target
+    /// are despawned too.
+    const LINKED_SPAWN: bool;
+    /// The [`ManyRelationship`] that populates this [`ManyRelationshipTarget`] collection.
+    type Relationship: ManyRelationship<RelationshipTarget = Self>;
+    /// The collection type that stores the "source" entities for this [`ManyRelationshipTarget`] component.
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`ManyRelationshipTarget::Collection`].
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns a mutable reference to the stored [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyRelationshipTarget`] from the given [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as constructing the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// Iterates the source entities relating to this entity.
+    #[inline]
+    fn iter(&self) -> <Self::Collection as RelationshipSourceCollection>::SourceIter<'_> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of source entities relating to this entity.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if no source entity relates to this entity.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+
+    /// The `on_replace` component hook that removes this entity from the targets of its sources.
+    ///
+    /// Sources left without any target lose their [`ManyRelationship`] component.
+    // note: think of this as "on_drop"
+    fn on_replace(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            RelationshipHookMode::Skip | RelationshipHookMode::RunIfNotLinked => return,
+        }
+        let sources = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        world.commands().queue(move |world: &mut World| {
+            for source in sources {
+                prune_targets::<Self::Relationship>(world, source, &[entity]);
+            }
+        });
+    }
+
+    /// The `on_despawn` component hook that despawns the sources of this entity which are left without any
+    /// other live target.
+    fn on_despawn(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let sources = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        world.commands().queue(move |world: &mut World| {
+            for source in sources {
+                let orphaned = world.get::<Self::Relationship>(source).is_some_and(|r| {
+                    r.iter()
+                        .all(|target| target == entity || world.get_entity(target).is_err())
+                });
+                if orphaned {
+                    world.try_despawn(source).ok();
+                }
+            }
+        });
+    }
+}
+
+/// Removes `targets` from the [`ManyRelationship`] `R` of `source` without running its hooks, removing the
+/// component if no target is left.
+fn prune_targets<R: ManyRelationship>(world: &mut World, source: Entity, targets: &[Entity]) {
+    let Ok(Some(is_empty)) = DeferredWorld::from(&mut *world)
+        .modify_component_with_relationship_hook_mode::<R, _>(
+            source,
+            RelationshipHookMode::Skip,
+            |relationship| {
+                let collection = relationship.collection_mut_risky();
+                for target in targets {
+                    collection.remove(*target);
+                }
+                collection.is_empty()
+            },
+        )
+    else {
+        return;
+    };
+    if is_empty {
+        // The collection is already empty, so the `on_replace` hook has nothing left to clean up.
+        world.entity_mut(source).remove::<R>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use alloc::{vec, vec::Vec};
+
+    use super::ManyRelationshipTarget;
+
+    #[derive(Component, Debug)]
+    #[many_relationship(relationship_target = MemberOf)]
+    struct HasMembers(Vec<Entity>);
+
+    #[derive(Component, Debug)]
+    #[many_relationship_target(relationship = HasMembers)]
+    struct MemberOf(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_relationship(relationship_target = TargetedBy)]
+    struct Targeting(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_relationship_target(relationship = Targeting, linked_spawn)]
+    struct TargetedBy(Vec<Entity>);
+
+    fn sources<T: ManyRelationshipTarget>(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<T>(entity)
+            .map(|target| target.iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn symmetric_bookkeeping() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let group_1 = world.spawn(HasMembers(vec![a, b])).id();
+        let group_2 = world.spawn(HasMembers(vec![a])).id();
+
+        assert_eq!(sources::<MemberOf>(&world, a), [group_1, group_2]);
+        assert_eq!(sources::<MemberOf>(&world, b), [group_1]);
+
+        // Replacing updates both sides.
+        world.entity_mut(group_1).insert(HasMembers(vec![b]));
+        assert_eq!(sources::<MemberOf>(&world, a), [group_2]);
+        assert_eq!(sources::<MemberOf>(&world, b), [group_1]);
+
+        // Removing the last source removes the target component.
+        world.entity_mut(group_2).remove::<HasMembers>();
+        assert!(!world.entity(a).contains::<MemberOf>());
+
+        world
+            .entity_mut(group_2)
+            .add_targets::<HasMembers>(&[a, b, a]);
+        assert_eq!(world.get::<HasMembers>(group_2).unwrap().0, [a, b]);
+        assert_eq!(sources::<MemberOf>(&world, b), [group_1, group_2]);
+
+        world.entity_mut(group_2).remove_targets::<HasMembers>(&[b]);
+        assert_eq!(world.get::<HasMembers>(group_2).unwrap().0, [a]);
+        assert_eq!(sources::<MemberOf>(&world, b), [group_1]);
+    }
+
+    #[test]
+    fn invalid_targets_are_dropped() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let dead = world.spawn_empty().id();
+        world.despawn(dead);
+        let group = world.spawn_empty().id();
+        world
+            .entity_mut(group)
+            .insert(HasMembers(vec![a, dead, group]));
+
+        assert_eq!(world.get::<HasMembers>(group).unwrap().0, [a]);
+        assert_eq!(sources::<MemberOf>(&world, a), [group]);
+        assert!(!world.entity(group).contains::<MemberOf>());
+    }
+
+    #[test]
+    fn despawn_cleans_up_both_sides() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let group = world.spawn(HasMembers(vec![a, b])).id();
+        let lonely_group = world.spawn(HasMembers(vec![a])).id();
+
+        world.despawn(a);
+        assert_eq!(world.get::<HasMembers>(group).unwrap().0, [b]);
+        // Without `linked_spawn`, sources left without targets survive but lose the relationship.
+        assert!(!world.entity(lonely_group).contains::<HasMembers>());
+
+        world.despawn(group);
+        assert!(!world.entity(b).contains::<MemberOf>());
+    }
+
+    #[test]
+    fn linked_spawn_despawns_orphaned_sources() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let both = world.spawn(Targeting(vec![a, b])).id();
+        let only_a = world.spawn(Targeting(vec![a])).id();
+
+        world.despawn(a);
+        assert!(world.get_entity(only_a).is_err());
+        assert_eq!(world.get::<Targeting>(both).unwrap().0, [b]);
+        assert_eq!(sources::<TargetedBy>(&world, b), [both]);
+
+        world.despawn(b);
+        assert!(world.get_entity(both).is_err());
+    }
+
+    #[test]
+    fn add_and_remove_targets_commands() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let group = world.spawn_empty().id();
+
+        world
+            .commands()
+            .entity(group)
+            .add_targets::<HasMembers>(&[a, b]);
+        world.flush();
+        assert_eq!(sources::<MemberOf>(&world, b), [group]);
+
+        world
+            .commands()
+            .entity(group)
+            .remove_targets::<HasMembers>(&[a, b]);
+        world.flush();
+        assert!(!world.entity(group).contains::<HasMembers>());
+        assert!(!world.entity(a).contains::<MemberOf>());
+        assert!(!world.entity(b).contains::<MemberOf>());
+    }
+
+    #[test]
+    fn reachable_traversal_handles_cycles() {
+        let mut world = World::new();
+        let [a, b, c, d] = core::array::from_fn(|_| world.spawn_empty().id());
+        world.entity_mut(a).insert(HasMembers(vec![b, c]));
+        world.entity_mut(b).insert(HasMembers(vec![c, a]));
+        world.entity_mut(c).insert(HasMembers(vec![d]));
+
+        let mut query = world.query::<&HasMembers>();
+        let query = query.query(&world);
+        assert_eq!(
+            query.many_related::<HasMembers>(b).collect::<Vec<_>>(),
+            [c, a]
+        );
+        assert_eq!(
+            query
+                .iter_reachable_targets::<HasMembers>(a)
+                .collect::<Vec<_>>(),
+            [b, c, d]
+        );
+        assert_eq!(query.iter_reachable_targets::<HasMembers>(d).count(), 0);
+
+        let mut query = world.query::<&MemberOf>();
+        let query = query.query(&world);
+        assert_eq!(
+            query
+                .many_relationship_sources::<MemberOf>(c)
+                .collect::<Vec<_>>(),
+            [a, b]
+        );
+        assert_eq!(
+            query
+                .iter_reachable_sources::<MemberOf>(d)
+                .collect::<Vec<_>>(),
+            [c, a, b]
+        );
+    }
+}
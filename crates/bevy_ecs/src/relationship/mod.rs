@@ -1,5 +1,6 @@
 //! This module provides functionality to link entities to each other using specialized components called "relationships". See the [`Relationship`] trait for more info.
 
+mod many_relationship;
 mod related_methods;
 mod relationship_query;
 mod relationship_source_collection;
@@ -9,6 +10,7 @@ use core::marker::PhantomData;
 use alloc::format;
 
 use bevy_utils::prelude::DebugName;
+pub use many_relationship::*;
 pub use related_methods::*;
 pub use relationship_query::*;
 pub use relationship_source_collection::*;
@@ -3,7 +3,8 @@ use crate::{
     entity::{hash_set::EntityHashSet, Entity},
     prelude::Children,
     relationship::{
-        Relationship, RelationshipHookMode, RelationshipSourceCollection, RelationshipTarget,
+        ManyRelationship, Relationship, RelationshipHookMode, RelationshipSourceCollection,
+        RelationshipTarget,
     },
     system::{Commands, EntityCommands},
     world::{DeferredWorld, EntityWorldMut, World},
@@ -405,6 +406,46 @@ impl<'w> EntityWorldMut<'w> {
 
         self.insert_with_relationship_hook_mode(R::from(entity), relationship_hook_mode);
     }
+
+    /// Adds the given entities to the targets of this entity's [`ManyRelationship`] `R`, inserting it if needed.
+    ///
+    /// Entities that are already targets are ignored.
+    pub fn add_targets<R: ManyRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        let current = self
+            .get::<R>()
+            .map(|relationship| relationship.iter().collect::<Vec<_>>())
+            .unwrap_or_default();
+        if targets.iter().all(|target| current.contains(target)) {
+            return self;
+        }
+        self.insert(R::from_targets(
+            current.into_iter().chain(targets.iter().copied()),
+        ))
+    }
+
+    /// Removes the given entities from the targets of this entity's [`ManyRelationship`] `R`.
+    ///
+    /// The component is removed once it has no target left.
+    pub fn remove_targets<R: ManyRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        let Some(current) = self
+            .get::<R>()
+            .map(|relationship| relationship.iter().collect::<Vec<_>>())
+        else {
+            return self;
+        };
+        if !targets.iter().any(|target| current.contains(target)) {
+            return self;
+        }
+        let remaining = current
+            .into_iter()
+            .filter(|target| !targets.contains(target))
+            .collect::<Vec<_>>();
+        if remaining.is_empty() {
+            self.remove::<R>()
+        } else {
+            self.insert(R::from_targets(remaining))
+        }
+    }
 }
 
 impl<'a> EntityCommands<'a> {
@@ -558,6 +599,28 @@ impl<'a> EntityCommands<'a> {
             entity.remove_recursive::<S, B>();
         })
     }
+
+    /// Adds the given entities to the targets of this entity's [`ManyRelationship`] `R`, inserting it if needed.
+    ///
+    /// Entities that are already targets are ignored.
+    pub fn add_targets<R: ManyRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        let targets: Box<[Entity]> = targets.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_targets::<R>(&targets);
+        })
+    }
+
+    /// Removes the given entities from the targets of this entity's [`ManyRelationship`] `R`.
+    ///
+    /// The component is removed once it has no target left.
+    pub fn remove_targets<R: ManyRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        let targets: Box<[Entity]> = targets.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_targets::<R>(&targets);
+        })
+    }
 }
 
 /// Directly spawns related "source" entities with the given [`Relationship`], targeting
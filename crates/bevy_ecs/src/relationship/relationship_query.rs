@@ -1,7 +1,8 @@
 use crate::{
-    entity::Entity,
+    component::Component,
+    entity::{Entity, EntityHashSet},
     query::{QueryData, QueryFilter},
-    relationship::{Relationship, RelationshipTarget},
+    relationship::{ManyRelationship, ManyRelationshipTarget, Relationship, RelationshipTarget},
     system::Query,
 };
 use alloc::collections::VecDeque;
@@ -141,6 +142,68 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     {
         AncestorIter::new(self, entity)
     }
+
+    /// If the given `entity` contains the `R` [`ManyRelationship`] component, returns the
+    /// target entities of that relationship.
+    pub fn many_related<R: ManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyRelationship::iter)
+    }
+
+    /// If the given `entity` contains the `S` [`ManyRelationshipTarget`] component, returns the
+    /// source entities stored on that component.
+    pub fn many_relationship_sources<S: ManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyRelationshipTarget::iter)
+    }
+
+    /// Iterates all entities reachable from the given `entity` by following the targets of the
+    /// `R` [`ManyRelationship`], in breadth-first order.
+    ///
+    /// Each entity is yielded at most once, so this terminates even if the relationship graph contains loops.
+    /// The given `entity` itself is never yielded.
+    pub fn iter_reachable_targets<R: ManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> ReachableIter<'w, 's, D, F, R>
+    where
+        D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+    {
+        ReachableIter::new(self, entity, |relationship, next| {
+            next.extend(relationship.iter());
+        })
+    }
+
+    /// Iterates all entities that can reach the given `entity` by following the targets of their
+    /// [`ManyRelationship`], as recorded in the `S` [`ManyRelationshipTarget`], in breadth-first order.
+    ///
+    /// Each entity is yielded at most once, so this terminates even if the relationship graph contains loops.
+    /// The given `entity` itself is never yielded.
+    pub fn iter_reachable_sources<S: ManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> ReachableIter<'w, 's, D, F, S>
+    where
+        D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+    {
+        ReachableIter::new(self, entity, |relationship_target, next| {
+            next.extend(relationship_target.iter());
+        })
+    }
 }
 
 /// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`].
@@ -270,3 +333,61 @@ where
         self.next
     }
 }
+
+/// An [`Iterator`] of [`Entity`]s over the entities reachable from an [`Entity`] through a many-to-many
+/// relationship, stored in the `C` component.
+///
+/// Traverses the graph breadth-first, skipping entities that were already visited.
+pub struct ReachableIter<'w, 's, D: QueryData, F: QueryFilter, C: Component>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w C>,
+{
+    query: &'w Query<'w, 's, D, F>,
+    edges: fn(&C, &mut VecDeque<Entity>),
+    vecdeque: VecDeque<Entity>,
+    visited: EntityHashSet,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, C: Component> ReachableIter<'w, 's, D, F, C>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w C>,
+{
+    /// Returns a new [`ReachableIter`], where `edges` pushes the entities `C` points to.
+    pub fn new(
+        query: &'w Query<'w, 's, D, F>,
+        entity: Entity,
+        edges: fn(&C, &mut VecDeque<Entity>),
+    ) -> Self {
+        let mut iter = ReachableIter {
+            query,
+            edges,
+            vecdeque: VecDeque::new(),
+            visited: EntityHashSet::from_iter([entity]),
+        };
+        iter.push_edges(entity);
+        iter
+    }
+
+    fn push_edges(&mut self, entity: Entity) {
+        if let Ok(component) = self.query.get(entity) {
+            (self.edges)(component, &mut self.vecdeque);
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, C: Component> Iterator for ReachableIter<'w, 's, D, F, C>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w C>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entity = self.vecdeque.pop_front()?;
+            if self.visited.insert(entity) {
+                self.push_edges(entity);
+                return Some(entity);
+            }
+        }
+    }
+}
@@ -21,8 +21,8 @@ use crate::{
     prelude::{IntoSystemSet, SystemSet},
     query::FilteredAccessSet,
     schedule::{
-        ConditionWithAccess, InternedSystemSet, SystemKey, SystemSetKey, SystemTypeSet,
-        SystemWithAccess,
+        ConditionWithAccess, InternedSystemSet, ScheduleTimer, SystemKey, SystemSetKey,
+        SystemTypeSet, SystemWithAccess,
     },
     system::{RunSystemError, System, SystemIn, SystemParamValidationError, SystemStateFlags},
     world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
@@ -38,8 +38,10 @@ pub(super) trait SystemExecutor: Send + Sync {
         world: &mut World,
         skip_systems: Option<&FixedBitSet>,
         error_handler: fn(BevyError, ErrorContext),
+        timer: Option<&ScheduleTimer>,
     );
     fn set_apply_final_deferred(&mut self, value: bool);
+    fn set_deterministic(&mut self, value: bool);
 }
 
 /// Specifies how a [`Schedule`](super::Schedule) will be run.
@@ -90,10 +92,6 @@ pub struct SystemSchedule {
     pub(super) system_dependencies: Vec<usize>,
     /// Indexed by system node id.
     /// List of systems that immediately depend on the system.
-    #[cfg_attr(
-        not(feature = "std"),
-        expect(dead_code, reason = "currently only used with the std feature")
-    )]
     pub(super) system_dependents: Vec<Vec<usize>>,
     /// Indexed by system node id.
     /// List of sets containing the system that have conditions
@@ -316,8 +314,8 @@ mod __rust_begin_short_backtrace {
 #[cfg(test)]
 mod tests {
     use crate::{
-        prelude::{Component, In, IntoSystem, Resource, Schedule},
-        schedule::ExecutorKind,
+        prelude::{Component, In, IntoScheduleConfigs, IntoSystem, Resource, Schedule},
+        schedule::{ExecutorKind, SystemTimings},
         system::{Populated, Res, ResMut, Single},
         world::World,
     };
@@ -379,6 +377,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn system_timings_recorded() {
+        fn exclusive(_: &mut World) {}
+
+        for executor in EXECUTORS {
+            let mut world = World::new();
+            world.init_resource::<SystemTimings>();
+
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+            schedule.add_systems((set_single_state, exclusive, (|| {}).run_if(|| false)));
+            schedule.run(&mut world);
+            schedule.run(&mut world);
+
+            let timings = world.resource::<SystemTimings>().drain();
+            // Two runs of the schedule and of `exclusive`, as the other systems are skipped.
+            assert_eq!(4, timings.len(), "{executor:?}");
+            assert_eq!(
+                2,
+                timings
+                    .iter()
+                    .filter(|timing| timing.system.is_none())
+                    .count()
+            );
+            assert!(timings
+                .iter()
+                .all(|timing| timing.schedule == schedule.label()));
+            assert!(world.resource::<SystemTimings>().drain().is_empty());
+
+            // Clones share their timings, so this sees whatever would be recorded in the resource.
+            let timings = world.remove_resource::<SystemTimings>().unwrap();
+            schedule.run(&mut world);
+            assert!(timings.drain().is_empty(), "{executor:?}");
+        }
+    }
+
     fn look_for_missing_resource(_res: Res<TestState>) {}
 
     #[test]
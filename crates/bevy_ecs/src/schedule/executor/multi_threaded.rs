@@ -1,6 +1,6 @@
 use alloc::{boxed::Box, vec::Vec};
 use bevy_platform::cell::SyncUnsafeCell;
-use bevy_platform::sync::Arc;
+use bevy_platform::{sync::Arc, time::Instant};
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
 use concurrent_queue::ConcurrentQueue;
 use core::{any::Any, panic::AssertUnwindSafe};
@@ -16,8 +16,8 @@ use crate::{
     error::{ErrorContext, ErrorHandler, Result},
     prelude::Resource,
     schedule::{
-        is_apply_deferred, ConditionWithAccess, ExecutorKind, SystemExecutor, SystemSchedule,
-        SystemWithAccess,
+        is_apply_deferred, ConditionWithAccess, ExecutorKind, ScheduleTimer, SystemExecutor,
+        SystemSchedule, SystemWithAccess,
     },
     system::{RunSystemError, ScheduleSystem},
     world::{unsafe_world_cell::UnsafeWorldCell, World},
@@ -33,6 +33,7 @@ struct Environment<'env, 'sys> {
     systems: &'sys [SyncUnsafeCell<SystemWithAccess>],
     conditions: SyncUnsafeCell<Conditions<'sys>>,
     world_cell: UnsafeWorldCell<'env>,
+    timer: Option<&'env ScheduleTimer>,
 }
 
 struct Conditions<'a> {
@@ -47,6 +48,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
         executor: &'env MultiThreadedExecutor,
         schedule: &'sys mut SystemSchedule,
         world: &'env mut World,
+        timer: Option<&'env ScheduleTimer>,
     ) -> Self {
         Environment {
             executor,
@@ -58,6 +60,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
                 systems_in_sets_with_conditions: &schedule.systems_in_sets_with_conditions,
             }),
             world_cell: world.as_unsafe_world_cell(),
+            timer,
         }
     }
 }
@@ -78,6 +81,11 @@ struct SystemTaskMetadata {
     is_send: bool,
     /// Is `true` if the system is exclusive.
     is_exclusive: bool,
+    /// The systems before this one that must complete before it can start in deterministic mode.
+    deterministic_predecessors: FixedBitSet,
+    /// The sets containing systems before this one, but not this one, whose conditions must be
+    /// evaluated before it can start in deterministic mode.
+    deterministic_blocking_sets: FixedBitSet,
 }
 
 /// The result of running a system that is sent across a channel.
@@ -93,6 +101,8 @@ pub struct MultiThreadedExecutor {
     system_completion: ConcurrentQueue<SystemResult>,
     /// Setting when true applies deferred system buffers after all systems have run
     apply_final_deferred: bool,
+    /// Setting when true makes systems that may affect each other run in topological order
+    deterministic: bool,
     /// When set, tells the executor that a thread has panicked.
     panic_payload: Mutex<Option<Box<dyn Any + Send>>>,
     starting_systems: FixedBitSet,
@@ -107,6 +117,11 @@ pub struct ExecutorState {
     system_task_metadata: Vec<SystemTaskMetadata>,
     /// The set of systems whose `component_access_set()` conflicts with this system set's conditions.
     set_condition_conflicting_systems: Vec<FixedBitSet>,
+    /// Returns `true` if systems that may affect each other run in topological order.
+    deterministic: bool,
+    /// The systems that must complete before this system set's conditions can be evaluated
+    /// in deterministic mode.
+    set_deterministic_predecessors: Vec<FixedBitSet>,
     /// Returns `true` if a system with non-`Send` access is running.
     local_thread_running: bool,
     /// Returns `true` if an exclusive system is running.
@@ -177,6 +192,8 @@ impl SystemExecutor for MultiThreadedExecutor {
                 dependents: schedule.system_dependents[index].clone(),
                 is_send: schedule.systems[index].system.is_send(),
                 is_exclusive: schedule.systems[index].system.is_exclusive(),
+                deterministic_predecessors: FixedBitSet::new(),
+                deterministic_blocking_sets: FixedBitSet::new(),
             });
             if schedule.system_dependencies[index] == 0 {
                 self.starting_systems.insert(index);
@@ -232,6 +249,14 @@ impl SystemExecutor for MultiThreadedExecutor {
             }
         }
 
+        state.deterministic = self.deterministic;
+        state.set_deterministic_predecessors.clear();
+        if self.deterministic {
+            #[cfg(feature = "trace")]
+            let _span = info_span!("calculate deterministic predecessors").entered();
+            state.calculate_deterministic_predecessors(schedule);
+        }
+
         state.num_dependencies_remaining = Vec::with_capacity(sys_count);
     }
 
@@ -241,6 +266,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         world: &mut World,
         _skip_systems: Option<&FixedBitSet>,
         error_handler: ErrorHandler,
+        timer: Option<&ScheduleTimer>,
     ) {
         let state = self.state.get_mut().unwrap();
         // reset counts
@@ -274,7 +300,7 @@ impl SystemExecutor for MultiThreadedExecutor {
             .map(|e| e.0.clone());
         let thread_executor = thread_executor.as_deref();
 
-        let environment = &Environment::new(self, schedule, world);
+        let environment = &Environment::new(self, schedule, world, timer);
 
         ComputeTaskPool::get_or_init(TaskPool::default).scope_with_executor(
             false,
@@ -323,6 +349,10 @@ impl SystemExecutor for MultiThreadedExecutor {
     fn set_apply_final_deferred(&mut self, value: bool) {
         self.apply_final_deferred = value;
     }
+
+    fn set_deterministic(&mut self, value: bool) {
+        self.deterministic = value;
+    }
 }
 
 impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
@@ -395,6 +425,7 @@ impl MultiThreadedExecutor {
             system_completion: ConcurrentQueue::unbounded(),
             starting_systems: FixedBitSet::new(),
             apply_final_deferred: true,
+            deterministic: false,
             panic_payload: Mutex::new(None),
             #[cfg(feature = "trace")]
             executor_span: info_span!("multithreaded executor"),
@@ -407,6 +438,8 @@ impl ExecutorState {
         Self {
             system_task_metadata: Vec::new(),
             set_condition_conflicting_systems: Vec::new(),
+            deterministic: false,
+            set_deterministic_predecessors: Vec::new(),
             num_running_systems: 0,
             num_dependencies_remaining: Vec::new(),
             local_thread_running: false,
@@ -421,6 +454,61 @@ impl ExecutorState {
         }
     }
 
+    /// Computes which systems must complete before each system can start, and before the conditions
+    /// of each system set can be evaluated, so that any two systems or conditions that may affect
+    /// each other run in topological order, as they would with a single-threaded executor.
+    ///
+    /// Two systems may affect each other if either is exclusive, if their accesses or the accesses
+    /// of their conditions conflict, or if they both have deferred buffers, as those reserve entities
+    /// while running and are applied in order.
+    fn calculate_deterministic_predecessors(&mut self, schedule: &SystemSchedule) {
+        let sys_count = schedule.systems.len();
+        for index1 in 0..sys_count {
+            let system1 = &schedule.systems[index1].system;
+            let mut predecessors = FixedBitSet::with_capacity(sys_count);
+            for index2 in 0..index1 {
+                let system2 = &schedule.systems[index2].system;
+                let meta1 = &self.system_task_metadata[index1];
+                let meta2 = &self.system_task_metadata[index2];
+                if meta1.is_exclusive
+                    || meta2.is_exclusive
+                    || (system1.has_deferred() && system2.has_deferred())
+                    || meta1.conflicting_systems.contains(index2)
+                    || meta1.condition_conflicting_systems.contains(index2)
+                    || meta2.condition_conflicting_systems.contains(index1)
+                {
+                    predecessors.insert(index2);
+                }
+            }
+            self.system_task_metadata[index1].deterministic_predecessors = predecessors;
+        }
+
+        let set_count = schedule.set_ids.len();
+        self.set_deterministic_predecessors.reserve(set_count);
+        for set_idx in 0..set_count {
+            let mut predecessors = FixedBitSet::with_capacity(sys_count);
+            let first_system = schedule.systems_in_sets_with_conditions[set_idx]
+                .minimum()
+                .unwrap_or(sys_count);
+            for sys_index in 0..sys_count {
+                let conflicts = self.system_task_metadata[sys_index].is_exclusive
+                    || self.set_condition_conflicting_systems[set_idx].contains(sys_index);
+                if !conflicts {
+                    continue;
+                }
+                if sys_index < first_system {
+                    predecessors.insert(sys_index);
+                } else if !schedule.systems_in_sets_with_conditions[set_idx].contains(sys_index) {
+                    let blocking_sets =
+                        &mut self.system_task_metadata[sys_index].deterministic_blocking_sets;
+                    blocking_sets.grow(set_count);
+                    blocking_sets.insert(set_idx);
+                }
+            }
+            self.set_deterministic_predecessors.push(predecessors);
+        }
+    }
+
     fn tick(&mut self, context: &Context, conditions: &mut Conditions) {
         #[cfg(feature = "trace")]
         let _span = context.environment.executor.executor_span.enter();
@@ -546,6 +634,10 @@ impl ExecutorState {
             return false;
         }
 
+        if self.deterministic && !self.can_run_deterministically(system_index, conditions) {
+            return false;
+        }
+
         // TODO: an earlier out if world's archetypes did not change
         for set_idx in conditions.sets_with_conditions_of_systems[system_index]
             .difference(&self.evaluated_sets)
@@ -573,6 +665,43 @@ impl ExecutorState {
         true
     }
 
+    /// Returns `true` if every system and system set condition that must precede the system
+    /// in deterministic mode has run.
+    fn can_run_deterministically(&self, system_index: usize, conditions: &Conditions) -> bool {
+        let system_meta = &self.system_task_metadata[system_index];
+        if !system_meta
+            .deterministic_predecessors
+            .is_subset(&self.completed_systems)
+        {
+            return false;
+        }
+
+        for set_idx in conditions.sets_with_conditions_of_systems[system_index]
+            .difference(&self.evaluated_sets)
+        {
+            if !self.set_deterministic_predecessors[set_idx].is_subset(&self.completed_systems) {
+                return false;
+            }
+        }
+
+        // The conditions of a set are evaluated by one of its systems, so a set whose systems
+        // before this one have all completed has been evaluated, unless they were skipped by stepping.
+        for set_idx in system_meta
+            .deterministic_blocking_sets
+            .difference(&self.evaluated_sets)
+        {
+            if conditions.systems_in_sets_with_conditions[set_idx]
+                .ones()
+                .take_while(|&index| index < system_index)
+                .any(|index| !self.completed_systems.contains(index))
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
     /// # Safety
     /// * `world` must have permission to read any world data required by
     ///   the system's conditions: this includes conditions for the system
@@ -675,26 +804,28 @@ impl ExecutorState {
 
         let task = async move {
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                let started = context.environment.timer.map(|_| Instant::now());
                 // SAFETY:
                 // - The caller ensures that we have permission to
                 // access the world data used by the system.
                 // - `is_exclusive` returned false
-                unsafe {
-                    if let Err(RunSystemError::Failed(err)) =
-                        __rust_begin_short_backtrace::run_unsafe(
-                            system,
-                            context.environment.world_cell,
-                        )
-                    {
-                        (context.error_handler)(
-                            err,
-                            ErrorContext::System {
-                                name: system.name(),
-                                last_run: system.get_last_run(),
-                            },
-                        );
-                    }
+                let result = unsafe {
+                    __rust_begin_short_backtrace::run_unsafe(system, context.environment.world_cell)
                 };
+                if let (Some(timer), Some(started)) = (context.environment.timer, started)
+                    && !matches!(result, Err(RunSystemError::Skipped(_)))
+                {
+                    timer.record_system(&**system, started);
+                }
+                if let Err(RunSystemError::Failed(err)) = result {
+                    (context.error_handler)(
+                        err,
+                        ErrorContext::System {
+                            name: system.name(),
+                            last_run: system.get_last_run(),
+                        },
+                    );
+                }
             }));
             context.system_completed(system_index, res, system);
         };
@@ -734,9 +865,14 @@ impl ExecutorState {
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
-                    if let Err(RunSystemError::Failed(err)) =
-                        __rust_begin_short_backtrace::run(system, world)
+                    let started = context.environment.timer.map(|_| Instant::now());
+                    let result = __rust_begin_short_backtrace::run(system, world);
+                    if let (Some(timer), Some(started)) = (context.environment.timer, started)
+                        && !matches!(result, Err(RunSystemError::Skipped(_)))
                     {
+                        timer.record_system(&**system, started);
+                    }
+                    if let Err(RunSystemError::Failed(err)) = result {
                         (context.error_handler)(
                             err,
                             ErrorContext::System {
@@ -912,6 +1048,124 @@ mod tests {
         assert!(world.get_resource::<R>().is_some());
     }
 
+    mod determinism {
+        use alloc::vec::Vec;
+        use core::time::Duration;
+
+        use crate::{
+            entity::Entity,
+            event::{BufferedEvent, EventWriter, Events},
+            prelude::{Component, Query, Res, ResMut, Resource, SystemSet},
+            schedule::{ExecutorKind, IntoScheduleConfigs, Schedule},
+            system::Commands,
+            world::World,
+        };
+
+        #[derive(Component)]
+        struct Value(usize);
+
+        #[derive(Resource, Default)]
+        struct Log(Vec<usize>);
+
+        #[derive(BufferedEvent)]
+        struct Written(usize);
+
+        #[derive(SystemSet, Hash, PartialEq, Eq, Debug, Clone)]
+        struct Conditioned;
+
+        /// What the systems of [`build_schedule`] left in the world.
+        type WorldState = (Vec<(Entity, usize)>, Vec<usize>, Vec<usize>);
+
+        /// Waits for a duration that depends on both the system and the run,
+        /// so that systems complete in a different order on every run.
+        fn wait(system: usize, run: usize) {
+            std::thread::sleep(Duration::from_micros(
+                (system * 7 + run * 3) as u64 % 5 * 100,
+            ));
+        }
+
+        /// Builds a schedule of mutually ambiguous systems which spawn entities,
+        /// queue commands, write events and mutate resources.
+        fn build_schedule(run: usize) -> Schedule {
+            let mut schedule = Schedule::default();
+            for system in 0..8 {
+                schedule.add_systems(move |mut commands: Commands| {
+                    wait(system, run);
+                    commands.spawn(Value(system));
+                    commands.queue(move |world: &mut World| {
+                        world.resource_mut::<Log>().0.push(system);
+                    });
+                });
+                schedule.add_systems(move |mut events: EventWriter<Written>| {
+                    wait(system + 8, run);
+                    events.write(Written(system));
+                });
+                schedule.add_systems(
+                    (move |mut log: ResMut<Log>| {
+                        wait(system + 16, run);
+                        log.0.push(system + 100);
+                    })
+                    .run_if(move |log: Res<Log>| log.0.len() % 3 != system % 3),
+                );
+                schedule.add_systems(
+                    (
+                        move |mut query: Query<&mut Value>| {
+                            wait(system + 24, run);
+                            for mut value in &mut query {
+                                value.0 = value.0 * 3 + system;
+                            }
+                        },
+                        move |mut commands: Commands| {
+                            commands.spawn(Value(system + 200));
+                        },
+                    )
+                        .chain()
+                        .in_set(Conditioned),
+                );
+            }
+            schedule.configure_sets(Conditioned.run_if(|log: Res<Log>| log.0.len() % 2 == 0));
+            schedule
+        }
+
+        /// Runs the schedule built by [`build_schedule`] a few times on a new world
+        /// and returns what it left in the world.
+        fn run_schedule(kind: ExecutorKind, run: usize) -> WorldState {
+            let mut world = World::new();
+            world.init_resource::<Log>();
+            world.init_resource::<Events<Written>>();
+            let mut schedule = build_schedule(run);
+            // Set before the executor kind, which must keep it.
+            schedule.set_deterministic(true).set_executor_kind(kind);
+            for _ in 0..3 {
+                schedule.run(&mut world);
+            }
+
+            let entities = world
+                .query::<(Entity, &Value)>()
+                .iter(&world)
+                .map(|(entity, value)| (entity, value.0))
+                .collect();
+            let log = world.resource::<Log>().0.clone();
+            let events = world
+                .resource_mut::<Events<Written>>()
+                .drain()
+                .map(|Written(system)| system)
+                .collect();
+            (entities, log, events)
+        }
+
+        #[test]
+        fn deterministic_world_state() {
+            let expected = run_schedule(ExecutorKind::SingleThreaded, 0);
+            for run in 0..10 {
+                assert!(
+                    run_schedule(ExecutorKind::MultiThreaded, run) == expected,
+                    "run {run} of the deterministic multithreaded executor diverged"
+                );
+            }
+        }
+    }
+
     /// Regression test for a weird bug flagged by MIRI in
     /// `spawn_exclusive_system_task`, related to a `&mut World` being captured
     /// inside an `async` block and somehow remaining alive even after its last use.
@@ -1,5 +1,6 @@
 #![expect(deprecated, reason = "Everything here is deprecated")]
 
+use bevy_platform::time::Instant;
 use core::panic::AssertUnwindSafe;
 use fixedbitset::FixedBitSet;
 
@@ -14,8 +15,8 @@ use crate::{change_detection::DetectChanges, HotPatchChanges};
 use crate::{
     error::{ErrorContext, ErrorHandler},
     schedule::{
-        executor::is_apply_deferred, ConditionWithAccess, ExecutorKind, SystemExecutor,
-        SystemSchedule,
+        executor::is_apply_deferred, ConditionWithAccess, ExecutorKind, ScheduleTimer,
+        SystemExecutor, SystemSchedule,
     },
     system::RunSystemError,
     world::World,
@@ -55,6 +56,7 @@ impl SystemExecutor for SimpleExecutor {
         world: &mut World,
         _skip_systems: Option<&FixedBitSet>,
         error_handler: ErrorHandler,
+        timer: Option<&ScheduleTimer>,
     ) {
         // If stepping is enabled, make sure we skip those systems that should
         // not be run.
@@ -129,9 +131,14 @@ impl SystemExecutor for SimpleExecutor {
             }
 
             let f = AssertUnwindSafe(|| {
-                if let Err(RunSystemError::Failed(err)) =
-                    __rust_begin_short_backtrace::run(system, world)
+                let started = timer.map(|_| Instant::now());
+                let result = __rust_begin_short_backtrace::run(system, world);
+                if let (Some(timer), Some(started)) = (timer, started)
+                    && !matches!(result, Err(RunSystemError::Skipped(_)))
                 {
+                    timer.record_system(&**system, started);
+                }
+                if let Err(RunSystemError::Failed(err)) = result {
                     error_handler(
                         err,
                         ErrorContext::System {
@@ -164,6 +171,10 @@ impl SystemExecutor for SimpleExecutor {
     fn set_apply_final_deferred(&mut self, _: bool) {
         // do nothing. simple executor does not do a final sync
     }
+
+    fn set_deterministic(&mut self, _: bool) {
+        // do nothing. simple executor always runs systems in order
+    }
 }
 
 impl SimpleExecutor {
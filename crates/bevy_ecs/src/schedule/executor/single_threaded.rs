@@ -1,3 +1,4 @@
+use bevy_platform::time::Instant;
 use core::panic::AssertUnwindSafe;
 use fixedbitset::FixedBitSet;
 
@@ -10,7 +11,8 @@ use std::eprintln;
 use crate::{
     error::{ErrorContext, ErrorHandler},
     schedule::{
-        is_apply_deferred, ConditionWithAccess, ExecutorKind, SystemExecutor, SystemSchedule,
+        is_apply_deferred, ConditionWithAccess, ExecutorKind, ScheduleTimer, SystemExecutor,
+        SystemSchedule,
     },
     system::RunSystemError,
     world::World,
@@ -57,6 +59,7 @@ impl SystemExecutor for SingleThreadedExecutor {
         world: &mut World,
         _skip_systems: Option<&FixedBitSet>,
         error_handler: ErrorHandler,
+        timer: Option<&ScheduleTimer>,
     ) {
         // If stepping is enabled, make sure we skip those systems that should
         // not be run.
@@ -132,9 +135,15 @@ impl SystemExecutor for SingleThreadedExecutor {
             }
 
             let f = AssertUnwindSafe(|| {
-                if let Err(RunSystemError::Failed(err)) =
-                    __rust_begin_short_backtrace::run_without_applying_deferred(system, world)
+                let started = timer.map(|_| Instant::now());
+                let result =
+                    __rust_begin_short_backtrace::run_without_applying_deferred(system, world);
+                if let (Some(timer), Some(started)) = (timer, started)
+                    && !matches!(result, Err(RunSystemError::Skipped(_)))
                 {
+                    timer.record_system(&**system, started);
+                }
+                if let Err(RunSystemError::Failed(err)) = result {
                     error_handler(
                         err,
                         ErrorContext::System {
@@ -172,6 +181,10 @@ impl SystemExecutor for SingleThreadedExecutor {
     fn set_apply_final_deferred(&mut self, apply_final_deferred: bool) {
         self.apply_final_deferred = apply_final_deferred;
     }
+
+    fn set_deterministic(&mut self, _: bool) {
+        // do nothing. single-threaded executor always runs systems in order
+    }
 }
 
 impl SingleThreadedExecutor {
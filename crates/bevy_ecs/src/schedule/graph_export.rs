@@ -0,0 +1,410 @@
+//! Exports [`Schedule`] graphs to text formats that can be rendered by external tools.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Write};
+
+use bevy_platform::collections::HashMap;
+use smallvec::SmallVec;
+
+use crate::schedule::{
+    graph::Direction::Outgoing, is_apply_deferred, ConditionWithAccess, NodeId, Schedule,
+};
+
+/// The text format produced by [`Schedule::export_graph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphExportFormat {
+    /// The [DOT](https://graphviz.org/doc/info/lang.html) language of Graphviz.
+    Dot,
+    /// A [Mermaid](https://mermaid.js.org/syntax/flowchart.html) flowchart.
+    Mermaid,
+}
+
+impl GraphExportFormat {
+    /// Returns the file extension conventionally used for this format, without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            GraphExportFormat::Dot => "dot",
+            GraphExportFormat::Mermaid => "mmd",
+        }
+    }
+}
+
+/// The kind of a node in an exported graph.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum NodeKind {
+    System,
+    SyncPoint,
+    Set,
+}
+
+/// The kind of an edge in an exported graph.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EdgeKind {
+    /// A set containing a system or another set.
+    Hierarchy,
+    /// A system or set running before another.
+    Dependency,
+    /// Two systems with conflicting access and no ordering between them.
+    Ambiguity,
+}
+
+struct ExportNode {
+    kind: NodeKind,
+    name: String,
+    conditions: Vec<String>,
+}
+
+/// A format-independent view of the graph of a [`Schedule`].
+struct ExportGraph {
+    nodes: Vec<ExportNode>,
+    edges: Vec<(usize, usize, EdgeKind)>,
+}
+
+impl Schedule {
+    /// Exports the graph of this schedule in the given `format`, to be rendered by external tools.
+    ///
+    /// The exported graph contains:
+    /// - the systems of the schedule, labeled with their run conditions;
+    /// - its named and anonymous system sets, labeled with their run conditions,
+    ///   with an edge from each set to the systems and sets it contains;
+    /// - the ordering dependencies between those systems and sets;
+    /// - [`ApplyDeferred`](crate::schedule::ApplyDeferred) sync points, drawn with their own shape;
+    /// - an undirected edge between each pair of systems with ambiguous ordering.
+    ///
+    /// Automatically inserted sync points and ambiguities are only known once the schedule
+    /// has been [initialized](Schedule::initialize), which happens when it is first run.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// use bevy_ecs::schedule::GraphExportFormat;
+    ///
+    /// fn spawn(mut commands: Commands) {}
+    /// fn count(query: Query<Entity>) {}
+    ///
+    /// let mut world = World::new();
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems((spawn, count).chain());
+    /// schedule.initialize(&mut world).unwrap();
+    ///
+    /// let dot = schedule.export_graph(GraphExportFormat::Dot);
+    /// assert!(dot.starts_with("digraph"));
+    /// ```
+    pub fn export_graph(&self, format: GraphExportFormat) -> String {
+        let graph = self.export_graph_nodes();
+        let mut output = String::new();
+        let result = match format {
+            GraphExportFormat::Dot => graph.write_dot(&mut output, &format!("{:?}", self.label())),
+            GraphExportFormat::Mermaid => graph.write_mermaid(&mut output),
+        };
+        result.expect("writing to a `String` cannot fail");
+        output
+    }
+
+    fn export_graph_nodes(&self) -> ExportGraph {
+        let graph = self.graph();
+        let use_shortnames = self.get_build_settings().use_shortnames;
+        let executable = self.executable();
+
+        let condition_names = |conditions: Option<&[ConditionWithAccess]>| {
+            conditions
+                .unwrap_or_default()
+                .iter()
+                .map(|condition| {
+                    let name = condition.condition.name();
+                    if use_shortnames {
+                        name.shortname().to_string()
+                    } else {
+                        name.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        let system_name = |id: NodeId| {
+            let Some(system) = id.as_system().and_then(|key| self.get_system(key)) else {
+                return String::new();
+            };
+            if use_shortnames {
+                system.name().shortname().to_string()
+            } else {
+                system.name().to_string()
+            }
+        };
+        // System type sets only mirror the system they are created for, so they are replaced by it.
+        let is_system_type_set = |id: NodeId| {
+            id.as_set()
+                .and_then(|key| graph.system_sets.get(key))
+                .is_some_and(|set| set.system_type().is_some())
+        };
+        let resolve = |id: NodeId| -> SmallVec<[NodeId; 1]> {
+            if is_system_type_set(id) {
+                graph
+                    .hierarchy()
+                    .graph()
+                    .neighbors_directed(id, Outgoing)
+                    .collect()
+            } else {
+                SmallVec::from_elem(id, 1)
+            }
+        };
+
+        let mut ids = HashMap::<NodeId, usize>::default();
+        let mut nodes = Vec::new();
+        let mut add_node = |id: NodeId, nodes: &mut Vec<ExportNode>| {
+            if ids.contains_key(&id) || is_system_type_set(id) {
+                return;
+            }
+            let node = match id {
+                NodeId::System(key) => ExportNode {
+                    kind: match self.get_system(key) {
+                        Some(system) if is_apply_deferred(system.as_ref()) => NodeKind::SyncPoint,
+                        _ => NodeKind::System,
+                    },
+                    name: system_name(id),
+                    conditions: condition_names(self.get_system_conditions(key)),
+                },
+                NodeId::Set(key) => {
+                    let set = &graph.system_sets[key];
+                    let name = if set.is_anonymous() {
+                        let members = graph
+                            .hierarchy()
+                            .graph()
+                            .neighbors_directed(id, Outgoing)
+                            .map(|member| match member {
+                                NodeId::System(_) => system_name(member),
+                                NodeId::Set(member) => format!("{:?}", &graph.system_sets[member]),
+                            })
+                            .collect::<Vec<_>>();
+                        format!("({})", members.join(", "))
+                    } else {
+                        format!("{set:?}")
+                    };
+                    ExportNode {
+                        kind: NodeKind::Set,
+                        name,
+                        conditions: condition_names(self.get_set_conditions(key)),
+                    }
+                }
+            };
+            ids.insert(id, nodes.len());
+            nodes.push(node);
+        };
+
+        for id in graph.hierarchy().graph().nodes() {
+            add_node(id, &mut nodes);
+        }
+        for id in graph.dependency().graph().nodes() {
+            add_node(id, &mut nodes);
+        }
+        // Sync points inserted when building the schedule only exist in the executable schedule.
+        let inserted = |key| !graph.hierarchy().graph().contains_node(NodeId::System(key));
+        for &key in &executable.system_ids {
+            if inserted(key) {
+                add_node(NodeId::System(key), &mut nodes);
+            }
+        }
+
+        let mut edges = Vec::new();
+        let mut add_edges = |a: NodeId, b: NodeId, kind: EdgeKind| {
+            for a in resolve(a) {
+                for b in resolve(b) {
+                    if let (Some(&a), Some(&b)) = (ids.get(&a), ids.get(&b)) {
+                        edges.push((a, b, kind));
+                    }
+                }
+            }
+        };
+
+        for (parent, child) in graph.hierarchy().graph().all_edges() {
+            if !is_system_type_set(parent) {
+                add_edges(parent, child, EdgeKind::Hierarchy);
+            }
+        }
+        for (before, after) in graph.dependency().graph().all_edges() {
+            add_edges(before, after, EdgeKind::Dependency);
+        }
+        for (index, dependents) in executable.system_dependents.iter().enumerate() {
+            let before = executable.system_ids[index];
+            for &dependent in dependents {
+                let after = executable.system_ids[dependent];
+                if inserted(before) || inserted(after) {
+                    add_edges(
+                        NodeId::System(before),
+                        NodeId::System(after),
+                        EdgeKind::Dependency,
+                    );
+                }
+            }
+        }
+        for &(a, b, _) in graph.conflicting_systems() {
+            add_edges(NodeId::System(a), NodeId::System(b), EdgeKind::Ambiguity);
+        }
+
+        ExportGraph { nodes, edges }
+    }
+}
+
+impl ExportGraph {
+    fn write_dot(&self, output: &mut String, label: &str) -> fmt::Result {
+        fn escape(text: &str) -> String {
+            text.replace('\\', "\\\\").replace('"', "\\\"")
+        }
+
+        writeln!(output, "digraph \"{}\" {{", escape(label))?;
+        writeln!(output, "    rankdir = LR;")?;
+        for (index, node) in self.nodes.iter().enumerate() {
+            let mut text = escape(&node.name);
+            if !node.conditions.is_empty() {
+                text += "\\nrun if: ";
+                text += &escape(&node.conditions.join(", "));
+            }
+            let shape = match node.kind {
+                NodeKind::System => "shape = box",
+                NodeKind::SyncPoint => "shape = diamond",
+                NodeKind::Set => "shape = box, style = \"rounded,dashed\"",
+            };
+            writeln!(output, "    n{index} [label = \"{text}\", {shape}];")?;
+        }
+        for &(a, b, kind) in &self.edges {
+            let style = match kind {
+                EdgeKind::Hierarchy => " [style = dashed, color = gray]",
+                EdgeKind::Dependency => "",
+                EdgeKind::Ambiguity => {
+                    " [dir = none, style = dotted, color = red, constraint = false]"
+                }
+            };
+            writeln!(output, "    n{a} -> n{b}{style};")?;
+        }
+        writeln!(output, "}}")
+    }
+
+    fn write_mermaid(&self, output: &mut String) -> fmt::Result {
+        fn escape(text: &str) -> String {
+            text.replace('#', "#35;")
+                .replace('"', "#quot;")
+                .replace('<', "#lt;")
+                .replace('>', "#gt;")
+        }
+
+        writeln!(output, "flowchart LR")?;
+        for (index, node) in self.nodes.iter().enumerate() {
+            let mut text = escape(&node.name);
+            if !node.conditions.is_empty() {
+                text += "<br>run if: ";
+                text += &escape(&node.conditions.join(", "));
+            }
+            let (open, close) = match node.kind {
+                NodeKind::System => ("[", "]"),
+                NodeKind::SyncPoint => ("{", "}"),
+                NodeKind::Set => ("([", "])"),
+            };
+            writeln!(output, "    n{index}{open}\"{text}\"{close}")?;
+        }
+        let mut ambiguities = Vec::new();
+        for (link, &(a, b, kind)) in self.edges.iter().enumerate() {
+            let arrow = match kind {
+                EdgeKind::Hierarchy => "-.->",
+                EdgeKind::Dependency => "-->",
+                EdgeKind::Ambiguity => {
+                    ambiguities.push(link.to_string());
+                    "-.-"
+                }
+            };
+            writeln!(output, "    n{a} {arrow} n{b}")?;
+        }
+        if !ambiguities.is_empty() {
+            writeln!(output, "    linkStyle {} stroke:red", ambiguities.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        schedule::{GraphExportFormat, LogLevel, ScheduleBuildSettings},
+    };
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Physics;
+
+    #[derive(Resource)]
+    struct Counter(u32);
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn_empty();
+    }
+
+    fn count(_: Query<Entity>) {}
+
+    fn increment(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    fn reset(mut counter: ResMut<Counter>) {
+        counter.0 = 0;
+    }
+
+    fn enabled() -> bool {
+        true
+    }
+
+    fn schedule() -> Schedule {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: LogLevel::Ignore,
+            ..Default::default()
+        });
+        schedule
+            .configure_sets(Physics.run_if(enabled))
+            .add_systems(((spawn, count).chain(), (increment, reset).in_set(Physics)));
+        schedule.initialize(&mut world).unwrap();
+        schedule
+    }
+
+    // System names are only available with the `debug` feature, so only set names are checked.
+    #[test]
+    fn export_dot() {
+        let dot = schedule().export_graph(GraphExportFormat::Dot);
+
+        assert!(dot.starts_with("digraph \"DefaultSchedule\" {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("[label = \"Physics\\nrun if: "));
+        assert_eq!(4, dot.matches(", shape = box];").count());
+        assert_eq!(
+            1,
+            dot.matches("shape = box, style = \"rounded,dashed\"];")
+                .count()
+        );
+        assert_eq!(1, dot.matches("shape = diamond];").count());
+        // `Physics` contains two systems, which are ambiguous with each other.
+        assert_eq!(2, dot.matches(" [style = dashed, color = gray];").count());
+        assert_eq!(1, dot.matches(" [dir = none, style = dotted").count());
+        // `spawn` runs before `count`, with a sync point inserted between them.
+        assert_eq!(
+            3,
+            dot.lines()
+                .filter(|line| line.ends_with(";") && line.contains("->") && !line.contains('['))
+                .count()
+        );
+    }
+
+    #[test]
+    fn export_mermaid() {
+        let mermaid = schedule().export_graph(GraphExportFormat::Mermaid);
+
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("([\"Physics<br>run if: "));
+        assert_eq!(1, mermaid.matches("\"}\n").count());
+        assert_eq!(2, mermaid.matches(" -.-> ").count());
+        assert_eq!(3, mermaid.matches(" --> ").count());
+        assert_eq!(1, mermaid.matches(" -.- ").count());
+        assert!(mermaid.ends_with("    linkStyle 5 stroke:red\n"));
+    }
+}
@@ -5,15 +5,20 @@ mod condition;
 mod config;
 mod error;
 mod executor;
+mod graph_export;
 mod node;
 mod pass;
 mod schedule;
 mod set;
 mod stepping;
+mod timings;
 
 pub use self::graph::GraphInfo;
 use self::graph::*;
-pub use self::{condition::*, config::*, error::*, executor::*, node::*, schedule::*, set::*};
+pub use self::{
+    condition::*, config::*, error::*, executor::*, graph_export::*, node::*, schedule::*, set::*,
+    timings::*,
+};
 pub use pass::ScheduleBuildPass;
 
 /// An implementation of a graph data structure.
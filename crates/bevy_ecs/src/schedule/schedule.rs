@@ -10,7 +10,10 @@ use alloc::{
     vec,
     vec::Vec,
 };
-use bevy_platform::collections::{HashMap, HashSet};
+use bevy_platform::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
 use bevy_utils::{default, prelude::DebugName, TypeIdMap};
 use core::{
     any::{Any, TypeId},
@@ -332,6 +335,7 @@ pub struct Schedule {
     executable: SystemSchedule,
     executor: Box<dyn SystemExecutor>,
     executor_initialized: bool,
+    deterministic: bool,
     warnings: Vec<ScheduleBuildWarning>,
 }
 
@@ -357,6 +361,7 @@ impl Schedule {
             executable: SystemSchedule::new(),
             executor: make_executor(ExecutorKind::default()),
             executor_initialized: false,
+            deterministic: false,
             warnings: Vec::new(),
         };
         // Call `set_build_settings` to add any default build passes
@@ -458,6 +463,7 @@ impl Schedule {
     pub fn set_executor_kind(&mut self, executor: ExecutorKind) -> &mut Self {
         if executor != self.executor.kind() {
             self.executor = make_executor(executor);
+            self.executor.set_deterministic(self.deterministic);
             self.executor_initialized = false;
         }
         self
@@ -472,6 +478,28 @@ impl Schedule {
         self
     }
 
+    /// Set whether the schedule runs deterministically or not, which is only meaningful with
+    /// [`ExecutorKind::MultiThreaded`]. By default this setting is false.
+    ///
+    /// When enabled, any two systems which may affect each other run in the topological order of
+    /// the schedule, like they would with [`ExecutorKind::SingleThreaded`], while other systems can
+    /// still run in parallel. This makes the state of the world after the schedule runs reproducible,
+    /// including the order in which commands are applied, in which events are written, and in which
+    /// entities are spawned. Two systems may affect each other if their accesses or the accesses of
+    /// their conditions conflict, if either is exclusive, or if both have deferred buffers such as
+    /// [`Commands`](crate::system::Commands).
+    ///
+    /// Work that a system itself spreads across threads, such as [`Query::par_iter`](crate::system::Query::par_iter)
+    /// or [`ParallelCommands`](crate::system::ParallelCommands), is not made deterministic.
+    ///
+    /// This setting is kept when the execution strategy is changed with [`Schedule::set_executor_kind`].
+    pub fn set_deterministic(&mut self, deterministic: bool) -> &mut Self {
+        self.deterministic = deterministic;
+        self.executor.set_deterministic(deterministic);
+        self.executor_initialized = false;
+        self
+    }
+
     /// Runs all systems in this schedule on the `world`, using its current execution strategy.
     pub fn run(&mut self, world: &mut World) {
         #[cfg(feature = "trace")]
@@ -487,10 +515,19 @@ impl Schedule {
         });
 
         let error_handler = world.default_error_handler();
+        let timer = world
+            .get_resource::<SystemTimings>()
+            .map(|timings| ScheduleTimer::new(timings.clone(), self.label));
+        let started = timer.as_ref().map(|_| Instant::now());
 
         #[cfg(not(feature = "bevy_debug_stepping"))]
-        self.executor
-            .run(&mut self.executable, world, None, error_handler);
+        self.executor.run(
+            &mut self.executable,
+            world,
+            None,
+            error_handler,
+            timer.as_ref(),
+        );
 
         #[cfg(feature = "bevy_debug_stepping")]
         {
@@ -504,8 +541,13 @@ impl Schedule {
                 world,
                 skip_systems.as_ref(),
                 error_handler,
+                timer.as_ref(),
             );
         }
+
+        if let (Some(timer), Some(started)) = (timer, started) {
+            timer.record_schedule(started);
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -620,6 +662,39 @@ impl Schedule {
         }
     }
 
+    /// Returns the system with the given key, if it exists.
+    ///
+    /// Unlike [`Systems::get`], this also finds systems that have been moved
+    /// out of the [`ScheduleGraph`] when the schedule was initialized.
+    pub fn get_system(&self, key: SystemKey) -> Option<&ScheduleSystem> {
+        match self.executable.system_ids.iter().position(|&id| id == key) {
+            Some(index) => Some(&self.executable.systems[index].system),
+            None => self.graph.systems.get(key).map(|system| &system.system),
+        }
+    }
+
+    /// Returns the run conditions of the system with the given key, if it exists.
+    ///
+    /// Unlike [`Systems::get_conditions`], this also finds conditions that have
+    /// been moved out of the [`ScheduleGraph`] when the schedule was initialized.
+    pub fn get_system_conditions(&self, key: SystemKey) -> Option<&[ConditionWithAccess]> {
+        match self.executable.system_ids.iter().position(|&id| id == key) {
+            Some(index) => Some(&self.executable.system_conditions[index]),
+            None => self.graph.systems.get_conditions(key),
+        }
+    }
+
+    /// Returns the run conditions of the system set with the given key, if it exists.
+    ///
+    /// Unlike [`SystemSets::get_conditions`], this also finds conditions that have
+    /// been moved out of the [`ScheduleGraph`] when the schedule was initialized.
+    pub fn get_set_conditions(&self, key: SystemSetKey) -> Option<&[ConditionWithAccess]> {
+        match self.executable.set_ids.iter().position(|&id| id == key) {
+            Some(index) => Some(&self.executable.set_conditions[index]),
+            None => self.graph.system_sets.get_conditions(key),
+        }
+    }
+
     /// Returns warnings that were generated during the last call to
     /// [`Schedule::initialize`].
     pub fn warnings(&self) -> &[ScheduleBuildWarning] {
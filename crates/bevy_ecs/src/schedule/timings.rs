@@ -0,0 +1,96 @@
+use alloc::vec::Vec;
+use bevy_platform::{
+    sync::{Arc, Mutex, PoisonError},
+    time::Instant,
+};
+use bevy_utils::prelude::DebugName;
+use core::time::Duration;
+
+use crate::{resource::Resource, schedule::InternedScheduleLabel, system::System};
+
+/// A [`Resource`] that makes schedules measure how long they take to run,
+/// and how long each of their systems takes to run.
+///
+/// Measurements are only taken by schedules run on a [`World`](crate::world::World) containing this
+/// resource, and accumulate until they are taken with [`SystemTimings::drain`].
+/// Only the systems themselves are measured: evaluating run conditions and applying
+/// deferred buffers is not included in their timings, but is included in the timing of the schedule.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::schedule::SystemTimings;
+///
+/// fn my_system() {}
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(my_system);
+///
+/// world.init_resource::<SystemTimings>();
+/// schedule.run(&mut world);
+///
+/// let timings = world.resource::<SystemTimings>().drain();
+/// // One timing for `my_system`, and one for the whole schedule.
+/// assert_eq!(2, timings.len());
+/// assert!(timings.iter().any(|timing| timing.system.is_none()));
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct SystemTimings {
+    timings: Arc<Mutex<Vec<SystemTiming>>>,
+}
+
+/// A wall time measurement recorded in [`SystemTimings`].
+#[derive(Clone, Debug)]
+pub struct SystemTiming {
+    /// The label of the schedule that was run.
+    pub schedule: InternedScheduleLabel,
+    /// The name of the system that was run, or `None` if this measures the whole schedule.
+    pub system: Option<DebugName>,
+    /// How long the run took.
+    pub duration: Duration,
+}
+
+impl SystemTimings {
+    /// Takes the timings recorded since the last call.
+    pub fn drain(&self) -> Vec<SystemTiming> {
+        core::mem::take(&mut *self.timings.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn record(&self, timing: SystemTiming) {
+        self.timings
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(timing);
+    }
+}
+
+/// Records the runs of a schedule and its systems into [`SystemTimings`].
+pub(super) struct ScheduleTimer {
+    timings: SystemTimings,
+    schedule: InternedScheduleLabel,
+}
+
+impl ScheduleTimer {
+    pub(super) fn new(timings: SystemTimings, schedule: InternedScheduleLabel) -> Self {
+        Self { timings, schedule }
+    }
+
+    /// Records a run of the schedule which started at `started`.
+    pub(super) fn record_schedule(&self, started: Instant) {
+        self.timings.record(SystemTiming {
+            schedule: self.schedule,
+            system: None,
+            duration: started.elapsed(),
+        });
+    }
+
+    /// Records a run of `system` which started at `started`.
+    pub(super) fn record_system(&self, system: &dyn System<In = (), Out = ()>, started: Instant) {
+        let duration = started.elapsed();
+        self.timings.record(SystemTiming {
+            schedule: self.schedule,
+            system: Some(system.name()),
+            duration,
+        });
+    }
+}
@@ -1,3 +1,4 @@
+use super::MemoryUsage;
 use alloc::alloc::handle_alloc_error;
 use bevy_ptr::{OwningPtr, Ptr, PtrMut};
 use bevy_utils::OnDrop;
@@ -94,6 +95,12 @@ impl BlobVec {
         self.item_layout
     }
 
+    /// Returns the memory held by the vector.
+    #[inline]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::of_buffer(self.item_layout.size(), self.len, self.capacity)
+    }
+
     /// Reserves the minimum capacity for at least `additional` more elements to be inserted in the given `BlobVec`.
     /// After calling `reserve_exact`, capacity will be greater than or equal to `self.len() + additional`. Does nothing if
     /// the capacity is already sufficient.
@@ -171,6 +178,39 @@ impl BlobVec {
         self.capacity = new_capacity;
     }
 
+    /// Shrinks the capacity of the vector to its length, releasing the memory of the unused slots.
+    ///
+    /// Does nothing for zero-sized types, which never allocate.
+    pub fn shrink_to_fit(&mut self) {
+        if self.item_layout.size() == 0 || self.capacity == self.len {
+            return;
+        }
+        let old_layout =
+            array_layout(&self.item_layout, self.capacity).expect("array layout should be valid");
+        if self.len == 0 {
+            // SAFETY:
+            // - ptr was be allocated via this allocator
+            // - the layout of the ptr was `array_layout(self.item_layout, self.capacity)`
+            unsafe { alloc::alloc::dealloc(self.get_ptr_mut().as_ptr(), old_layout) };
+            let align =
+                NonZero::<usize>::new(self.item_layout.align()).expect("alignment must be > 0");
+            self.data = bevy_ptr::dangling_with_align(align);
+        } else {
+            let new_layout =
+                array_layout(&self.item_layout, self.len).expect("array layout should be valid");
+            // SAFETY:
+            // - ptr was be allocated via this allocator
+            // - the layout of the ptr was `array_layout(self.item_layout, self.capacity)`
+            // - `item_layout.size() > 0` and `self.len > 0`, so the layout size is non-zero
+            // - the new size is smaller than the current one, so it cannot overflow
+            let new_data = unsafe {
+                alloc::alloc::realloc(self.get_ptr_mut().as_ptr(), old_layout, new_layout.size())
+            };
+            self.data = NonNull::new(new_data).unwrap_or_else(|| handle_alloc_error(new_layout));
+        }
+        self.capacity = self.len;
+    }
+
     /// Initializes the value at `index` to `value`. This function does not do any bounds checking.
     ///
     /// # Safety
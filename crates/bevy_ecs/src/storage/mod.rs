@@ -11,6 +11,8 @@
 //!  - [`Tables`] - columnar contiguous blocks of memory, optimized for fast iteration.
 //!  - [`SparseSets`] - sparse `HashMap`-like mappings from entities to components, optimized for random
 //!    lookup and regular insertion/removal of components.
+//!  - [`SharedComponents`] - the distinct values of shared components, each stored once for all
+//!    the entities that have it.
 //!  - [`Resources`] - singleton storage for the resources in the world
 //!
 //! # Safety
@@ -23,15 +25,22 @@
 mod blob_array;
 mod blob_vec;
 mod resource;
+mod shared;
 mod sparse_set;
 mod table;
 mod thin_array_ptr;
 
 pub use resource::*;
+pub use shared::*;
 pub use sparse_set::*;
 pub use table::*;
 
 use crate::component::{ComponentInfo, StorageType};
+use alloc::vec::Vec;
+use core::{
+    iter::Sum,
+    ops::{Add, AddAssign},
+};
 
 /// The raw data stores of a [`World`](crate::world::World)
 #[derive(Default)]
@@ -41,6 +50,8 @@ pub struct Storages {
     pub sparse_sets: SparseSets,
     /// Backing storage for [`Table`] components.
     pub tables: Tables,
+    /// Backing storage for [`Shared`](StorageType::Shared) components.
+    pub shared_components: SharedComponents,
     /// Backing storage for resources.
     pub resources: Resources<true>,
     /// Backing storage for `!Send` resources.
@@ -57,6 +68,71 @@ impl Storages {
             StorageType::SparseSet => {
                 self.sparse_sets.get_or_insert(component);
             }
+            StorageType::Shared => {
+                self.shared_components.get_or_insert(component);
+            }
+        }
+    }
+}
+
+/// The heap memory held by a storage, in bytes.
+///
+/// This only accounts for the memory owned by the storage itself: memory that the stored values
+/// allocate on their own, such as the buffer of a `Vec` component, is not included.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemoryUsage {
+    /// The number of bytes holding values.
+    pub used: usize,
+    /// The number of bytes allocated, which includes `used` and the unused capacity.
+    pub allocated: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the usage of a buffer of `capacity` items of `item_size` bytes, `len` of which
+    /// are used.
+    #[inline]
+    pub const fn of_buffer(item_size: usize, len: usize, capacity: usize) -> Self {
+        // Zero-sized items may report a capacity of `usize::MAX`.
+        Self {
+            used: item_size.saturating_mul(len),
+            allocated: item_size.saturating_mul(capacity),
+        }
+    }
+
+    /// Returns the usage of the buffer of `vec`.
+    #[inline]
+    pub(crate) fn of_vec<T>(vec: &Vec<T>) -> Self {
+        Self::of_buffer(size_of::<T>(), vec.len(), vec.capacity())
+    }
+
+    /// Returns the number of allocated bytes which are not used.
+    #[inline]
+    pub const fn unused(&self) -> usize {
+        self.allocated - self.used
+    }
+}
+
+impl Add for MemoryUsage {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            used: self.used + rhs.used,
+            allocated: self.allocated + rhs.allocated,
         }
     }
 }
+
+impl AddAssign for MemoryUsage {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for MemoryUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
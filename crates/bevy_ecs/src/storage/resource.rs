@@ -1,7 +1,7 @@
 use crate::{
     change_detection::{MaybeLocation, MutUntyped, TicksMut},
     component::{CheckChangeTicks, ComponentId, ComponentTicks, Components, Tick, TickCells},
-    storage::{blob_vec::BlobVec, SparseSet},
+    storage::{blob_vec::BlobVec, MemoryUsage, SparseSet},
 };
 use bevy_ptr::{OwningPtr, Ptr, UnsafeCellDeref};
 use bevy_utils::prelude::DebugName;
@@ -89,6 +89,14 @@ impl<const SEND: bool> ResourceData<SEND> {
         !self.data.is_empty()
     }
 
+    /// Returns the memory held by the resource's storage.
+    ///
+    /// The change detection ticks are stored inline, and are not included.
+    #[inline]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.data.memory_usage()
+    }
+
     /// Returns a reference to the resource, if it exists.
     ///
     /// # Panics
@@ -0,0 +1,304 @@
+use crate::{
+    change_detection::MaybeLocation,
+    component::{CheckChangeTicks, ComponentId, ComponentInfo, ComponentTicks, Tick, TickCells},
+    storage::{Column, MemoryUsage, SparseSet, TableRow},
+};
+use alloc::vec::Vec;
+use bevy_platform::collections::HashMap;
+use bevy_ptr::{OwningPtr, Ptr};
+use core::{cell::UnsafeCell, panic::Location};
+use nonmax::NonMaxU32;
+
+/// An opaque identifier for one of the values stored in a [`SharedComponentValues`].
+///
+/// Archetypes store the [`SharedValueId`] of each of their [`Shared`](crate::component::StorageType::Shared)
+/// components, which is why entities with different values of a shared component are in different
+/// archetypes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct SharedValueId(u32);
+
+impl SharedValueId {
+    /// Returns the index of the value in its [`SharedComponentValues`].
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    #[inline]
+    fn row(self) -> TableRow {
+        // SAFETY: Values are pushed one at a time, so there can't be `u32::MAX` of them.
+        TableRow::new(unsafe { NonMaxU32::new_unchecked(self.0) })
+    }
+}
+
+/// The distinct values of a [`Shared`](crate::component::StorageType::Shared) component.
+///
+/// Each value is stored once, along with its change detection ticks, no matter how many entities
+/// have it. Values are deduplicated using their hash and [`Eq`] implementation, and are kept for
+/// the lifetime of the [`World`](crate::world::World), even when no entity has them anymore.
+#[derive(Debug)]
+pub struct SharedComponentValues {
+    values: Column,
+    hashes: Vec<u64>,
+    by_hash: HashMap<u64, Vec<SharedValueId>>,
+}
+
+impl SharedComponentValues {
+    pub(crate) fn new(component_info: &ComponentInfo) -> Self {
+        Self {
+            values: Column::with_capacity(component_info, 0),
+            hashes: Vec::new(),
+            by_hash: HashMap::default(),
+        }
+    }
+
+    /// Returns the number of distinct values stored.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no value is stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns a reference to the value `id`.
+    ///
+    /// Returns `None` if `id` is not a value of this storage.
+    #[inline]
+    pub fn get(&self, id: SharedValueId) -> Option<Ptr<'_>> {
+        self.values.get_data(id.row())
+    }
+
+    /// Returns references to the value `id` and its added and changed ticks.
+    ///
+    /// Returns `None` if `id` is not a value of this storage.
+    #[inline]
+    pub fn get_with_ticks(
+        &self,
+        id: SharedValueId,
+    ) -> Option<(
+        Ptr<'_>,
+        TickCells<'_>,
+        MaybeLocation<&UnsafeCell<&'static Location<'static>>>,
+    )> {
+        if id.index() >= self.len() {
+            return None;
+        }
+        // SAFETY: The index was just checked to be in bounds.
+        unsafe {
+            Some((
+                self.values.get_data_unchecked(id.row()),
+                TickCells {
+                    added: self.values.get_added_tick_unchecked(id.row()),
+                    changed: self.values.get_changed_tick_unchecked(id.row()),
+                },
+                self.values.get_changed_by_unchecked(id.row()),
+            ))
+        }
+    }
+
+    /// Returns a reference to the "added" tick of the value `id`.
+    ///
+    /// Returns `None` if `id` is not a value of this storage.
+    #[inline]
+    pub fn get_added_tick(&self, id: SharedValueId) -> Option<&UnsafeCell<Tick>> {
+        self.values.get_added_tick(id.row())
+    }
+
+    /// Returns a reference to the "changed" tick of the value `id`.
+    ///
+    /// Returns `None` if `id` is not a value of this storage.
+    #[inline]
+    pub fn get_changed_tick(&self, id: SharedValueId) -> Option<&UnsafeCell<Tick>> {
+        self.values.get_changed_tick(id.row())
+    }
+
+    /// Returns the "added" and "changed" ticks of the value `id`.
+    ///
+    /// Returns `None` if `id` is not a value of this storage.
+    #[inline]
+    pub fn get_ticks(&self, id: SharedValueId) -> Option<ComponentTicks> {
+        self.values.get_ticks(id.row())
+    }
+
+    /// Returns a reference to the calling location that last changed the value `id`.
+    ///
+    /// Returns `None` if `id` is not a value of this storage.
+    #[inline]
+    pub fn get_changed_by(
+        &self,
+        id: SharedValueId,
+    ) -> MaybeLocation<Option<&UnsafeCell<&'static Location<'static>>>> {
+        self.values.get_changed_by(id.row())
+    }
+
+    /// Returns the id of the stored value equal to `value`, whose hash is `hash`.
+    ///
+    /// # Safety
+    /// `T` must be the type of the values of this storage.
+    pub(crate) unsafe fn find<T: Eq>(&self, hash: u64, value: &T) -> Option<SharedValueId> {
+        self.by_hash.get(&hash)?.iter().copied().find(|&id| {
+            // SAFETY: The ids in `by_hash` are in bounds, and the caller ensures `T` is correct.
+            unsafe { self.values.get_data_unchecked(id.row()).deref::<T>() == value }
+        })
+    }
+
+    /// Stores a new distinct value, whose hash is `hash`, and returns its id.
+    ///
+    /// # Safety
+    /// - `value` must point to a valid value of the component type of this storage.
+    /// - No value equal to `value` may already be stored.
+    pub(crate) unsafe fn push(
+        &mut self,
+        hash: u64,
+        value: OwningPtr<'_>,
+        change_tick: Tick,
+        caller: MaybeLocation,
+    ) -> SharedValueId {
+        let id = SharedValueId(
+            u32::try_from(self.values.len())
+                .ok()
+                .filter(|&index| index != u32::MAX)
+                .expect("too many shared component values"),
+        );
+        // SAFETY: The caller ensures `value` is valid for this column.
+        unsafe {
+            self.values
+                .push(value, ComponentTicks::new(change_tick), caller);
+        }
+        self.hashes.push(hash);
+        self.by_hash.entry(hash).or_default().push(id);
+        id
+    }
+
+    /// Replaces the value `id` by `value`, whose hash is `hash`, dropping the previous value.
+    ///
+    /// # Safety
+    /// - `id` must be a value of this storage.
+    /// - `value` must point to a valid value of the component type of this storage.
+    /// - No other value equal to `value` may be stored.
+    pub(crate) unsafe fn replace(
+        &mut self,
+        id: SharedValueId,
+        hash: u64,
+        value: OwningPtr<'_>,
+        change_tick: Tick,
+        caller: MaybeLocation,
+    ) {
+        let old_hash = core::mem::replace(&mut self.hashes[id.index()], hash);
+        if let Some(ids) = self.by_hash.get_mut(&old_hash) {
+            ids.retain(|&other| other != id);
+            if ids.is_empty() {
+                self.by_hash.remove(&old_hash);
+            }
+        }
+        self.by_hash.entry(hash).or_default().push(id);
+        // SAFETY: The caller ensures `id` is in bounds and `value` is valid for this column.
+        unsafe {
+            self.values.replace(id.row(), value, change_tick, caller);
+        }
+    }
+
+    /// Returns the memory held by the stored values and their change detection ticks, as well as
+    /// by the index used to find them.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let index_entry_size = size_of::<(u64, Vec<SharedValueId>)>();
+        self.values.memory_usage()
+            + MemoryUsage::of_vec(&self.hashes)
+            + MemoryUsage::of_buffer(
+                index_entry_size,
+                self.by_hash.len(),
+                self.by_hash.capacity(),
+            )
+            + self
+                .by_hash
+                .values()
+                .map(MemoryUsage::of_vec)
+                .sum::<MemoryUsage>()
+    }
+
+    pub(crate) fn check_change_ticks(&mut self, check: CheckChangeTicks) {
+        self.values.check_change_ticks(check);
+    }
+
+    /// Shrinks the capacity of the storage and its index to the number of stored values.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.values.shrink_to_fit();
+        self.hashes.shrink_to_fit();
+        self.by_hash.shrink_to_fit();
+        for ids in self.by_hash.values_mut() {
+            ids.shrink_to_fit();
+        }
+    }
+}
+
+/// A collection of [`SharedComponentValues`] storages, indexed by [`ComponentId`]
+///
+/// Can be accessed via [`Storages`](crate::storage::Storages)
+#[derive(Default)]
+pub struct SharedComponents {
+    sets: SparseSet<ComponentId, SharedComponentValues>,
+}
+
+impl SharedComponents {
+    /// Returns the number of [`SharedComponentValues`] this collection contains.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Returns true if this collection contains no [`SharedComponentValues`].
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// An Iterator visiting all ([`ComponentId`], [`SharedComponentValues`]) pairs.
+    /// NOTE: Order is not guaranteed.
+    pub fn iter(&self) -> impl Iterator<Item = (ComponentId, &SharedComponentValues)> {
+        self.sets.iter().map(|(id, values)| (*id, values))
+    }
+
+    /// Gets a reference to the [`SharedComponentValues`] of a [`ComponentId`]. This may be `None` if the component has never been registered.
+    #[inline]
+    pub fn get(&self, component_id: ComponentId) -> Option<&SharedComponentValues> {
+        self.sets.get(component_id)
+    }
+
+    /// Gets a mutable reference of [`SharedComponentValues`] of a [`ComponentInfo`].
+    /// Create a new [`SharedComponentValues`] if not exists.
+    pub(crate) fn get_or_insert(
+        &mut self,
+        component_info: &ComponentInfo,
+    ) -> &mut SharedComponentValues {
+        self.sets.get_or_insert_with(component_info.id(), || {
+            SharedComponentValues::new(component_info)
+        })
+    }
+
+    /// Gets a mutable reference to the [`SharedComponentValues`] of a [`ComponentId`]. This may be `None` if the component has never been registered.
+    pub(crate) fn get_mut(
+        &mut self,
+        component_id: ComponentId,
+    ) -> Option<&mut SharedComponentValues> {
+        self.sets.get_mut(component_id)
+    }
+
+    pub(crate) fn check_change_ticks(&mut self, check: CheckChangeTicks) {
+        for values in self.sets.values_mut() {
+            values.check_change_ticks(check);
+        }
+    }
+
+    /// An Iterator visiting all ([`ComponentId`], [`SharedComponentValues`]) pairs, with mutable
+    /// access to the storages.
+    /// NOTE: Order is not guaranteed.
+    pub(crate) fn iter_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (ComponentId, &mut SharedComponentValues)> {
+        self.sets.iter_mut().map(|(id, values)| (*id, values))
+    }
+}
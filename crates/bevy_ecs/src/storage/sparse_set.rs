@@ -2,7 +2,7 @@ use crate::{
     change_detection::MaybeLocation,
     component::{CheckChangeTicks, ComponentId, ComponentInfo, ComponentTicks, Tick, TickCells},
     entity::{Entity, EntityRow},
-    storage::{Column, TableRow},
+    storage::{Column, MemoryUsage, TableRow},
 };
 use alloc::{boxed::Box, vec::Vec};
 use bevy_ptr::{OwningPtr, Ptr};
@@ -37,6 +37,15 @@ impl<I, V> SparseArray<I, V> {
             marker: PhantomData,
         }
     }
+
+    /// Returns the memory held by the array, where only the slots holding a value are used.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::of_buffer(
+            size_of::<Option<V>>(),
+            self.values.iter().filter(|value| value.is_some()).count(),
+            self.values.capacity(),
+        )
+    }
 }
 
 macro_rules! impl_sparse_array {
@@ -100,6 +109,17 @@ impl<I: SparseSetIndex, V> SparseArray<I, V> {
         self.values.clear();
     }
 
+    /// Drops the empty slots past the last value, and shrinks the capacity of the array to fit.
+    pub fn shrink_to_fit(&mut self) {
+        let len = self
+            .values
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |index| index + 1);
+        self.values.truncate(len);
+        self.values.shrink_to_fit();
+    }
+
     /// Converts the [`SparseArray`] into an immutable variant.
     pub(crate) fn into_immutable(self) -> ImmutableSparseArray<I, V> {
         ImmutableSparseArray {
@@ -155,6 +175,11 @@ impl ComponentSparseSet {
         self.dense.len() == 0
     }
 
+    /// Returns the memory held by the sparse set, including the change detection ticks.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.dense.memory_usage() + MemoryUsage::of_vec(&self.entities) + self.sparse.memory_usage()
+    }
+
     /// Inserts the `entity` key and component `value` pair into this sparse
     /// set.
     ///
@@ -363,6 +388,13 @@ impl ComponentSparseSet {
     pub(crate) fn check_change_ticks(&mut self, check: CheckChangeTicks) {
         self.dense.check_change_ticks(check);
     }
+
+    /// Shrinks the capacity of the sparse set to its number of values.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.dense.shrink_to_fit();
+        self.entities.shrink_to_fit();
+        self.sparse.shrink_to_fit();
+    }
 }
 
 /// A data structure that blends dense and sparse storage
@@ -451,6 +483,19 @@ macro_rules! impl_sparse_set {
 impl_sparse_set!(SparseSet);
 impl_sparse_set!(ImmutableSparseSet);
 
+impl<I, V> ImmutableSparseSet<I, V> {
+    /// Returns the memory held by the sparse set, not including the memory held by the values.
+    pub(crate) fn memory_usage(&self) -> MemoryUsage {
+        let size = size_of_val(&*self.dense)
+            + size_of_val(&*self.indices)
+            + size_of_val(&*self.sparse.values);
+        MemoryUsage {
+            used: size,
+            allocated: size,
+        }
+    }
+}
+
 impl<I: SparseSetIndex, V> Default for SparseSet<I, V> {
     fn default() -> Self {
         Self::new()
@@ -655,6 +700,15 @@ impl SparseSets {
             set.check_change_ticks(check);
         }
     }
+
+    /// An Iterator visiting all ([`ComponentId`], [`ComponentSparseSet`]) pairs, with mutable
+    /// access to the sparse sets.
+    /// NOTE: Order is not guaranteed.
+    pub(crate) fn iter_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (ComponentId, &mut ComponentSparseSet)> {
+        self.sets.iter_mut().map(|(id, data)| (*id, data))
+    }
 }
 
 #[cfg(test)]
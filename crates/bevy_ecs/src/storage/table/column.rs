@@ -36,6 +36,19 @@ impl ThinColumn {
         }
     }
 
+    /// Returns the memory held by the column, including the change detection ticks, given the
+    /// `len` and `capacity` of its [`Table`].
+    pub fn memory_usage(&self, len: usize, capacity: usize) -> MemoryUsage {
+        let item_size = self.data.layout().size()
+            + 2 * size_of::<UnsafeCell<Tick>>()
+            + self
+                .changed_by
+                .as_ref()
+                .map(|_| size_of::<UnsafeCell<&'static Location<'static>>>())
+                .unwrap_or_default();
+        MemoryUsage::of_buffer(item_size, len, capacity)
+    }
+
     /// Swap-remove and drop the removed element, but the component at `row` must not be the last element.
     ///
     /// # Safety
@@ -275,6 +288,23 @@ impl ThinColumn {
             .map(|changed_by| changed_by.drop(cap, len));
     }
 
+    /// Releases the memory of this empty [`ThinColumn`], leaving it with a capacity of 0.
+    ///
+    /// # Safety
+    /// - `current_capacity` is indeed the capacity of the column
+    /// - the column holds no element
+    /// - The caller should update their saved `capacity` value to 0 after this operation
+    pub(crate) unsafe fn dealloc(&mut self, current_capacity: usize) {
+        self.drop(current_capacity, 0);
+        // SAFETY: the drop function is the one of the components this column was created for.
+        self.data = unsafe { BlobArray::with_capacity(self.data.layout(), self.data.drop, 0) };
+        self.added_ticks = ThinArrayPtr::with_capacity(0);
+        self.changed_ticks = ThinArrayPtr::with_capacity(0);
+        self.changed_by
+            .as_mut()
+            .map(|changed_by| *changed_by = ThinArrayPtr::with_capacity(0));
+    }
+
     /// Drops the last component in this column.
     ///
     /// # Safety
@@ -399,6 +429,26 @@ impl Column {
         self.data.is_empty()
     }
 
+    /// Returns the memory held by the column, including the change detection ticks.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.data.memory_usage()
+            + MemoryUsage::of_vec(&self.added_ticks)
+            + MemoryUsage::of_vec(&self.changed_ticks)
+            + self
+                .changed_by
+                .as_ref()
+                .map(MemoryUsage::of_vec)
+                .unwrap_or_default()
+    }
+
+    /// Shrinks the capacity of the column to its length, releasing the memory of the unused slots.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+        self.added_ticks.shrink_to_fit();
+        self.changed_ticks.shrink_to_fit();
+        self.changed_by.as_mut().map(Vec::shrink_to_fit);
+    }
+
     /// Removes an element from the [`Column`].
     ///
     /// - The value will be dropped if it implements [`Drop`].
@@ -3,7 +3,7 @@ use crate::{
     component::{CheckChangeTicks, ComponentId, ComponentInfo, ComponentTicks, Components, Tick},
     entity::Entity,
     query::DebugCheckedUnwrap,
-    storage::{blob_vec::BlobVec, ImmutableSparseSet, SparseSet},
+    storage::{blob_vec::BlobVec, ImmutableSparseSet, MemoryUsage, SparseSet},
 };
 use alloc::{boxed::Box, vec, vec::Vec};
 use bevy_platform::collections::HashMap;
@@ -564,6 +564,46 @@ impl Table {
         core::mem::forget(_guard); // The allocation was successful, so we don't drop the guard.
     }
 
+    /// Shrinks the capacity of the [`Table`] to its number of entities, releasing the memory of
+    /// the unused rows of its columns.
+    ///
+    /// An empty table releases all of its column memory, which is allocated again when an entity
+    /// is added to it.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        let column_cap = self.capacity();
+        self.entities.shrink_to_fit();
+
+        // use entities vector capacity as driving capacity for all related allocations
+        let new_capacity = self.entities.capacity();
+        if new_capacity == column_cap {
+            return;
+        }
+
+        match (
+            NonZeroUsize::new(column_cap),
+            NonZeroUsize::new(new_capacity),
+        ) {
+            (Some(column_cap), Some(new_capacity)) => {
+                // SAFETY: `column_cap` is indeed the columns' capacity
+                unsafe { self.realloc_columns(column_cap, new_capacity) };
+            }
+            (Some(column_cap), None) => {
+                // If any of these deallocations trigger an unwind, the wrong capacity will be used while dropping this table - UB.
+                // To avoid this, we use `AbortOnPanic`.
+                let _guard = AbortOnPanic;
+                for col in self.columns.values_mut() {
+                    // SAFETY:
+                    // - `column_cap` is indeed the columns' capacity
+                    // - the table is empty, since `entities` has no capacity left
+                    unsafe { col.dealloc(column_cap.get()) };
+                }
+                core::mem::forget(_guard); // The deallocation was successful, so we don't drop the guard.
+            }
+            // `Vec::shrink_to_fit` never grows the capacity.
+            (None, _) => unreachable!(),
+        }
+    }
+
     /// Allocates space for a new entity
     ///
     /// # Safety
@@ -642,6 +682,24 @@ impl Table {
         self.columns.values()
     }
 
+    /// Returns the memory held by each column of the [`Table`].
+    pub fn column_memory_usage(&self) -> impl Iterator<Item = (ComponentId, MemoryUsage)> + '_ {
+        let len = self.entity_count() as usize;
+        let capacity = self.capacity();
+        self.columns
+            .iter()
+            .map(move |(id, column)| (*id, column.memory_usage(len, capacity)))
+    }
+
+    /// Returns the memory held by the [`Table`], including its columns.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::of_vec(&self.entities)
+            + self
+                .column_memory_usage()
+                .map(|(_, usage)| usage)
+                .sum::<MemoryUsage>()
+    }
+
     /// Clears all of the stored components in the [`Table`].
     pub(crate) fn clear(&mut self) {
         let len = self.entity_count() as usize;
@@ -798,6 +856,11 @@ impl Tables {
             table.check_change_ticks(check);
         }
     }
+
+    /// Iterates mutably over the [`Table`]s, in the order of their [`TableId`].
+    pub(crate) fn iter_mut(&mut self) -> core::slice::IterMut<'_, Table> {
+        self.tables.iter_mut()
+    }
 }
 
 impl Index<TableId> for Tables {
@@ -10,7 +10,7 @@ use log::info;
 use crate::{
     bundle::{Bundle, InsertMode},
     change_detection::MaybeLocation,
-    component::{Component, ComponentId, ComponentInfo},
+    component::{Component, ComponentId, ComponentInfo, Immutable},
     entity::{Entity, EntityClonerBuilder, OptIn, OptOut},
     event::EntityEvent,
     relationship::RelationshipHookMode,
@@ -18,6 +18,7 @@ use crate::{
     world::{error::EntityMutableFetchError, EntityWorldMut, FromWorld},
 };
 use bevy_ptr::OwningPtr;
+use core::hash::Hash;
 
 /// A command which gets executed for a given [`Entity`].
 ///
@@ -112,6 +113,18 @@ pub fn insert(bundle: impl Bundle, mode: InsertMode) -> impl EntityCommand {
     }
 }
 
+/// An [`EntityCommand`] that adds a [`Shared`](crate::component::StorageType::Shared) component
+/// to an entity.
+#[track_caller]
+pub fn insert_shared<T: Component<Mutability = Immutable> + Eq + Hash>(
+    value: T,
+) -> impl EntityCommand {
+    let caller = MaybeLocation::caller();
+    move |mut entity: EntityWorldMut| {
+        entity.insert_shared_with_caller(value, caller, RelationshipHookMode::Run);
+    }
+}
+
 /// An [`EntityCommand`] that adds a dynamic component to an entity.
 ///
 /// # Safety
@@ -11,13 +11,13 @@ pub use entity_command::EntityCommand;
 pub use parallel_scope::*;
 
 use alloc::boxed::Box;
-use core::marker::PhantomData;
+use core::{hash::Hash, marker::PhantomData};
 
 use crate::{
     self as bevy_ecs,
     bundle::{Bundle, InsertMode, NoBundleEffect},
     change_detection::{MaybeLocation, Mut},
-    component::{Component, ComponentId, Mutable},
+    component::{Component, ComponentId, Immutable, Mutable},
     entity::{Entities, Entity, EntityClonerBuilder, EntityDoesNotExistError, OptIn, OptOut},
     error::{warn, BevyError, CommandWithEntity, ErrorContext, HandleError},
     event::{BufferedEvent, EntityEvent, Event},
@@ -1413,6 +1413,33 @@ impl<'a> EntityCommands<'a> {
         }
     }
 
+    /// Adds a [`Shared`](crate::component::StorageType::Shared) component to the entity.
+    ///
+    /// This will overwrite any previous value of the same component type.
+    ///
+    /// See [`EntityWorldMut::insert_shared`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, PartialEq, Eq, Hash)]
+    /// #[component(storage = "Shared")]
+    /// struct Team(u32);
+    ///
+    /// fn join_team_system(mut commands: Commands) {
+    ///     commands.spawn_empty().insert_shared(Team(1));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(join_team_system);
+    /// ```
+    #[track_caller]
+    pub fn insert_shared<T: Component<Mutability = Immutable> + Eq + Hash>(
+        &mut self,
+        value: T,
+    ) -> &mut Self {
+        self.queue(entity_command::insert_shared(value))
+    }
+
     /// Adds a [`Bundle`] of components to the entity without overwriting.
     ///
     /// This is the same as [`EntityCommands::insert`], but in case of duplicate
@@ -0,0 +1,237 @@
+//! Releasing the memory that the storages of a [`World`] keep after entities are despawned.
+
+use crate::{resource::Resource, storage::MemoryUsage, world::World};
+
+/// Decides which storages are shrunk by [`World::compact_with`].
+///
+/// A storage is shrunk when at least [`min_unused_bytes`] of its allocation are unused, and
+/// these bytes make up at least [`min_unused_ratio`] of the allocation. Shrinking a storage
+/// reallocates it, so these thresholds avoid paying for a reallocation to reclaim little memory,
+/// or for a storage which is likely to grow back soon.
+///
+/// When inserted as a resource, it is used by the [`compact_world`] system.
+///
+/// [`min_unused_bytes`]: Self::min_unused_bytes
+/// [`min_unused_ratio`]: Self::min_unused_ratio
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct CompactionPolicy {
+    /// The minimum number of unused bytes a storage must hold to be shrunk.
+    pub min_unused_bytes: usize,
+    /// The minimum fraction of its allocation, between `0.0` and `1.0`, a storage must leave
+    /// unused to be shrunk.
+    pub min_unused_ratio: f32,
+}
+
+impl CompactionPolicy {
+    /// A policy shrinking every storage with unused capacity, as done by [`World::compact`].
+    pub const ALWAYS: Self = Self {
+        min_unused_bytes: 0,
+        min_unused_ratio: 0.0,
+    };
+
+    /// Returns `true` if a storage with the given memory usage should be shrunk.
+    pub fn should_compact(&self, usage: MemoryUsage) -> bool {
+        let unused = usage.unused();
+        unused > 0
+            && unused >= self.min_unused_bytes
+            && unused as f32 >= usage.allocated as f32 * self.min_unused_ratio
+    }
+}
+
+impl Default for CompactionPolicy {
+    /// Shrinks storages leaving at least 64 KiB and half of their allocation unused.
+    fn default() -> Self {
+        Self {
+            min_unused_bytes: 64 * 1024,
+            min_unused_ratio: 0.5,
+        }
+    }
+}
+
+impl World {
+    /// Shrinks the storages of this world to fit the entities and values they hold, returning the
+    /// number of bytes released.
+    ///
+    /// This shrinks table columns, sparse sets, shared component values, the entity lists of
+    /// archetypes and the entity allocator. Empty tables release all of their column memory.
+    /// Tables and archetypes themselves are never removed, as their ids are kept by queries and
+    /// archetype edges, and the metadata of freed entities is kept so that stale [`Entity`]
+    /// handles are still detected.
+    ///
+    /// This reallocates every storage with unused capacity: use [`World::compact_with`] to only
+    /// shrink the storages worth it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component)]
+    /// struct Position(f32, f32);
+    ///
+    /// let mut world = World::new();
+    /// let entities: Vec<_> = world
+    ///     .spawn_batch((0..1000).map(|i| Position(i as f32, 0.)))
+    ///     .collect();
+    /// for entity in entities {
+    ///     world.despawn(entity);
+    /// }
+    ///
+    /// let before = world.memory_report().total();
+    /// let released = world.compact();
+    /// assert!(released >= 1000 * size_of::<Position>());
+    /// assert!(world.memory_report().total().allocated < before.allocated);
+    /// ```
+    ///
+    /// [`Entity`]: crate::entity::Entity
+    pub fn compact(&mut self) -> usize {
+        self.compact_with(&CompactionPolicy::ALWAYS)
+    }
+
+    /// Shrinks the storages of this world selected by `policy` to fit the entities and values
+    /// they hold, returning the number of bytes released.
+    ///
+    /// See [`World::compact`].
+    pub fn compact_with(&mut self, policy: &CompactionPolicy) -> usize {
+        self.flush_entities();
+        let mut released = 0;
+        for table in self.storages.tables.iter_mut() {
+            let before = table.memory_usage();
+            if policy.should_compact(before) {
+                table.shrink_to_fit();
+                released += before.allocated - table.memory_usage().allocated;
+            }
+        }
+        for (_, sparse_set) in self.storages.sparse_sets.iter_mut() {
+            let before = sparse_set.memory_usage();
+            if policy.should_compact(before) {
+                sparse_set.shrink_to_fit();
+                released += before.allocated - sparse_set.memory_usage().allocated;
+            }
+        }
+        for (_, values) in self.storages.shared_components.iter_mut() {
+            let before = values.memory_usage();
+            if policy.should_compact(before) {
+                values.shrink_to_fit();
+                released += before.allocated - values.memory_usage().allocated;
+            }
+        }
+        for archetype in &mut self.archetypes.archetypes {
+            let before = archetype.memory_usage();
+            if policy.should_compact(before) {
+                archetype.shrink_to_fit();
+                released += before.allocated - archetype.memory_usage().allocated;
+            }
+        }
+        let before = self.entities.memory_usage();
+        if policy.should_compact(before) {
+            self.entities.shrink_to_fit();
+            released += before.allocated - self.entities.memory_usage().allocated;
+        }
+        released
+    }
+}
+
+/// Shrinks the storages of the world according to the [`CompactionPolicy`] resource.
+///
+/// Checking the storages takes time proportional to their number and to the number of entities,
+/// so this system is best run with a run condition rather than on every frame.
+///
+/// Does nothing if there is no [`CompactionPolicy`] resource.
+pub fn compact_world(world: &mut World) {
+    if let Some(policy) = world.get_resource::<CompactionPolicy>().copied() {
+        world.compact_with(&policy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompactionPolicy;
+    use crate::prelude::*;
+    use alloc::vec::Vec;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Position([u64; 2]);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct Marker(u32);
+
+    #[test]
+    fn compact_shrinks_storages_and_keeps_values() {
+        let mut world = World::new();
+        let entities: Vec<_> = world
+            .spawn_batch((0..256).map(|i| (Position([i; 2]), Marker(i as u32))))
+            .collect();
+        for &entity in &entities[1..] {
+            world.despawn(entity);
+        }
+        let kept = entities[0];
+        let unused = world.memory_report().total().unused();
+
+        let released = world.compact();
+        assert!(released >= 255 * (size_of::<Position>() + size_of::<Marker>()));
+        assert!(world.memory_report().total().unused() < unused);
+        assert_eq!(world.get::<Position>(kept), Some(&Position([0; 2])));
+        assert_eq!(world.get::<Marker>(kept), Some(&Marker(0)));
+
+        // Storages grow back as entities are added.
+        let spawned: Vec<_> = world
+            .spawn_batch((0..64).map(|i| (Position([i; 2]), Marker(i as u32))))
+            .collect();
+        assert_eq!(world.get::<Position>(spawned[63]), Some(&Position([63; 2])));
+        assert_eq!(world.get::<Marker>(spawned[63]), Some(&Marker(63)));
+        assert_eq!(world.get::<Position>(kept), Some(&Position([0; 2])));
+    }
+
+    #[test]
+    fn compact_releases_empty_tables() {
+        let mut world = World::new();
+        let entities: Vec<_> = world
+            .spawn_batch((0..64).map(|i| Position([i; 2])))
+            .collect();
+        for entity in entities.iter().copied() {
+            world.despawn(entity);
+        }
+        world.compact();
+
+        let position = world.component_id::<Position>().unwrap();
+        let report = world.memory_report();
+        let usage = report.components.iter().find(|c| c.id == position).unwrap();
+        assert_eq!(usage.usage.allocated, 0);
+
+        // Despawned entities stay invalid while their index is reused.
+        let entity = world.spawn(Position([1; 2])).id();
+        assert!(entities
+            .iter()
+            .all(|&despawned| world.get_entity(despawned).is_err()));
+        assert_eq!(world.get::<Position>(entity), Some(&Position([1; 2])));
+    }
+
+    #[test]
+    fn policy_thresholds() {
+        let mut world = World::new();
+        let entities: Vec<_> = world
+            .spawn_batch((0..64).map(|i| Position([i; 2])))
+            .collect();
+        for entity in entities.into_iter().skip(1) {
+            world.despawn(entity);
+        }
+
+        let policy = CompactionPolicy {
+            min_unused_bytes: usize::MAX,
+            ..CompactionPolicy::ALWAYS
+        };
+        assert_eq!(world.compact_with(&policy), 0);
+        let policy = CompactionPolicy {
+            min_unused_ratio: 1.0,
+            ..CompactionPolicy::ALWAYS
+        };
+        assert_eq!(world.compact_with(&policy), 0);
+
+        world.insert_resource(CompactionPolicy::ALWAYS);
+        let unused = world.memory_report().total().unused();
+        world.run_system_cached(super::compact_world).unwrap();
+        assert!(world.memory_report().total().unused() < unused);
+    }
+}
@@ -135,6 +135,10 @@ impl<'w> DeferredWorld<'w> {
     ///
     /// You should prefer the typed [`modify_component_with_relationship_hook_mode`](DeferredWorld::modify_component_with_relationship_hook_mode)
     /// whenever possible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component is a [`Shared`](crate::component::StorageType::Shared) component.
     #[inline]
     #[track_caller]
     pub(crate) fn modify_component_by_id_with_relationship_hook_mode<R>(
@@ -150,6 +154,11 @@ impl<'w> DeferredWorld<'w> {
             return Ok(None);
         }
 
+        assert!(
+            entity_cell.archetype().get_shared_value(component_id).is_none(),
+            "Shared components can't be modified through a single entity, use `World::replace_shared` instead"
+        );
+
         let archetype = &raw const *entity_cell.archetype();
 
         // SAFETY:
@@ -1,13 +1,13 @@
 use crate::{
     archetype::Archetype,
     bundle::{
-        Bundle, BundleEffect, BundleFromComponents, BundleInserter, BundleRemover, DynamicBundle,
-        InsertMode,
+        Bundle, BundleEffect, BundleFromComponents, BundleInserter, BundleRemover,
+        BundleStorageType, DynamicBundle, InsertMode,
     },
     change_detection::{MaybeLocation, MutUntyped},
     component::{
-        Component, ComponentId, ComponentTicks, Components, ComponentsRegistrator, Mutable,
-        StorageType, Tick,
+        Component, ComponentId, ComponentTicks, Components, ComponentsRegistrator, Immutable,
+        Mutable, StorageType, Tick,
     },
     entity::{
         ContainsEntity, Entity, EntityCloner, EntityClonerBuilder, EntityEquivalent,
@@ -2057,6 +2057,51 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Inserts a [`Shared`](StorageType::Shared) component into the entity.
+    ///
+    /// The entity is moved to the archetype of the entities with the same value, which is stored
+    /// only once. This will overwrite any previous value of the same component type.
+    ///
+    /// Shared components can't be inserted as part of a [`Bundle`], nor be required components.
+    ///
+    /// # Panics
+    ///
+    /// If the entity has been despawned while this `EntityWorldMut` is still alive.
+    /// If `T` is not a shared component.
+    #[track_caller]
+    pub fn insert_shared<T: Component<Mutability = Immutable> + Eq + Hash>(
+        &mut self,
+        value: T,
+    ) -> &mut Self {
+        self.insert_shared_with_caller(value, MaybeLocation::caller(), RelationshipHookMode::Run)
+    }
+
+    #[inline]
+    pub(crate) fn insert_shared_with_caller<T: Component<Mutability = Immutable> + Eq + Hash>(
+        &mut self,
+        value: T,
+        caller: MaybeLocation,
+        relationship_hook_mode: RelationshipHookMode,
+    ) -> &mut Self {
+        let location = self.location();
+        let (component_id, value) = self.world.store_shared_value(value, caller);
+        // SAFETY: `location` is the entity's, and `value` was just stored for `component_id`.
+        let location = unsafe {
+            self.world.set_shared_value(
+                self.entity,
+                location,
+                component_id,
+                value,
+                caller,
+                relationship_hook_mode,
+            )
+        };
+        self.location = Some(location);
+        self.world.flush();
+        self.update_location();
+        self
+    }
+
     /// Inserts a dynamic [`Component`] into the entity.
     ///
     /// This will overwrite any previous value(s) of the same component type.
@@ -2194,6 +2239,7 @@ impl<'w> EntityWorldMut<'w> {
     /// # Panics
     ///
     /// If the entity has been despawned while this `EntityWorldMut` is still alive.
+    /// If the entity has a [`Shared`](StorageType::Shared) component of the bundle.
     #[must_use]
     #[track_caller]
     pub fn take<T: Bundle + BundleFromComponents>(&mut self) -> Option<T> {
@@ -2217,8 +2263,12 @@ impl<'w> EntityWorldMut<'w> {
                             let component_id = bundle_components.next().unwrap();
                             // SAFETY: the component existed to be removed, so its id must be valid.
                             let component_info = components.get_info_unchecked(component_id);
-                            match component_info.storage_type() {
-                                StorageType::Table => {
+                            // SAFETY: `BundleInfo::remove_bundle_from_archetype` rejects taking
+                            // shared components.
+                            match BundleStorageType::new(component_info.storage_type())
+                                .debug_checked_unwrap()
+                            {
+                                BundleStorageType::Table => {
                                     table
                                         .as_mut()
                                         // SAFETY: The table must be valid if the component is in it.
@@ -2226,7 +2276,7 @@ impl<'w> EntityWorldMut<'w> {
                                         // SAFETY: The remover is cleaning this up.
                                         .take_component(component_id, location.table_row)
                                 }
-                                StorageType::SparseSet => sets
+                                BundleStorageType::SparseSet => sets
                                     .get_mut(component_id)
                                     .unwrap()
                                     .remove_and_forget(entity)
@@ -0,0 +1,279 @@
+//! Reports of the memory held by the storages of a [`World`].
+
+use alloc::vec::Vec;
+use bevy_platform::collections::HashMap;
+use bevy_utils::prelude::DebugName;
+use core::cmp::Reverse;
+
+use crate::{
+    archetype::ArchetypeId,
+    component::{ComponentId, StorageType},
+    storage::{MemoryUsage, TableId},
+    world::World,
+};
+
+/// The memory held by the storages of a [`World`], as returned by [`World::memory_report`].
+///
+/// The memory of each component is counted twice: once in the [`Table`] or sparse set storing
+/// it, and once in [`MemoryReport::components`]. [`MemoryReport::total`] only counts it once.
+///
+/// [`Table`]: crate::storage::Table
+#[derive(Debug, Clone, Default)]
+pub struct MemoryReport {
+    /// The memory held by each [`Archetype`](crate::archetype::Archetype), for its bookkeeping.
+    pub archetypes: Vec<ArchetypeMemoryUsage>,
+    /// The memory held by each [`Table`](crate::storage::Table), including its columns.
+    pub tables: Vec<TableMemoryUsage>,
+    /// The memory held by the values of each component type, summed over all of its storages,
+    /// from the largest allocation to the smallest.
+    pub components: Vec<ComponentMemoryUsage>,
+    /// The memory held by each resource, including `!Send` ones, from the largest allocation to
+    /// the smallest.
+    pub resources: Vec<ResourceMemoryUsage>,
+}
+
+/// The memory held by an [`Archetype`](crate::archetype::Archetype) in a [`MemoryReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchetypeMemoryUsage {
+    /// The id of the archetype.
+    pub id: ArchetypeId,
+    /// The number of entities in the archetype.
+    pub entities: usize,
+    /// The memory held by the archetype.
+    pub usage: MemoryUsage,
+}
+
+/// The memory held by a [`Table`](crate::storage::Table) in a [`MemoryReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableMemoryUsage {
+    /// The id of the table.
+    pub id: TableId,
+    /// The number of entities in the table.
+    pub entities: usize,
+    /// The memory held by the table.
+    pub usage: MemoryUsage,
+}
+
+/// The memory held by the values of a component type in a [`MemoryReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentMemoryUsage {
+    /// The id of the component.
+    pub id: ComponentId,
+    /// The name of the component.
+    pub name: DebugName,
+    /// How the component is stored.
+    pub storage_type: StorageType,
+    /// The memory held by the component's storages.
+    pub usage: MemoryUsage,
+}
+
+/// The memory held by a resource in a [`MemoryReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceMemoryUsage {
+    /// The id of the resource.
+    pub id: ComponentId,
+    /// The name of the resource.
+    pub name: DebugName,
+    /// The memory held by the resource.
+    pub usage: MemoryUsage,
+}
+
+impl MemoryReport {
+    /// Returns the memory held by all the storages in the report.
+    pub fn total(&self) -> MemoryUsage {
+        let mut total = MemoryUsage::default();
+        for archetype in &self.archetypes {
+            total += archetype.usage;
+        }
+        for table in &self.tables {
+            total += table.usage;
+        }
+        // Table components are already counted in their tables.
+        for component in &self.components {
+            if component.storage_type != StorageType::Table {
+                total += component.usage;
+            }
+        }
+        for resource in &self.resources {
+            total += resource.usage;
+        }
+        total
+    }
+}
+
+impl World {
+    /// Reports how much memory is held by each archetype, table, component type and resource
+    /// of this world.
+    ///
+    /// Only the memory owned by the storages is reported: memory that components and resources
+    /// allocate on their own, such as the buffer of a `Vec`, is not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component)]
+    /// struct Position(f32, f32);
+    ///
+    /// let mut world = World::new();
+    /// world.spawn_batch((0..100).map(|i| Position(i as f32, 0.)));
+    ///
+    /// let report = world.memory_report();
+    /// let position = world.component_id::<Position>().unwrap();
+    /// let usage = report.components.iter().find(|c| c.id == position).unwrap().usage;
+    /// assert!(usage.used >= 100 * size_of::<Position>());
+    /// assert!(usage.allocated >= usage.used);
+    /// ```
+    pub fn memory_report(&self) -> MemoryReport {
+        let archetypes = self
+            .archetypes
+            .iter()
+            .map(|archetype| ArchetypeMemoryUsage {
+                id: archetype.id(),
+                entities: archetype.len() as usize,
+                usage: archetype.memory_usage(),
+            })
+            .collect();
+
+        let mut components = HashMap::<ComponentId, MemoryUsage>::default();
+        let tables = self
+            .storages
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(index, table)| {
+                for (id, usage) in table.column_memory_usage() {
+                    *components.entry(id).or_default() += usage;
+                }
+                TableMemoryUsage {
+                    id: TableId::from_usize(index),
+                    entities: table.entity_count() as usize,
+                    usage: table.memory_usage(),
+                }
+            })
+            .collect();
+        for (id, sparse_set) in self.storages.sparse_sets.iter() {
+            *components.entry(id).or_default() += sparse_set.memory_usage();
+        }
+        for (id, values) in self.storages.shared_components.iter() {
+            *components.entry(id).or_default() += values.memory_usage();
+        }
+
+        let mut components: Vec<_> = components
+            .into_iter()
+            .filter_map(|(id, usage)| {
+                let info = self.components.get_info(id)?;
+                Some(ComponentMemoryUsage {
+                    id,
+                    name: info.name(),
+                    storage_type: info.storage_type(),
+                    usage,
+                })
+            })
+            .collect();
+        components.sort_by_key(|component| Reverse(component.usage.allocated));
+
+        let resources = self
+            .storages
+            .resources
+            .iter()
+            .map(|(id, data)| (id, data.memory_usage()))
+            .chain(
+                self.storages
+                    .non_send_resources
+                    .iter()
+                    .map(|(id, data)| (id, data.memory_usage())),
+            );
+        let mut resources: Vec<_> = resources
+            .filter_map(|(id, usage)| {
+                Some(ResourceMemoryUsage {
+                    id,
+                    name: self.components.get_info(id)?.name(),
+                    usage,
+                })
+            })
+            .collect();
+        resources.sort_by_key(|resource| Reverse(resource.usage.allocated));
+
+        MemoryReport {
+            archetypes,
+            tables,
+            components,
+            resources,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{component::StorageType, prelude::*, storage::MemoryUsage};
+    use alloc::vec::Vec;
+
+    #[expect(dead_code, reason = "Only the size of the value matters.")]
+    #[derive(Component)]
+    struct Position([u64; 2]);
+
+    #[expect(dead_code, reason = "Only the size of the value matters.")]
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct Marker(u32);
+
+    #[expect(dead_code, reason = "Only the size of the value matters.")]
+    #[derive(Resource)]
+    struct Buffer([u8; 64]);
+
+    #[test]
+    fn reports_components_and_resources() {
+        let mut world = World::new();
+        world.spawn_batch((0..10).map(|i| Position([i; 2])));
+        world.spawn((Position([0; 2]), Marker(0)));
+        world.insert_resource(Buffer([0; 64]));
+
+        let report = world.memory_report();
+        let position = world.component_id::<Position>().unwrap();
+        let position = report.components.iter().find(|c| c.id == position).unwrap();
+        assert_eq!(position.storage_type, StorageType::Table);
+        assert!(position.usage.used >= 11 * size_of::<Position>());
+        assert!(position.usage.allocated >= position.usage.used);
+
+        let marker = world.component_id::<Marker>().unwrap();
+        let marker = report.components.iter().find(|c| c.id == marker).unwrap();
+        assert_eq!(marker.storage_type, StorageType::SparseSet);
+        assert!(marker.usage.used >= size_of::<Marker>());
+
+        let buffer = world.resource_id::<Buffer>().unwrap();
+        let buffer = report.resources.iter().find(|r| r.id == buffer).unwrap();
+        assert_eq!(
+            buffer.usage,
+            MemoryUsage {
+                used: 64,
+                allocated: 64
+            }
+        );
+
+        let tables = report.tables.iter().map(|table| table.usage);
+        assert!(report.total().allocated >= tables.sum::<MemoryUsage>().allocated + 64);
+    }
+
+    #[test]
+    fn reports_unused_capacity() {
+        let mut world = World::new();
+        let entities: Vec<_> = world
+            .spawn_batch((0..64).map(|i| Position([i; 2])))
+            .collect();
+        for entity in entities.into_iter().skip(1) {
+            world.despawn(entity);
+        }
+
+        let position = world.component_id::<Position>().unwrap();
+        let report = world.memory_report();
+        let usage = report
+            .components
+            .iter()
+            .find(|c| c.id == position)
+            .unwrap()
+            .usage;
+        assert!(usage.unused() >= 63 * size_of::<Position>());
+    }
+}
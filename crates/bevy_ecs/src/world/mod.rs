@@ -1,12 +1,16 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
 pub(crate) mod command_queue;
+pub mod compaction;
 mod deferred_world;
 mod entity_fetch;
 mod entity_ref;
 pub mod error;
 mod filtered_resource;
 mod identifier;
+pub mod memory_report;
+mod shared;
+pub mod snapshot;
 mod spawn_batch;
 pub mod unsafe_world_cell;
 
@@ -1314,6 +1318,10 @@ impl World {
     /// #
     /// # assert_eq!(world.get::<Foo>(entity), Some(&Foo(true)));
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is a [`Shared`](crate::component::StorageType::Shared) component.
     #[inline]
     #[track_caller]
     pub fn modify_component<T: Component, R>(
@@ -1347,6 +1355,10 @@ impl World {
     ///
     /// You should prefer the typed [`modify_component`](World::modify_component)
     /// whenever possible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component is a [`Shared`](crate::component::StorageType::Shared) component.
     #[inline]
     #[track_caller]
     pub fn modify_component_by_id<R>(
@@ -3011,6 +3023,7 @@ impl World {
         let Storages {
             ref mut tables,
             ref mut sparse_sets,
+            ref mut shared_components,
             ref mut resources,
             ref mut non_send_resources,
         } = self.storages;
@@ -3019,6 +3032,7 @@ impl World {
         let _span = tracing::info_span!("check component ticks").entered();
         tables.check_change_ticks(check);
         sparse_sets.check_change_ticks(check);
+        shared_components.check_change_ticks(check);
         resources.check_change_ticks(check);
         non_send_resources.check_change_ticks(check);
         self.entities.check_change_ticks(check);
@@ -0,0 +1,518 @@
+//! Inserting and replacing the values of [`Shared`](crate::component::StorageType::Shared)
+//! components.
+
+use alloc::vec::Vec;
+use bevy_platform::hash::FixedHasher;
+use bevy_ptr::OwningPtr;
+use core::hash::{BuildHasher, Hash};
+
+use crate::{
+    archetype::{ArchetypeCreated, ArchetypeId},
+    change_detection::MaybeLocation,
+    component::{Component, ComponentId, Immutable, StorageType},
+    entity::{Entity, EntityLocation},
+    lifecycle::{ADD, INSERT, REPLACE},
+    relationship::RelationshipHookMode,
+    storage::SharedValueId,
+    world::World,
+};
+
+impl World {
+    /// Returns the id of the stored value of the shared component `T` equal to `value`, storing
+    /// `value` first if there is none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is not a [`Shared`](StorageType::Shared) component.
+    pub(crate) fn store_shared_value<T: Component<Mutability = Immutable> + Eq + Hash>(
+        &mut self,
+        value: T,
+        caller: MaybeLocation,
+    ) -> (ComponentId, SharedValueId) {
+        assert!(
+            T::STORAGE_TYPE == StorageType::Shared,
+            "{} is not a shared component",
+            core::any::type_name::<T>()
+        );
+        let component_id = self.register_component::<T>();
+        let change_tick = self.change_tick();
+        // SAFETY: The component was just registered.
+        let info = unsafe { self.components.get_info_unchecked(component_id) };
+        let values = self.storages.shared_components.get_or_insert(info);
+        let hash = FixedHasher.hash_one(&value);
+        // SAFETY: `values` stores the values of `T`.
+        if let Some(id) = unsafe { values.find(hash, &value) } {
+            return (component_id, id);
+        }
+        let id = OwningPtr::make(value, |ptr| {
+            // SAFETY: `ptr` points to a `T`, and no equal value is stored.
+            unsafe { values.push(hash, ptr, change_tick, caller) }
+        });
+        (component_id, id)
+    }
+
+    /// Sets the value of the shared component `component_id` of `entity` to `value`, moving the
+    /// entity to the archetype with that value, and returns its new location.
+    ///
+    /// This triggers the `Replace` hooks and observers if the entity already had the component,
+    /// the `Add` ones if it did not, then the `Insert` ones. Commands are not flushed.
+    ///
+    /// # Safety
+    /// - `location` must be the location of `entity`.
+    /// - `component_id` must be a [`Shared`](StorageType::Shared) component of this world.
+    /// - `value` must be a value of its [`SharedComponentValues`](crate::storage::SharedComponentValues).
+    pub(crate) unsafe fn set_shared_value(
+        &mut self,
+        entity: Entity,
+        location: EntityLocation,
+        component_id: ComponentId,
+        value: SharedValueId,
+        caller: MaybeLocation,
+        relationship_hook_mode: RelationshipHookMode,
+    ) -> EntityLocation {
+        let existed = self.archetypes[location.archetype_id].contains(component_id);
+        if existed {
+            let world = self.as_unsafe_world_cell();
+            // SAFETY: Hooks and observers can't move entities between archetypes, and the
+            // component exists on the entity.
+            unsafe {
+                let archetype = &world.archetypes()[location.archetype_id];
+                let mut deferred_world = world.into_deferred();
+                if archetype.has_replace_observer() {
+                    deferred_world.trigger_observers(
+                        REPLACE,
+                        Some(entity),
+                        [component_id].into_iter(),
+                        caller,
+                    );
+                }
+                deferred_world.trigger_on_replace(
+                    archetype,
+                    entity,
+                    [component_id].into_iter(),
+                    caller,
+                    relationship_hook_mode,
+                );
+            }
+        }
+
+        let old_archetype = &self.archetypes[location.archetype_id];
+        let table_id = old_archetype.table_id();
+        let table_components = old_archetype.table_components().collect();
+        let sparse_set_components = old_archetype.sparse_set_components().collect();
+        let mut shared_components: Vec<_> = old_archetype
+            .shared_components()
+            .filter(|&(id, _)| id != component_id)
+            .collect();
+        shared_components.push((component_id, value));
+        // Sort to ignore order while hashing.
+        shared_components.sort_unstable();
+        // SAFETY: The components of the entity's archetype and `component_id` exist, as does
+        // `value`, and the table is the one of the entity's archetype.
+        let (new_archetype_id, is_new_created) = unsafe {
+            self.archetypes.get_id_or_insert(
+                &self.components,
+                &self.observers,
+                table_id,
+                table_components,
+                sparse_set_components,
+                shared_components,
+            )
+        };
+
+        let new_location = if new_archetype_id == location.archetype_id {
+            location
+        } else {
+            let remove_result =
+                self.archetypes[location.archetype_id].swap_remove(location.archetype_row);
+            // If an entity was moved into this entity's archetype row, update its archetype row.
+            if let Some(swapped_entity) = remove_result.swapped_entity {
+                let swapped_location = self.entities.get(swapped_entity).unwrap();
+                // SAFETY: The swapped entity was just moved to this row.
+                unsafe {
+                    self.entities.set(
+                        swapped_entity.index(),
+                        Some(EntityLocation {
+                            archetype_row: location.archetype_row,
+                            ..swapped_location
+                        }),
+                    );
+                }
+            }
+            // SAFETY: Both archetypes store their table components in the same table, so the
+            // entity's components stay where they are.
+            let new_location =
+                unsafe { self.archetypes[new_archetype_id].allocate(entity, location.table_row) };
+            // SAFETY: The entity was just moved to this location.
+            unsafe { self.entities.set(entity.index(), Some(new_location)) };
+            new_location
+        };
+
+        let world = self.as_unsafe_world_cell();
+        // SAFETY: Hooks and observers can't move entities between archetypes, and the component
+        // exists on the entity.
+        unsafe {
+            let archetype = &world.archetypes()[new_archetype_id];
+            let mut deferred_world = world.into_deferred();
+            if is_new_created {
+                deferred_world.trigger(ArchetypeCreated(new_archetype_id));
+            }
+            if !existed {
+                deferred_world.trigger_on_add(
+                    archetype,
+                    entity,
+                    [component_id].into_iter(),
+                    caller,
+                );
+                if archetype.has_add_observer() {
+                    deferred_world.trigger_observers(
+                        ADD,
+                        Some(entity),
+                        [component_id].into_iter(),
+                        caller,
+                    );
+                }
+            }
+            deferred_world.trigger_on_insert(
+                archetype,
+                entity,
+                [component_id].into_iter(),
+                caller,
+                relationship_hook_mode,
+            );
+            if archetype.has_insert_observer() {
+                deferred_world.trigger_observers(
+                    INSERT,
+                    Some(entity),
+                    [component_id].into_iter(),
+                    caller,
+                );
+            }
+        }
+
+        new_location
+    }
+
+    /// Replaces the value `old` of the [`Shared`](StorageType::Shared) component `T` by `new`,
+    /// for all the entities that have it.
+    ///
+    /// If no entity has the value `new` yet, the value is replaced in place, without moving any
+    /// entity. Otherwise, the entities with the value `old` are moved to the archetypes of the
+    /// entities with the value `new`.
+    ///
+    /// Either way, the `Replace` and `Insert` hooks and observers are triggered for each of the
+    /// entities that had the value `old`.
+    ///
+    /// Returns `false` if `old` is not a value of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component, PartialEq, Eq, Hash, Debug)]
+    /// #[component(storage = "Shared")]
+    /// struct Team(u32);
+    ///
+    /// let mut world = World::new();
+    /// let a = world.spawn_empty().insert_shared(Team(1)).id();
+    /// let b = world.spawn_empty().insert_shared(Team(1)).id();
+    ///
+    /// assert!(world.replace_shared(&Team(1), Team(2)));
+    /// assert_eq!(world.get::<Team>(a), Some(&Team(2)));
+    /// assert_eq!(world.get::<Team>(b), Some(&Team(2)));
+    /// ```
+    #[track_caller]
+    pub fn replace_shared<T: Component<Mutability = Immutable> + Eq + Hash>(
+        &mut self,
+        old: &T,
+        new: T,
+    ) -> bool {
+        let caller = MaybeLocation::caller();
+        let Some(component_id) = self.component_id::<T>() else {
+            return false;
+        };
+        let Some(values) = self.storages.shared_components.get(component_id) else {
+            return false;
+        };
+        // SAFETY: `values` stores the values of `T`.
+        let Some(old_id) = (unsafe { values.find(FixedHasher.hash_one(old), old) }) else {
+            return false;
+        };
+        let new_hash = FixedHasher.hash_one(&new);
+        // SAFETY: `values` stores the values of `T`.
+        let new_id = unsafe { values.find(new_hash, &new) };
+
+        let entities: Vec<(Entity, ArchetypeId)> = self
+            .archetypes
+            .iter()
+            .filter(|archetype| archetype.get_shared_value(component_id) == Some(old_id))
+            .flat_map(|archetype| {
+                archetype
+                    .entities()
+                    .iter()
+                    .map(|entity| (entity.id(), archetype.id()))
+            })
+            .collect();
+
+        match new_id {
+            Some(new_id) if new_id != old_id => {
+                for (entity, _) in entities {
+                    let location = self.entities.get(entity).unwrap();
+                    // SAFETY: `location` is the entity's, `component_id` is a shared component
+                    // and `new_id` one of its values.
+                    unsafe {
+                        self.set_shared_value(
+                            entity,
+                            location,
+                            component_id,
+                            new_id,
+                            caller,
+                            RelationshipHookMode::Run,
+                        );
+                    }
+                }
+            }
+            _ => {
+                let world = self.as_unsafe_world_cell();
+                // SAFETY: Hooks and observers can't move entities between archetypes, and the
+                // component exists on the entities.
+                unsafe {
+                    let mut deferred_world = world.into_deferred();
+                    for &(entity, archetype_id) in &entities {
+                        let archetype = &world.archetypes()[archetype_id];
+                        if archetype.has_replace_observer() {
+                            deferred_world.trigger_observers(
+                                REPLACE,
+                                Some(entity),
+                                [component_id].into_iter(),
+                                caller,
+                            );
+                        }
+                        deferred_world.trigger_on_replace(
+                            archetype,
+                            entity,
+                            [component_id].into_iter(),
+                            caller,
+                            RelationshipHookMode::Run,
+                        );
+                    }
+                }
+
+                let change_tick = self.change_tick();
+                let values = self
+                    .storages
+                    .shared_components
+                    .get_mut(component_id)
+                    .unwrap();
+                OwningPtr::make(new, |ptr| {
+                    // SAFETY: `old_id` is a value of `values`, `ptr` points to a `T`, and the only
+                    // stored value that may be equal to `new` is `old_id`'s.
+                    unsafe { values.replace(old_id, new_hash, ptr, change_tick, caller) };
+                });
+
+                let world = self.as_unsafe_world_cell();
+                // SAFETY: Hooks and observers can't move entities between archetypes, and the
+                // component exists on the entities.
+                unsafe {
+                    let mut deferred_world = world.into_deferred();
+                    for &(entity, archetype_id) in &entities {
+                        let archetype = &world.archetypes()[archetype_id];
+                        deferred_world.trigger_on_insert(
+                            archetype,
+                            entity,
+                            [component_id].into_iter(),
+                            caller,
+                            RelationshipHookMode::Run,
+                        );
+                        if archetype.has_insert_observer() {
+                            deferred_world.trigger_observers(
+                                INSERT,
+                                Some(entity),
+                                [component_id].into_iter(),
+                                caller,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        self.flush();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{component::RequiredComponentsError, prelude::*};
+    use alloc::vec::Vec;
+    use bevy_ptr::OwningPtr;
+
+    #[derive(Component, PartialEq, Eq, Hash, Debug)]
+    #[component(storage = "Shared")]
+    struct Team(u32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Health(u32);
+
+    #[derive(Resource, Default)]
+    struct Replaced(u32);
+
+    #[test]
+    fn entities_with_equal_values_share_an_archetype() {
+        let mut world = World::new();
+        let a = world.spawn(Health(1)).insert_shared(Team(1)).id();
+        let b = world.spawn(Health(2)).insert_shared(Team(1)).id();
+        let c = world.spawn(Health(3)).insert_shared(Team(2)).id();
+
+        let archetype =
+            |world: &World, entity: Entity| world.entity(entity).location().archetype_id;
+        assert_eq!(archetype(&world, a), archetype(&world, b));
+        assert_ne!(archetype(&world, a), archetype(&world, c));
+        assert_eq!(
+            world
+                .storages()
+                .shared_components
+                .get(world.component_id::<Team>().unwrap())
+                .unwrap()
+                .len(),
+            2
+        );
+
+        assert_eq!(world.get::<Team>(a), Some(&Team(1)));
+        assert_eq!(world.get::<Team>(c), Some(&Team(2)));
+        assert_eq!(world.get::<Health>(b), Some(&Health(2)));
+
+        let mut query = world.query::<(Entity, &Team, &Health)>();
+        let mut results: Vec<_> = query
+            .iter(&world)
+            .map(|(entity, team, health)| (entity, team.0, health.0))
+            .collect();
+        results.sort_by_key(|&(_, _, health)| health);
+        assert_eq!(results, [(a, 1, 1), (b, 1, 2), (c, 2, 3)]);
+    }
+
+    #[test]
+    fn insert_shared_moves_to_new_value() {
+        let mut world = World::new();
+        let a = world.spawn(Health(1)).insert_shared(Team(1)).id();
+        world.entity_mut(a).insert_shared(Team(2));
+        assert_eq!(world.get::<Team>(a), Some(&Team(2)));
+        assert_eq!(world.get::<Health>(a), Some(&Health(1)));
+
+        world.entity_mut(a).remove::<Team>();
+        assert_eq!(world.get::<Team>(a), None);
+        assert_eq!(world.get::<Health>(a), Some(&Health(1)));
+    }
+
+    #[test]
+    fn replace_shared_in_place_and_merge() {
+        let mut world = World::new();
+        world.init_resource::<Replaced>();
+        world.add_observer(|_: On<Replace, Team>, mut replaced: ResMut<Replaced>| {
+            replaced.0 += 1;
+        });
+        let a = world.spawn_empty().insert_shared(Team(1)).id();
+        let b = world.spawn_empty().insert_shared(Team(1)).id();
+        let c = world.spawn_empty().insert_shared(Team(3)).id();
+
+        assert!(world.replace_shared(&Team(1), Team(2)));
+        assert_eq!(world.get::<Team>(a), Some(&Team(2)));
+        assert_eq!(world.get::<Team>(b), Some(&Team(2)));
+        assert_eq!(world.resource::<Replaced>().0, 2);
+
+        assert!(world.replace_shared(&Team(2), Team(3)));
+        assert_eq!(world.get::<Team>(a), Some(&Team(3)));
+        assert_eq!(
+            world.entity(a).location().archetype_id,
+            world.entity(c).location().archetype_id
+        );
+        assert_eq!(world.resource::<Replaced>().0, 4);
+
+        assert!(!world.replace_shared(&Team(5), Team(6)));
+    }
+
+    #[test]
+    fn change_detection_is_per_value() {
+        let mut world = World::new();
+        world.spawn_empty().insert_shared(Team(1));
+        world.spawn_empty().insert_shared(Team(2));
+
+        let mut refs = world.query::<Ref<Team>>();
+        let mut changed = |world: &World| {
+            let mut changed: Vec<_> = refs
+                .iter(world)
+                .map(|team| (team.0, team.is_added(), team.is_changed()))
+                .collect();
+            changed.sort();
+            changed
+        };
+        assert_eq!(changed(&world), [(1, true, true), (2, true, true)]);
+        world.clear_trackers();
+
+        world.replace_shared(&Team(2), Team(3));
+        // Joining an existing value doesn't add or change it.
+        world.spawn_empty().insert_shared(Team(1));
+        assert_eq!(
+            changed(&world),
+            [(1, false, false), (1, false, false), (3, false, true)]
+        );
+    }
+
+    #[test]
+    fn clone_keeps_shared_value() {
+        let mut world = World::new();
+        let a = world.spawn(Health(1)).insert_shared(Team(1)).id();
+        let b = world.entity_mut(a).clone_and_spawn();
+        assert_eq!(world.get::<Team>(b), Some(&Team(1)));
+        assert_eq!(world.get::<Health>(b), Some(&Health(1)));
+        assert_eq!(
+            world.entity(a).location().archetype_id,
+            world.entity(b).location().archetype_id
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be inserted as part of a bundle")]
+    fn shared_component_in_bundle_panics() {
+        let mut world = World::new();
+        world.spawn((Health(1), Team(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be inserted as part of a bundle")]
+    fn shared_component_inserted_by_id_panics() {
+        let mut world = World::new();
+        let team = world.register_component::<Team>();
+        let mut entity = world.spawn_empty();
+        OwningPtr::make(Team(1), |ptr| {
+            // SAFETY: `ptr` points to a `Team`, the component of `team`.
+            unsafe { entity.insert_by_id(team, ptr) };
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be a required component")]
+    fn shared_component_in_derived_requirements_panics() {
+        #[derive(Component)]
+        #[require(Team(0))]
+        struct Member;
+
+        let mut world = World::new();
+        world.register_component::<Member>();
+    }
+
+    #[test]
+    fn shared_component_in_runtime_requirements_is_rejected() {
+        #[derive(Component, PartialEq, Eq, Hash, Default)]
+        #[component(storage = "Shared")]
+        struct Faction(u32);
+
+        let mut world = World::new();
+        assert!(matches!(
+            world.try_register_required_components::<Health, Faction>(),
+            Err(RequiredComponentsError::SharedRequirement(_))
+        ));
+    }
+}
@@ -0,0 +1,649 @@
+//! Snapshots of a [`World`], to roll it back to an earlier state.
+//!
+//! [`World::snapshot`] captures the entity allocator, the entities that are alive, and the values of
+//! the components and resources that opted in with [`World::register_snapshot_component`],
+//! [`World::register_snapshot_resource`] or (with reflection) [`World::register_snapshot_reflect`].
+//! [`World::restore_snapshot`] then brings the world back to that state in place:
+//!
+//! - entities spawned since the snapshot are despawned,
+//! - entities despawned since the snapshot are respawned with the same [`Entity`] id,
+//! - opted-in components and resources are set back to their captured values, and removed if they
+//!   were not present,
+//! - the entity allocator is reset, so that the same ids are handed out again.
+//!
+//! Components and resources that did not opt in are left untouched on the entities that are kept.
+//!
+//! Values are written back through the regular insertion path, so component hooks and observers run
+//! for components that get inserted or replaced, except for relationship hooks: both sides of a
+//! relationship are expected to be captured, like [`ChildOf`](crate::hierarchy::ChildOf) and
+//! [`Children`](crate::hierarchy::Children) (which only supports the reflection path).
+//!
+//! Restored values are flagged as changed at the current change tick, so that systems observe the
+//! rollback. Components and resources that were present both in the world and in the snapshot
+//! keep the tick they were added at from the snapshot.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::any::{Any, TypeId};
+
+use thiserror::Error;
+
+use crate::{
+    archetype::ArchetypeEntity,
+    change_detection::{DetectChangesMut, MaybeLocation},
+    component::{CheckChangeTicks, Component, ComponentId, ComponentMutability, StorageType, Tick},
+    entity::{EntitiesSnapshot, Entity, EntityHashSet},
+    relationship::RelationshipHookMode,
+    resource::Resource,
+    world::{EntityRef, EntityWorldMut, World, WorldId},
+};
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    crate::{
+        entity::EntityHashMap,
+        reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    },
+    bevy_reflect::{PartialReflect, Reflect},
+};
+
+type CapturedValue = Box<dyn Any + Send + Sync>;
+
+/// How a component is captured by a [`WorldSnapshot`].
+#[derive(Clone)]
+enum ComponentSnapshotFns {
+    Clone {
+        capture: fn(EntityRef) -> Option<CapturedValue>,
+        restore: fn(&mut EntityWorldMut, &(dyn Any + Send + Sync)),
+    },
+    #[cfg(feature = "bevy_reflect")]
+    Reflect(ReflectComponent),
+}
+
+/// How a resource is captured by a [`WorldSnapshot`].
+#[derive(Clone)]
+enum ResourceSnapshotFns {
+    Clone {
+        capture: fn(&World) -> Option<CapturedValue>,
+        restore: fn(&mut World, &(dyn Any + Send + Sync)),
+    },
+    #[cfg(feature = "bevy_reflect")]
+    Reflect(ReflectResource),
+}
+
+/// The [`Resource`] listing the components and resources captured by [`World::snapshot`].
+///
+/// Add to it with [`World::register_snapshot_component`], [`World::register_snapshot_resource`]
+/// and [`World::register_snapshot_reflect`].
+#[derive(Resource, Default, Clone)]
+pub struct SnapshotRegistry {
+    components: Vec<(ComponentId, ComponentSnapshotFns)>,
+    resources: Vec<(ComponentId, ResourceSnapshotFns)>,
+}
+
+impl SnapshotRegistry {
+    /// Returns the ids of the components captured by snapshots.
+    pub fn components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components.iter().map(|(id, _)| *id)
+    }
+
+    /// Returns the ids of the resources captured by snapshots.
+    pub fn resources(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.resources.iter().map(|(id, _)| *id)
+    }
+
+    fn component_fns(&self, id: ComponentId) -> Option<&ComponentSnapshotFns> {
+        self.components
+            .iter()
+            .find_map(|(component_id, fns)| (*component_id == id).then_some(fns))
+    }
+
+    fn register_component(&mut self, id: ComponentId, fns: ComponentSnapshotFns) {
+        self.components
+            .retain(|(component_id, _)| *component_id != id);
+        self.components.push((id, fns));
+    }
+
+    fn register_resource(&mut self, id: ComponentId, fns: ResourceSnapshotFns) {
+        self.resources
+            .retain(|(component_id, _)| *component_id != id);
+        self.resources.push((id, fns));
+    }
+}
+
+/// A value captured by a [`WorldSnapshot`].
+enum SnapshotValue {
+    Clone(CapturedValue),
+    #[cfg(feature = "bevy_reflect")]
+    Reflect(Box<dyn PartialReflect>),
+}
+
+struct CapturedComponent {
+    id: ComponentId,
+    added: Tick,
+    value: SnapshotValue,
+}
+
+/// The state of a [`World`] captured by [`World::snapshot`], which can be restored with
+/// [`World::restore_snapshot`].
+///
+/// See the [module docs](crate::world::snapshot) for what is captured.
+pub struct WorldSnapshot {
+    world_id: WorldId,
+    allocator: EntitiesSnapshot,
+    entities: Vec<(Entity, Vec<CapturedComponent>)>,
+    resources: Vec<CapturedComponent>,
+}
+
+impl WorldSnapshot {
+    /// Returns the id of the [`World`] this snapshot was taken from.
+    pub fn world_id(&self) -> WorldId {
+        self.world_id
+    }
+
+    /// Returns the entities that were alive when this snapshot was taken.
+    pub fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + '_ {
+        self.entities.iter().map(|(entity, _)| *entity)
+    }
+}
+
+/// An error returned when registering snapshot types or restoring a [`WorldSnapshot`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WorldSnapshotError {
+    /// The snapshot was taken from another [`World`].
+    #[error("The snapshot was taken from {snapshot:?} and cannot be restored in {world:?}")]
+    WorldMismatch {
+        /// The world the snapshot was taken from.
+        snapshot: WorldId,
+        /// The world the snapshot was restored in.
+        world: WorldId,
+    },
+    /// The world has no `AppTypeRegistry`, which reflected components and resources require.
+    #[error("Reflected snapshot types require the `AppTypeRegistry` resource")]
+    MissingTypeRegistry,
+    /// The type is registered neither with `ReflectComponent` nor with `ReflectResource`.
+    #[error("Type {0:?} is registered neither with `ReflectComponent` nor with `ReflectResource`")]
+    NotReflected(TypeId),
+}
+
+fn capture_component<C: Component + Clone>(entity: EntityRef) -> Option<CapturedValue> {
+    entity
+        .get::<C>()
+        .map(|component| Box::new(component.clone()) as CapturedValue)
+}
+
+fn restore_component<C: Component + Clone>(
+    entity: &mut EntityWorldMut,
+    value: &(dyn Any + Send + Sync),
+) {
+    let Some(value) = value.downcast_ref::<C>() else {
+        return;
+    };
+    if C::Mutability::MUTABLE {
+        // SAFETY: `C` is a mutable component.
+        if let Some(mut component) = unsafe { entity.get_mut_assume_mutable::<C>() } {
+            *component = value.clone();
+            return;
+        }
+    }
+    entity.insert_with_relationship_hook_mode(value.clone(), RelationshipHookMode::Skip);
+}
+
+fn capture_resource<R: Resource + Clone>(world: &World) -> Option<CapturedValue> {
+    world
+        .get_resource::<R>()
+        .map(|resource| Box::new(resource.clone()) as CapturedValue)
+}
+
+fn restore_resource<R: Resource + Clone>(world: &mut World, value: &(dyn Any + Send + Sync)) {
+    if let Some(value) = value.downcast_ref::<R>() {
+        world.insert_resource(value.clone());
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+fn clone_reflect(value: &dyn Reflect) -> Box<dyn PartialReflect> {
+    value
+        .reflect_clone()
+        .map(PartialReflect::into_partial_reflect)
+        .unwrap_or_else(|_| value.to_dynamic())
+}
+
+impl World {
+    /// Captures the component `C` in the snapshots of this world.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` is a [`Shared`](StorageType::Shared) component, which can't be written back
+    /// through the regular insertion path.
+    pub fn register_snapshot_component<C: Component + Clone>(&mut self) -> &mut Self {
+        assert!(
+            C::STORAGE_TYPE != StorageType::Shared,
+            "Shared components can't be captured in world snapshots"
+        );
+        let id = self.register_component::<C>();
+        self.get_resource_or_init::<SnapshotRegistry>()
+            .register_component(
+                id,
+                ComponentSnapshotFns::Clone {
+                    capture: capture_component::<C>,
+                    restore: restore_component::<C>,
+                },
+            );
+        self
+    }
+
+    /// Captures the resource `R` in the snapshots of this world.
+    pub fn register_snapshot_resource<R: Resource + Clone>(&mut self) -> &mut Self {
+        let id = self.register_resource::<R>();
+        self.get_resource_or_init::<SnapshotRegistry>()
+            .register_resource(
+                id,
+                ResourceSnapshotFns::Clone {
+                    capture: capture_resource::<R>,
+                    restore: restore_resource::<R>,
+                },
+            );
+        self
+    }
+
+    /// Captures the component or resource with the given [`TypeId`] in the snapshots of this world,
+    /// using its [`ReflectComponent`] or [`ReflectResource`] registration in the [`AppTypeRegistry`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn register_snapshot_reflect(
+        &mut self,
+        type_id: TypeId,
+    ) -> Result<&mut Self, WorldSnapshotError> {
+        let type_registry = self
+            .get_resource::<AppTypeRegistry>()
+            .ok_or(WorldSnapshotError::MissingTypeRegistry)?
+            .clone();
+        let type_registry = type_registry.read();
+        if let Some(reflect_component) = type_registry.get_type_data::<ReflectComponent>(type_id) {
+            let id = reflect_component.register_component(self);
+            self.get_resource_or_init::<SnapshotRegistry>()
+                .register_component(id, ComponentSnapshotFns::Reflect(reflect_component.clone()));
+        } else if let Some(reflect_resource) =
+            type_registry.get_type_data::<ReflectResource>(type_id)
+        {
+            let id = reflect_resource.register_resource(self);
+            self.get_resource_or_init::<SnapshotRegistry>()
+                .register_resource(id, ResourceSnapshotFns::Reflect(reflect_resource.clone()));
+        } else {
+            return Err(WorldSnapshotError::NotReflected(type_id));
+        }
+        Ok(self)
+    }
+
+    /// Captures the current state of this world.
+    ///
+    /// See the [module docs](crate::world::snapshot) for what is captured.
+    pub fn snapshot(&mut self) -> WorldSnapshot {
+        self.flush();
+        let registry = self
+            .get_resource::<SnapshotRegistry>()
+            .cloned()
+            .unwrap_or_default();
+
+        let mut entities = Vec::new();
+        for archetype in self.archetypes.iter() {
+            let components = registry
+                .components
+                .iter()
+                .filter(|(id, _)| archetype.contains(*id))
+                .collect::<Vec<_>>();
+            for entity in archetype.entities().iter().map(ArchetypeEntity::id) {
+                let entity_ref = self.entity(entity);
+                let captured = components
+                    .iter()
+                    .filter_map(|(id, fns)| {
+                        let added = entity_ref.get_change_ticks_by_id(*id)?.added;
+                        let value = match fns {
+                            ComponentSnapshotFns::Clone { capture, .. } => {
+                                SnapshotValue::Clone(capture(entity_ref)?)
+                            }
+                            #[cfg(feature = "bevy_reflect")]
+                            ComponentSnapshotFns::Reflect(reflect_component) => {
+                                SnapshotValue::Reflect(clone_reflect(
+                                    reflect_component.reflect(entity_ref)?,
+                                ))
+                            }
+                        };
+                        Some(CapturedComponent {
+                            id: *id,
+                            added,
+                            value,
+                        })
+                    })
+                    .collect();
+                entities.push((entity, captured));
+            }
+        }
+
+        let resources = registry
+            .resources
+            .iter()
+            .filter_map(|(id, fns)| {
+                let added = self.get_resource_change_ticks_by_id(*id)?.added;
+                let value = match fns {
+                    ResourceSnapshotFns::Clone { capture, .. } => {
+                        SnapshotValue::Clone(capture(self)?)
+                    }
+                    #[cfg(feature = "bevy_reflect")]
+                    ResourceSnapshotFns::Reflect(reflect_resource) => SnapshotValue::Reflect(
+                        clone_reflect(reflect_resource.reflect(&*self).ok()?),
+                    ),
+                };
+                Some(CapturedComponent {
+                    id: *id,
+                    added,
+                    value,
+                })
+            })
+            .collect();
+
+        WorldSnapshot {
+            world_id: self.id(),
+            allocator: self.entities.snapshot(),
+            entities,
+            resources,
+        }
+    }
+
+    /// Restores this world in place to the state captured in `snapshot`.
+    ///
+    /// See the [module docs](crate::world::snapshot) for what is restored.
+    pub fn restore_snapshot(&mut self, snapshot: &WorldSnapshot) -> Result<(), WorldSnapshotError> {
+        if snapshot.world_id != self.id() {
+            return Err(WorldSnapshotError::WorldMismatch {
+                snapshot: snapshot.world_id,
+                world: self.id(),
+            });
+        }
+        let registry = self
+            .get_resource::<SnapshotRegistry>()
+            .cloned()
+            .unwrap_or_default();
+        #[cfg(feature = "bevy_reflect")]
+        let type_registry = self.get_resource::<AppTypeRegistry>().cloned();
+        #[cfg(feature = "bevy_reflect")]
+        if type_registry.is_none()
+            && snapshot
+                .entities
+                .iter()
+                .flat_map(|(_, components)| components)
+                .chain(&snapshot.resources)
+                .any(|captured| matches!(captured.value, SnapshotValue::Reflect(_)))
+        {
+            return Err(WorldSnapshotError::MissingTypeRegistry);
+        }
+
+        // Despawn the entities spawned since the snapshot, which may spawn or despawn more entities.
+        self.flush();
+        let alive = snapshot.entities().collect::<EntityHashSet>();
+        loop {
+            let spawned = self
+                .archetypes
+                .iter()
+                .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id))
+                .filter(|entity| !alive.contains(entity))
+                .collect::<Vec<_>>();
+            if spawned.is_empty() {
+                break;
+            }
+            for entity in spawned {
+                // Despawning an entity may already have despawned the others.
+                let _ = self.try_despawn(entity);
+            }
+            self.flush();
+        }
+
+        // Every entity alive now is in the snapshot, so the allocator can be reset.
+        let check = CheckChangeTicks(self.change_tick());
+        self.entities.restore(&snapshot.allocator, check);
+        for entity in snapshot.entities() {
+            if self.entities.get(entity).is_none() {
+                // SAFETY: the entity was just restored in the allocator, without a location.
+                unsafe { self.spawn_at_empty_internal(entity, MaybeLocation::caller()) };
+            }
+        }
+
+        // Remove the captured components that were inserted since the snapshot, before writing
+        // the captured values so that their hooks do not undo any of them.
+        for (entity, components) in &snapshot.entities {
+            let Ok(mut entity_mut) = self.get_entity_mut(*entity) else {
+                continue;
+            };
+            for id in registry.components() {
+                if entity_mut.contains_id(id) && components.iter().all(|captured| captured.id != id)
+                {
+                    entity_mut.remove_by_id(id);
+                }
+            }
+        }
+        self.flush();
+
+        for (entity, components) in &snapshot.entities {
+            let Ok(mut entity_mut) = self.get_entity_mut(*entity) else {
+                continue;
+            };
+            for captured in components {
+                let Some(fns) = registry.component_fns(captured.id) else {
+                    continue;
+                };
+                let existed = entity_mut.contains_id(captured.id);
+                match (fns, &captured.value) {
+                    (ComponentSnapshotFns::Clone { restore, .. }, SnapshotValue::Clone(value)) => {
+                        restore(&mut entity_mut, value.as_ref());
+                    }
+                    #[cfg(feature = "bevy_reflect")]
+                    (
+                        ComponentSnapshotFns::Reflect(reflect_component),
+                        SnapshotValue::Reflect(value),
+                    ) => {
+                        let Some(type_registry) = &type_registry else {
+                            continue;
+                        };
+                        reflect_component.apply_or_insert_mapped(
+                            &mut entity_mut,
+                            value.as_ref(),
+                            &type_registry.read(),
+                            &mut EntityHashMap::<Entity>::default(),
+                            RelationshipHookMode::Skip,
+                        );
+                    }
+                    #[cfg(feature = "bevy_reflect")]
+                    _ => continue,
+                }
+                if existed {
+                    let mut added = captured.added;
+                    added.check_tick(check);
+                    // SAFETY: only the ticks are changed, not the value.
+                    let component = unsafe { entity_mut.get_mut_assume_mutable_by_id(captured.id) };
+                    if let Ok(mut component) = component {
+                        component.set_last_added(added);
+                        component.set_changed();
+                    }
+                }
+            }
+        }
+        self.flush();
+
+        for (id, fns) in &registry.resources {
+            let Some(captured) = snapshot
+                .resources
+                .iter()
+                .find(|captured| captured.id == *id)
+            else {
+                self.remove_resource_by_id(*id);
+                continue;
+            };
+            let existed = self.contains_resource_by_id(*id);
+            match (fns, &captured.value) {
+                (ResourceSnapshotFns::Clone { restore, .. }, SnapshotValue::Clone(value)) => {
+                    restore(self, value.as_ref());
+                }
+                #[cfg(feature = "bevy_reflect")]
+                (ResourceSnapshotFns::Reflect(reflect_resource), SnapshotValue::Reflect(value)) => {
+                    let Some(type_registry) = &type_registry else {
+                        continue;
+                    };
+                    reflect_resource.apply_or_insert(self, value.as_ref(), &type_registry.read());
+                }
+                #[cfg(feature = "bevy_reflect")]
+                _ => continue,
+            }
+            if let Some(mut resource) = self.get_resource_mut_by_id(*id) {
+                if existed {
+                    let mut added = captured.added;
+                    added.check_tick(check);
+                    resource.set_last_added(added);
+                }
+                resource.set_changed();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::WorldSnapshotError;
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Position(i32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    #[component(immutable)]
+    struct Team(u8);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct NotCaptured;
+
+    #[derive(Resource, Clone, PartialEq, Debug)]
+    struct Score(u32);
+
+    fn snapshot_world() -> World {
+        let mut world = World::new();
+        world
+            .register_snapshot_component::<Position>()
+            .register_snapshot_component::<Team>()
+            .register_snapshot_resource::<Score>();
+        world
+    }
+
+    #[test]
+    fn restore_entities_components_and_resources() {
+        let mut world = snapshot_world();
+        world.insert_resource(Score(1));
+        let a = world.spawn((Position(1), Team(1), NotCaptured)).id();
+        let b = world.spawn(Position(2)).id();
+        let snapshot = world.snapshot();
+        let next = world.spawn_empty().id();
+        world.despawn(next);
+
+        world.get_mut::<Position>(a).unwrap().0 = 10;
+        world.entity_mut(a).insert(Team(2));
+        world.entity_mut(b).insert(Team(3));
+        let c = world.spawn(Position(3)).id();
+        world.despawn(b);
+        world.insert_resource(Score(5));
+
+        world.restore_snapshot(&snapshot).unwrap();
+
+        assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+        assert_eq!(world.get::<Team>(a), Some(&Team(1)));
+        assert_eq!(world.get::<NotCaptured>(a), Some(&NotCaptured));
+        assert_eq!(world.get::<Position>(b), Some(&Position(2)));
+        assert!(!world.entity(b).contains::<Team>());
+        assert!(world.get_entity(c).is_err());
+        assert_eq!(world.resource::<Score>(), &Score(1));
+        // The allocator hands out the same ids again.
+        assert_eq!(world.spawn_empty().id(), next);
+
+        world.remove_resource::<Score>();
+        world.restore_snapshot(&snapshot).unwrap();
+        assert_eq!(world.resource::<Score>(), &Score(1));
+        assert!(world.get_entity(next).is_err());
+    }
+
+    #[test]
+    fn restore_change_ticks() {
+        let mut world = snapshot_world();
+        let a = world.spawn(Position(1)).id();
+        let added = world
+            .entity(a)
+            .get_change_ticks::<Position>()
+            .unwrap()
+            .added;
+        let snapshot = world.snapshot();
+
+        world.clear_trackers();
+        world.entity_mut(a).remove::<Position>();
+        world.clear_trackers();
+        world.restore_snapshot(&snapshot).unwrap();
+        let ticks = world.entity(a).get_change_ticks::<Position>().unwrap();
+        // The component was not present, so it is flagged as added.
+        assert_eq!(ticks.added, world.change_tick());
+
+        world.clear_trackers();
+        world.get_mut::<Position>(a).unwrap().0 = 5;
+        world.clear_trackers();
+        world.restore_snapshot(&snapshot).unwrap();
+        let ticks = world.entity(a).get_change_ticks::<Position>().unwrap();
+        assert_eq!(ticks.added, added);
+        assert!(ticks.is_changed(world.last_change_tick(), world.change_tick()));
+    }
+
+    #[test]
+    fn restore_in_another_world() {
+        let mut world = snapshot_world();
+        let snapshot = world.snapshot();
+        let mut other = snapshot_world();
+        assert!(matches!(
+            other.restore_snapshot(&snapshot),
+            Err(WorldSnapshotError::WorldMismatch { .. })
+        ));
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn restore_reflected_relationships() {
+        use crate::reflect::AppTypeRegistry;
+        use core::any::TypeId;
+
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<ChildOf>();
+            registry.register::<Children>();
+        }
+        world.register_snapshot_component::<ChildOf>();
+        world
+            .register_snapshot_reflect(TypeId::of::<Children>())
+            .unwrap();
+        assert_eq!(
+            world
+                .register_snapshot_reflect(TypeId::of::<Position>())
+                .err(),
+            Some(WorldSnapshotError::NotReflected(TypeId::of::<Position>()))
+        );
+
+        let parent = world.spawn_empty().id();
+        let other_parent = world.spawn_empty().id();
+        let child = world.spawn(ChildOf(parent)).id();
+        let snapshot = world.snapshot();
+
+        world.entity_mut(child).insert(ChildOf(other_parent));
+        let new_child = world.spawn(ChildOf(parent)).id();
+        world.restore_snapshot(&snapshot).unwrap();
+
+        assert_eq!(world.get::<ChildOf>(child), Some(&ChildOf(parent)));
+        assert_eq!(&**world.get::<Children>(parent).unwrap(), &[child]);
+        assert!(!world.entity(other_parent).contains::<Children>());
+        assert!(world.get_entity(new_child).is_err());
+    }
+}
@@ -13,7 +13,7 @@ use crate::{
     prelude::Component,
     query::{DebugCheckedUnwrap, ReleaseStateQueryData},
     resource::Resource,
-    storage::{ComponentSparseSet, Storages, Table},
+    storage::{ComponentSparseSet, SharedComponentValues, SharedValueId, Storages, Table},
     world::RawCommandQueue,
 };
 use bevy_platform::sync::atomic::Ordering;
@@ -1214,6 +1214,25 @@ impl<'w> UnsafeWorldCell<'w> {
         // of component/resource data
         unsafe { self.storages() }.sparse_sets.get(component_id)
     }
+
+    #[inline]
+    /// # Safety
+    /// - the returned `SharedComponentValues` is only used in ways that this [`UnsafeWorldCell`] has permission for.
+    /// - the returned `SharedComponentValues` is only used in ways that would not conflict with any existing
+    ///   borrows of world data.
+    unsafe fn fetch_shared_value(
+        self,
+        component_id: ComponentId,
+        location: EntityLocation,
+    ) -> Option<(&'w SharedComponentValues, SharedValueId)> {
+        let value = self.archetypes()[location.archetype_id].get_shared_value(component_id)?;
+        // SAFETY: caller ensures returned data is not misused and we have not created any borrows
+        // of component/resource data
+        let values = unsafe { self.storages() }
+            .shared_components
+            .get(component_id)?;
+        Some((values, value))
+    }
 }
 
 /// Get an untyped pointer to a particular [`Component`] on a particular [`Entity`] in the provided [`World`].
@@ -1240,6 +1259,10 @@ unsafe fn get_component(
             table.get_component(component_id, location.table_row)
         }
         StorageType::SparseSet => world.fetch_sparse_set(component_id)?.get(entity),
+        StorageType::Shared => {
+            let (values, value) = world.fetch_shared_value(component_id, location)?;
+            values.get(value)
+        }
     }
 }
 
@@ -1283,6 +1306,10 @@ unsafe fn get_component_and_ticks(
             ))
         }
         StorageType::SparseSet => world.fetch_sparse_set(component_id)?.get_with_ticks(entity),
+        StorageType::Shared => {
+            let (values, value) = world.fetch_shared_value(component_id, location)?;
+            values.get_with_ticks(value)
+        }
     }
 }
 
@@ -1309,6 +1336,10 @@ unsafe fn get_ticks(
             table.get_ticks_unchecked(component_id, location.table_row)
         }
         StorageType::SparseSet => world.fetch_sparse_set(component_id)?.get_ticks(entity),
+        StorageType::Shared => {
+            let (values, value) = world.fetch_shared_value(component_id, location)?;
+            values.get_ticks(value)
+        }
     }
 }
 
@@ -9,7 +9,7 @@ use bevy_reflect::TypePath;
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 
-use bevy_asset::{Asset, RenderAssetUsages};
+use bevy_asset::{Asset, AssetSize, RenderAssetUsages};
 use bevy_color::{Color, ColorToComponents, Gray, LinearRgba, Srgba, Xyza};
 use bevy_ecs::resource::Resource;
 use bevy_math::{AspectRatio, UVec2, UVec3, Vec2};
@@ -747,6 +747,12 @@ impl From<SamplerDescriptor<Option<&str>>> for ImageSamplerDescriptor {
     }
 }
 
+impl AssetSize for Image {
+    fn byte_size(&self) -> usize {
+        size_of::<Self>() + self.data.as_ref().map_or(0, Vec::capacity)
+    }
+}
+
 impl Default for Image {
     /// default is a 1x1x1 all '1.0' texture
     fn default() -> Self {
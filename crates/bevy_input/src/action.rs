@@ -0,0 +1,903 @@
+//! Mapping of abstract, game-defined actions to physical inputs.
+//!
+//! Rather than reading [`ButtonInput<KeyCode>`] or a [`Gamepad`] directly, games can describe
+//! the things a player can *do* as an action type, bind each action to one or more
+//! [`InputBinding`]s in an [`InputMap`], and read the result from an [`ActionState`].
+//!
+//! ```
+//! # use bevy_app::{App, Update};
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::{action::*, keyboard::KeyCode, gamepad::GamepadButton};
+//! # use bevy_reflect::Reflect;
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+//! enum PlayerAction {
+//!     Jump,
+//!     Move,
+//! }
+//!
+//! fn spawn_player(mut commands: Commands) {
+//!     let input_map = InputMap::default()
+//!         .with(PlayerAction::Jump, KeyCode::Space)
+//!         .with(PlayerAction::Jump, GamepadButton::South)
+//!         .with(PlayerAction::Move, VirtualDPad::wasd());
+//!
+//!     // `ActionState<PlayerAction>` is added automatically.
+//!     commands.spawn(input_map);
+//! }
+//!
+//! fn jump(players: Query<&ActionState<PlayerAction>>) {
+//!     for action_state in &players {
+//!         if action_state.just_pressed(PlayerAction::Jump) {
+//!             // Jump!
+//!         }
+//!         let movement = action_state.axis_pair(PlayerAction::Move);
+//!     }
+//! }
+//!
+//! App::new()
+//!     .add_plugins(InputActionPlugin::<PlayerAction>::default())
+//!     .add_systems(Update, jump);
+//! ```
+//!
+//! An [`InputMap`] and [`ActionState`] can also be inserted as resources for games with a single
+//! set of controls. Both are updated in [`PreUpdate`] as part of [`ActionSystems`].
+
+use crate::{
+    gamepad::{Gamepad, GamepadAxis, GamepadButton},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    ButtonInput, InputSystems,
+};
+use alloc::vec::Vec;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    resource::Resource,
+    schedule::{IntoScheduleConfigs, SystemSet},
+    system::{Query, Res, ResMut},
+};
+use bevy_math::Vec2;
+use bevy_platform::collections::HashMap;
+use core::{fmt::Debug, hash::Hash, marker::PhantomData};
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::reflect::{ReflectComponent, ReflectResource},
+    bevy_reflect::{
+        std_traits::ReflectDefault, FromReflect, GetTypeRegistration, Reflect, TypePath, Typed,
+    },
+};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A game-defined action that can be bound to inputs in an [`InputMap`].
+///
+/// This is implemented automatically for every type meeting the bounds, which are usually
+/// satisfied by a fieldless enum deriving `Debug`, `Clone`, `Copy`, `PartialEq`, `Eq`, `Hash`
+/// and `Reflect`.
+#[cfg(feature = "bevy_reflect")]
+pub trait InputAction:
+    Debug + Copy + Eq + Hash + Send + Sync + FromReflect + TypePath + Typed + GetTypeRegistration
+{
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl<
+        A: Debug
+            + Copy
+            + Eq
+            + Hash
+            + Send
+            + Sync
+            + FromReflect
+            + TypePath
+            + Typed
+            + GetTypeRegistration,
+    > InputAction for A
+{
+}
+
+/// A game-defined action that can be bound to inputs in an [`InputMap`].
+///
+/// This is implemented automatically for every type meeting the bounds, which are usually
+/// satisfied by a fieldless enum deriving `Debug`, `Clone`, `Copy`, `PartialEq`, `Eq` and `Hash`.
+#[cfg(not(feature = "bevy_reflect"))]
+pub trait InputAction: Debug + Copy + Eq + Hash + Send + Sync + 'static {}
+
+#[cfg(not(feature = "bevy_reflect"))]
+impl<A: Debug + Copy + Eq + Hash + Send + Sync + 'static> InputAction for A {}
+
+/// Adds an [`InputMap<A>`] and [`ActionState<A>`] for the action type `A`.
+///
+/// The plugin does not insert any resources: spawn an [`InputMap<A>`] on each player entity, or
+/// insert it together with an [`ActionState<A>`] as resources.
+pub struct InputActionPlugin<A: InputAction>(PhantomData<A>);
+
+impl<A: InputAction> Default for InputActionPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: InputAction> Plugin for InputActionPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(PreUpdate, ActionSystems.after(InputSystems))
+            .add_systems(PreUpdate, update_action_state::<A>.in_set(ActionSystems));
+
+        #[cfg(feature = "bevy_reflect")]
+        app.register_type::<InputMap<A>>()
+            .register_type::<ActionState<A>>();
+    }
+}
+
+/// Label for the systems that update every [`ActionState`] from its [`InputMap`].
+///
+/// Runs in [`PreUpdate`] after [`InputSystems`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct ActionSystems;
+
+/// A single button on any supported input device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputButton {
+    /// A key on the keyboard, by physical position.
+    Keyboard(KeyCode),
+    /// A mouse button.
+    Mouse(MouseButton),
+    /// A gamepad button.
+    Gamepad(GamepadButton),
+}
+
+impl InputButton {
+    /// Returns the analog value of the button, which is `0.0` when released.
+    ///
+    /// Keyboard and mouse buttons are either `0.0` or `1.0`, pressed gamepad buttons report
+    /// their analog value if the gamepad provides one.
+    fn value(&self, sources: &InputSources) -> f32 {
+        match *self {
+            InputButton::Keyboard(key) => {
+                sources.keyboard.is_some_and(|k| k.pressed(key)) as u8 as f32
+            }
+            InputButton::Mouse(button) => {
+                sources.mouse.is_some_and(|m| m.pressed(button)) as u8 as f32
+            }
+            InputButton::Gamepad(button) => sources
+                .gamepads
+                .iter()
+                .filter(|gamepad| gamepad.pressed(button))
+                .map(|gamepad| {
+                    gamepad
+                        .get(button)
+                        .filter(|value| *value > 0.0)
+                        .unwrap_or(1.0)
+                })
+                .fold(0.0, f32::max),
+        }
+    }
+
+    /// Returns `true` if the button is pressed.
+    fn pressed(&self, sources: &InputSources) -> bool {
+        self.value(sources) > 0.0
+    }
+}
+
+impl From<KeyCode> for InputButton {
+    fn from(key: KeyCode) -> Self {
+        InputButton::Keyboard(key)
+    }
+}
+
+impl From<MouseButton> for InputButton {
+    fn from(button: MouseButton) -> Self {
+        InputButton::Mouse(button)
+    }
+}
+
+impl From<GamepadButton> for InputButton {
+    fn from(button: GamepadButton) -> Self {
+        InputButton::Gamepad(button)
+    }
+}
+
+/// Four buttons that together act as a two-dimensional axis, such as WASD or the arrow keys.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct VirtualDPad {
+    /// The button for the positive Y direction.
+    pub up: InputButton,
+    /// The button for the negative Y direction.
+    pub down: InputButton,
+    /// The button for the negative X direction.
+    pub left: InputButton,
+    /// The button for the positive X direction.
+    pub right: InputButton,
+}
+
+impl VirtualDPad {
+    /// Creates a new [`VirtualDPad`] from its four buttons.
+    pub fn new(
+        up: impl Into<InputButton>,
+        down: impl Into<InputButton>,
+        left: impl Into<InputButton>,
+        right: impl Into<InputButton>,
+    ) -> Self {
+        Self {
+            up: up.into(),
+            down: down.into(),
+            left: left.into(),
+            right: right.into(),
+        }
+    }
+
+    /// The W, A, S and D keys.
+    pub fn wasd() -> Self {
+        Self::new(KeyCode::KeyW, KeyCode::KeyS, KeyCode::KeyA, KeyCode::KeyD)
+    }
+
+    /// The arrow keys.
+    pub fn arrow_keys() -> Self {
+        Self::new(
+            KeyCode::ArrowUp,
+            KeyCode::ArrowDown,
+            KeyCode::ArrowLeft,
+            KeyCode::ArrowRight,
+        )
+    }
+
+    /// The directional pad of a gamepad.
+    pub fn gamepad_dpad() -> Self {
+        Self::new(
+            GamepadButton::DPadUp,
+            GamepadButton::DPadDown,
+            GamepadButton::DPadLeft,
+            GamepadButton::DPadRight,
+        )
+    }
+
+    fn axis_pair(&self, sources: &InputSources) -> Vec2 {
+        Vec2::new(
+            self.right.value(sources) - self.left.value(sources),
+            self.up.value(sources) - self.down.value(sources),
+        )
+    }
+}
+
+/// A physical input that an action can be bound to.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputBinding {
+    /// A single button.
+    Button(InputButton),
+    /// Several buttons that must all be held at the same time, such as `Ctrl + S`.
+    Chord(Vec<InputButton>),
+    /// A single gamepad axis.
+    ///
+    /// The binding is pressed once the axis passes `threshold`: a positive threshold is passed
+    /// by values greater than or equal to it, a negative threshold by values less than or equal
+    /// to it.
+    GamepadAxis {
+        /// The axis to read.
+        axis: GamepadAxis,
+        /// The value at which the binding counts as pressed.
+        threshold: f32,
+    },
+    /// Two gamepad axes read together, such as a thumbstick.
+    ///
+    /// The binding is pressed whenever the stick is outside of its dead zone.
+    DualAxis {
+        /// The axis used for the X component.
+        x: GamepadAxis,
+        /// The axis used for the Y component.
+        y: GamepadAxis,
+    },
+    /// Four buttons read as a two-dimensional axis.
+    VirtualDPad(VirtualDPad),
+}
+
+impl InputBinding {
+    /// Creates an [`InputBinding::Chord`] from the given buttons.
+    pub fn chord<B: Into<InputButton>>(buttons: impl IntoIterator<Item = B>) -> Self {
+        InputBinding::Chord(buttons.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates an [`InputBinding::DualAxis`] for the left thumbstick.
+    pub fn left_stick() -> Self {
+        InputBinding::DualAxis {
+            x: GamepadAxis::LeftStickX,
+            y: GamepadAxis::LeftStickY,
+        }
+    }
+
+    /// Creates an [`InputBinding::DualAxis`] for the right thumbstick.
+    pub fn right_stick() -> Self {
+        InputBinding::DualAxis {
+            x: GamepadAxis::RightStickX,
+            y: GamepadAxis::RightStickY,
+        }
+    }
+
+    /// Reads the current state of this binding.
+    fn read(&self, sources: &InputSources) -> BindingState {
+        match self {
+            InputBinding::Button(button) => {
+                let value = button.value(sources);
+                BindingState {
+                    pressed: value > 0.0,
+                    value,
+                    axis_pair: Vec2::new(value, 0.0),
+                }
+            }
+            InputBinding::Chord(buttons) => {
+                let pressed = !buttons.is_empty() && buttons.iter().all(|b| b.pressed(sources));
+                let value = pressed as u8 as f32;
+                BindingState {
+                    pressed,
+                    value,
+                    axis_pair: Vec2::new(value, 0.0),
+                }
+            }
+            InputBinding::GamepadAxis { axis, threshold } => {
+                let value = sources.axis_value(*axis);
+                let pressed = if *threshold < 0.0 {
+                    value <= *threshold
+                } else {
+                    value >= *threshold
+                };
+                BindingState {
+                    pressed,
+                    value,
+                    axis_pair: Vec2::new(value, 0.0),
+                }
+            }
+            InputBinding::DualAxis { x, y } => {
+                let axis_pair = Vec2::new(sources.axis_value(*x), sources.axis_value(*y));
+                BindingState::from_axis_pair(axis_pair)
+            }
+            InputBinding::VirtualDPad(dpad) => {
+                BindingState::from_axis_pair(dpad.axis_pair(sources).clamp_length_max(1.0))
+            }
+        }
+    }
+}
+
+impl From<InputButton> for InputBinding {
+    fn from(button: InputButton) -> Self {
+        InputBinding::Button(button)
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        InputBinding::Button(key.into())
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        InputBinding::Button(button.into())
+    }
+}
+
+impl From<GamepadButton> for InputBinding {
+    fn from(button: GamepadButton) -> Self {
+        InputBinding::Button(button.into())
+    }
+}
+
+impl From<VirtualDPad> for InputBinding {
+    fn from(dpad: VirtualDPad) -> Self {
+        InputBinding::VirtualDPad(dpad)
+    }
+}
+
+/// The devices an [`InputMap`] reads from during one update.
+struct InputSources<'a> {
+    keyboard: Option<&'a ButtonInput<KeyCode>>,
+    mouse: Option<&'a ButtonInput<MouseButton>>,
+    gamepads: Vec<&'a Gamepad>,
+}
+
+impl InputSources<'_> {
+    /// Returns the value of the axis with the largest magnitude across all gamepads.
+    fn axis_value(&self, axis: GamepadAxis) -> f32 {
+        self.gamepads
+            .iter()
+            .filter_map(|gamepad| gamepad.get(axis))
+            .fold(0.0, |max, value| {
+                if value.abs() > f32::abs(max) {
+                    value
+                } else {
+                    max
+                }
+            })
+    }
+}
+
+/// The state of a single [`InputBinding`] for the current frame.
+struct BindingState {
+    pressed: bool,
+    value: f32,
+    axis_pair: Vec2,
+}
+
+impl BindingState {
+    fn from_axis_pair(axis_pair: Vec2) -> Self {
+        Self {
+            pressed: axis_pair != Vec2::ZERO,
+            value: axis_pair.length(),
+            axis_pair,
+        }
+    }
+}
+
+/// The bindings from each action of type `A` to the physical inputs that trigger it.
+///
+/// An action may have any number of bindings, and is pressed while any of them is pressed.
+/// Bindings can be changed at any time, for example from a controls menu, and take effect
+/// during the next update of [`ActionSystems`].
+///
+/// Insert this as a component on each player entity to give every player their own controls,
+/// or as a resource together with an [`ActionState<A>`] for games with a single set of
+/// controls. When `bevy_reflect` is enabled the bindings can be saved and loaded through
+/// reflection like any other component.
+#[derive(Debug, Clone, Component, Resource)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Component, Resource)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[require(ActionState<A>)]
+pub struct InputMap<A: InputAction> {
+    bindings: HashMap<A, Vec<InputBinding>>,
+    gamepad: Option<Entity>,
+}
+
+impl<A: InputAction> Default for InputMap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: HashMap::default(),
+            gamepad: None,
+        }
+    }
+}
+
+impl<A: InputAction> InputMap<A> {
+    /// Adds `binding` to `action`, returning the map for chaining.
+    pub fn with(mut self, action: A, binding: impl Into<InputBinding>) -> Self {
+        self.insert(action, binding);
+        self
+    }
+
+    /// Restricts gamepad bindings to the given gamepad entity, returning the map for chaining.
+    pub fn with_gamepad(mut self, gamepad: Entity) -> Self {
+        self.gamepad = Some(gamepad);
+        self
+    }
+
+    /// Adds `binding` to `action`.
+    ///
+    /// Adding a binding that is already bound to `action` does nothing.
+    pub fn insert(&mut self, action: A, binding: impl Into<InputBinding>) -> &mut Self {
+        let binding = binding.into();
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+        self
+    }
+
+    /// Replaces every binding of `action` with `binding`.
+    pub fn rebind(&mut self, action: A, binding: impl Into<InputBinding>) -> &mut Self {
+        self.bindings.insert(action, alloc::vec![binding.into()]);
+        self
+    }
+
+    /// Removes `binding` from `action`, returning `true` if it was bound.
+    pub fn remove(&mut self, action: A, binding: &InputBinding) -> bool {
+        let Some(bindings) = self.bindings.get_mut(&action) else {
+            return false;
+        };
+        let len = bindings.len();
+        bindings.retain(|b| b != binding);
+        len != bindings.len()
+    }
+
+    /// Removes every binding of `action`, returning them.
+    pub fn clear_action(&mut self, action: A) -> Vec<InputBinding> {
+        self.bindings.remove(&action).unwrap_or_default()
+    }
+
+    /// Removes every binding of every action.
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+    }
+
+    /// Returns the bindings of `action`.
+    pub fn bindings(&self, action: A) -> &[InputBinding] {
+        self.bindings
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over every action and its bindings.
+    pub fn iter(&self) -> impl Iterator<Item = (&A, &[InputBinding])> {
+        self.bindings
+            .iter()
+            .map(|(action, bindings)| (action, bindings.as_slice()))
+    }
+
+    /// Returns an iterator over the actions that `binding` is bound to.
+    ///
+    /// This is useful for detecting conflicts when rebinding.
+    pub fn actions_bound_to<'a>(
+        &'a self,
+        binding: &'a InputBinding,
+    ) -> impl Iterator<Item = &'a A> {
+        self.bindings
+            .iter()
+            .filter(move |(_, bindings)| bindings.contains(binding))
+            .map(|(action, _)| action)
+    }
+
+    /// Returns the gamepad this map reads from, or `None` if it reads from every gamepad.
+    pub fn gamepad(&self) -> Option<Entity> {
+        self.gamepad
+    }
+
+    /// Sets the gamepad this map reads from, or `None` to read from every gamepad.
+    pub fn set_gamepad(&mut self, gamepad: Option<Entity>) {
+        self.gamepad = gamepad;
+    }
+}
+
+/// The current state of a single action in an [`ActionState`].
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Clone)
+)]
+pub struct ActionData {
+    /// Whether the action is currently pressed.
+    pub pressed: bool,
+    /// Whether the action was pressed during the most recent update.
+    pub just_pressed: bool,
+    /// Whether the action was released during the most recent update.
+    pub just_released: bool,
+    /// The analog value of the action.
+    ///
+    /// This is `0.0` or `1.0` for digital buttons, the raw value for single axes, and the length
+    /// of the axis pair for two-dimensional bindings.
+    pub value: f32,
+    /// The two-dimensional value of the action.
+    ///
+    /// Single-axis bindings report their value as the X component.
+    pub axis_pair: Vec2,
+}
+
+/// The state of each action of type `A`, updated from an [`InputMap<A>`] every frame.
+///
+/// Actions can also be pressed and released manually, for example by AI or networking code, but
+/// the next update of [`ActionSystems`] overwrites them with the state of the bound inputs.
+#[derive(Debug, Clone, Component, Resource)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Component, Resource)
+)]
+pub struct ActionState<A: InputAction> {
+    actions: HashMap<A, ActionData>,
+}
+
+impl<A: InputAction> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            actions: HashMap::default(),
+        }
+    }
+}
+
+impl<A: InputAction> ActionState<A> {
+    /// Returns the full state of `action`, if it has ever been updated.
+    pub fn get(&self, action: A) -> Option<&ActionData> {
+        self.actions.get(&action)
+    }
+
+    /// Returns `true` if `action` is pressed.
+    pub fn pressed(&self, action: A) -> bool {
+        self.get(action).is_some_and(|data| data.pressed)
+    }
+
+    /// Returns `true` if `action` was pressed during the most recent update.
+    pub fn just_pressed(&self, action: A) -> bool {
+        self.get(action).is_some_and(|data| data.just_pressed)
+    }
+
+    /// Returns `true` if `action` was released during the most recent update.
+    pub fn just_released(&self, action: A) -> bool {
+        self.get(action).is_some_and(|data| data.just_released)
+    }
+
+    /// Returns the analog value of `action`. See [`ActionData::value`].
+    pub fn value(&self, action: A) -> f32 {
+        self.get(action).map_or(0.0, |data| data.value)
+    }
+
+    /// Returns the two-dimensional value of `action`. See [`ActionData::axis_pair`].
+    pub fn axis_pair(&self, action: A) -> Vec2 {
+        self.get(action).map_or(Vec2::ZERO, |data| data.axis_pair)
+    }
+
+    /// Returns an iterator over every pressed action.
+    pub fn get_pressed(&self) -> impl Iterator<Item = &A> {
+        self.actions
+            .iter()
+            .filter(|(_, data)| data.pressed)
+            .map(|(action, _)| action)
+    }
+
+    /// Returns an iterator over every action pressed during the most recent update.
+    pub fn get_just_pressed(&self) -> impl Iterator<Item = &A> {
+        self.actions
+            .iter()
+            .filter(|(_, data)| data.just_pressed)
+            .map(|(action, _)| action)
+    }
+
+    /// Presses `action` with a value of `1.0`.
+    pub fn press(&mut self, action: A) {
+        self.set(action, true, 1.0, Vec2::X);
+    }
+
+    /// Releases `action`.
+    pub fn release(&mut self, action: A) {
+        self.set(action, false, 0.0, Vec2::ZERO);
+    }
+
+    /// Releases every action.
+    pub fn release_all(&mut self) {
+        let actions: Vec<A> = self.actions.keys().copied().collect();
+        for action in actions {
+            self.release(action);
+        }
+    }
+
+    fn set(&mut self, action: A, pressed: bool, value: f32, axis_pair: Vec2) {
+        let data = self.actions.entry(action).or_default();
+        data.just_pressed = pressed && !data.pressed;
+        data.just_released = !pressed && data.pressed;
+        data.pressed = pressed;
+        data.value = value;
+        data.axis_pair = axis_pair;
+    }
+
+    /// Updates every action from the bindings in `input_map`.
+    fn update(&mut self, input_map: &InputMap<A>, sources: &InputSources) {
+        for (action, bindings) in &input_map.bindings {
+            let mut pressed = false;
+            let mut value = 0.0f32;
+            let mut axis_pair = Vec2::ZERO;
+            for binding in bindings {
+                let state = binding.read(sources);
+                pressed |= state.pressed;
+                if state.value.abs() > value.abs() {
+                    value = state.value;
+                }
+                if state.axis_pair.length_squared() > axis_pair.length_squared() {
+                    axis_pair = state.axis_pair;
+                }
+            }
+            self.set(*action, pressed, value, axis_pair);
+        }
+
+        // Actions that lost all of their bindings are released.
+        let unbound: Vec<A> = self
+            .actions
+            .keys()
+            .filter(|action| !input_map.bindings.contains_key(*action))
+            .copied()
+            .collect();
+        for action in unbound {
+            self.release(action);
+        }
+    }
+}
+
+/// Updates every [`ActionState<A>`] from its [`InputMap<A>`], both for the resources and for
+/// entities with both components.
+pub fn update_action_state<A: InputAction>(
+    keyboard: Option<Res<ButtonInput<KeyCode>>>,
+    mouse: Option<Res<ButtonInput<MouseButton>>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    input_map: Option<Res<InputMap<A>>>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    mut players: Query<(&InputMap<A>, &mut ActionState<A>)>,
+) {
+    let sources = |input_map: &InputMap<A>| InputSources {
+        keyboard: keyboard.as_deref(),
+        mouse: mouse.as_deref(),
+        gamepads: gamepads
+            .iter()
+            .filter(|(entity, _)| input_map.gamepad.is_none_or(|gamepad| gamepad == *entity))
+            .map(|(_, gamepad)| gamepad)
+            .collect(),
+    };
+
+    if let (Some(input_map), Some(mut action_state)) = (input_map, action_state) {
+        action_state.update(&input_map, &sources(&input_map));
+    }
+
+    for (input_map, mut action_state) in &mut players {
+        action_state.update(input_map, &sources(input_map));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gamepad::GamepadInput;
+    use bevy_app::App;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    enum Action {
+        Jump,
+        Save,
+        Move,
+        Throttle,
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .add_plugins(InputActionPlugin::<Action>::default());
+        app
+    }
+
+    fn press_key(app: &mut App, key: KeyCode) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+    }
+
+    #[test]
+    fn buttons_and_chords() {
+        let mut app = app();
+        let player = app
+            .world_mut()
+            .spawn(
+                InputMap::default()
+                    .with(Action::Jump, KeyCode::Space)
+                    .with(Action::Jump, MouseButton::Left)
+                    .with(
+                        Action::Save,
+                        InputBinding::chord([KeyCode::ControlLeft, KeyCode::KeyS]),
+                    ),
+            )
+            .id();
+
+        press_key(&mut app, KeyCode::Space);
+        press_key(&mut app, KeyCode::KeyS);
+        app.update();
+
+        let state = app.world().get::<ActionState<Action>>(player).unwrap();
+        assert!(state.pressed(Action::Jump));
+        assert!(state.just_pressed(Action::Jump));
+        assert_eq!(state.value(Action::Jump), 1.0);
+        assert!(!state.pressed(Action::Save));
+
+        press_key(&mut app, KeyCode::ControlLeft);
+        app.update();
+
+        let state = app.world().get::<ActionState<Action>>(player).unwrap();
+        assert!(state.pressed(Action::Jump));
+        assert!(!state.just_pressed(Action::Jump));
+        assert!(state.just_pressed(Action::Save));
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release_all();
+        app.update();
+
+        let state = app.world().get::<ActionState<Action>>(player).unwrap();
+        assert!(state.just_released(Action::Jump));
+        assert!(state.just_released(Action::Save));
+    }
+
+    #[test]
+    fn virtual_dpad_and_rebinding() {
+        let mut app = app();
+        app.insert_resource(InputMap::default().with(Action::Move, VirtualDPad::wasd()))
+            .init_resource::<ActionState<Action>>();
+
+        press_key(&mut app, KeyCode::KeyW);
+        press_key(&mut app, KeyCode::KeyD);
+        app.update();
+
+        let state = app.world().resource::<ActionState<Action>>();
+        let axis_pair = state.axis_pair(Action::Move);
+        assert!((axis_pair - Vec2::ONE.normalize()).length() < 1e-6);
+        assert!(state.pressed(Action::Move));
+
+        let mut input_map = app.world_mut().resource_mut::<InputMap<Action>>();
+        input_map.rebind(Action::Move, VirtualDPad::arrow_keys());
+        assert_eq!(
+            input_map
+                .actions_bound_to(&VirtualDPad::arrow_keys().into())
+                .collect::<Vec<_>>(),
+            [&Action::Move]
+        );
+        app.update();
+
+        let state = app.world().resource::<ActionState<Action>>();
+        assert_eq!(state.axis_pair(Action::Move), Vec2::ZERO);
+        assert!(state.just_released(Action::Move));
+    }
+
+    #[test]
+    fn per_player_gamepads() {
+        let mut app = app();
+        let mut first = Gamepad::default();
+        first.digital_mut().press(GamepadButton::South);
+        first
+            .analog_mut()
+            .set(GamepadInput::Axis(GamepadAxis::LeftStickY), 0.75);
+        let first = app.world_mut().spawn(first).id();
+        let second = app.world_mut().spawn(Gamepad::default()).id();
+
+        let input_map = InputMap::default()
+            .with(Action::Jump, GamepadButton::South)
+            .with(
+                Action::Throttle,
+                InputBinding::GamepadAxis {
+                    axis: GamepadAxis::LeftStickY,
+                    threshold: 0.5,
+                },
+            );
+        let player_one = app
+            .world_mut()
+            .spawn(input_map.clone().with_gamepad(first))
+            .id();
+        let player_two = app.world_mut().spawn(input_map.with_gamepad(second)).id();
+        app.update();
+
+        let state = app.world().get::<ActionState<Action>>(player_one).unwrap();
+        assert!(state.pressed(Action::Jump));
+        assert!(state.pressed(Action::Throttle));
+        assert_eq!(state.value(Action::Throttle), 0.75);
+
+        let state = app.world().get::<ActionState<Action>>(player_two).unwrap();
+        assert!(!state.pressed(Action::Jump));
+        assert!(!state.pressed(Action::Throttle));
+    }
+}
@@ -11,12 +11,18 @@
 //! # Supported input devices
 //!
 //! `bevy` currently supports keyboard, mouse, gamepad, and touch inputs.
+//!
+//! # Actions
+//!
+//! The [`action`] module maps game-defined actions to any of these inputs, with per-player
+//! bindings that can be changed at runtime.
 
 #[cfg(feature = "std")]
 extern crate std;
 
 extern crate alloc;
 
+pub mod action;
 mod axis;
 mod button_input;
 /// Common run conditions
@@ -36,6 +42,7 @@ pub use button_input::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
+        action::{ActionState, InputActionPlugin, InputMap},
         gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadSettings},
         keyboard::KeyCode,
         mouse::MouseButton,
@@ -5,20 +5,22 @@ use core::any::TypeId;
 use anyhow::{anyhow, Result as AnyhowResult};
 use bevy_ecs::{
     component::ComponentId,
-    entity::Entity,
+    entity::{Entity, EntityHashSet},
     event::EventCursor,
-    hierarchy::ChildOf,
+    hierarchy::{ChildOf, Children},
     lifecycle::RemovedComponentEntity,
     query::QueryBuilder,
-    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    reflect::{AppTypeRegistry, ComponentHistory, ReflectComponent, ReflectResource},
+    schedule::{ConditionWithAccess, Dag, NodeId, Schedule, Schedules, Stepping},
+    storage::MemoryUsage,
     system::{In, Local},
-    world::{EntityRef, EntityWorldMut, FilteredEntityRef, World},
+    world::{EntityRef, EntityWorldMut, FilteredEntityRef, Mut, World},
 };
 use bevy_log::warn_once;
 use bevy_platform::collections::HashMap;
 use bevy_reflect::{
-    serde::{ReflectSerializer, TypedReflectDeserializer},
-    GetPath, PartialReflect, TypeRegistration, TypeRegistry,
+    serde::{ReflectSerializer, TypedReflectDeserializer, TypedReflectSerializer},
+    GetPath, PartialReflect, Reflect, TypeInfo, TypeRegistration, TypeRegistry,
 };
 use serde::{de::DeserializeSeed as _, Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -89,6 +91,52 @@ pub const BRP_REGISTRY_SCHEMA_METHOD: &str = "registry.schema";
 /// The method path for a `rpc.discover` request.
 pub const RPC_DISCOVER_METHOD: &str = "rpc.discover";
 
+/// The method path for a `world.transaction` request.
+pub const BRP_TRANSACTION_METHOD: &str = "world.transaction";
+
+/// The method path for a `world.get_component_history` request.
+pub const BRP_GET_COMPONENT_HISTORY_METHOD: &str = "world.get_component_history";
+
+/// The method path for a `world.memory_report` request.
+pub const BRP_MEMORY_REPORT_METHOD: &str = "world.memory_report";
+
+/// The method path for an `asset.dependency_graph` request.
+#[cfg(feature = "bevy_asset")]
+pub const BRP_ASSET_DEPENDENCY_GRAPH_METHOD: &str = "asset.dependency_graph";
+
+/// The method path for a `schedule.list` request.
+pub const BRP_LIST_SCHEDULES_METHOD: &str = "schedule.list";
+
+/// The method path for a `schedule.graph` request.
+pub const BRP_SCHEDULE_GRAPH_METHOD: &str = "schedule.graph";
+
+/// The method path for a `stepping.state` request.
+pub const BRP_STEPPING_STATE_METHOD: &str = "stepping.state";
+
+/// The method path for a `stepping.enable` request.
+pub const BRP_STEPPING_ENABLE_METHOD: &str = "stepping.enable";
+
+/// The method path for a `stepping.disable` request.
+pub const BRP_STEPPING_DISABLE_METHOD: &str = "stepping.disable";
+
+/// The method path for a `stepping.add_schedule` request.
+pub const BRP_STEPPING_ADD_SCHEDULE_METHOD: &str = "stepping.add_schedule";
+
+/// The method path for a `stepping.remove_schedule` request.
+pub const BRP_STEPPING_REMOVE_SCHEDULE_METHOD: &str = "stepping.remove_schedule";
+
+/// The method path for a `stepping.step` request.
+pub const BRP_STEPPING_STEP_METHOD: &str = "stepping.step";
+
+/// The method path for a `stepping.continue` request.
+pub const BRP_STEPPING_CONTINUE_METHOD: &str = "stepping.continue";
+
+/// The method path for a `stepping.set_breakpoint` request.
+pub const BRP_STEPPING_SET_BREAKPOINT_METHOD: &str = "stepping.set_breakpoint";
+
+/// The method path for a `stepping.clear_breakpoint` request.
+pub const BRP_STEPPING_CLEAR_BREAKPOINT_METHOD: &str = "stepping.clear_breakpoint";
+
 /// `world.get_components`: Retrieves one or more components from the entity with the given
 /// ID.
 ///
@@ -114,6 +162,21 @@ pub struct BrpGetComponentsParams {
     pub strict: bool,
 }
 
+/// `world.get_component_history`: Retrieves the recorded values of one or more components
+/// of an entity, from a [`ComponentHistory`].
+///
+/// The server responds with a [`BrpGetComponentHistoryResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpGetComponentHistoryParams {
+    /// The ID of the entity whose component history is requested.
+    pub entity: Entity,
+
+    /// The [full paths] of the component types whose history is requested.
+    ///
+    /// [full paths]: bevy_reflect::TypePath::type_path
+    pub components: Vec<String>,
+}
+
 /// `world.get_resources`: Retrieves the value of a given resource.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct BrpGetResourcesParams {
@@ -462,6 +525,311 @@ pub struct BrpQueryRow {
     pub has: HashMap<String, Value>,
 }
 
+/// `world.transaction`: Applies a list of operations atomically.
+///
+/// The server responds with a [`BrpTransactionResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpTransactionParams {
+    /// The operations to apply, in order.
+    pub operations: Vec<BrpTransactionOperation>,
+}
+
+/// A single operation of a `world.transaction` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpTransactionOperation {
+    /// The method of the operation, e.g. `world.spawn_entity`.
+    ///
+    /// Only the built-in methods that modify entities and resources are supported.
+    pub method: String,
+
+    /// The parameters of the operation.
+    ///
+    /// Any object of the form `{ "$entity": n }` is replaced with the entity spawned by
+    /// the `n`th operation of the same transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+/// `schedule.graph`: Describes the systems, system sets and ordering of a schedule.
+///
+/// The server responds with a [`BrpScheduleGraphResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleGraphParams {
+    /// The label of the schedule, as printed by its `Debug` implementation (e.g. `Update`).
+    pub schedule: String,
+}
+
+/// `stepping.enable`: Enables system stepping.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingEnableParams {
+    /// The labels of schedules to enable stepping for, in addition to the ones
+    /// that were already added.
+    #[serde(default)]
+    pub schedules: Vec<String>,
+}
+
+/// `stepping.add_schedule` and `stepping.remove_schedule`: Changes which schedules are stepped.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingScheduleParams {
+    /// The label of the schedule, as printed by its `Debug` implementation (e.g. `Update`).
+    pub schedule: String,
+}
+
+/// `stepping.set_breakpoint` and `stepping.clear_breakpoint`: Changes the breakpoints of a system.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingBreakpointParams {
+    /// The label of the schedule containing the system.
+    pub schedule: String,
+
+    /// The system to change the breakpoint of.
+    ///
+    /// This is either the `id` of a system reported by `schedule.graph`, or the
+    /// full or short name of a system. Names apply to every instance of the system
+    /// in the schedule.
+    pub system: String,
+}
+
+/// The response to a `world.transaction` request.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpTransactionResponse {
+    /// The result of each operation, in the order they were given.
+    pub results: Vec<Value>,
+}
+
+/// A value recorded in a [`ComponentHistory`], in the response to a
+/// `world.get_component_history` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpComponentHistoryEntry {
+    /// The serialized value of the component.
+    pub value: Value,
+
+    /// The change tick at which the component was changed.
+    pub tick: u32,
+
+    /// The name of the system that changed the component, if it is known.
+    pub system: Option<String>,
+}
+
+/// The response to a `world.get_component_history` request: a map from each component's full
+/// path to its recorded values, from the oldest to the most recent.
+pub type BrpGetComponentHistoryResponse = HashMap<String, Vec<BrpComponentHistoryEntry>>;
+
+/// The memory held by a storage, in bytes, in a [`BrpMemoryReportResponse`].
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BrpMemoryUsage {
+    /// The number of bytes holding values.
+    pub used: usize,
+
+    /// The number of bytes allocated, including unused capacity.
+    pub allocated: usize,
+}
+
+impl From<MemoryUsage> for BrpMemoryUsage {
+    fn from(usage: MemoryUsage) -> Self {
+        Self {
+            used: usage.used,
+            allocated: usage.allocated,
+        }
+    }
+}
+
+/// The memory held by an archetype or a table, in a [`BrpMemoryReportResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BrpStorageMemoryUsage {
+    /// The index of the archetype or table.
+    pub id: u32,
+
+    /// The number of entities stored.
+    pub entities: usize,
+
+    /// The memory held by the archetype or table.
+    #[serde(flatten)]
+    pub usage: BrpMemoryUsage,
+}
+
+/// The memory held by the values of a component type or by a resource, in a
+/// [`BrpMemoryReportResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BrpTypeMemoryUsage {
+    /// The name of the component or resource.
+    pub name: String,
+
+    /// The memory held by the component or resource.
+    #[serde(flatten)]
+    pub usage: BrpMemoryUsage,
+}
+
+/// The response to a `world.memory_report` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BrpMemoryReportResponse {
+    /// The memory held by all the storages of the world.
+    pub total: BrpMemoryUsage,
+
+    /// The memory held by each archetype for its bookkeeping.
+    pub archetypes: Vec<BrpStorageMemoryUsage>,
+
+    /// The memory held by each table, including its columns.
+    pub tables: Vec<BrpStorageMemoryUsage>,
+
+    /// The memory held by the values of each component type, from the largest allocation to
+    /// the smallest.
+    pub components: Vec<BrpTypeMemoryUsage>,
+
+    /// The memory held by each resource, from the largest allocation to the smallest.
+    pub resources: Vec<BrpTypeMemoryUsage>,
+}
+
+/// An asset in a [`BrpAssetDependencyGraphResponse`].
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetNode {
+    /// An opaque identifier of the asset.
+    pub id: String,
+
+    /// The path of the asset, if it was loaded from a path.
+    pub path: Option<String>,
+
+    /// The type path of the asset type, if it is known.
+    pub type_path: Option<String>,
+
+    /// The load state of the asset: `NotLoaded`, `Queued`, `Loading`, `Loaded` or `Failed`.
+    pub load_state: String,
+
+    /// The error the asset failed to load with, if its load state is `Failed`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+
+    /// The `id`s of the assets this asset depends on.
+    pub dependencies: Vec<String>,
+
+    /// The `id`s of the assets depending on this asset.
+    pub dependants: Vec<String>,
+
+    /// The number of bytes used by the asset, if its size is tracked.
+    pub byte_size: Option<usize>,
+}
+
+/// The response to an `asset.dependency_graph` request.
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetDependencyGraphResponse {
+    /// The assets managed by the asset server, sorted by path.
+    pub assets: Vec<BrpAssetNode>,
+}
+
+/// One entry of the response to a `schedule.list` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleInfo {
+    /// The label of the schedule.
+    pub label: String,
+
+    /// The number of systems in the schedule.
+    pub systems: usize,
+
+    /// Whether the schedule has been initialized, which happens the first time it runs.
+    pub initialized: bool,
+}
+
+/// The response to a `schedule.list` request.
+pub type BrpListSchedulesResponse = Vec<BrpScheduleInfo>;
+
+/// A system or system set in a [`BrpScheduleGraphResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleNode {
+    /// An opaque identifier of this node, unique within its schedule.
+    pub id: String,
+
+    /// The name of the system, or the `Debug` representation of the system set.
+    pub name: String,
+
+    /// The names of the run conditions of this node.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub conditions: Vec<String>,
+}
+
+/// A directed edge between two nodes of a [`BrpScheduleGraphResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleEdge {
+    /// The `id` of the node this edge starts at.
+    pub from: String,
+
+    /// The `id` of the node this edge ends at.
+    pub to: String,
+}
+
+/// A pair of systems with conflicting data access and no ordering between them.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleConflict {
+    /// The `id` of the first system.
+    pub a: String,
+
+    /// The `id` of the second system.
+    pub b: String,
+
+    /// The names of the components and resources both systems access.
+    ///
+    /// If empty, the systems conflict on [`World`] access.
+    pub components: Vec<String>,
+}
+
+/// The response to a `schedule.graph` request.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleGraphResponse {
+    /// The label of the schedule.
+    pub label: String,
+
+    /// All systems in the schedule.
+    pub systems: Vec<BrpScheduleNode>,
+
+    /// All system sets in the schedule.
+    pub sets: Vec<BrpScheduleNode>,
+
+    /// Edges from system sets to the systems and sets they contain.
+    pub hierarchy: Vec<BrpScheduleEdge>,
+
+    /// Edges from systems and sets to the systems and sets that run after them.
+    pub dependencies: Vec<BrpScheduleEdge>,
+
+    /// The `id`s of the systems in the order they are executed by a single threaded executor.
+    ///
+    /// This is empty until the schedule has been initialized.
+    pub order: Vec<String>,
+
+    /// Ambiguities detected when the schedule was initialized.
+    ///
+    /// This is only populated if ambiguity detection is enabled for the schedule.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub conflicts: Vec<BrpScheduleConflict>,
+}
+
+/// The position of the [`Stepping`] cursor in a [`BrpSteppingStateResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingCursor {
+    /// The label of the schedule the cursor is in.
+    pub schedule: String,
+
+    /// The `id` of the next system to run.
+    pub system: String,
+
+    /// The name of the next system to run.
+    pub name: Option<String>,
+}
+
+/// The response to a `stepping.state` request.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingStateResponse {
+    /// Whether stepping is currently enabled.
+    pub enabled: bool,
+
+    /// The schedules with stepping enabled, in the order they are executed.
+    ///
+    /// This is [`None`] until all of those schedules have run at least once.
+    pub schedules: Option<Vec<String>>,
+
+    /// The next system that will run when stepping.
+    pub cursor: Option<BrpSteppingCursor>,
+}
+
 /// A helper function used to parse a `serde_json::Value`.
 fn parse<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T, BrpError> {
     serde_json::from_value(value).map_err(|err| BrpError {
@@ -503,6 +871,50 @@ pub fn process_remote_get_components_request(
     serde_json::to_value(response).map_err(BrpError::internal)
 }
 
+/// Handles a `world.get_component_history` request coming from a client.
+pub fn process_remote_get_component_history_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpGetComponentHistoryParams { entity, components } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    get_entity(world, entity)?;
+    let history = world.get_resource::<ComponentHistory>();
+
+    let mut response = BrpGetComponentHistoryResponse::default();
+    for component_path in components {
+        let type_registration = get_component_type_registration(&type_registry, &component_path)
+            .map_err(BrpError::component_error)?;
+        let component_id = world
+            .components()
+            .get_valid_id(type_registration.type_id())
+            .filter(|&component_id| history.is_some_and(|history| history.is_tracked(component_id)))
+            .ok_or_else(|| {
+                BrpError::component_error(format!(
+                    "The history of `{component_path}` is not recorded"
+                ))
+            })?;
+
+        let entries = history
+            .into_iter()
+            .flat_map(|history| history.get(entity, component_id))
+            .map(|entry| {
+                let serializer = TypedReflectSerializer::new(entry.value.as_ref(), &type_registry);
+                Ok(BrpComponentHistoryEntry {
+                    value: serde_json::to_value(serializer).map_err(BrpError::component_error)?,
+                    tick: entry.tick.get(),
+                    system: entry.system.as_ref().map(ToString::to_string),
+                })
+            })
+            .collect::<BrpResult<Vec<_>>>()?;
+        response.insert(component_path, entries);
+    }
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
 /// Handles a `world.get_resources` request coming from a client.
 pub fn process_remote_get_resources_request(
     In(params): In<Option<Value>>,
@@ -1306,6 +1718,96 @@ pub fn process_remote_list_resources_request(
     serde_json::to_value(response).map_err(BrpError::internal)
 }
 
+/// Handles an `asset.dependency_graph` request coming from a client.
+#[cfg(feature = "bevy_asset")]
+pub fn process_remote_asset_dependency_graph_request(
+    In(_params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    use bevy_asset::{AssetServer, LoadState};
+
+    let asset_server = world
+        .get_resource::<AssetServer>()
+        .ok_or_else(|| BrpError::resource_not_present("AssetServer"))?;
+    let graph = asset_server.dependency_graph_with_byte_sizes(world);
+    let to_strings =
+        |ids: &[bevy_asset::UntypedAssetId]| ids.iter().map(ToString::to_string).collect();
+    let response = BrpAssetDependencyGraphResponse {
+        assets: graph
+            .nodes
+            .iter()
+            .map(|node| {
+                let (load_state, error) = match &node.load_state {
+                    LoadState::NotLoaded => ("NotLoaded", None),
+                    LoadState::Queued => ("Queued", None),
+                    LoadState::Loading => ("Loading", None),
+                    LoadState::Loaded => ("Loaded", None),
+                    LoadState::Failed(error) => ("Failed", Some(error.to_string())),
+                };
+                BrpAssetNode {
+                    id: node.id.to_string(),
+                    path: node.path.as_ref().map(ToString::to_string),
+                    type_path: node.type_path.map(ToOwned::to_owned),
+                    load_state: load_state.to_owned(),
+                    error,
+                    dependencies: to_strings(&node.dependencies),
+                    dependants: to_strings(&node.dependants),
+                    byte_size: node.byte_size,
+                }
+            })
+            .collect(),
+    };
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `world.memory_report` request coming from a client.
+pub fn process_remote_memory_report_request(
+    In(_params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let report = world.memory_report();
+    let response = BrpMemoryReportResponse {
+        total: report.total().into(),
+        archetypes: report
+            .archetypes
+            .iter()
+            .map(|archetype| BrpStorageMemoryUsage {
+                id: archetype.id.index() as u32,
+                entities: archetype.entities,
+                usage: archetype.usage.into(),
+            })
+            .collect(),
+        tables: report
+            .tables
+            .iter()
+            .map(|table| BrpStorageMemoryUsage {
+                id: table.id.as_u32(),
+                entities: table.entities,
+                usage: table.usage.into(),
+            })
+            .collect(),
+        components: report
+            .components
+            .iter()
+            .map(|component| BrpTypeMemoryUsage {
+                name: component.name.to_string(),
+                usage: component.usage.into(),
+            })
+            .collect(),
+        resources: report
+            .resources
+            .iter()
+            .map(|resource| BrpTypeMemoryUsage {
+                name: resource.name.to_string(),
+                usage: resource.usage.into(),
+            })
+            .collect(),
+    };
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
 /// Handles a `world.list_components+watch` request coming from a client.
 pub fn process_remote_list_components_watching_request(
     In(params): In<Option<Value>>,
@@ -1405,6 +1907,600 @@ pub fn export_registry_types(In(params): In<Option<Value>>, world: &World) -> Br
     serde_json::to_value(schemas).map_err(BrpError::internal)
 }
 
+/// Handles a `world.transaction` request coming from a client.
+///
+/// The operations are applied in order during a single exclusive access to the [`World`].
+/// If any of them fails, the changes made by the previous ones are reverted and the error
+/// is returned with the index of the failed operation in its `data`.
+///
+/// Despawns are deferred until all other operations have succeeded, and operations on
+/// entities despawned earlier in the transaction fail. Side effects of hooks and observers
+/// triggered by the reverted changes are not undone.
+pub fn process_remote_transaction_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpTransactionParams { operations } = parse_some(params)?;
+
+    let mut transaction = Transaction::default();
+    let mut results = Vec::with_capacity(operations.len());
+    for (index, BrpTransactionOperation { method, params }) in operations.into_iter().enumerate() {
+        let result = params
+            .map(|params| resolve_entity_references(params, &results))
+            .transpose()
+            .and_then(|params| transaction.apply(world, &method, params));
+
+        match result {
+            Ok(result) => results.push(result),
+            Err(mut error) => {
+                transaction.rollback(world);
+                error.data = Some(serde_json::json!({
+                    "operation": index,
+                    "data": error.data,
+                }));
+                return Err(error);
+            }
+        }
+    }
+    transaction.commit(world);
+
+    serde_json::to_value(BrpTransactionResponse { results }).map_err(BrpError::internal)
+}
+
+/// The changes made by a `world.transaction` request that can still be reverted.
+#[derive(Default)]
+struct Transaction {
+    /// Operations reverting the applied changes, in the order they were recorded.
+    undo: Vec<UndoOperation>,
+    /// Entities that will be despawned when the transaction is committed.
+    despawned: EntityHashSet,
+    /// The order in which `despawned` entities were requested to be despawned.
+    despawn_order: Vec<Entity>,
+}
+
+/// A single step of reverting a [`Transaction`].
+enum UndoOperation {
+    /// Despawns an entity spawned during the transaction.
+    Despawn(Entity),
+    /// Restores the previous value of a component, removing it if there was none.
+    Component {
+        entity: Entity,
+        type_path: String,
+        value: Option<Box<dyn PartialReflect>>,
+    },
+    /// Restores the previous value of a resource, removing it if there was none.
+    Resource {
+        type_path: String,
+        value: Option<Box<dyn PartialReflect>>,
+    },
+    /// Restores the previous parent of an entity.
+    Parent {
+        entity: Entity,
+        parent: Option<Entity>,
+    },
+}
+
+impl Transaction {
+    /// Applies a single operation, recording how to revert it first.
+    fn apply(&mut self, world: &mut World, method: &str, params: Option<Value>) -> BrpResult {
+        match method {
+            BRP_SPAWN_ENTITY_METHOD => {
+                let BrpSpawnEntityParams { components } = parse_some(params)?;
+                let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+                let type_registry = app_type_registry.read();
+
+                // Validate everything up front, so that a failure can't leave behind a
+                // partially constructed entity.
+                let reflect_components = deserialize_components(&type_registry, components)
+                    .map_err(BrpError::component_error)?;
+                for reflected in &reflect_components {
+                    get_reflect_component(&type_registry, represented_type_path(&**reflected))
+                        .map_err(BrpError::component_error)?;
+                }
+
+                let entity = world.spawn_empty();
+                let entity_id = entity.id();
+                self.undo.push(UndoOperation::Despawn(entity_id));
+                insert_reflected_components(&type_registry, entity, reflect_components)
+                    .map_err(BrpError::component_error)?;
+
+                serde_json::to_value(BrpSpawnEntityResponse { entity: entity_id })
+                    .map_err(BrpError::internal)
+            }
+            BRP_DESPAWN_COMPONENTS_METHOD => {
+                let BrpDespawnEntityParams { entity } = parse_some(params)?;
+                self.check_entity(world, entity)?;
+                self.despawn_order.push(entity);
+                let mut stack = vec![entity];
+                while let Some(entity) = stack.pop() {
+                    self.despawned.insert(entity);
+                    if let Some(children) = world.get::<Children>(entity) {
+                        stack.extend(children.iter());
+                    }
+                }
+                Ok(Value::Null)
+            }
+            BRP_INSERT_COMPONENTS_METHOD => {
+                let BrpInsertComponentsParams { entity, components } = parse_some(params.clone())?;
+                self.check_entity(world, entity)?;
+                for type_path in components.keys() {
+                    self.record_component(world, entity, type_path)?;
+                }
+                process_remote_insert_components_request(In(params), world)
+            }
+            BRP_REMOVE_COMPONENTS_METHOD => {
+                let BrpRemoveComponentsParams { entity, components } = parse_some(params.clone())?;
+                self.check_entity(world, entity)?;
+                for type_path in &components {
+                    self.record_component(world, entity, type_path)?;
+                }
+                process_remote_remove_components_request(In(params), world)
+            }
+            BRP_MUTATE_COMPONENTS_METHOD => {
+                let BrpMutateComponentsParams {
+                    entity, component, ..
+                } = parse_some(params.clone())?;
+                self.check_entity(world, entity)?;
+                self.record_component(world, entity, &component)?;
+                process_remote_mutate_components_request(In(params), world)
+            }
+            BRP_REPARENT_ENTITIES_METHOD => {
+                let BrpReparentEntitiesParams { entities, parent } = parse_some(params.clone())?;
+                for &entity in entities.iter().chain(&parent) {
+                    self.check_entity(world, entity)?;
+                }
+                for entity in entities {
+                    self.undo.push(UndoOperation::Parent {
+                        entity,
+                        parent: world.get::<ChildOf>(entity).map(ChildOf::parent),
+                    });
+                }
+                process_remote_reparent_entities_request(In(params), world)
+            }
+            BRP_INSERT_RESOURCE_METHOD => {
+                let BrpInsertResourcesParams { resource, .. } = parse_some(params.clone())?;
+                self.record_resource(world, &resource)?;
+                process_remote_insert_resources_request(In(params), world)
+            }
+            BRP_REMOVE_RESOURCE_METHOD => {
+                let BrpRemoveResourcesParams { resource } = parse_some(params.clone())?;
+                self.record_resource(world, &resource)?;
+                process_remote_remove_resources_request(In(params), world)
+            }
+            BRP_MUTATE_RESOURCE_METHOD => {
+                let BrpMutateResourcesParams { resource, .. } = parse_some(params.clone())?;
+                self.record_resource(world, &resource)?;
+                process_remote_mutate_resources_request(In(params), world)
+            }
+            _ => Err(BrpError {
+                code: error_codes::METHOD_NOT_FOUND,
+                message: format!("Method `{method}` can't be used in a transaction"),
+                data: None,
+            }),
+        }
+    }
+
+    /// Returns an error if the entity doesn't exist or will be despawned by this transaction.
+    fn check_entity(&self, world: &World, entity: Entity) -> Result<(), BrpError> {
+        if self.despawned.contains(&entity) {
+            return Err(BrpError::entity_not_found(entity));
+        }
+        get_entity(world, entity).map(|_| ())
+    }
+
+    /// Records the current value of a component so that it can be restored.
+    fn record_component(
+        &mut self,
+        world: &World,
+        entity: Entity,
+        type_path: &str,
+    ) -> Result<(), BrpError> {
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let reflect_component =
+            get_reflect_component(&type_registry, type_path).map_err(BrpError::component_error)?;
+        let value = reflect_component
+            .reflect(get_entity(world, entity)?)
+            .map(clone_reflected);
+
+        self.undo.push(UndoOperation::Component {
+            entity,
+            type_path: type_path.to_owned(),
+            value,
+        });
+        Ok(())
+    }
+
+    /// Records the current value of a resource so that it can be restored.
+    fn record_resource(&mut self, world: &World, type_path: &str) -> Result<(), BrpError> {
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let reflect_resource =
+            get_reflect_resource(&type_registry, type_path).map_err(BrpError::resource_error)?;
+        let value = reflect_resource.reflect(world).ok().map(clone_reflected);
+
+        self.undo.push(UndoOperation::Resource {
+            type_path: type_path.to_owned(),
+            value,
+        });
+        Ok(())
+    }
+
+    /// Reverts all changes made by the transaction so far.
+    fn rollback(self, world: &mut World) {
+        let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = app_type_registry.read();
+
+        for undo in self.undo.into_iter().rev() {
+            match undo {
+                UndoOperation::Despawn(entity) => {
+                    world.despawn(entity);
+                }
+                UndoOperation::Component {
+                    entity,
+                    type_path,
+                    value,
+                } => {
+                    let (Ok(reflect_component), Ok(mut entity)) = (
+                        get_reflect_component(&type_registry, &type_path),
+                        world.get_entity_mut(entity),
+                    ) else {
+                        continue;
+                    };
+                    match value {
+                        Some(value) => {
+                            reflect_component.insert(&mut entity, &*value, &type_registry);
+                        }
+                        None => reflect_component.remove(&mut entity),
+                    }
+                }
+                UndoOperation::Resource { type_path, value } => {
+                    let Ok(reflect_resource) = get_reflect_resource(&type_registry, &type_path)
+                    else {
+                        continue;
+                    };
+                    match value {
+                        Some(value) => reflect_resource.insert(world, &*value, &type_registry),
+                        None => reflect_resource.remove(world),
+                    }
+                }
+                UndoOperation::Parent { entity, parent } => {
+                    let Ok(mut entity) = world.get_entity_mut(entity) else {
+                        continue;
+                    };
+                    match parent {
+                        Some(parent) => {
+                            entity.insert(ChildOf(parent));
+                        }
+                        None => {
+                            entity.remove::<ChildOf>();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies the deferred despawns, making the transaction permanent.
+    fn commit(self, world: &mut World) {
+        for entity in self.despawn_order {
+            // The entity may already be gone if one of its ancestors was despawned first.
+            world.try_despawn(entity).ok();
+        }
+    }
+}
+
+/// Clones a reflected value, preferring a concrete clone over a dynamic one.
+fn clone_reflected(value: &dyn Reflect) -> Box<dyn PartialReflect> {
+    match value.reflect_clone() {
+        Ok(cloned) => cloned.into_partial_reflect(),
+        Err(_) => value.to_dynamic(),
+    }
+}
+
+/// Replaces `{ "$entity": n }` objects in `value` with the entity spawned by the `n`th operation.
+fn resolve_entity_references(mut value: Value, results: &[Value]) -> BrpResult {
+    match &mut value {
+        Value::Object(map) if map.len() == 1 && map.contains_key("$entity") => {
+            let index = map["$entity"].as_u64().ok_or_else(|| BrpError {
+                code: error_codes::INVALID_PARAMS,
+                message: "`$entity` must refer to the index of a previous operation".to_owned(),
+                data: None,
+            })?;
+            return usize::try_from(index)
+                .ok()
+                .and_then(|index| results.get(index))
+                .and_then(|result| result.get("entity"))
+                .cloned()
+                .ok_or_else(|| BrpError {
+                    code: error_codes::INVALID_PARAMS,
+                    message: format!("Operation {index} did not spawn an entity"),
+                    data: None,
+                });
+        }
+        Value::Object(map) => {
+            for field in map.values_mut() {
+                *field = resolve_entity_references(core::mem::take(field), results)?;
+            }
+        }
+        Value::Array(array) => {
+            for element in array {
+                *element = resolve_entity_references(core::mem::take(element), results)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(value)
+}
+
+/// Handles a `schedule.list` request coming from a client.
+///
+/// Note that schedules that are running while the request is handled, such as
+/// [`Main`](bevy_app::Main) and [`RemoteLast`](crate::RemoteLast), are not listed.
+pub fn process_remote_list_schedules_request(
+    In(_params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let Some(schedules) = world.get_resource::<Schedules>() else {
+        return serde_json::to_value(BrpListSchedulesResponse::new()).map_err(BrpError::internal);
+    };
+
+    let mut response = schedules
+        .iter()
+        .map(|(label, schedule)| BrpScheduleInfo {
+            label: format!("{label:?}"),
+            systems: schedule.systems_len(),
+            initialized: schedule.systems().is_ok(),
+        })
+        .collect::<BrpListSchedulesResponse>();
+    response.sort_by(|a, b| a.label.cmp(&b.label));
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `schedule.graph` request coming from a client.
+pub fn process_remote_schedule_graph_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpScheduleGraphParams { schedule } = parse_some(params)?;
+    let schedule = get_schedule(world, &schedule)?;
+    let graph = schedule.graph();
+
+    let condition_names = |conditions: Option<&[ConditionWithAccess]>| {
+        conditions
+            .unwrap_or_default()
+            .iter()
+            .map(|condition| condition.condition.name().to_string())
+            .collect::<Vec<_>>()
+    };
+    let edges = |dag: &Dag<NodeId>| {
+        dag.graph()
+            .all_edges()
+            .map(|(from, to)| BrpScheduleEdge {
+                from: schedule_node_id(from),
+                to: schedule_node_id(to),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let systems = graph
+        .dependency()
+        .graph()
+        .nodes()
+        .filter_map(|node| node.as_system())
+        .filter_map(|key| {
+            Some(BrpScheduleNode {
+                id: schedule_node_id(key.into()),
+                name: schedule.get_system(key)?.name().to_string(),
+                conditions: condition_names(schedule.get_system_conditions(key)),
+            })
+        })
+        .collect();
+
+    let sets = graph
+        .system_sets
+        .iter()
+        .map(|(key, set, _)| BrpScheduleNode {
+            id: schedule_node_id(key.into()),
+            name: format!("{set:?}"),
+            conditions: condition_names(schedule.get_set_conditions(key)),
+        })
+        .collect();
+
+    let order = schedule
+        .systems()
+        .map(|systems| {
+            systems
+                .map(|(key, _)| schedule_node_id(key.into()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let conflicts = graph
+        .conflicting_systems()
+        .iter()
+        .map(|(a, b, components)| BrpScheduleConflict {
+            a: schedule_node_id((*a).into()),
+            b: schedule_node_id((*b).into()),
+            components: components
+                .iter()
+                .map(|&id| world.components().get_name(id).map(|name| name.to_string()))
+                .collect::<Option<_>>()
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    let response = BrpScheduleGraphResponse {
+        label: format!("{:?}", schedule.label()),
+        systems,
+        sets,
+        hierarchy: edges(graph.hierarchy()),
+        dependencies: edges(graph.dependency()),
+        order,
+        conflicts,
+    };
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `stepping.state` request coming from a client.
+pub fn process_remote_stepping_state_request(
+    In(_params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let Some(stepping) = world.get_resource::<Stepping>() else {
+        return serde_json::to_value(BrpSteppingStateResponse::default())
+            .map_err(BrpError::internal);
+    };
+
+    let cursor = stepping.cursor().map(|(label, node)| {
+        let name = world
+            .get_resource::<Schedules>()
+            .and_then(|schedules| schedules.get(label))
+            .zip(node.as_system())
+            .and_then(|(schedule, key)| schedule.get_system(key))
+            .map(|system| system.name().to_string());
+        BrpSteppingCursor {
+            schedule: format!("{label:?}"),
+            system: schedule_node_id(node),
+            name,
+        }
+    });
+
+    let response = BrpSteppingStateResponse {
+        enabled: stepping.is_enabled(),
+        schedules: stepping
+            .schedules()
+            .ok()
+            .map(|schedules| schedules.iter().map(|label| format!("{label:?}")).collect()),
+        cursor,
+    };
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `stepping.enable` request coming from a client.
+///
+/// Changes to [`Stepping`] take effect at the start of the next frame.
+pub fn process_remote_stepping_enable_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingEnableParams { schedules } = match params {
+        None => Default::default(),
+        Some(params) => parse(params)?,
+    };
+
+    if !cfg!(feature = "bevy_debug_stepping") {
+        return Err(BrpError::stepping_error(
+            "Stepping cannot be enabled; bevy_remote was compiled without the \
+            bevy_debug_stepping feature",
+        ));
+    }
+
+    let labels = schedules
+        .iter()
+        .map(|schedule| get_schedule(world, schedule).map(Schedule::label))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut stepping = world.get_resource_or_init::<Stepping>();
+    for label in labels {
+        stepping.add_schedule(label);
+    }
+    stepping.enable();
+
+    Ok(Value::Null)
+}
+
+/// Handles a `stepping.disable` request coming from a client.
+pub fn process_remote_stepping_disable_request(
+    In(_params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    if let Some(mut stepping) = world.get_resource_mut::<Stepping>() {
+        stepping.disable();
+    }
+    Ok(Value::Null)
+}
+
+/// Handles a `stepping.add_schedule` request coming from a client.
+pub fn process_remote_stepping_add_schedule_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingScheduleParams { schedule } = parse_some(params)?;
+    let label = get_schedule(world, &schedule)?.label();
+    world.get_resource_or_init::<Stepping>().add_schedule(label);
+    Ok(Value::Null)
+}
+
+/// Handles a `stepping.remove_schedule` request coming from a client.
+pub fn process_remote_stepping_remove_schedule_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingScheduleParams { schedule } = parse_some(params)?;
+    let label = get_schedule(world, &schedule)?.label();
+    get_stepping_mut(world)?.remove_schedule(label);
+    Ok(Value::Null)
+}
+
+/// Handles a `stepping.step` request coming from a client.
+///
+/// This runs the next system in the stepping frame during the next frame.
+pub fn process_remote_stepping_step_request(
+    In(_params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    get_stepping_mut(world)?.step_frame();
+    Ok(Value::Null)
+}
+
+/// Handles a `stepping.continue` request coming from a client.
+///
+/// This runs the remaining systems of the stepping frame during the next frame,
+/// stopping at the next breakpoint.
+pub fn process_remote_stepping_continue_request(
+    In(_params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    get_stepping_mut(world)?.continue_frame();
+    Ok(Value::Null)
+}
+
+/// Handles a `stepping.set_breakpoint` request coming from a client.
+pub fn process_remote_stepping_set_breakpoint_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingBreakpointParams { schedule, system } = parse_some(params)?;
+    let schedule = get_schedule(world, &schedule)?;
+    let label = schedule.label();
+    let nodes = find_schedule_systems(schedule, &system)?;
+
+    let mut stepping = get_stepping_mut(world)?;
+    for node in nodes {
+        stepping.set_breakpoint_node(label, node);
+    }
+    Ok(Value::Null)
+}
+
+/// Handles a `stepping.clear_breakpoint` request coming from a client.
+pub fn process_remote_stepping_clear_breakpoint_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingBreakpointParams { schedule, system } = parse_some(params)?;
+    let schedule = get_schedule(world, &schedule)?;
+    let label = schedule.label();
+    let nodes = find_schedule_systems(schedule, &system)?;
+
+    let mut stepping = get_stepping_mut(world)?;
+    for node in nodes {
+        stepping.clear_breakpoint_node(label, node);
+    }
+    Ok(Value::Null)
+}
+
 /// Immutably retrieves an entity from the [`World`], returning an error if the
 /// entity isn't present.
 fn get_entity(world: &World, entity: Entity) -> Result<EntityRef<'_>, BrpError> {
@@ -1558,13 +2654,24 @@ fn insert_reflected_components(
 ) -> AnyhowResult<()> {
     for reflected in reflect_components {
         let reflect_component =
-            get_reflect_component(type_registry, reflected.reflect_type_path())?;
+            get_reflect_component(type_registry, represented_type_path(&*reflected))?;
         reflect_component.insert(&mut entity_world_mut, &*reflected, type_registry);
     }
 
     Ok(())
 }
 
+/// Returns the type path of the type a reflected value represents.
+///
+/// Unlike [`PartialReflect::reflect_type_path`], this returns the path of the concrete
+/// type for dynamic values produced by deserialization.
+fn represented_type_path(reflected: &dyn PartialReflect) -> &str {
+    reflected
+        .get_represented_type_info()
+        .map(TypeInfo::type_path)
+        .unwrap_or_else(|| reflected.reflect_type_path())
+}
+
 /// Given a component's type path, return the associated [`ReflectComponent`] from the given
 /// `type_registry` if possible.
 fn get_reflect_component<'r>(
@@ -1602,6 +2709,57 @@ fn get_reflect_resource<'r>(
         .ok_or_else(|| anyhow!("Resource `{}` isn't reflectable", resource_path))
 }
 
+/// Finds the schedule whose label has the given `Debug` representation.
+fn get_schedule<'w>(world: &'w World, label: &str) -> Result<&'w Schedule, BrpError> {
+    world
+        .get_resource::<Schedules>()
+        .and_then(|schedules| {
+            schedules
+                .iter()
+                .find(|(schedule_label, _)| format!("{schedule_label:?}") == label)
+        })
+        .map(|(_, schedule)| schedule)
+        .ok_or_else(|| BrpError::schedule_not_found(label))
+}
+
+/// Mutably retrieves the [`Stepping`] resource, returning an error if stepping was never enabled.
+fn get_stepping_mut(world: &mut World) -> Result<Mut<'_, Stepping>, BrpError> {
+    world
+        .get_resource_mut::<Stepping>()
+        .ok_or_else(|| BrpError::stepping_error("Stepping has not been enabled"))
+}
+
+/// Returns the opaque identifier of a schedule node used in BRP responses.
+fn schedule_node_id(node: NodeId) -> String {
+    format!("{node:?}")
+}
+
+/// Finds the systems in `schedule` that match the given node id or system name.
+fn find_schedule_systems(schedule: &Schedule, system: &str) -> Result<Vec<NodeId>, BrpError> {
+    let nodes = schedule
+        .graph()
+        .dependency()
+        .graph()
+        .nodes()
+        .filter(|node| {
+            if schedule_node_id(*node) == system {
+                return true;
+            }
+            node.as_system()
+                .and_then(|key| schedule.get_system(key))
+                .is_some_and(|instance| {
+                    let name = instance.name();
+                    name.as_string() == system || name.shortname().to_string() == system
+                })
+        })
+        .collect::<Vec<_>>();
+
+    if nodes.is_empty() {
+        return Err(BrpError::system_not_found(system, schedule.label()));
+    }
+    Ok(nodes)
+}
+
 /// Given a resource's type path, return the associated [`TypeRegistration`] from the given
 /// `type_registry` if possible.
 fn get_resource_type_registration<'r>(
@@ -1635,6 +2793,13 @@ mod tests {
     }
 
     use super::*;
+    use bevy_ecs::{
+        component::Component,
+        reflect::record_component_history,
+        resource::Resource,
+        schedule::{IntoScheduleConfigs, ScheduleLabel, SystemSet},
+    };
+    use bevy_reflect::TypePath;
 
     #[test]
     fn serialization_tests() {
@@ -1657,4 +2822,379 @@ mod tests {
             entity: Entity::from_raw_u32(0).unwrap(),
         });
     }
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestSchedule;
+
+    #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestSet;
+
+    fn first() {}
+    fn second() {}
+    fn always() -> bool {
+        true
+    }
+
+    fn schedule_world() -> World {
+        let mut world = World::new();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((first, second.run_if(always)).chain().in_set(TestSet));
+        world.add_schedule(schedule);
+        world.run_schedule(TestSchedule);
+        world
+    }
+
+    #[test]
+    fn schedule_list() {
+        let mut world = schedule_world();
+        let response = world
+            .run_system_cached_with(process_remote_list_schedules_request, None)
+            .unwrap()
+            .unwrap();
+        let schedules: BrpListSchedulesResponse = serde_json::from_value(response).unwrap();
+        assert_eq!(
+            schedules,
+            vec![BrpScheduleInfo {
+                label: "TestSchedule".to_owned(),
+                systems: 2,
+                initialized: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn schedule_graph() {
+        let mut world = schedule_world();
+        let params = serde_json::json!({ "schedule": "TestSchedule" });
+        let response = world
+            .run_system_cached_with(process_remote_schedule_graph_request, Some(params))
+            .unwrap()
+            .unwrap();
+        let graph: BrpScheduleGraphResponse = serde_json::from_value(response).unwrap();
+
+        let system = |short_name: &str| {
+            graph
+                .systems
+                .iter()
+                .find(|system| system.name.ends_with(short_name))
+                .unwrap()
+        };
+        let first = system("::first");
+        let second = system("::second");
+        assert!(first.conditions.is_empty());
+        assert_eq!(second.conditions.len(), 1);
+        assert!(second.conditions[0].ends_with("::always"));
+
+        assert!(graph.dependencies.contains(&BrpScheduleEdge {
+            from: first.id.clone(),
+            to: second.id.clone(),
+        }));
+        assert_eq!(graph.order, vec![first.id.clone(), second.id.clone()]);
+
+        let set = graph.sets.iter().find(|set| set.name == "TestSet").unwrap();
+        for system in [first, second] {
+            assert!(graph.hierarchy.contains(&BrpScheduleEdge {
+                from: set.id.clone(),
+                to: system.id.clone(),
+            }));
+        }
+    }
+
+    #[test]
+    fn schedule_not_found() {
+        let mut world = schedule_world();
+        let params = serde_json::json!({ "schedule": "Missing" });
+        let error = world
+            .run_system_cached_with(process_remote_schedule_graph_request, Some(params))
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.code, error_codes::SCHEDULE_NOT_FOUND);
+    }
+
+    #[test]
+    fn stepping_breakpoints() {
+        let mut world = schedule_world();
+
+        // Breakpoints can only be set once stepping has been set up.
+        let params = serde_json::json!({ "schedule": "TestSchedule", "system": "first" });
+        let error = world
+            .run_system_cached_with(
+                process_remote_stepping_set_breakpoint_request,
+                Some(params.clone()),
+            )
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.code, error_codes::STEPPING_ERROR);
+
+        let add = serde_json::json!({ "schedule": "TestSchedule" });
+        world
+            .run_system_cached_with(process_remote_stepping_add_schedule_request, Some(add))
+            .unwrap()
+            .unwrap();
+        world
+            .run_system_cached_with(process_remote_stepping_set_breakpoint_request, Some(params))
+            .unwrap()
+            .unwrap();
+
+        let missing = serde_json::json!({ "schedule": "TestSchedule", "system": "third" });
+        let error = world
+            .run_system_cached_with(
+                process_remote_stepping_set_breakpoint_request,
+                Some(missing),
+            )
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.code, error_codes::SYSTEM_NOT_FOUND);
+
+        let enable = world
+            .run_system_cached_with(process_remote_stepping_enable_request, None)
+            .unwrap();
+        assert_eq!(enable.is_ok(), cfg!(feature = "bevy_debug_stepping"));
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        value: u32,
+    }
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    fn transaction_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Score>();
+            registry.register::<ChildOf>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    fn transaction(world: &mut World, operations: Value) -> BrpResult {
+        let params = serde_json::json!({ "operations": operations });
+        world
+            .run_system_cached_with(process_remote_transaction_request, Some(params))
+            .unwrap()
+    }
+
+    #[test]
+    fn transaction_references_spawned_entities() {
+        let mut world = transaction_world();
+        let health = Health::type_path();
+
+        let response = transaction(
+            &mut world,
+            serde_json::json!([
+                { "method": BRP_SPAWN_ENTITY_METHOD, "params": { "components": {} } },
+                { "method": BRP_SPAWN_ENTITY_METHOD, "params": { "components": {} } },
+                {
+                    "method": BRP_REPARENT_ENTITIES_METHOD,
+                    "params": { "entities": [{ "$entity": 1 }], "parent": { "$entity": 0 } },
+                },
+                {
+                    "method": BRP_INSERT_COMPONENTS_METHOD,
+                    "params": { "entity": { "$entity": 1 }, "components": { health: { "value": 3 } } },
+                },
+            ]),
+        )
+        .unwrap();
+        let response: BrpTransactionResponse = serde_json::from_value(response).unwrap();
+        let parent: Entity = serde_json::from_value(response.results[0]["entity"].clone()).unwrap();
+        let child: Entity = serde_json::from_value(response.results[1]["entity"].clone()).unwrap();
+
+        assert_eq!(world.get::<ChildOf>(child), Some(&ChildOf(parent)));
+        assert_eq!(world.get::<Health>(child), Some(&Health { value: 3 }));
+    }
+
+    #[test]
+    fn transaction_rolls_back_on_error() {
+        let mut world = transaction_world();
+        world.insert_resource(Score(1));
+        let existing = world.spawn(Health { value: 10 }).id();
+        let parent = world.spawn_empty().id();
+        let health = Health::type_path();
+        let score = Score::type_path();
+
+        let error = transaction(
+            &mut world,
+            serde_json::json!([
+                {
+                    "method": BRP_INSERT_COMPONENTS_METHOD,
+                    "params": { "entity": existing, "components": { health: { "value": 20 } } },
+                },
+                {
+                    "method": BRP_SPAWN_ENTITY_METHOD,
+                    "params": { "components": { health: { "value": 5 } } },
+                },
+                {
+                    "method": BRP_REPARENT_ENTITIES_METHOD,
+                    "params": { "entities": [existing], "parent": parent },
+                },
+                {
+                    "method": BRP_MUTATE_RESOURCE_METHOD,
+                    "params": { "resource": score, "path": ".0", "value": 2 },
+                },
+                { "method": BRP_DESPAWN_COMPONENTS_METHOD, "params": { "entity": parent } },
+                {
+                    "method": BRP_INSERT_COMPONENTS_METHOD,
+                    "params": { "entity": existing, "components": { "unknown::Component": 0 } },
+                },
+            ]),
+        )
+        .unwrap_err();
+
+        assert_eq!(error.data.unwrap()["operation"], 5);
+        assert_eq!(world.get::<Health>(existing), Some(&Health { value: 10 }));
+        assert_eq!(world.get::<ChildOf>(existing), None);
+        assert!(world.get_entity(parent).is_ok());
+        assert_eq!(world.query::<&Health>().iter(&world).count(), 1);
+        assert_eq!(world.resource::<Score>(), &Score(1));
+    }
+
+    #[test]
+    fn transaction_defers_despawns() {
+        let mut world = transaction_world();
+        let entity = world.spawn(Health { value: 1 }).id();
+        let health = Health::type_path();
+
+        let error = transaction(
+            &mut world,
+            serde_json::json!([
+                { "method": BRP_DESPAWN_COMPONENTS_METHOD, "params": { "entity": entity } },
+                {
+                    "method": BRP_INSERT_COMPONENTS_METHOD,
+                    "params": { "entity": entity, "components": { health: { "value": 2 } } },
+                },
+            ]),
+        )
+        .unwrap_err();
+        assert_eq!(error.code, error_codes::ENTITY_NOT_FOUND);
+        assert_eq!(world.get::<Health>(entity), Some(&Health { value: 1 }));
+
+        transaction(
+            &mut world,
+            serde_json::json!([
+                { "method": BRP_DESPAWN_COMPONENTS_METHOD, "params": { "entity": entity } },
+            ]),
+        )
+        .unwrap();
+        assert!(world.get_entity(entity).is_err());
+    }
+
+    #[test]
+    fn component_history() {
+        let mut world = transaction_world();
+        let entity = world.spawn(Health { value: 3 }).id();
+        let health = Health::type_path();
+        let params = serde_json::json!({ "entity": entity, "components": [health] });
+
+        let error = world
+            .run_system_cached_with(
+                process_remote_get_component_history_request,
+                Some(params.clone()),
+            )
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.code, error_codes::COMPONENT_ERROR);
+
+        world.track_component_history::<Health>(4);
+        record_component_history(&mut world);
+        world.get_mut::<Health>(entity).unwrap().value = 2;
+        record_component_history(&mut world);
+
+        let response = world
+            .run_system_cached_with(process_remote_get_component_history_request, Some(params))
+            .unwrap()
+            .unwrap();
+        let response: BrpGetComponentHistoryResponse = serde_json::from_value(response).unwrap();
+        let values: Vec<_> = response[health].iter().map(|entry| &entry.value).collect();
+        assert_eq!(
+            values,
+            [
+                &serde_json::json!({ "value": 3 }),
+                &serde_json::json!({ "value": 2 })
+            ]
+        );
+    }
+
+    #[test]
+    fn memory_report() {
+        let mut world = World::new();
+        world.spawn(Health { value: 1 });
+        world.insert_resource(Score(0));
+
+        let response = world
+            .run_system_cached_with(process_remote_memory_report_request, None)
+            .unwrap()
+            .unwrap();
+        let response: BrpMemoryReportResponse = serde_json::from_value(response).unwrap();
+        assert!(response.total.allocated >= response.total.used);
+        assert!(response
+            .components
+            .iter()
+            .any(|component| component.name.ends_with("Health")
+                && component.usage.used >= size_of::<Health>()));
+        assert!(response
+            .resources
+            .iter()
+            .any(|resource| resource.name.ends_with("Score")));
+    }
+    #[cfg(feature = "bevy_asset")]
+    #[test]
+    fn asset_dependency_graph() {
+        use bevy_asset::{
+            io::{
+                memory::{Dir, MemoryAssetReader},
+                AssetSourceBuilder, AssetSourceBuilders, AssetSourceId,
+            },
+            Asset, AssetServer, AssetServerMode, Assets,
+        };
+        use bevy_tasks::{IoTaskPool, TaskPool};
+
+        #[derive(Asset, TypePath)]
+        struct TestAsset;
+
+        let mut world = World::new();
+        assert!(world
+            .run_system_cached_with(process_remote_asset_dependency_graph_request, None)
+            .unwrap()
+            .is_err());
+
+        IoTaskPool::get_or_init(TaskPool::new);
+        let mut sources = AssetSourceBuilders::default();
+        sources.insert(
+            AssetSourceId::Default,
+            AssetSourceBuilder::default().with_reader(|| {
+                Box::new(MemoryAssetReader {
+                    root: Dir::default(),
+                })
+            }),
+        );
+        let asset_server = AssetServer::new(
+            sources.build_sources(false, false),
+            AssetServerMode::Unprocessed,
+            false,
+            Default::default(),
+        );
+        asset_server.register_asset(&Assets::<TestAsset>::default());
+        let _handle = asset_server.load::<TestAsset>("test.asset");
+        world.insert_resource(asset_server);
+
+        let response = world
+            .run_system_cached_with(process_remote_asset_dependency_graph_request, None)
+            .unwrap()
+            .unwrap();
+        let response: BrpAssetDependencyGraphResponse = serde_json::from_value(response).unwrap();
+        assert_eq!(response.assets.len(), 1);
+        let asset = &response.assets[0];
+        assert_eq!(asset.path.as_deref(), Some("test.asset"));
+        assert_eq!(asset.type_path.as_deref(), Some(TestAsset::type_path()));
+        assert!(asset.dependencies.is_empty());
+        assert_eq!(asset.byte_size, None);
+    }
 }
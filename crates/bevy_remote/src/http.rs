@@ -277,31 +277,38 @@ async fn process_request_batch(
             let response = process_single_request(request, request_sender).await?;
             match response {
                 BrpHttpResponse::Complete(res) => {
-                    BrpHttpResponse::Complete(serde_json::to_string(&res)?)
+                    BrpHttpResponse::Complete(serde_json::to_string(&res.resolve().await?)?)
                 }
                 BrpHttpResponse::Stream(stream) => BrpHttpResponse::Stream(stream),
             }
         }
         Ok(BrpBatch::Batch(requests)) => {
-            let mut responses = Vec::new();
+            // Send every request before waiting on any of the results, so that the whole
+            // batch is handled during the same frame.
+            let mut pending = Vec::new();
 
             for request in requests {
                 let response = process_single_request(request, request_sender).await?;
                 match response {
-                    BrpHttpResponse::Complete(res) => responses.push(res),
+                    BrpHttpResponse::Complete(res) => pending.push(res),
                     BrpHttpResponse::Stream(BrpStream { id, .. }) => {
-                        responses.push(BrpResponse::new(
+                        pending.push(PendingResponse::Complete(BrpResponse::new(
                             id,
                             Err(BrpError {
                                 code: error_codes::INVALID_REQUEST,
                                 message: "Streaming can not be used in batch requests".to_string(),
                                 data: None,
                             }),
-                        ));
+                        )));
                     }
                 }
             }
 
+            let mut responses = Vec::with_capacity(pending.len());
+            for response in pending {
+                responses.push(response.resolve().await?);
+            }
+
             BrpHttpResponse::Complete(serde_json::to_string(&responses)?)
         }
         Err(err) => {
@@ -346,35 +353,41 @@ async fn process_request_batch(
 
 /// A helper function for the Bevy Remote Protocol server that processes a single
 /// request coming from a client.
+///
+/// The request is sent to the world, but its response isn't awaited.
 async fn process_single_request(
     request: Value,
     request_sender: &Sender<BrpMessage>,
-) -> AnyhowResult<BrpHttpResponse<BrpResponse, BrpStream>> {
+) -> AnyhowResult<BrpHttpResponse<PendingResponse, BrpStream>> {
     // Reach in and get the request ID early so that we can report it even when parsing fails.
     let id = request.as_object().and_then(|map| map.get("id")).cloned();
 
     let request: BrpRequest = match serde_json::from_value(request) {
         Ok(v) => v,
         Err(err) => {
-            return Ok(BrpHttpResponse::Complete(BrpResponse::new(
-                id,
-                Err(BrpError {
-                    code: error_codes::INVALID_REQUEST,
-                    message: err.to_string(),
-                    data: None,
-                }),
+            return Ok(BrpHttpResponse::Complete(PendingResponse::Complete(
+                BrpResponse::new(
+                    id,
+                    Err(BrpError {
+                        code: error_codes::INVALID_REQUEST,
+                        message: err.to_string(),
+                        data: None,
+                    }),
+                ),
             )));
         }
     };
 
     if request.jsonrpc != "2.0" {
-        return Ok(BrpHttpResponse::Complete(BrpResponse::new(
-            id,
-            Err(BrpError {
-                code: error_codes::INVALID_REQUEST,
-                message: String::from("JSON-RPC request requires `\"jsonrpc\": \"2.0\"`"),
-                data: None,
-            }),
+        return Ok(BrpHttpResponse::Complete(PendingResponse::Complete(
+            BrpResponse::new(
+                id,
+                Err(BrpError {
+                    code: error_codes::INVALID_REQUEST,
+                    message: String::from("JSON-RPC request requires `\"jsonrpc\": \"2.0\"`"),
+                    data: None,
+                }),
+            ),
         )));
     }
 
@@ -396,10 +409,29 @@ async fn process_single_request(
             rx: Box::pin(result_receiver),
         }))
     } else {
-        let result = result_receiver.recv().await?;
-        Ok(BrpHttpResponse::Complete(BrpResponse::new(
-            request.id, result,
-        )))
+        Ok(BrpHttpResponse::Complete(PendingResponse::Waiting {
+            id: request.id,
+            rx: result_receiver,
+        }))
+    }
+}
+
+/// The response to a request that may still be waiting to be processed by the world.
+enum PendingResponse {
+    Complete(BrpResponse),
+    Waiting {
+        id: Option<Value>,
+        rx: Receiver<BrpResult>,
+    },
+}
+
+impl PendingResponse {
+    /// Waits for the world to process the request.
+    async fn resolve(self) -> AnyhowResult<BrpResponse> {
+        match self {
+            PendingResponse::Complete(response) => Ok(response),
+            PendingResponse::Waiting { id, rx } => Ok(BrpResponse::new(id, rx.recv().await?)),
+        }
     }
 }
 
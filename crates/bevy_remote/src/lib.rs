@@ -3,7 +3,8 @@
 //! Adding the [`RemotePlugin`] to your [`App`] will setup everything needed without
 //! starting any transports. To start accepting remote connections you will need to
 //! add a second plugin like the [`RemoteHttpPlugin`](http::RemoteHttpPlugin) to enable communication
-//! over HTTP. These *remote clients* can inspect and alter the state of the
+//! over HTTP, or the `RemoteWebSocketPlugin` (behind the `websocket` feature) to enable
+//! communication over WebSocket. These *remote clients* can inspect and alter the state of the
 //! entity-component system.
 //!
 //! The Bevy Remote Protocol is based on the JSON-RPC 2.0 protocol.
@@ -455,6 +456,159 @@
 //!
 //! `result`: An array of [fully-qualified type names] of registered resource types.
 //!
+//! ### `world.transaction`
+//!
+//! Apply a list of operations atomically, during a single access to the world.
+//!
+//! `params`:
+//! - `operations`: An array of objects with a `method` and optional `params`, where `method`
+//!   is one of `world.spawn_entity`, `world.despawn_entity`, `world.insert_components`,
+//!   `world.remove_components`, `world.mutate_components`, `world.reparent_entities`,
+//!   `world.insert_resources`, `world.remove_resources` or `world.mutate_resources`.
+//!   Within `params`, an object of the form `{ "$entity": n }` is replaced with the ID of the
+//!   entity spawned by the `n`th operation.
+//!
+//! If any operation fails, all changes made by the transaction are reverted and the error of
+//! that operation is returned, with the index of the operation in `data.operation`.
+//! Despawns are only applied once all other operations have succeeded.
+//!
+//! `result`:
+//! - `results`: An array holding the result of each operation.
+//!
+//! ### `world.get_component_history`
+//!
+//! Retrieve the recorded values of one or more components of an entity. Only components whose
+//! history is recorded, such as with `App::track_component_history`, can be requested.
+//!
+//! `params`:
+//! - `entity`: The ID of the entity whose component history to get.
+//! - `components`: An array of [fully-qualified type names] of components.
+//!
+//! `result`: A map associating each type name to an array of the recorded values of the
+//! component, from the oldest to the most recent, each with:
+//! - `value`: The serialized value of the component.
+//! - `tick`: The change tick at which the component was changed.
+//! - `system`: The name of the system that changed the component, or null if it is unknown.
+//!
+//! ### `world.memory_report`
+//!
+//! Report the memory held by the storages of the world, in bytes. This method has no parameters.
+//!
+//! `result`:
+//! - `total`: The memory held by all the storages, as an object with `used` and `allocated`
+//!   byte counts, where `allocated` includes unused capacity.
+//! - `archetypes`: An array with the `id`, number of `entities`, and `used` and `allocated` bytes
+//!   of the bookkeeping of each archetype.
+//! - `tables`: An array with the `id`, number of `entities`, and `used` and `allocated` bytes of
+//!   each table.
+//! - `components`: An array with the `name`, and `used` and `allocated` bytes of the values of
+//!   each component type, from the largest allocation to the smallest.
+//! - `resources`: An array with the `name`, and `used` and `allocated` bytes of each resource,
+//!   from the largest allocation to the smallest.
+//!
+//! ### `asset.dependency_graph`
+//!
+//! List the assets managed by the asset server, along with the dependencies between them. This
+//! method has no parameters, and requires the `bevy_asset` feature.
+//!
+//! `result`:
+//! - `assets`: An array of objects, sorted by path, with the following fields:
+//!   - `id`: An opaque identifier of the asset.
+//!   - `path`: The path of the asset, or null if it was not loaded from a path.
+//!   - `type_path`: The type path of the asset type, or null if it is unknown.
+//!   - `load_state`: One of `NotLoaded`, `Queued`, `Loading`, `Loaded` or `Failed`.
+//!   - `error` (optional): The error the asset failed to load with.
+//!   - `dependencies`: The `id`s of the assets this asset depends on.
+//!   - `dependants`: The `id`s of the assets depending on this asset.
+//!   - `byte_size`: The number of bytes used by the asset, or null if its size is not tracked
+//!     with an asset memory budget.
+//!
+//! ### `schedule.list`
+//!
+//! List the schedules of the app. This method has no parameters.
+//!
+//! Schedules that are running while the request is handled (such as `Main` and `RemoteLast`)
+//! are not listed.
+//!
+//! `result`: An array of objects with the following fields:
+//! - `label`: The label of the schedule, as printed by its `Debug` implementation.
+//! - `systems`: The number of systems in the schedule.
+//! - `initialized`: Whether the schedule has been initialized, which happens when it first runs.
+//!
+//! ### `schedule.graph`
+//!
+//! Describe the systems, system sets and ordering constraints of a schedule.
+//!
+//! `params`:
+//! - `schedule`: The label of the schedule, as reported by `schedule.list`.
+//!
+//! `result`:
+//! - `label`: The label of the schedule.
+//! - `systems`: An array of systems, each with an `id`, a `name` and the names of its `conditions`.
+//! - `sets`: An array of system sets, each with an `id`, a `name` and the names of its `conditions`.
+//! - `hierarchy`: An array of `from`/`to` edges from sets to the systems and sets they contain.
+//! - `dependencies`: An array of `from`/`to` edges from systems and sets to those that run after them.
+//! - `order`: The `id`s of the systems in execution order, once the schedule is initialized.
+//! - `conflicts`: Ambiguities between systems, if ambiguity detection is enabled.
+//!
+//! ### `stepping.state`
+//!
+//! Report the state of system stepping. This method has no parameters.
+//!
+//! `result`:
+//! - `enabled`: Whether stepping is enabled.
+//! - `schedules`: The labels of the stepped schedules in execution order, or null if they
+//!   haven't all run yet.
+//! - `cursor` (optional): The `schedule`, system `id` and system `name` that will run on the
+//!   next step.
+//!
+//! ### `stepping.enable`
+//!
+//! Enable system stepping. This requires the `bevy_debug_stepping` feature.
+//!
+//! `params` (optional):
+//! - `schedules`: An array of schedule labels to enable stepping for.
+//!
+//! `result`: null.
+//!
+//! ### `stepping.disable`
+//!
+//! Disable system stepping, resuming normal execution. This method has no parameters.
+//!
+//! `result`: null.
+//!
+//! ### `stepping.add_schedule` / `stepping.remove_schedule`
+//!
+//! Enable or disable stepping for a single schedule.
+//!
+//! `params`:
+//! - `schedule`: The label of the schedule.
+//!
+//! `result`: null.
+//!
+//! ### `stepping.step`
+//!
+//! Run the next system of the stepping frame. This method has no parameters.
+//!
+//! `result`: null.
+//!
+//! ### `stepping.continue`
+//!
+//! Run the remaining systems of the stepping frame, stopping at the next breakpoint.
+//! Without breakpoints, this steps through a whole frame. This method has no parameters.
+//!
+//! `result`: null.
+//!
+//! ### `stepping.set_breakpoint` / `stepping.clear_breakpoint`
+//!
+//! Set or clear a breakpoint on a system.
+//!
+//! `params`:
+//! - `schedule`: The label of the schedule containing the system.
+//! - `system`: The `id` of the system as reported by `schedule.graph`, or its full or short name.
+//!
+//! `result`: null.
+//!
 //! ## Custom methods
 //!
 //! In addition to the provided methods, the Bevy Remote Protocol can be extended to include custom
@@ -506,7 +660,7 @@ use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     entity::Entity,
     resource::Resource,
-    schedule::{IntoScheduleConfigs, ScheduleLabel, SystemSet},
+    schedule::{InternedScheduleLabel, IntoScheduleConfigs, ScheduleLabel, SystemSet},
     system::{Commands, In, IntoSystem, ResMut, System, SystemId},
     world::World,
 };
@@ -520,6 +674,8 @@ pub mod builtin_methods;
 #[cfg(feature = "http")]
 pub mod http;
 pub mod schemas;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 const CHANNEL_SIZE: usize = 16;
 
@@ -574,7 +730,7 @@ impl RemotePlugin {
 
 impl Default for RemotePlugin {
     fn default() -> Self {
-        Self::empty()
+        let plugin = Self::empty()
             .with_method(
                 builtin_methods::BRP_GET_COMPONENTS_METHOD,
                 builtin_methods::process_remote_get_components_request,
@@ -647,6 +803,68 @@ impl Default for RemotePlugin {
                 builtin_methods::BRP_REGISTRY_SCHEMA_METHOD,
                 builtin_methods::export_registry_types,
             )
+            .with_method(
+                builtin_methods::BRP_TRANSACTION_METHOD,
+                builtin_methods::process_remote_transaction_request,
+            )
+            .with_method(
+                builtin_methods::BRP_GET_COMPONENT_HISTORY_METHOD,
+                builtin_methods::process_remote_get_component_history_request,
+            )
+            .with_method(
+                builtin_methods::BRP_MEMORY_REPORT_METHOD,
+                builtin_methods::process_remote_memory_report_request,
+            )
+            .with_method(
+                builtin_methods::BRP_LIST_SCHEDULES_METHOD,
+                builtin_methods::process_remote_list_schedules_request,
+            )
+            .with_method(
+                builtin_methods::BRP_SCHEDULE_GRAPH_METHOD,
+                builtin_methods::process_remote_schedule_graph_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_STATE_METHOD,
+                builtin_methods::process_remote_stepping_state_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_ENABLE_METHOD,
+                builtin_methods::process_remote_stepping_enable_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_DISABLE_METHOD,
+                builtin_methods::process_remote_stepping_disable_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_ADD_SCHEDULE_METHOD,
+                builtin_methods::process_remote_stepping_add_schedule_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_REMOVE_SCHEDULE_METHOD,
+                builtin_methods::process_remote_stepping_remove_schedule_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_STEP_METHOD,
+                builtin_methods::process_remote_stepping_step_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_CONTINUE_METHOD,
+                builtin_methods::process_remote_stepping_continue_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_SET_BREAKPOINT_METHOD,
+                builtin_methods::process_remote_stepping_set_breakpoint_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_CLEAR_BREAKPOINT_METHOD,
+                builtin_methods::process_remote_stepping_clear_breakpoint_request,
+            );
+        #[cfg(feature = "bevy_asset")]
+        let plugin = plugin.with_method(
+            builtin_methods::BRP_ASSET_DEPENDENCY_GRAPH_METHOD,
+            builtin_methods::process_remote_asset_dependency_graph_request,
+        );
+        plugin
     }
 }
 
@@ -974,6 +1192,36 @@ impl BrpError {
             data: None,
         }
     }
+
+    /// Schedule wasn't found in the world.
+    #[must_use]
+    pub fn schedule_not_found(schedule: &str) -> Self {
+        Self {
+            code: error_codes::SCHEDULE_NOT_FOUND,
+            message: format!("Schedule `{schedule}` not found"),
+            data: None,
+        }
+    }
+
+    /// System wasn't found in a schedule.
+    #[must_use]
+    pub fn system_not_found(system: &str, schedule: InternedScheduleLabel) -> Self {
+        Self {
+            code: error_codes::SYSTEM_NOT_FOUND,
+            message: format!("System `{system}` not found in schedule `{schedule:?}`"),
+            data: None,
+        }
+    }
+
+    /// An arbitrary error related to system stepping.
+    #[must_use]
+    pub fn stepping_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::STEPPING_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
 }
 
 /// Error codes used by BRP.
@@ -1015,6 +1263,15 @@ pub mod error_codes {
 
     /// Could not find resource in the world.
     pub const RESOURCE_NOT_PRESENT: i16 = -23502;
+
+    /// Could not find schedule in the world.
+    pub const SCHEDULE_NOT_FOUND: i16 = -23601;
+
+    /// Could not find system in the schedule.
+    pub const SYSTEM_NOT_FOUND: i16 = -23602;
+
+    /// Stepping is unavailable or not enabled.
+    pub const STEPPING_ERROR: i16 = -23603;
 }
 
 /// The result of a request.
@@ -1086,7 +1343,7 @@ fn process_remote_requests(world: &mut World) {
                 message: format!("Method `{}` not found", message.method),
                 data: None,
             }));
-            return;
+            continue;
         };
 
         match handler {
@@ -0,0 +1,674 @@
+//! The BRP transport using JSON-RPC over WebSocket.
+//!
+//! Adding the [`RemoteWebSocketPlugin`] to your [`App`] causes Bevy to accept
+//! WebSocket connections (by default, on port 15703) while your app is running.
+//!
+//! Unlike the HTTP transport, a single WebSocket connection is bidirectional and
+//! multiplexed: clients may send any number of requests (or JSON-RPC batch arrays)
+//! as text messages without waiting for earlier responses, and every response
+//! carries the `id` of the request it answers.
+//!
+//! Watching methods (those ending in `+watch`) open a *subscription* keyed by the
+//! request `id`. Every change reported by the watcher is sent as a separate
+//! response with that `id` until the client sends a [`BRP_UNSUBSCRIBE_METHOD`]
+//! request or closes the connection:
+//!
+//! ```json
+//! {
+//!     "jsonrpc": "2.0",
+//!     "method": "rpc.unsubscribe",
+//!     "id": 7,
+//!     "params": { "id": 3 }
+//! }
+//! ```
+
+#![cfg(not(target_family = "wasm"))]
+
+use crate::{
+    error_codes, BrpBatch, BrpError, BrpMessage, BrpRequest, BrpResponse, BrpResult, BrpSender,
+};
+use alloc::sync::Arc;
+use anyhow::Result as AnyhowResult;
+use async_channel::{Receiver, Sender};
+use async_io::Async;
+use async_tungstenite::tungstenite::Message;
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::resource::Resource;
+use bevy_ecs::system::Res;
+use bevy_platform::collections::HashMap;
+use bevy_tasks::{futures_lite::StreamExt, IoTaskPool};
+use core::net::{IpAddr, Ipv4Addr};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    net::{TcpListener, TcpStream},
+    sync::Mutex,
+};
+
+/// The default port that Bevy will listen on for WebSocket connections.
+///
+/// This is one above the default port of the HTTP transport, so that both can
+/// be enabled at the same time.
+pub const DEFAULT_PORT: u16 = 15703;
+
+/// The default host address that Bevy will use for its WebSocket server.
+pub const DEFAULT_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+
+/// The method used to cancel a watching request made over the same WebSocket connection.
+///
+/// This method is handled by the transport itself and is not part of [`RemoteMethods`].
+///
+/// `params`:
+/// - `id`: The `id` of the watching request to cancel.
+///
+/// `result`: null.
+///
+/// [`RemoteMethods`]: crate::RemoteMethods
+pub const BRP_UNSUBSCRIBE_METHOD: &str = "rpc.unsubscribe";
+
+/// `rpc.unsubscribe`: Cancels a watching request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpUnsubscribeParams {
+    /// The `id` of the watching request to cancel.
+    pub id: Value,
+}
+
+/// Add this plugin to your [`App`] to allow remote connections over WebSocket to inspect and
+/// modify entities. It requires the [`RemotePlugin`](super::RemotePlugin).
+///
+/// This BRP transport cannot be used when targeting WASM.
+///
+/// The defaults are:
+/// - [`DEFAULT_ADDR`] : 127.0.0.1.
+/// - [`DEFAULT_PORT`] : 15703.
+pub struct RemoteWebSocketPlugin {
+    /// The address that Bevy will bind to.
+    address: IpAddr,
+    /// The port that Bevy will listen on.
+    port: u16,
+}
+
+impl Default for RemoteWebSocketPlugin {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDR,
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+impl Plugin for RemoteWebSocketPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WebSocketHostAddress(self.address))
+            .insert_resource(WebSocketHostPort(self.port))
+            .add_systems(Startup, start_websocket_server);
+    }
+}
+
+impl RemoteWebSocketPlugin {
+    /// Set the IP address that the server will use.
+    #[must_use]
+    pub fn with_address(mut self, address: impl Into<IpAddr>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// Set the remote port that the server will listen on.
+    #[must_use]
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+}
+
+/// A resource containing the IP address that the WebSocket server will host on.
+///
+/// Currently, changing this while the application is running has no effect; this merely
+/// reflects the IP address that is set during the setup of the [`RemoteWebSocketPlugin`].
+#[derive(Debug, Resource)]
+pub struct WebSocketHostAddress(pub IpAddr);
+
+/// A resource containing the port number that the WebSocket server will listen on.
+///
+/// Currently, changing this while the application is running has no effect; this merely
+/// reflects the port that is set during the setup of the [`RemoteWebSocketPlugin`].
+#[derive(Debug, Resource)]
+pub struct WebSocketHostPort(pub u16);
+
+/// A system that starts up the Bevy Remote Protocol WebSocket server.
+fn start_websocket_server(
+    request_sender: Res<BrpSender>,
+    address: Res<WebSocketHostAddress>,
+    remote_port: Res<WebSocketHostPort>,
+) {
+    IoTaskPool::get()
+        .spawn(server_main(
+            address.0,
+            remote_port.0,
+            request_sender.clone(),
+        ))
+        .detach();
+}
+
+/// The Bevy Remote Protocol WebSocket server main loop.
+async fn server_main(
+    address: IpAddr,
+    port: u16,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()> {
+    let listener = Async::<TcpListener>::bind((address, port))?;
+    loop {
+        let (client, _) = listener.accept().await?;
+
+        let request_sender = request_sender.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let _ = handle_client(client, request_sender).await;
+            })
+            .detach();
+    }
+}
+
+/// Performs the WebSocket handshake and serves a single connection until it is closed.
+async fn handle_client(
+    client: Async<TcpStream>,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()> {
+    let (mut ws_sender, mut ws_receiver) = async_tungstenite::accept_async(client).await?.split();
+
+    // All responses, including the ones produced by subscriptions, are funneled
+    // through this channel so that only one task ever writes to the socket.
+    let (outgoing_sender, outgoing_receiver) = async_channel::unbounded::<String>();
+    IoTaskPool::get()
+        .spawn(async move {
+            while let Ok(text) = outgoing_receiver.recv().await {
+                if ws_sender.send(Message::text(text)).await.is_err() {
+                    break;
+                }
+            }
+            let _ = ws_sender.close(None).await;
+        })
+        .detach();
+
+    let connection = Connection {
+        request_sender,
+        outgoing: outgoing_sender,
+        subscriptions: Arc::default(),
+    };
+
+    let result = async {
+        while let Some(message) = ws_receiver.next().await {
+            match message? {
+                Message::Text(text) => connection.process_message(text.as_str().as_bytes()),
+                Message::Binary(bytes) => connection.process_message(&bytes),
+                Message::Close(_) => break,
+                // Pings are answered by `tungstenite` itself.
+                Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => {}
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    connection.close();
+    result
+}
+
+/// The state of a single WebSocket connection.
+#[derive(Clone)]
+struct Connection {
+    /// The channel used to forward requests to the main world.
+    request_sender: Sender<BrpMessage>,
+    /// Serialized responses waiting to be written to the socket.
+    outgoing: Sender<String>,
+    /// The result channels of the active watching requests, keyed by the serialized request `id`.
+    subscriptions: Arc<Mutex<HashMap<String, Receiver<BrpResult>>>>,
+}
+
+impl Connection {
+    /// Handles a single incoming WebSocket message, which may contain one request or a batch.
+    ///
+    /// Each message is processed on its own task so that slow requests don't hold up
+    /// the ones that follow them on the same connection.
+    fn process_message(&self, bytes: &[u8]) {
+        let batch: Result<BrpBatch, _> = serde_json::from_slice(bytes);
+        let connection = self.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                match batch {
+                    Ok(BrpBatch::Single(request)) => connection.process_single(request).await,
+                    Ok(BrpBatch::Batch(requests)) => connection.process_batch(requests).await,
+                    Err(err) => connection.respond(&BrpResponse::new(
+                        None,
+                        Err(BrpError {
+                            code: error_codes::INVALID_REQUEST,
+                            message: err.to_string(),
+                            data: None,
+                        }),
+                    )),
+                }
+            })
+            .detach();
+    }
+
+    /// Processes a request that was sent on its own, which may start a subscription.
+    async fn process_single(&self, request: Value) {
+        let request = match parse_request(request) {
+            Ok(request) => request,
+            Err(response) => return self.respond(&response),
+        };
+
+        if request.method == BRP_UNSUBSCRIBE_METHOD {
+            let result = self.unsubscribe(request.params);
+            return self.respond(&BrpResponse::new(request.id, result));
+        }
+
+        if request.method.contains("+watch") {
+            return self.subscribe(request).await;
+        }
+
+        let response = match self.send(request.method, request.params).await {
+            Some(receiver) => BrpResponse::new(request.id, recv_result(&receiver).await),
+            None => return,
+        };
+        self.respond(&response);
+    }
+
+    /// Processes a JSON-RPC batch array.
+    ///
+    /// All requests of the batch are forwarded to the world before waiting on any of the
+    /// results, so they are handled during the same frame and in the order they were given.
+    async fn process_batch(&self, requests: Vec<Value>) {
+        let mut pending = Vec::with_capacity(requests.len());
+        for request in requests {
+            let request = match parse_request(request) {
+                Ok(request) => request,
+                Err(response) => {
+                    pending.push(PendingResponse::Complete(response));
+                    continue;
+                }
+            };
+
+            if request.method == BRP_UNSUBSCRIBE_METHOD {
+                let result = self.unsubscribe(request.params);
+                pending.push(PendingResponse::Complete(BrpResponse::new(
+                    request.id, result,
+                )));
+            } else if request.method.contains("+watch") {
+                pending.push(PendingResponse::Complete(BrpResponse::new(
+                    request.id,
+                    Err(BrpError {
+                        code: error_codes::INVALID_REQUEST,
+                        message: "Streaming can not be used in batch requests".to_string(),
+                        data: None,
+                    }),
+                )));
+            } else {
+                let Some(receiver) = self.send(request.method, request.params).await else {
+                    return;
+                };
+                pending.push(PendingResponse::Waiting(request.id, receiver));
+            }
+        }
+
+        let mut responses = Vec::with_capacity(pending.len());
+        for pending in pending {
+            responses.push(match pending {
+                PendingResponse::Complete(response) => response,
+                PendingResponse::Waiting(id, receiver) => {
+                    BrpResponse::new(id, recv_result(&receiver).await)
+                }
+            });
+        }
+        self.respond(&responses);
+    }
+
+    /// Starts a watching request and forwards its results until it is unsubscribed.
+    async fn subscribe(&self, request: BrpRequest) {
+        let Some(id) = request.id.clone().filter(|id| !id.is_null()) else {
+            return self.respond(&BrpResponse::new(
+                request.id,
+                Err(BrpError {
+                    code: error_codes::INVALID_REQUEST,
+                    message: "Watching requests require an `id` to be able to unsubscribe"
+                        .to_string(),
+                    data: None,
+                }),
+            ));
+        };
+        let key = id.to_string();
+
+        let (result_sender, result_receiver) = async_channel::bounded(8);
+        {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            if subscriptions.contains_key(&key) {
+                drop(subscriptions);
+                return self.respond(&BrpResponse::new(
+                    Some(id),
+                    Err(BrpError {
+                        code: error_codes::INVALID_REQUEST,
+                        message: format!("A watching request with id {key} is already active"),
+                        data: None,
+                    }),
+                ));
+            }
+            subscriptions.insert(key.clone(), result_receiver.clone());
+        }
+
+        if self
+            .request_sender
+            .send(BrpMessage {
+                method: request.method,
+                params: request.params,
+                sender: result_sender,
+            })
+            .await
+            .is_err()
+        {
+            self.subscriptions.lock().unwrap().remove(&key);
+            return;
+        }
+
+        while let Ok(result) = result_receiver.recv().await {
+            self.respond(&BrpResponse::new(Some(id.clone()), result));
+        }
+
+        // The subscription might have been replaced after an unsubscribe, so only remove
+        // the entry if it still belongs to this request.
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        if subscriptions
+            .get(&key)
+            .is_some_and(|receiver| receiver.same_channel(&result_receiver))
+        {
+            subscriptions.remove(&key);
+        }
+    }
+
+    /// Handles [`BRP_UNSUBSCRIBE_METHOD`] by closing the channel of the matching subscription.
+    ///
+    /// Closing the channel causes the world to drop the watching request during cleanup.
+    fn unsubscribe(&self, params: Option<Value>) -> BrpResult {
+        let BrpUnsubscribeParams { id } = params
+            .ok_or_else(|| BrpError {
+                code: error_codes::INVALID_PARAMS,
+                message: String::from("Params not provided"),
+                data: None,
+            })
+            .and_then(|params| {
+                serde_json::from_value(params).map_err(|err| BrpError {
+                    code: error_codes::INVALID_PARAMS,
+                    message: err.to_string(),
+                    data: None,
+                })
+            })?;
+
+        let key = id.to_string();
+        let Some(receiver) = self.subscriptions.lock().unwrap().remove(&key) else {
+            return Err(BrpError {
+                code: error_codes::INVALID_PARAMS,
+                message: format!("No active watching request with id {key}"),
+                data: None,
+            });
+        };
+
+        receiver.close();
+        // Discard any updates that were produced before the subscription was closed so that
+        // nothing is reported for it after the unsubscribe response.
+        while receiver.try_recv().is_ok() {}
+
+        Ok(Value::Null)
+    }
+
+    /// Forwards a request to the world, returning the channel its result will arrive on.
+    ///
+    /// Returns [`None`] if the app is no longer accepting requests.
+    async fn send(&self, method: String, params: Option<Value>) -> Option<Receiver<BrpResult>> {
+        let (result_sender, result_receiver) = async_channel::bounded(1);
+        self.request_sender
+            .send(BrpMessage {
+                method,
+                params,
+                sender: result_sender,
+            })
+            .await
+            .ok()?;
+        Some(result_receiver)
+    }
+
+    /// Queues a response to be written to the socket.
+    fn respond<T: Serialize>(&self, response: &T) {
+        if let Ok(serialized) = serde_json::to_string(response) {
+            let _ = self.outgoing.try_send(serialized);
+        }
+    }
+
+    /// Cancels all active subscriptions and stops writing to the socket.
+    fn close(&self) {
+        for (_, receiver) in self.subscriptions.lock().unwrap().drain() {
+            receiver.close();
+        }
+        self.outgoing.close();
+    }
+}
+
+/// A response in a batch that is either already known or still being processed by the world.
+enum PendingResponse {
+    Complete(BrpResponse),
+    Waiting(Option<Value>, Receiver<BrpResult>),
+}
+
+/// Parses and validates a single JSON-RPC request, producing an error response on failure.
+fn parse_request(request: Value) -> Result<BrpRequest, BrpResponse> {
+    // Reach in and get the request ID early so that we can report it even when parsing fails.
+    let id = request.as_object().and_then(|map| map.get("id")).cloned();
+
+    let request: BrpRequest = serde_json::from_value(request).map_err(|err| {
+        BrpResponse::new(
+            id.clone(),
+            Err(BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: err.to_string(),
+                data: None,
+            }),
+        )
+    })?;
+
+    if request.jsonrpc != "2.0" {
+        return Err(BrpResponse::new(
+            id,
+            Err(BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: String::from("JSON-RPC request requires `\"jsonrpc\": \"2.0\"`"),
+                data: None,
+            }),
+        ));
+    }
+
+    Ok(request)
+}
+
+/// Waits for the result of an instant request.
+async fn recv_result(receiver: &Receiver<BrpResult>) -> BrpResult {
+    receiver.recv().await.unwrap_or_else(|_| {
+        Err(BrpError {
+            code: error_codes::INTERNAL_ERROR,
+            message: String::from("The request was dropped before it was processed"),
+            data: None,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RemoteWatchingRequests;
+    use crate::{builtin_methods::BRP_GET_COMPONENTS_AND_WATCH_METHOD, RemotePlugin};
+    use async_tungstenite::WebSocketStream;
+    use bevy_app::{TaskPoolPlugin, Update};
+    use bevy_ecs::{component::Component, reflect::ReflectComponent, system::Query};
+    use bevy_reflect::{Reflect, TypePath};
+    use core::time::Duration;
+    use serde_json::json;
+    use std::time::Instant;
+    use std::{sync::mpsc, thread};
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Counter(u32);
+
+    type Client = WebSocketStream<Async<TcpStream>>;
+
+    async fn connect(port: u16) -> Client {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            if let Ok(stream) = Async::<TcpStream>::connect((DEFAULT_ADDR, port)).await {
+                let url = format!("ws://{DEFAULT_ADDR}:{port}");
+                return async_tungstenite::client_async(url, stream)
+                    .await
+                    .unwrap()
+                    .0;
+            }
+            assert!(Instant::now() < deadline, "server did not start");
+            async_io::Timer::after(Duration::from_millis(10)).await;
+        }
+    }
+
+    async fn request(client: &mut Client, request: Value) {
+        client
+            .send(Message::text(request.to_string()))
+            .await
+            .unwrap();
+    }
+
+    /// Waits for the first response matching `predicate`, skipping all others.
+    async fn response(client: &mut Client, predicate: impl Fn(&Value) -> bool) -> Value {
+        loop {
+            if let Message::Text(text) = client.next().await.unwrap().unwrap() {
+                let response = serde_json::from_str(text.as_str()).unwrap();
+                if predicate(&response) {
+                    return response;
+                }
+            }
+        }
+    }
+
+    fn increment_counters(mut counters: Query<&mut Counter>) {
+        for mut counter in &mut counters {
+            counter.0 += 1;
+        }
+    }
+
+    #[test]
+    fn websocket_watch_and_unsubscribe() {
+        const PORT: u16 = 15713;
+
+        let mut app = App::new();
+        app.register_type::<Counter>().add_plugins((
+            TaskPoolPlugin::default(),
+            RemotePlugin::default(),
+            RemoteWebSocketPlugin::default().with_port(PORT),
+        ));
+        app.add_systems(Update, increment_counters);
+        let entity = app.world_mut().spawn(Counter(0)).id();
+        app.update();
+
+        let (checkpoint_sender, checkpoint_receiver) = mpsc::channel();
+        let (resume_sender, resume_receiver) = mpsc::channel();
+        let client = thread::spawn(move || {
+            bevy_tasks::block_on(async move {
+                let mut client = connect(PORT).await;
+                let watch = |id: u32| {
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "method": BRP_GET_COMPONENTS_AND_WATCH_METHOD,
+                        "params": {
+                            "entity": entity,
+                            "components": [Counter::type_path()],
+                        },
+                    })
+                };
+
+                // Two subscriptions on one connection.
+                request(&mut client, watch(1)).await;
+                request(&mut client, watch(2)).await;
+                for id in [1, 2] {
+                    let update = response(&mut client, |r| r["id"] == json!(id)).await;
+                    assert!(update["result"]["components"][Counter::type_path()].is_number());
+                }
+
+                // Reusing an active id is rejected.
+                request(&mut client, watch(1)).await;
+                let duplicate = response(&mut client, |r| r.get("error").is_some()).await;
+                assert_eq!(duplicate["id"], json!(1));
+                assert_eq!(
+                    duplicate["error"]["code"],
+                    json!(error_codes::INVALID_REQUEST)
+                );
+
+                request(
+                    &mut client,
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": 3,
+                        "method": BRP_UNSUBSCRIBE_METHOD,
+                        "params": { "id": 1 },
+                    }),
+                )
+                .await;
+                let unsubscribed = response(&mut client, |r| r["id"] == json!(3)).await;
+                assert_eq!(unsubscribed["result"], Value::Null);
+                checkpoint_sender.send(()).unwrap();
+                resume_receiver.recv().unwrap();
+
+                // Instant requests can be batched and are answered in order.
+                request(
+                    &mut client,
+                    json!([
+                        { "jsonrpc": "2.0", "id": 4, "method": "world.list_resources" },
+                        { "jsonrpc": "2.0", "id": 5, "method": "does.not_exist" },
+                    ]),
+                )
+                .await;
+                let batch = response(&mut client, Value::is_array).await;
+                assert_eq!(batch[0]["id"], json!(4));
+                assert!(batch[0]["result"].is_array());
+                assert_eq!(
+                    batch[1]["error"]["code"],
+                    json!(error_codes::METHOD_NOT_FOUND)
+                );
+            });
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while !client.is_finished() {
+            assert!(Instant::now() < deadline, "client timed out");
+            app.update();
+            if checkpoint_receiver.try_recv().is_ok() {
+                // Only the unsubscribed watcher is dropped.
+                app.update();
+                let watching = &app.world().resource::<RemoteWatchingRequests>().0;
+                assert_eq!(watching.len(), 1);
+                resume_sender.send(()).unwrap();
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        client.join().unwrap();
+
+        // Closing the connection cancels the remaining subscriptions.
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !app
+            .world()
+            .resource::<RemoteWatchingRequests>()
+            .0
+            .is_empty()
+        {
+            assert!(
+                Instant::now() < deadline,
+                "subscriptions were not cleaned up"
+            );
+            app.update();
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
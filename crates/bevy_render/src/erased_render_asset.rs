@@ -4,7 +4,7 @@ use crate::{
 };
 use bevy_app::{App, Plugin, SubApp};
 pub use bevy_asset::RenderAssetUsages;
-use bevy_asset::{Asset, AssetEvent, AssetId, Assets, UntypedAssetId};
+use bevy_asset::{Asset, AssetEvent, AssetEvicted, AssetId, Assets, UntypedAssetId};
 use bevy_ecs::{
     prelude::{Commands, EventReader, IntoScheduleConfigs, ResMut, Resource},
     schedule::{ScheduleConfigs, SystemSet},
@@ -231,6 +231,7 @@ impl<ERA> ErasedRenderAssets<ERA> {
 struct CachedExtractErasedRenderAssetSystemState<A: ErasedRenderAsset> {
     state: SystemState<(
         EventReader<'static, 'static, AssetEvent<A::SourceAsset>>,
+        EventReader<'static, 'static, AssetEvicted<A::SourceAsset>>,
         ResMut<'static, Assets<A::SourceAsset>>,
     )>,
 }
@@ -251,7 +252,7 @@ pub(crate) fn extract_erased_render_asset<A: ErasedRenderAsset>(
 ) {
     main_world.resource_scope(
         |world, mut cached_state: Mut<CachedExtractErasedRenderAssetSystemState<A>>| {
-            let (mut events, mut assets) = cached_state.state.get_mut(world);
+            let (mut events, mut evicted, mut assets) = cached_state.state.get_mut(world);
 
             let mut needs_extracting = <HashSet<_>>::default();
             let mut removed = <HashSet<_>>::default();
@@ -285,6 +286,11 @@ pub(crate) fn extract_erased_render_asset<A: ErasedRenderAsset>(
                 }
             }
 
+            // Evicted assets are extracted again once they are reloaded.
+            for AssetEvicted { id } in evicted.read() {
+                removed.insert(*id);
+            }
+
             let mut extracted_assets = Vec::new();
             let mut added = <HashSet<_>>::default();
             for id in needs_extracting.drain() {
@@ -0,0 +1,724 @@
+//! A compact binary encoding for [`DynamicScene`]s (`.scn.bin`).
+//!
+//! The RON format produced by [`DynamicScene::serialize`] is meant to be read and edited by humans,
+//! which makes large scenes slow to parse. The binary format stores the same data with [postcard],
+//! writing every type path used in the scene exactly once in a table at the start of the file and
+//! referring to it by index afterwards.
+//!
+//! Authored RON scenes can be converted ahead of time by the asset processor using
+//! [`SceneToBinaryProcessor`].
+//!
+//! [postcard]: https://crates.io/crates/postcard
+
+use crate::{DynamicEntity, DynamicScene, SceneLoader};
+use alloc::borrow::Cow;
+use bevy_asset::{
+    io::{Reader, Writer},
+    processor::LoadTransformAndSave,
+    saver::{AssetSaver, SavedAsset},
+    transformer::IdentityAssetTransformer,
+    AssetLoader, AsyncWriteExt, LoadContext,
+};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    PartialReflect, ReflectFromReflect, TypeRegistration, TypeRegistry, TypeRegistryArc,
+};
+use core::fmt::Formatter;
+use serde::{
+    de::{DeserializeSeed, Error, SeqAccess, Visitor},
+    ser::{SerializeSeq, SerializeTuple},
+    Deserializer, Serialize, Serializer,
+};
+use thiserror::Error;
+
+/// Bytes every binary scene starts with.
+pub const BINARY_SCENE_MAGIC: [u8; 4] = *b"BSCN";
+
+/// Version of the binary scene encoding written by [`DynamicScene::serialize_binary`].
+///
+/// Files with a different version are rejected by [`DynamicScene::deserialize_binary`].
+pub const BINARY_SCENE_VERSION: u8 = 1;
+
+/// Possible errors that can be produced when reading or writing a binary scene.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum BinarySceneError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read or write the binary scene: {0}")]
+    Io(#[from] std::io::Error),
+    /// The data does not start with [`BINARY_SCENE_MAGIC`].
+    #[error("Not a binary scene: missing header")]
+    InvalidHeader,
+    /// The data was written with an encoding version this build cannot read.
+    #[error("Unsupported binary scene version {0} (expected {BINARY_SCENE_VERSION})")]
+    UnsupportedVersion(u8),
+    /// The data continues after the end of the scene.
+    #[error("Found {0} unexpected bytes after the end of the binary scene")]
+    TrailingBytes(usize),
+    /// A [postcard Error](postcard::Error)
+    #[error("Could not encode or decode binary scene: {0}")]
+    Postcard(#[from] postcard::Error),
+}
+
+impl DynamicScene {
+    /// Serialize this dynamic scene into the compact binary scene format (`.scn.bin`).
+    ///
+    /// To deserialize the scene, use [`DynamicScene::deserialize_binary`] or the [`BinarySceneLoader`].
+    pub fn serialize_binary(&self, registry: &TypeRegistry) -> Result<Vec<u8>, BinarySceneError> {
+        let mut bytes = Vec::from(BINARY_SCENE_MAGIC);
+        bytes.push(BINARY_SCENE_VERSION);
+        Ok(postcard::to_extend(
+            &BinarySceneSerializer::new(self, registry),
+            bytes,
+        )?)
+    }
+
+    /// Deserialize a dynamic scene written by [`DynamicScene::serialize_binary`].
+    pub fn deserialize_binary(
+        bytes: &[u8],
+        registry: &TypeRegistry,
+    ) -> Result<DynamicScene, BinarySceneError> {
+        let payload = bytes
+            .strip_prefix(&BINARY_SCENE_MAGIC)
+            .ok_or(BinarySceneError::InvalidHeader)?;
+        let (&version, payload) = payload
+            .split_first()
+            .ok_or(BinarySceneError::InvalidHeader)?;
+        if version != BINARY_SCENE_VERSION {
+            return Err(BinarySceneError::UnsupportedVersion(version));
+        }
+        let mut deserializer = postcard::Deserializer::from_bytes(payload);
+        let scene = BinarySceneDeserializer { registry }.deserialize(&mut deserializer)?;
+        let remaining = deserializer.finalize()?;
+        if !remaining.is_empty() {
+            return Err(BinarySceneError::TrailingBytes(remaining.len()));
+        }
+        Ok(scene)
+    }
+}
+
+/// Serializer for a [`DynamicScene`] in the binary scene format.
+///
+/// The scene is written as a tuple of the interned type path table, the resources and the entities.
+/// Each resource and component is written as a pair of its index in the table and its value.
+///
+/// Unlike [`SceneSerializer`](crate::serde::SceneSerializer), this is only meant for
+/// non-self-describing formats, and is usually driven through [`DynamicScene::serialize_binary`].
+pub struct BinarySceneSerializer<'a> {
+    /// The scene to serialize.
+    pub scene: &'a DynamicScene,
+    /// The type registry containing the types present in the scene.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> BinarySceneSerializer<'a> {
+    /// Create a new serializer from a [`DynamicScene`] and an associated [`TypeRegistry`].
+    pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistry) -> Self {
+        BinarySceneSerializer { scene, registry }
+    }
+}
+
+impl<'a> Serialize for BinarySceneSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut type_paths = self
+            .scene
+            .resources
+            .iter()
+            .chain(
+                self.scene
+                    .entities
+                    .iter()
+                    .flat_map(|entity| entity.components.iter()),
+            )
+            .map(|value| type_path_of::<S>(value.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        type_paths.sort_unstable();
+        type_paths.dedup();
+
+        let indices = type_paths
+            .iter()
+            .enumerate()
+            .map(|(index, type_path)| (*type_path, index as u32))
+            .collect::<HashMap<_, _>>();
+
+        let mut state = serializer.serialize_tuple(3)?;
+        state.serialize_element(&type_paths)?;
+        state.serialize_element(&InternedValuesSerializer {
+            entries: &self.scene.resources,
+            indices: &indices,
+            registry: self.registry,
+        })?;
+        state.serialize_element(&BinaryEntitiesSerializer {
+            entities: &self.scene.entities,
+            indices: &indices,
+            registry: self.registry,
+        })?;
+        state.end()
+    }
+}
+
+fn type_path_of<S: Serializer>(value: &dyn PartialReflect) -> Result<&'static str, S::Error> {
+    value
+        .get_represented_type_info()
+        .map(bevy_reflect::TypeInfo::type_path)
+        .ok_or_else(|| {
+            serde::ser::Error::custom(format_args!(
+                "cannot get represented type info for `{}`",
+                value.reflect_type_path()
+            ))
+        })
+}
+
+struct BinaryEntitiesSerializer<'a> {
+    entities: &'a [DynamicEntity],
+    indices: &'a HashMap<&'static str, u32>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for BinaryEntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities {
+            state.serialize_element(&(
+                entity.entity,
+                InternedValuesSerializer {
+                    entries: &entity.components,
+                    indices: self.indices,
+                    registry: self.registry,
+                },
+            ))?;
+        }
+        state.end()
+    }
+}
+
+struct InternedValuesSerializer<'a> {
+    entries: &'a [Box<dyn PartialReflect>],
+    indices: &'a HashMap<&'static str, u32>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for InternedValuesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entries.len()))?;
+        for entry in self.entries {
+            let index = self.indices[type_path_of::<S>(entry.as_ref())?];
+            state.serialize_element(&(
+                index,
+                TypedReflectSerializer::new(entry.as_partial_reflect(), self.registry),
+            ))?;
+        }
+        state.end()
+    }
+}
+
+/// Handles deserialization of a [`DynamicScene`] written by [`BinarySceneSerializer`].
+pub struct BinarySceneDeserializer<'a> {
+    /// Type registry in which the components and resources types used in the scene to deserialize are registered.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinarySceneDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(
+            3,
+            BinarySceneVisitor {
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+struct BinarySceneVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for BinarySceneVisitor<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("binary scene")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let type_paths: Vec<Cow<'de, str>> = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let registrations = type_paths
+            .iter()
+            .map(|type_path| {
+                self.registry.get_with_type_path(type_path).ok_or_else(|| {
+                    Error::custom(format_args!("no registration found for `{type_path}`"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let resources = seq
+            .next_element_seed(InternedValuesDeserializer {
+                registrations: &registrations,
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        let entities = seq
+            .next_element_seed(BinaryEntitiesDeserializer {
+                registrations: &registrations,
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::invalid_length(2, &self))?;
+
+        Ok(DynamicScene {
+            resources,
+            entities,
+        })
+    }
+}
+
+/// Returns the capacity to preallocate for a sequence of `size_hint` elements.
+///
+/// The length of a sequence is read from the file before any of its elements, so it can't be
+/// trusted: not every format checks it against the remaining input, and a corrupt file could
+/// otherwise make us allocate far more memory than it contains.
+fn cautious_capacity(size_hint: Option<usize>) -> usize {
+    const MAX_PREALLOCATED_ELEMENTS: usize = 4096;
+    size_hint.unwrap_or(0).min(MAX_PREALLOCATED_ELEMENTS)
+}
+
+struct BinaryEntitiesDeserializer<'a> {
+    registrations: &'a [&'a TypeRegistration],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("sequence of entities")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entities = Vec::with_capacity(cautious_capacity(seq.size_hint()));
+        while let Some(entity) = seq.next_element_seed(BinaryEntityDeserializer {
+            registrations: self.registrations,
+            registry: self.registry,
+        })? {
+            entities.push(entity);
+        }
+        Ok(entities)
+    }
+}
+
+struct BinaryEntityDeserializer<'a> {
+    registrations: &'a [&'a TypeRegistration],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryEntityDeserializer<'a> {
+    type Value = DynamicEntity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryEntityDeserializer<'a> {
+    type Value = DynamicEntity;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("entities")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entity: Entity = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let components = seq
+            .next_element_seed(InternedValuesDeserializer {
+                registrations: self.registrations,
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        Ok(DynamicEntity { entity, components })
+    }
+}
+
+struct InternedValuesDeserializer<'a> {
+    registrations: &'a [&'a TypeRegistration],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for InternedValuesDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for InternedValuesDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("sequence of interned reflect values")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut added = <HashSet<_>>::default();
+        let mut entries = Vec::with_capacity(cautious_capacity(seq.size_hint()));
+        while let Some(value) = seq.next_element_seed(InternedValueDeserializer {
+            registrations: self.registrations,
+            registry: self.registry,
+        })? {
+            let type_info = value.get_represented_type_info().ok_or_else(|| {
+                Error::custom(format_args!(
+                    "cannot get represented type info for `{}`",
+                    value.reflect_type_path()
+                ))
+            })?;
+            if !added.insert(type_info.type_id()) {
+                return Err(Error::custom(format_args!(
+                    "duplicate reflect type: `{}`",
+                    type_info.type_path(),
+                )));
+            }
+            entries.push(value);
+        }
+        Ok(entries)
+    }
+}
+
+struct InternedValueDeserializer<'a> {
+    registrations: &'a [&'a TypeRegistration],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for InternedValueDeserializer<'a> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for InternedValueDeserializer<'a> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("type index and reflect value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let index: u32 = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let registration = *self
+            .registrations
+            .get(index as usize)
+            .ok_or_else(|| Error::custom(format_args!("type index {index} is out of range")))?;
+        let value = seq
+            .next_element_seed(TypedReflectDeserializer::new(registration, self.registry))?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+
+        // Attempt to convert using FromReflect.
+        Ok(registration
+            .data::<ReflectFromReflect>()
+            .and_then(|fr| fr.from_reflect(value.as_partial_reflect()))
+            .map(PartialReflect::into_partial_reflect)
+            .unwrap_or(value))
+    }
+}
+
+/// Asset loader for a Bevy dynamic scene in the binary scene format (`.scn.bin`).
+///
+/// The loader handles assets serialized with [`DynamicScene::serialize_binary`] or [`BinarySceneSaver`].
+#[derive(Debug)]
+pub struct BinarySceneLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BinarySceneLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+impl AssetLoader for BinarySceneLoader {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type Error = BinarySceneError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        DynamicScene::deserialize_binary(&bytes, &self.type_registry.read())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn.bin"]
+    }
+}
+
+/// Asset saver writing a [`DynamicScene`] in the binary scene format, to be read back by [`BinarySceneLoader`].
+#[derive(Debug)]
+pub struct BinarySceneSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BinarySceneSaver {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+impl AssetSaver for BinarySceneSaver {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type OutputLoader = BinarySceneLoader;
+    type Error = BinarySceneError;
+
+    async fn save(
+        &self,
+        writer: &mut Writer,
+        asset: SavedAsset<'_, Self::Asset>,
+        _settings: &Self::Settings,
+    ) -> Result<(), Self::Error> {
+        let bytes = asset.serialize_binary(&self.type_registry.read())?;
+        writer.write_all(&bytes).await?;
+        Ok(())
+    }
+}
+
+/// Asset processor converting RON scenes loaded by [`SceneLoader`] into binary scenes.
+///
+/// It is registered by [`ScenePlugin`](crate::ScenePlugin) but not set as the default processor for any
+/// extension. Opt in per file through its `.meta` file, or for all RON scenes with
+/// [`AssetApp::set_default_asset_processor`](bevy_asset::AssetApp::set_default_asset_processor).
+pub type SceneToBinaryProcessor =
+    LoadTransformAndSave<SceneLoader, IdentityAssetTransformer<DynamicScene>, BinarySceneSaver>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynamicScene, DynamicSceneBuilder};
+    use bevy_ecs::{
+        entity::{Entity, EntityHashMap},
+        prelude::{Component, ReflectComponent, ReflectResource, Resource, With, World},
+        reflect::AppTypeRegistry,
+    };
+    use bevy_reflect::Reflect;
+
+    use super::{BinarySceneError, BINARY_SCENE_MAGIC};
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Position(f32, f32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Label {
+        name: String,
+        tags: Vec<String>,
+    }
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u64);
+
+    fn create_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Position>();
+            registry.register::<Label>();
+            registry.register::<Score>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn should_roundtrip_binary() {
+        let mut world = create_world();
+        world.insert_resource(Score(42));
+        for i in 0..3 {
+            world.spawn((
+                Position(i as f32, -(i as f32)),
+                Label {
+                    name: format!("entity {i}"),
+                    tags: vec!["a".to_string(); i],
+                },
+            ));
+        }
+        world.spawn(Position(9.0, 9.0));
+
+        let entities = world
+            .query_filtered::<Entity, With<Position>>()
+            .iter(&world)
+            .collect::<Vec<_>>();
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entities(entities.into_iter())
+            .extract_resources()
+            .build();
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let bytes = scene.serialize_binary(&registry.read()).unwrap();
+        assert!(bytes.starts_with(&BINARY_SCENE_MAGIC));
+        // Type paths are interned, so each one is only written once.
+        let type_path = <Position as bevy_reflect::TypePath>::type_path();
+        assert_eq!(
+            1,
+            bytes
+                .windows(type_path.len())
+                .filter(|window| *window == type_path.as_bytes())
+                .count()
+        );
+
+        let deserialized = DynamicScene::deserialize_binary(&bytes, &registry.read()).unwrap();
+        assert_eq!(scene.entities.len(), deserialized.entities.len());
+        assert_eq!(1, deserialized.resources.len());
+
+        let mut dst_world = create_world();
+        deserialized
+            .write_to_world(&mut dst_world, &mut EntityHashMap::default())
+            .unwrap();
+
+        assert_eq!(&Score(42), dst_world.resource::<Score>());
+        let mut labels = dst_world
+            .query::<(&Position, &Label)>()
+            .iter(&dst_world)
+            .map(|(position, label)| (position.0, label.name.clone(), label.tags.len()))
+            .collect::<Vec<_>>();
+        labels.sort_by(|a, b| a.0.total_cmp(&b.0));
+        assert_eq!(
+            vec![
+                (0.0, "entity 0".to_string(), 0),
+                (1.0, "entity 1".to_string(), 1),
+                (2.0, "entity 2".to_string(), 2),
+            ],
+            labels
+        );
+        assert_eq!(4, dst_world.query::<&Position>().iter(&dst_world).count());
+    }
+
+    #[test]
+    fn should_reject_truncated_and_corrupt_data() {
+        let mut world = create_world();
+        world.insert_resource(Score(7));
+        world.spawn((
+            Position(1.0, 2.0),
+            Label {
+                name: "entity".to_string(),
+                tags: vec!["a".to_string()],
+            },
+        ));
+        let scene = DynamicScene::from_world(&world);
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let bytes = scene.serialize_binary(&registry).unwrap();
+
+        for len in 0..bytes.len() {
+            assert!(
+                DynamicScene::deserialize_binary(&bytes[..len], &registry).is_err(),
+                "truncated to {len} bytes"
+            );
+        }
+
+        // Sequences claiming `u64::MAX` elements, with none of them present.
+        let huge_length = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+        let header = [&BINARY_SCENE_MAGIC[..], &[super::BINARY_SCENE_VERSION]].concat();
+        let huge_resources = [&header[..], &[0], &huge_length].concat();
+        let huge_entities = [&header[..], &[0, 0], &huge_length].concat();
+        let huge_components = [&header[..], &[0, 0, 1, 0], &huge_length].concat();
+        for corrupt in [huge_resources, huge_entities, huge_components] {
+            assert!(DynamicScene::deserialize_binary(&corrupt, &registry).is_err());
+        }
+
+        let trailing = [&bytes[..], b"extra"].concat();
+        assert!(matches!(
+            DynamicScene::deserialize_binary(&trailing, &registry),
+            Err(BinarySceneError::TrailingBytes(5))
+        ));
+    }
+
+    #[test]
+    fn should_reject_invalid_header() {
+        let world = create_world();
+        let registry = world.resource::<AppTypeRegistry>().read();
+
+        assert!(matches!(
+            DynamicScene::deserialize_binary(b"(resources: {}, entities: {})", &registry),
+            Err(BinarySceneError::InvalidHeader)
+        ));
+
+        let mut bytes = DynamicScene::default().serialize_binary(&registry).unwrap();
+        bytes[BINARY_SCENE_MAGIC.len()] += 1;
+        assert!(matches!(
+            DynamicScene::deserialize_binary(&bytes, &registry),
+            Err(BinarySceneError::UnsupportedVersion(_))
+        ));
+    }
+}
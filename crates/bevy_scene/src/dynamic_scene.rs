@@ -6,7 +6,7 @@ use bevy_ecs::{
     reflect::{AppTypeRegistry, ReflectComponent},
     world::World,
 };
-use bevy_reflect::{PartialReflect, TypePath};
+use bevy_reflect::{PartialReflect, TypePath, TypeRegistration, TypeRegistry};
 
 use crate::reflect_utils::clone_reflect_value;
 use bevy_ecs::component::ComponentCloneBehavior;
@@ -15,7 +15,6 @@ use bevy_ecs::relationship::RelationshipHookMode;
 #[cfg(feature = "serialize")]
 use {
     crate::{ron, serde::SceneSerializer},
-    bevy_reflect::TypeRegistry,
     serde::Serialize,
 };
 
@@ -99,22 +98,8 @@ impl DynamicScene {
 
             // Apply/ add each component to the given entity.
             for component in &scene_entity.components {
-                let type_info = component.get_represented_type_info().ok_or_else(|| {
-                    SceneSpawnError::NoRepresentedType {
-                        type_path: component.reflect_type_path().to_string(),
-                    }
-                })?;
-                let registration = type_registry.get(type_info.type_id()).ok_or_else(|| {
-                    SceneSpawnError::UnregisteredButReflectedType {
-                        type_path: type_info.type_path().to_string(),
-                    }
-                })?;
                 let reflect_component =
-                    registration.data::<ReflectComponent>().ok_or_else(|| {
-                        SceneSpawnError::UnregisteredComponent {
-                            type_path: type_info.type_path().to_string(),
-                        }
-                    })?;
+                    reflect_component(component.as_partial_reflect(), &type_registry)?;
 
                 {
                     let component_id = reflect_component.register_component(world);
@@ -142,21 +127,8 @@ impl DynamicScene {
         // Insert resources after all entities have been added to the world.
         // This ensures the entities are available for the resources to reference during mapping.
         for resource in &self.resources {
-            let type_info = resource.get_represented_type_info().ok_or_else(|| {
-                SceneSpawnError::NoRepresentedType {
-                    type_path: resource.reflect_type_path().to_string(),
-                }
-            })?;
-            let registration = type_registry.get(type_info.type_id()).ok_or_else(|| {
-                SceneSpawnError::UnregisteredButReflectedType {
-                    type_path: type_info.type_path().to_string(),
-                }
-            })?;
-            let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
-                SceneSpawnError::UnregisteredResource {
-                    type_path: type_info.type_path().to_string(),
-                }
-            })?;
+            let (registration, reflect_resource) =
+                reflect_resource(resource.as_partial_reflect(), &type_registry)?;
 
             // If this component references entities in the scene, update
             // them to the entities in the world.
@@ -181,6 +153,22 @@ impl DynamicScene {
         Ok(())
     }
 
+    /// Checks that [`write_to_world_with`](Self::write_to_world_with) won't fail because of a
+    /// resource or component type missing from `type_registry`, without writing anything.
+    #[cfg(feature = "serialize")]
+    pub(crate) fn check_registrations(
+        &self,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        for component in self.entities.iter().flat_map(|entity| &entity.components) {
+            reflect_component(component.as_partial_reflect(), type_registry)?;
+        }
+        for resource in &self.resources {
+            reflect_resource(resource.as_partial_reflect(), type_registry)?;
+        }
+        Ok(())
+    }
+
     /// Write the resources, the dynamic entities, and their corresponding components to the given world.
     ///
     /// This method will return a [`SceneSpawnError`] if a type either is not registered
@@ -209,6 +197,48 @@ impl DynamicScene {
     }
 }
 
+fn registration<'a>(
+    value: &dyn PartialReflect,
+    type_registry: &'a TypeRegistry,
+) -> Result<&'a TypeRegistration, SceneSpawnError> {
+    let type_info =
+        value
+            .get_represented_type_info()
+            .ok_or_else(|| SceneSpawnError::NoRepresentedType {
+                type_path: value.reflect_type_path().to_string(),
+            })?;
+    type_registry.get(type_info.type_id()).ok_or_else(|| {
+        SceneSpawnError::UnregisteredButReflectedType {
+            type_path: type_info.type_path().to_string(),
+        }
+    })
+}
+
+fn reflect_component<'a>(
+    component: &dyn PartialReflect,
+    type_registry: &'a TypeRegistry,
+) -> Result<&'a ReflectComponent, SceneSpawnError> {
+    let registration = registration(component, type_registry)?;
+    registration
+        .data::<ReflectComponent>()
+        .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+            type_path: registration.type_info().type_path().to_string(),
+        })
+}
+
+fn reflect_resource<'a>(
+    resource: &dyn PartialReflect,
+    type_registry: &'a TypeRegistry,
+) -> Result<(&'a TypeRegistration, &'a ReflectResource), SceneSpawnError> {
+    let registration = registration(resource, type_registry)?;
+    let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+        SceneSpawnError::UnregisteredResource {
+            type_path: registration.type_info().type_path().to_string(),
+        }
+    })?;
+    Ok((registration, reflect_resource))
+}
+
 /// Serialize a given Rust data structure into rust object notation (ron).
 #[cfg(feature = "serialize")]
 pub fn serialize_ron<S>(serialize: S) -> Result<String, ron::Error>
@@ -9,13 +9,20 @@
 //! Scenes are collections of entities and their associated components that can be
 //! instantiated or removed from a world to allow composition. Scenes can be serialized/deserialized,
 //! for example to save part of the world state to a file.
+//!
+//! Save games build on this: entities marked with [`Persist`] can be captured as a [`SaveGame`],
+//! written through an asset source, and restored later, migrating older saves on the way in.
 
 extern crate alloc;
 
+#[cfg(feature = "serialize")]
+mod binary;
 mod components;
 mod dynamic_scene;
 mod dynamic_scene_builder;
 mod reflect_utils;
+#[cfg(feature = "serialize")]
+mod save_game;
 mod scene;
 mod scene_filter;
 mod scene_loader;
@@ -27,9 +34,13 @@ pub mod serde;
 /// Rusty Object Notation, a crate used to serialize and deserialize bevy scenes.
 pub use bevy_asset::ron;
 
+#[cfg(feature = "serialize")]
+pub use binary::*;
 pub use components::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+#[cfg(feature = "serialize")]
+pub use save_game::*;
 pub use scene::*;
 pub use scene_filter::*;
 pub use scene_loader::*;
@@ -44,12 +55,19 @@ pub mod prelude {
         DynamicScene, DynamicSceneBuilder, DynamicSceneRoot, Scene, SceneFilter, SceneRoot,
         SceneSpawner,
     };
+
+    #[cfg(feature = "serialize")]
+    #[doc(hidden)]
+    pub use crate::{Persist, SaveGameCommandsExt};
 }
 
 use bevy_app::prelude::*;
 
 #[cfg(feature = "serialize")]
-use {bevy_asset::AssetApp, bevy_ecs::schedule::IntoScheduleConfigs};
+use {
+    bevy_asset::AssetApp,
+    bevy_ecs::{schedule::IntoScheduleConfigs, world::FromWorld},
+};
 
 /// Plugin that provides scene functionality to an [`App`].
 #[derive(Default)]
@@ -61,10 +79,18 @@ impl Plugin for ScenePlugin {
         app.init_asset::<DynamicScene>()
             .init_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<BinarySceneLoader>()
             .init_resource::<SceneSpawner>()
             .register_type::<SceneRoot>()
             .register_type::<DynamicSceneRoot>()
-            .add_systems(SpawnScene, (scene_spawner, scene_spawner_system).chain());
+            .register_type::<Persist>()
+            .init_resource::<SaveGameTasks>()
+            .add_event::<SaveGameEvent>()
+            .add_systems(SpawnScene, (scene_spawner, scene_spawner_system).chain())
+            .add_systems(PreUpdate, save_game_system);
+
+        let binary_saver = BinarySceneSaver::from_world(app.world_mut());
+        app.register_asset_processor::<SceneToBinaryProcessor>(binary_saver.into());
 
         // Register component hooks for DynamicSceneRoot
         app.world_mut()
@@ -0,0 +1,1183 @@
+use crate::{ron, serde::EntitiesSerializer, DynamicEntity, DynamicScene, DynamicSceneBuilder};
+use crate::{serde::SceneMapSerializer, SceneFilter, SceneSpawnError};
+use alloc::sync::Arc;
+use bevy_asset::{
+    io::{
+        AssetReaderError, AssetWriterError, ErasedAssetReader, ErasedAssetWriter,
+        MissingAssetSourceError, MissingAssetWriterError,
+    },
+    AssetPath, AssetServer, AsyncWriteExt,
+};
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityHashMap},
+    event::BufferedEvent,
+    prelude::ReflectComponent,
+    reflect::AppTypeRegistry,
+    resource::Resource,
+    system::Commands,
+    world::World,
+};
+use bevy_platform::collections::HashSet;
+use bevy_reflect::{
+    serde::TypedReflectDeserializer, std_traits::ReflectDefault, DynamicStruct, PartialReflect,
+    Reflect, ReflectFromReflect, StructInfo, TypeInfo, TypeRegistration, TypeRegistry,
+    TypeRegistryArc,
+};
+use bevy_tasks::{futures::check_ready, IoTaskPool, Task};
+use core::fmt::Formatter;
+use serde::{
+    de::{DeserializeSeed, Error as _, IgnoredAny, MapAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::path::Path;
+use thiserror::Error;
+
+/// Name of the serialized save game struct type.
+pub const SAVE_GAME_STRUCT: &str = "SaveGame";
+/// Name of the serialized save game version field.
+pub const SAVE_GAME_VERSION: &str = "version";
+/// Name of the serialized resources field in a save game.
+pub const SAVE_GAME_RESOURCES: &str = "resources";
+/// Name of the serialized entities field in a save game.
+pub const SAVE_GAME_ENTITIES: &str = "entities";
+
+/// Marks an entity to be included in [`SaveGame`]s.
+///
+/// Only entities with this component are captured by [`SaveGame::from_world`], and only
+/// entities with this component are despawned by [`SaveGame::restore`].
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+pub struct Persist;
+
+/// Controls what is captured in a [`SaveGame`], and how older saves are migrated when loaded.
+///
+/// If this resource is missing, every reflected component of [`Persist`] entities is saved,
+/// no resources are saved, and the save version is `0`.
+#[derive(Resource, Clone)]
+pub struct SaveGameSettings {
+    /// The current version of the save format, written to every new save.
+    ///
+    /// Bump this whenever a [`SaveMigration`] is added.
+    pub version: u32,
+    /// Filters which components of [`Persist`] entities are saved.
+    pub component_filter: SceneFilter,
+    /// Filters which resources are saved.
+    pub resource_filter: SceneFilter,
+    /// Migrations applied to saves with an older version.
+    pub migrations: Vec<SaveMigration>,
+}
+
+impl Default for SaveGameSettings {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            component_filter: SceneFilter::allow_all(),
+            resource_filter: SceneFilter::deny_all(),
+            migrations: Vec::new(),
+        }
+    }
+}
+
+impl SaveGameSettings {
+    /// Sets the current save version.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Includes the resource `R` in saves.
+    pub fn allow_resource<R: Resource>(mut self) -> Self {
+        self.resource_filter = self.resource_filter.allow::<R>();
+        self
+    }
+
+    /// Excludes the component `C` from saves.
+    pub fn deny_component<C: Component>(mut self) -> Self {
+        self.component_filter = self.component_filter.deny::<C>();
+        self
+    }
+
+    /// Adds a migration for saves older than the migration's version.
+    pub fn with_migration(mut self, migration: SaveMigration) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    /// Returns the migrations needed to bring a value saved at `version` under `type_path` up
+    /// to date, in version order, along with the current type path of the value.
+    fn migrations_for<'a>(
+        &'a self,
+        version: u32,
+        saved_type_path: &'a str,
+    ) -> (&'a str, Vec<&'a SaveMigration>) {
+        let mut pending: Vec<_> = self
+            .migrations
+            .iter()
+            .filter(|migration| migration.version > version)
+            .collect();
+        pending.sort_by_key(|migration| migration.version);
+
+        let mut type_path = saved_type_path;
+        let mut applicable = Vec::new();
+        for migration in pending {
+            if migration.renamed_from.as_deref() == Some(type_path) {
+                type_path = &migration.type_path;
+            }
+            if migration.type_path == type_path {
+                applicable.push(migration);
+            }
+        }
+        (type_path, applicable)
+    }
+}
+
+/// A schema change to a reflected type, applied to saves older than [`SaveMigration::new`]'s
+/// `version`.
+///
+/// Migrations are applied while a save is deserialized, so fields that no longer exist on the
+/// type never reach the reflection deserializer. Migrations for the same type are applied in
+/// version order, so a field renamed twice is followed to its current name.
+///
+/// ```
+/// # use bevy_scene::{SaveGameSettings, SaveMigration};
+/// # use bevy_reflect::{DynamicStruct, Struct};
+/// let settings = SaveGameSettings::default().with_version(2).with_migration(
+///     SaveMigration::new(2, "my_game::Health")
+///         .renamed_from("my_game::Hp")
+///         .rename_field("hp", "current")
+///         .remove_field("regen")
+///         .with_hook(|health: &mut DynamicStruct| {
+///             if health.field("max").is_none() {
+///                 health.insert("max", 100u32);
+///             }
+///         }),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct SaveMigration {
+    version: u32,
+    type_path: String,
+    renamed_from: Option<String>,
+    renamed_fields: Vec<(String, String)>,
+    removed_fields: Vec<String>,
+    hooks: Vec<Arc<dyn Fn(&mut DynamicStruct) + Send + Sync>>,
+}
+
+impl SaveMigration {
+    /// Creates a migration for the struct with the given current type path, applied to saves
+    /// with a version lower than `version`.
+    pub fn new(version: u32, type_path: impl Into<String>) -> Self {
+        Self {
+            version,
+            type_path: type_path.into(),
+            renamed_from: None,
+            renamed_fields: Vec::new(),
+            removed_fields: Vec::new(),
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Marks the type as having been saved under `type_path` before this version.
+    pub fn renamed_from(mut self, type_path: impl Into<String>) -> Self {
+        self.renamed_from = Some(type_path.into());
+        self
+    }
+
+    /// Marks the field `old` as having been renamed to `new` in this version.
+    pub fn rename_field(mut self, old: impl Into<String>, new: impl Into<String>) -> Self {
+        self.renamed_fields.push((old.into(), new.into()));
+        self
+    }
+
+    /// Marks the field `name` as having been removed in this version.
+    pub fn remove_field(mut self, name: impl Into<String>) -> Self {
+        self.removed_fields.push(name.into());
+        self
+    }
+
+    /// Adds a hook that edits the deserialized value, for example to fill in new fields.
+    ///
+    /// Hooks run after all field renames and removals have been applied.
+    pub fn with_hook(mut self, hook: impl Fn(&mut DynamicStruct) + Send + Sync + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+}
+
+/// A snapshot of every [`Persist`] entity, and of the resources allowed by
+/// [`SaveGameSettings`], tagged with the save version.
+///
+/// A save game is serialized like a [`DynamicScene`] with an additional `version` field, and
+/// can be written and read through any [`AssetWriter`](bevy_asset::io::AssetWriter) and
+/// [`AssetReader`](bevy_asset::io::AssetReader). For use from systems, see
+/// [`SaveGameCommandsExt`].
+pub struct SaveGame {
+    /// The version of the save format this save was written with.
+    pub version: u32,
+    /// The saved entities and resources.
+    pub scene: DynamicScene,
+}
+
+impl SaveGame {
+    /// Captures every [`Persist`] entity in `world`, using the world's [`SaveGameSettings`].
+    pub fn from_world(world: &World) -> Self {
+        let default_settings = SaveGameSettings::default();
+        let settings = world
+            .get_resource::<SaveGameSettings>()
+            .unwrap_or(&default_settings);
+
+        let entities = world
+            .component_id::<Persist>()
+            .into_iter()
+            .flat_map(|persist| {
+                world
+                    .archetypes()
+                    .iter()
+                    .filter(move |archetype| archetype.contains(persist))
+            })
+            .flat_map(bevy_ecs::archetype::Archetype::entities)
+            .map(bevy_ecs::archetype::ArchetypeEntity::id);
+
+        let scene = DynamicSceneBuilder::from_world(world)
+            .with_component_filter(settings.component_filter.clone())
+            .with_resource_filter(settings.resource_filter.clone())
+            .extract_entities(entities)
+            .extract_resources()
+            .build();
+
+        Self {
+            version: settings.version,
+            scene,
+        }
+    }
+
+    /// Replaces every [`Persist`] entity in `world` with the saved entities, and applies the
+    /// saved resources.
+    ///
+    /// Saved entities are always spawned as new entities. Entity references inside saved
+    /// components and resources are remapped to the new entities, and the returned map
+    /// translates each saved entity to the entity it was restored as.
+    ///
+    /// If a saved component or resource type isn't registered in the [`AppTypeRegistry`], an
+    /// error is returned before any [`Persist`] entity is despawned.
+    pub fn restore(&self, world: &mut World) -> Result<EntityHashMap<Entity>, SceneSpawnError> {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        self.scene.check_registrations(&registry.read())?;
+
+        let persisted: Vec<Entity> = world
+            .query_filtered::<Entity, bevy_ecs::query::With<Persist>>()
+            .iter(world)
+            .collect();
+        for entity in persisted {
+            // Despawning a parent may have already despawned its persisted children.
+            world.try_despawn(entity).ok();
+        }
+
+        let mut entity_map = EntityHashMap::default();
+        self.scene
+            .write_to_world_with(world, &mut entity_map, &registry)?;
+        Ok(entity_map)
+    }
+
+    /// Serializes this save game into RON.
+    pub fn serialize(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
+        crate::serialize_ron(SaveGameSerializer {
+            save_game: self,
+            registry,
+        })
+    }
+
+    /// Deserializes a save game from RON, migrating it to the current version of `settings`.
+    pub fn deserialize(
+        bytes: &[u8],
+        registry: &TypeRegistry,
+        settings: &SaveGameSettings,
+    ) -> Result<Self, SaveGameError> {
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        let save_game = SaveGameDeserializer { registry, settings }
+            .deserialize(&mut deserializer)
+            .map_err(|error| deserializer.span_error(error))?;
+        Ok(save_game)
+    }
+
+    /// Serializes this save game and writes it to `path`.
+    pub async fn write(
+        &self,
+        writer: &dyn ErasedAssetWriter,
+        path: &Path,
+        registry: &TypeRegistry,
+    ) -> Result<(), SaveGameError> {
+        let serialized = self.serialize(registry)?;
+        write_bytes(writer, path, serialized.as_bytes()).await
+    }
+
+    /// Reads and deserializes the save game at `path`.
+    pub async fn read(
+        reader: &dyn ErasedAssetReader,
+        path: &Path,
+        registry: &TypeRegistryArc,
+        settings: &SaveGameSettings,
+    ) -> Result<Self, SaveGameError> {
+        let bytes = read_bytes(reader, path).await?;
+        Self::deserialize(&bytes, &registry.read(), settings)
+    }
+
+    /// Returns the saved entity ids.
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.scene.entities.iter().map(|entity| entity.entity)
+    }
+}
+
+async fn write_bytes(
+    writer: &dyn ErasedAssetWriter,
+    path: &Path,
+    bytes: &[u8],
+) -> Result<(), SaveGameError> {
+    let mut file = writer.write(path).await?;
+    file.write_all(bytes).await?;
+    file.flush().await?;
+    Ok(())
+}
+
+async fn read_bytes(reader: &dyn ErasedAssetReader, path: &Path) -> Result<Vec<u8>, SaveGameError> {
+    let mut file = reader.read(path).await?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).await?;
+    Ok(bytes)
+}
+
+/// An error that occurred while saving or loading a [`SaveGame`].
+#[derive(Error, Debug)]
+pub enum SaveGameError {
+    /// The save game could not be serialized.
+    #[error("could not serialize save game: {0}")]
+    Serialize(#[from] ron::Error),
+    /// The save game could not be deserialized.
+    #[error("could not deserialize save game: {0}")]
+    Deserialize(#[from] ron::error::SpannedError),
+    /// The asset source of the save game path does not exist.
+    #[error(transparent)]
+    MissingSource(#[from] MissingAssetSourceError),
+    /// The asset source of the save game path cannot be written to.
+    #[error(transparent)]
+    MissingWriter(#[from] MissingAssetWriterError),
+    /// The save game could not be read.
+    #[error(transparent)]
+    Read(#[from] AssetReaderError),
+    /// The save game could not be written.
+    #[error(transparent)]
+    Write(#[from] AssetWriterError),
+    /// An I/O error occurred while reading or writing the save game.
+    #[error("i/o error while accessing save game: {0}")]
+    Io(#[from] std::io::Error),
+    /// The save game could not be restored into the world.
+    #[error(transparent)]
+    Restore(#[from] SceneSpawnError),
+}
+
+/// Sent when a save or load queued through [`SaveGameCommandsExt`] finishes.
+#[derive(BufferedEvent, Debug)]
+pub enum SaveGameEvent {
+    /// The save game was written to `path`.
+    Saved {
+        /// The path the save game was written to.
+        path: AssetPath<'static>,
+    },
+    /// The save game at `path` was restored into the world.
+    Loaded {
+        /// The path the save game was read from.
+        path: AssetPath<'static>,
+        /// Maps each saved entity to the entity it was restored as.
+        entity_map: EntityHashMap<Entity>,
+    },
+    /// Saving to or loading from `path` failed.
+    Failed {
+        /// The path of the save game.
+        path: AssetPath<'static>,
+        /// The reason for the failure.
+        error: SaveGameError,
+    },
+}
+
+/// Extension trait for [`Commands`] to save and load [`SaveGame`]s in the background.
+///
+/// Save games are written and read through the [`AssetServer`]'s asset sources, so `path` may
+/// name any source that has a writer, for example `"saves://slot_1.save.ron"`. The outcome is
+/// reported with a [`SaveGameEvent`].
+pub trait SaveGameCommandsExt {
+    /// Captures a [`SaveGame`] of the world and writes it to `path`.
+    fn save_game(&mut self, path: impl Into<AssetPath<'static>>);
+
+    /// Reads the [`SaveGame`] at `path` and restores it into the world once it has loaded.
+    fn load_game(&mut self, path: impl Into<AssetPath<'static>>);
+}
+
+impl SaveGameCommandsExt for Commands<'_, '_> {
+    fn save_game(&mut self, path: impl Into<AssetPath<'static>>) {
+        let path = path.into();
+        self.queue(move |world: &mut World| {
+            let save_game = SaveGame::from_world(world);
+            let serialized = save_game.serialize(&world.resource::<AppTypeRegistry>().read());
+            let asset_server = world.resource::<AssetServer>().clone();
+            let task_path = path.clone();
+            let task = IoTaskPool::get().spawn(async move {
+                let serialized = serialized?;
+                let source = asset_server.get_source(task_path.source())?;
+                write_bytes(source.writer()?, task_path.path(), serialized.as_bytes()).await?;
+                Ok::<_, SaveGameError>(None)
+            });
+            world.resource_mut::<SaveGameTasks>().0.push((path, task));
+        });
+    }
+
+    fn load_game(&mut self, path: impl Into<AssetPath<'static>>) {
+        let path = path.into();
+        self.queue(move |world: &mut World| {
+            let registry = world.resource::<AppTypeRegistry>().0.clone();
+            let settings = world
+                .get_resource::<SaveGameSettings>()
+                .cloned()
+                .unwrap_or_default();
+            let asset_server = world.resource::<AssetServer>().clone();
+            let task_path = path.clone();
+            let task = IoTaskPool::get().spawn(async move {
+                let source = asset_server.get_source(task_path.source())?;
+                let save_game =
+                    SaveGame::read(source.reader(), task_path.path(), &registry, &settings).await?;
+                Ok::<_, SaveGameError>(Some(save_game))
+            });
+            world.resource_mut::<SaveGameTasks>().0.push((path, task));
+        });
+    }
+}
+
+/// The in-flight saves and loads queued through [`SaveGameCommandsExt`].
+///
+/// A finished save yields `None`, a finished load yields the [`SaveGame`] to restore.
+#[derive(Resource, Default)]
+pub struct SaveGameTasks(
+    Vec<(
+        AssetPath<'static>,
+        Task<Result<Option<SaveGame>, SaveGameError>>,
+    )>,
+);
+
+/// Finishes the saves and loads in [`SaveGameTasks`], restoring loaded save games and sending
+/// a [`SaveGameEvent`] for each.
+pub fn save_game_system(world: &mut World) {
+    let finished: Vec<_> = {
+        let mut tasks = world.resource_mut::<SaveGameTasks>();
+        let mut finished = Vec::new();
+        tasks.0.retain_mut(|(path, task)| match check_ready(task) {
+            Some(result) => {
+                finished.push((path.clone(), result));
+                false
+            }
+            None => true,
+        });
+        finished
+    };
+
+    for (path, result) in finished {
+        let event = match result {
+            Ok(None) => SaveGameEvent::Saved { path },
+            Ok(Some(save_game)) => match save_game.restore(world) {
+                Ok(entity_map) => SaveGameEvent::Loaded { path, entity_map },
+                Err(error) => SaveGameEvent::Failed {
+                    path,
+                    error: error.into(),
+                },
+            },
+            Err(error) => SaveGameEvent::Failed { path, error },
+        };
+        world.write_event(event);
+    }
+}
+
+/// Serializer for a [`SaveGame`].
+pub struct SaveGameSerializer<'a> {
+    /// The save game to serialize.
+    pub save_game: &'a SaveGame,
+    /// The type registry containing the types present in the save game.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for SaveGameSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(SAVE_GAME_STRUCT, 3)?;
+        state.serialize_field(SAVE_GAME_VERSION, &self.save_game.version)?;
+        state.serialize_field(
+            SAVE_GAME_RESOURCES,
+            &SceneMapSerializer {
+                entries: &self.save_game.scene.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SAVE_GAME_ENTITIES,
+            &EntitiesSerializer {
+                entities: &self.save_game.scene.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SaveGameField {
+    Version,
+    Resources,
+    Entities,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum EntityField {
+    Components,
+}
+
+/// Deserializer for a [`SaveGame`], applying the migrations in [`SaveGameSettings`].
+///
+/// The `version` field must come before the resources and entities, as it determines which
+/// migrations apply to them. [`SaveGameSerializer`] always writes it first.
+pub struct SaveGameDeserializer<'a> {
+    /// Type registry in which the saved component and resource types are registered.
+    pub registry: &'a TypeRegistry,
+    /// The settings providing the migrations for older saves.
+    pub settings: &'a SaveGameSettings,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SaveGameDeserializer<'a> {
+    type Value = SaveGame;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SAVE_GAME_STRUCT,
+            &[SAVE_GAME_VERSION, SAVE_GAME_RESOURCES, SAVE_GAME_ENTITIES],
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for SaveGameDeserializer<'a> {
+    type Value = SaveGame;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("save game struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut resources = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            let context = |version: Option<u32>| {
+                version
+                    .map(|version| MigrationContext {
+                        registry: self.registry,
+                        settings: self.settings,
+                        version,
+                    })
+                    .ok_or_else(|| {
+                        A::Error::custom("the save game version must precede its contents")
+                    })
+            };
+            match key {
+                SaveGameField::Version => {
+                    if version.is_some() {
+                        return Err(A::Error::duplicate_field(SAVE_GAME_VERSION));
+                    }
+                    version = Some(map.next_value()?);
+                }
+                SaveGameField::Resources => {
+                    if resources.is_some() {
+                        return Err(A::Error::duplicate_field(SAVE_GAME_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(MigratingMapDeserializer {
+                        context: context(version)?,
+                    })?);
+                }
+                SaveGameField::Entities => {
+                    if entities.is_some() {
+                        return Err(A::Error::duplicate_field(SAVE_GAME_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(MigratingEntitiesDeserializer {
+                        context: context(version)?,
+                    })?);
+                }
+            }
+        }
+
+        Ok(SaveGame {
+            version: version.ok_or_else(|| A::Error::missing_field(SAVE_GAME_VERSION))?,
+            scene: DynamicScene {
+                resources: resources.ok_or_else(|| A::Error::missing_field(SAVE_GAME_RESOURCES))?,
+                entities: entities.ok_or_else(|| A::Error::missing_field(SAVE_GAME_ENTITIES))?,
+            },
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct MigrationContext<'a> {
+    registry: &'a TypeRegistry,
+    settings: &'a SaveGameSettings,
+    version: u32,
+}
+
+struct MigratingEntitiesDeserializer<'a> {
+    context: MigrationContext<'a>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for MigratingEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for MigratingEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("map of entities")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = map.next_key::<Entity>()? {
+            let components = map.next_value_seed(MigratingEntityDeserializer {
+                context: self.context,
+            })?;
+            entities.push(DynamicEntity { entity, components });
+        }
+        Ok(entities)
+    }
+}
+
+struct MigratingEntityDeserializer<'a> {
+    context: MigrationContext<'a>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for MigratingEntityDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            crate::serde::ENTITY_STRUCT,
+            &[crate::serde::ENTITY_FIELD_COMPONENTS],
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for MigratingEntityDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("entity struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut components = None;
+        while let Some(EntityField::Components) = map.next_key()? {
+            if components.is_some() {
+                return Err(A::Error::duplicate_field(
+                    crate::serde::ENTITY_FIELD_COMPONENTS,
+                ));
+            }
+            components = Some(map.next_value_seed(MigratingMapDeserializer {
+                context: self.context,
+            })?);
+        }
+        components.ok_or_else(|| A::Error::missing_field(crate::serde::ENTITY_FIELD_COMPONENTS))
+    }
+}
+
+/// Deserializes a map of type path to reflected value, like
+/// [`SceneMapDeserializer`](crate::serde::SceneMapDeserializer), migrating each value first.
+struct MigratingMapDeserializer<'a> {
+    context: MigrationContext<'a>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for MigratingMapDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for MigratingMapDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("map of reflect types")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let MigrationContext {
+            registry,
+            settings,
+            version,
+        } = self.context;
+
+        let mut added = <HashSet<_>>::default();
+        let mut entries: Vec<Box<dyn PartialReflect>> = Vec::new();
+        while let Some(saved_type_path) = map.next_key::<String>()? {
+            let (type_path, migrations) = settings.migrations_for(version, &saved_type_path);
+            let registration = registry.get_with_type_path(type_path).ok_or_else(|| {
+                A::Error::custom(format_args!("no registration found for `{type_path}`"))
+            })?;
+
+            let value = if migrations.is_empty() {
+                map.next_value_seed(TypedReflectDeserializer::new(registration, registry))?
+            } else {
+                let TypeInfo::Struct(struct_info) = registration.type_info() else {
+                    return Err(A::Error::custom(format_args!(
+                        "save migrations are only supported for structs, but `{type_path}` is not a struct"
+                    )));
+                };
+                let mut value = map.next_value_seed(MigratingStructDeserializer {
+                    struct_info,
+                    registration,
+                    registry,
+                    migrations: &migrations,
+                })?;
+                for hook in migrations.iter().flat_map(|migration| &migration.hooks) {
+                    hook(&mut value);
+                }
+                Box::new(value)
+            };
+
+            if !added.insert(registration.type_id()) {
+                return Err(A::Error::custom(format_args!(
+                    "duplicate reflect type: `{type_path}`"
+                )));
+            }
+
+            // Attempt to convert using FromReflect.
+            let value = registration
+                .data::<ReflectFromReflect>()
+                .and_then(|fr| fr.from_reflect(value.as_partial_reflect()))
+                .map(PartialReflect::into_partial_reflect)
+                .unwrap_or(value);
+
+            entries.push(value);
+        }
+        Ok(entries)
+    }
+}
+
+/// Deserializes a struct while renaming and dropping fields as described by `migrations`.
+struct MigratingStructDeserializer<'a> {
+    struct_info: &'static StructInfo,
+    registration: &'a TypeRegistration,
+    registry: &'a TypeRegistry,
+    migrations: &'a [&'a SaveMigration],
+}
+
+impl<'a> MigratingStructDeserializer<'a> {
+    /// Follows a saved field name through every migration, returning its current name, or
+    /// `None` if it was removed.
+    fn current_field_name(&self, saved: String) -> Option<String> {
+        self.migrations.iter().try_fold(saved, |name, migration| {
+            if migration.removed_fields.contains(&name) {
+                return None;
+            }
+            Some(
+                migration
+                    .renamed_fields
+                    .iter()
+                    .find(|(old, _)| *old == name)
+                    .map(|(_, new)| new.clone())
+                    .unwrap_or(name),
+            )
+        })
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for MigratingStructDeserializer<'a> {
+    type Value = DynamicStruct;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            self.struct_info
+                .type_path_table()
+                .ident()
+                .unwrap_or_default(),
+            self.struct_info.field_names(),
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for MigratingStructDeserializer<'a> {
+    type Value = DynamicStruct;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("reflected struct value")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut dynamic_struct = DynamicStruct::default();
+        while let Some(saved) = map.next_key::<String>()? {
+            let Some(name) = self.current_field_name(saved) else {
+                map.next_value::<IgnoredAny>()?;
+                continue;
+            };
+            let field = self.struct_info.field(&name).ok_or_else(|| {
+                A::Error::custom(format_args!(
+                    "no field named `{name}` on struct `{}`",
+                    self.struct_info.type_path()
+                ))
+            })?;
+            let registration = self.registry.get(field.type_id()).ok_or_else(|| {
+                A::Error::custom(format_args!(
+                    "no registration found for `{}`",
+                    field.type_path()
+                ))
+            })?;
+            let value =
+                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?;
+            dynamic_struct.insert_boxed(name, value);
+        }
+        dynamic_struct.set_represented_type(Some(self.registration.type_info()));
+        Ok(dynamic_struct)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_asset::{
+        io::{
+            file::{FileAssetReader, FileAssetWriter},
+            AssetSourceBuilder,
+        },
+        AssetApp, AssetPlugin,
+    };
+    use bevy_ecs::{event::Events, reflect::ReflectResource, resource::Resource};
+    use bevy_reflect::{Struct, TypePath};
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        current: u32,
+        max: u32,
+    }
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Target(#[entities] Entity);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Transient;
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Gold(u32);
+
+    fn world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Persist>();
+            registry.register::<Health>();
+            registry.register::<Target>();
+            registry.register::<Transient>();
+            registry.register::<Gold>();
+        }
+        world.insert_resource(registry);
+        world.insert_resource(
+            SaveGameSettings::default()
+                .with_version(1)
+                .allow_resource::<Gold>()
+                .deny_component::<Transient>(),
+        );
+        world
+    }
+
+    #[test]
+    fn save_and_restore() {
+        let mut world = world();
+        world.insert_resource(Gold(10));
+        let player = world
+            .spawn((
+                Persist,
+                Health {
+                    current: 5,
+                    max: 10,
+                },
+                Transient,
+            ))
+            .id();
+        let enemy = world.spawn((Persist, Target(player))).id();
+        let unsaved = world.spawn(Health { current: 1, max: 1 }).id();
+
+        let save_game = SaveGame::from_world(&world);
+        assert_eq!(save_game.version, 1);
+        assert_eq!(save_game.entities().count(), 2);
+        let serialized = save_game
+            .serialize(&world.resource::<AppTypeRegistry>().read())
+            .unwrap();
+
+        world.resource_mut::<Gold>().0 = 0;
+        world.entity_mut(player).insert(Health {
+            current: 0,
+            max: 10,
+        });
+        world.spawn(Persist);
+
+        let save_game = SaveGame::deserialize(
+            serialized.as_bytes(),
+            &world.resource::<AppTypeRegistry>().read(),
+            world.resource::<SaveGameSettings>(),
+        )
+        .unwrap();
+        let entity_map = save_game.restore(&mut world).unwrap();
+
+        assert!(world.get_entity(player).is_err());
+        assert!(world.get_entity(enemy).is_err());
+        assert!(world.get_entity(unsaved).is_ok());
+        assert_eq!(world.resource::<Gold>(), &Gold(10));
+        assert_eq!(
+            world
+                .query_filtered::<(), bevy_ecs::query::With<Persist>>()
+                .iter(&world)
+                .count(),
+            2
+        );
+
+        let new_player = entity_map[&player];
+        let new_enemy = entity_map[&enemy];
+        assert_eq!(
+            world.get::<Health>(new_player),
+            Some(&Health {
+                current: 5,
+                max: 10
+            })
+        );
+        assert!(world.get::<Transient>(new_player).is_none());
+        assert_eq!(world.get::<Target>(new_enemy), Some(&Target(new_player)));
+    }
+
+    #[test]
+    fn restore_with_unregistered_component_keeps_persisted_entities() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Unregistered;
+
+        let mut world = world();
+        let player = world
+            .spawn((
+                Persist,
+                Health {
+                    current: 5,
+                    max: 10,
+                },
+            ))
+            .id();
+
+        let save_game = SaveGame {
+            version: 1,
+            scene: DynamicScene {
+                resources: Vec::new(),
+                entities: vec![DynamicEntity {
+                    entity: player,
+                    components: vec![Box::new(Persist), Box::new(Unregistered)],
+                }],
+            },
+        };
+        assert!(matches!(
+            save_game.restore(&mut world),
+            Err(SceneSpawnError::UnregisteredButReflectedType { .. })
+        ));
+
+        assert_eq!(
+            world.get::<Health>(player),
+            Some(&Health {
+                current: 5,
+                max: 10
+            })
+        );
+        assert_eq!(world.entities().len(), 1);
+    }
+
+    #[test]
+    fn migrate_renamed_and_removed_fields() {
+        let mut world = world();
+        let type_path = Health::type_path();
+        world.insert_resource(
+            SaveGameSettings::default()
+                .with_version(3)
+                .with_migration(
+                    SaveMigration::new(2, type_path)
+                        .renamed_from("old_game::Hp")
+                        .rename_field("hp", "value")
+                        .remove_field("regen"),
+                )
+                .with_migration(
+                    SaveMigration::new(3, type_path)
+                        .rename_field("value", "current")
+                        .with_hook(|health| {
+                            if health.field("max").is_none() {
+                                health.insert("max", 100u32);
+                            }
+                        }),
+                ),
+        );
+
+        let old_save = r#"(
+            version: 1,
+            resources: {},
+            entities: {
+                4294967295: (
+                    components: {
+                        "bevy_scene::save_game::Persist": (),
+                        "old_game::Hp": (hp: 42, regen: 3),
+                    },
+                ),
+            },
+        )"#;
+
+        let save_game = SaveGame::deserialize(
+            old_save.as_bytes(),
+            &world.resource::<AppTypeRegistry>().read(),
+            world.resource::<SaveGameSettings>(),
+        )
+        .unwrap();
+        let entity_map = save_game.restore(&mut world).unwrap();
+        let entity = entity_map[&Entity::from_bits(4294967295)];
+        assert_eq!(
+            world.get::<Health>(entity),
+            Some(&Health {
+                current: 42,
+                max: 100
+            })
+        );
+
+        // Saves that are already up to date are not migrated.
+        let current_save = old_save.replace("version: 1", "version: 3");
+        assert!(SaveGame::deserialize(
+            current_save.as_bytes(),
+            &world.resource::<AppTypeRegistry>().read(),
+            world.resource::<SaveGameSettings>(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn save_and_load_through_asset_source() {
+        let root =
+            std::env::temp_dir().join(format!("bevy_scene_save_game_{}", std::process::id()));
+        let reader_root = root.clone();
+        let writer_root = root.clone();
+
+        let mut app = App::new();
+        app.register_asset_source(
+            "saves",
+            AssetSourceBuilder::default()
+                .with_reader(move || Box::new(FileAssetReader::new(reader_root.clone())))
+                .with_writer(move |_| {
+                    Some(Box::new(FileAssetWriter::new(writer_root.clone(), true)))
+                }),
+        )
+        .add_plugins((
+            bevy_app::TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            crate::ScenePlugin,
+        ))
+        .register_type::<Health>();
+
+        let run_until_finished = |app: &mut App| {
+            for _ in 0..1000 {
+                app.update();
+                if app.world().resource::<SaveGameTasks>().0.is_empty() {
+                    break;
+                }
+            }
+            let mut events = app.world_mut().resource_mut::<Events<SaveGameEvent>>();
+            events.drain().collect::<Vec<_>>()
+        };
+
+        app.world_mut()
+            .spawn((Persist, Health { current: 1, max: 2 }));
+        app.world_mut()
+            .commands()
+            .save_game("saves://slot.save.ron");
+        app.world_mut().flush();
+        let events = run_until_finished(&mut app);
+        assert!(
+            matches!(events.as_slice(), [SaveGameEvent::Saved { .. }]),
+            "{events:?}"
+        );
+
+        for mut health in app
+            .world_mut()
+            .query::<&mut Health>()
+            .iter_mut(app.world_mut())
+        {
+            health.current = 0;
+        }
+        app.world_mut()
+            .commands()
+            .load_game("saves://slot.save.ron");
+        app.world_mut().flush();
+        let events = run_until_finished(&mut app);
+        assert!(
+            matches!(events.as_slice(), [SaveGameEvent::Loaded { .. }]),
+            "{events:?}"
+        );
+        assert_eq!(
+            app.world_mut()
+                .query::<&Health>()
+                .iter(app.world())
+                .collect::<Vec<_>>(),
+            [&Health { current: 1, max: 2 }]
+        );
+
+        std::fs::remove_dir_all(root).ok();
+    }
+}